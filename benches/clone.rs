@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use monopoly::{generate_state, Command, GameConfig, GameState, PlayerId, PropertyId, RollResult};
+use std::hint::black_box;
+
+fn mid_game() -> GameState {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state
+        .apply(Command::BuyProperty(p0, PropertyId(3)))
+        .unwrap();
+    state.apply(Command::EndTurn(p0)).unwrap();
+    state
+        .apply(Command::RollDice(p1, RollResult(2, 3)))
+        .unwrap();
+    state
+        .apply(Command::BuyProperty(p1, PropertyId(5)))
+        .unwrap();
+    state.apply(Command::EndTurn(p1)).unwrap();
+    state
+}
+
+fn clone(c: &mut Criterion) {
+    let state = mid_game();
+    c.bench_function("clone mid-game state", |b| {
+        b.iter(|| black_box(&state).clone())
+    });
+    // A game well under way, with a history to carry along.
+    let played = generate_state(7, 150);
+    c.bench_function("clone generated mid-game state", |b| {
+        b.iter(|| black_box(&played).clone())
+    });
+    c.bench_function("applied roll", |b| {
+        b.iter(|| {
+            black_box(&state)
+                .applied(Command::RollDice(PlayerId(2), RollResult(3, 4)))
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, clone);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use monopoly::{
+    play_seeded, read_events, Command, Event, GameConfig, GameState, GreedyBuyer, RandomStrategy,
+    StrategyFactory,
+};
+use std::hint::black_box;
+
+const RANDOM: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+
+// The first 5000 events of a four-player game between RandomStrategy seats,
+// played by play_seeded from seed 1 with the engine's dice. Recorded rather
+// than played here so a change in the strategies or the dice can't quietly
+// change what is measured.
+fn recorded() -> (GameConfig, Vec<Event>) {
+    let mut bytes: &[u8] = include_bytes!("fixtures/random-game.bin");
+    let records = read_events(&mut bytes).unwrap();
+    let events = records.iter().flat_map(|record| record.events()).collect();
+    let config = GameConfig {
+        seed: 1,
+        engine_dice: true,
+        ..GameConfig::default()
+    };
+    (config, events)
+}
+
+// The first 10,000 commands of a game like the recorded one, every one of
+// them legal in order. The dice are given, so they apply with the engine's
+// own dice turned off.
+fn commands() -> (GameConfig, Vec<Command>) {
+    let config = GameConfig {
+        seed: 1,
+        engine_dice: true,
+        turn_limit: 5000,
+        ..GameConfig::default()
+    };
+    let result = play_seeded(&config, &[RANDOM; 4], 1).unwrap();
+    let commands: Vec<Command> = result
+        .log
+        .into_iter()
+        .take(10_000)
+        .map(|record| record.command)
+        .collect();
+    assert_eq!(commands.len(), 10_000);
+    let config = GameConfig {
+        engine_dice: false,
+        ..config
+    };
+    (config, commands)
+}
+
+fn apply(c: &mut Criterion) {
+    let (config, commands) = commands();
+    c.bench_function("apply 10k commands", |b| {
+        b.iter_batched(
+            || (GameState::new(config.clone()).unwrap(), commands.clone()),
+            |(mut state, commands)| {
+                for command in commands {
+                    state.apply(command).unwrap();
+                }
+                state
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn replay(c: &mut Criterion) {
+    let (config, events) = recorded();
+    c.bench_function("replay 5k events", |b| {
+        b.iter(|| GameState::replay(config.clone(), black_box(&events)).unwrap())
+    });
+}
+
+fn simulate(c: &mut Criterion) {
+    // Seed 3 is a game four GreedyBuyers finish, in 163 turns.
+    let config = GameConfig {
+        engine_dice: true,
+        ..GameConfig::default()
+    };
+    assert!(play_seeded(&config, &[GREEDY; 4], 3)
+        .unwrap()
+        .winner
+        .is_some());
+    c.bench_function("simulate a 4-player GreedyBuyer game", |b| {
+        b.iter(|| play_seeded(&config, &[GREEDY; 4], black_box(3)).unwrap())
+    });
+}
+
+criterion_group!(benches, apply, replay, simulate);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+fn main() {
+    println!("cargo:rerun-if-changed=proto");
+    #[cfg(feature = "protobuf")]
+    protobuf();
+}
+
+// Generates the prost types for proto/monopoly.proto into OUT_DIR.
+#[cfg(feature = "protobuf")]
+fn protobuf() {
+    let files = protox::compile(["proto/monopoly.proto"], ["proto"]).expect("monopoly.proto");
+    prost_build::Config::new()
+        .compile_fds(files)
+        .expect("generating the protobuf types");
+}
@@ -0,0 +1,44 @@
+/*
+ * The monopoly engine, for C. Games are opaque handles; everything else
+ * crosses as NUL-terminated UTF-8 JSON, in the same shapes as the save files
+ * and the HTTP API.
+ *
+ * Ownership:
+ *
+ * - A game from monopoly_game_new belongs to the caller until it is handed
+ *   back to monopoly_game_free, once. Nothing else frees or keeps it.
+ * - Strings passed in are only borrowed for the length of the call.
+ * - Strings come out by being copied into a buffer the caller owns. The
+ *   functions that do this return the length of the string, not counting
+ *   its NUL, and copy it only when the buffer has room for it and the NUL;
+ *   so a call with a NULL buffer and a length of 0 asks how big a buffer
+ *   must be.
+ * - On failure a function returns -1 (or NULL), and monopoly_last_error
+ *   has the reason until the next failure on the same thread.
+ *
+ * No panic crosses into C: one is caught, and reported like any other
+ * failure.
+ */
+
+#ifndef MONOPOLY_H
+#define MONOPOLY_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stddef.h>
+
+typedef struct MonopolyGame MonopolyGame;
+
+struct MonopolyGame *monopoly_game_new(const char *config);
+
+void monopoly_game_free(struct MonopolyGame *game);
+
+ptrdiff_t monopoly_game_state(const struct MonopolyGame *game, char *buffer, size_t length);
+
+ptrdiff_t monopoly_game_legal_actions(const struct MonopolyGame *game, char *buffer, size_t length);
+
+int monopoly_game_apply(struct MonopolyGame *game, const char *command);
+
+ptrdiff_t monopoly_last_error(char *buffer, size_t length);
+
+#endif  /* MONOPOLY_H */
@@ -0,0 +1,72 @@
+// A compact binary form for event logs. Each record is written as its
+// postcard encoding behind a LEB128 length, so logs can be streamed, and
+// appended to by simply writing more records onto the end.
+
+use crate::game::EventRecord;
+use std::io::{self, Read, Write};
+
+pub fn write_events(writer: &mut impl Write, records: &[EventRecord]) -> io::Result<()> {
+    for record in records {
+        let bytes = postcard::to_allocvec(record)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let mut length = bytes.len();
+        loop {
+            let byte = (length & 0x7f) as u8;
+            length >>= 7;
+            if length == 0 {
+                writer.write_all(&[byte])?;
+                break;
+            }
+            writer.write_all(&[byte | 0x80])?;
+        }
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+// Reads records until the input runs out.
+pub fn read_events(reader: &mut impl Read) -> io::Result<Vec<EventRecord>> {
+    let mut records = Vec::new();
+    while let Some(length) = read_length(reader)? {
+        // Read what is there rather than allocating what the length claims:
+        // a corrupt length could ask for more memory than there is.
+        let mut bytes = Vec::new();
+        reader
+            .by_ref()
+            .take(length as u64)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let record = postcard::from_bytes(&bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+// None at a clean end of input, between records.
+fn read_length(reader: &mut impl Read) -> io::Result<Option<usize>> {
+    let mut length = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0];
+        if reader.read(&mut byte)? == 0 {
+            if shift == 0 {
+                return Ok(None);
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if shift >= usize::BITS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "record length is too long",
+            ));
+        }
+        length |= ((byte[0] & 0x7f) as usize) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(length));
+        }
+        shift += 7;
+    }
+}
@@ -0,0 +1,165 @@
+// The engine for C, and anything else that can call C. Games are opaque
+// handles, and everything else crosses as NUL-terminated UTF-8 JSON in the
+// same shapes as the save files and the HTTP API. include/monopoly.h is
+// generated from this file with
+//
+//     cbindgen --config cbindgen.toml --output include/monopoly.h
+//
+// and spells out the ownership rules:
+//
+// - A game from monopoly_game_new belongs to the caller until it is handed
+//   back to monopoly_game_free, once. Nothing else frees or keeps it.
+// - Strings passed in are only borrowed for the length of the call.
+// - Strings come out by being copied into a buffer the caller owns. The
+//   functions that do this return the length of the string, not counting
+//   its NUL, and copy it only when the buffer has room for it and the NUL;
+//   so a call with a NULL buffer and a length of 0 asks how big a buffer
+//   must be.
+// - On failure a function returns -1 (or NULL), and monopoly_last_error
+//   has the reason until the next failure on the same thread.
+//
+// No panic crosses into C: one is caught, and reported like any other
+// failure.
+
+// Each function's safety rests on the rules above, which the header carries.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::game::{GameConfig, GameState};
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+// A game, from C's side.
+pub struct MonopolyGame {
+    state: GameState,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+// A game set up by config, a GameConfig as JSON in which fields left out
+// take their defaults; NULL for the defaults throughout. NULL if the config
+// is refused.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_game_new(config: *const c_char) -> *mut MonopolyGame {
+    guarded(ptr::null_mut(), || {
+        let config = match config.is_null() {
+            true => GameConfig::default(),
+            false => serde_json::from_str(text(config)?).map_err(|error| error.to_string())?,
+        };
+        let state = GameState::new(config).map_err(|error| error.to_string())?;
+        Ok(Box::into_raw(Box::new(MonopolyGame { state })))
+    })
+}
+
+// Frees a game. NULL is let be.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_game_free(game: *mut MonopolyGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+// The whole game as JSON, into buffer.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_game_state(
+    game: *const MonopolyGame,
+    buffer: *mut c_char,
+    length: usize,
+) -> isize {
+    guarded(-1, || {
+        let json = serde_json::to_string(&borrow(game)?.state).expect("games always serialize");
+        Ok(copy_out(&json, buffer, length))
+    })
+}
+
+// The commands open to the game as it stands, as a JSON array, into buffer.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_game_legal_actions(
+    game: *const MonopolyGame,
+    buffer: *mut c_char,
+    length: usize,
+) -> isize {
+    guarded(-1, || {
+        let json = serde_json::to_string(&borrow(game)?.state.legal_actions())
+            .expect("commands always serialize");
+        Ok(copy_out(&json, buffer, length))
+    })
+}
+
+// Applies command, a Command as JSON such as {"Roll": 0}. 0 if the game
+// accepted it; -1 if it was refused or could not be read.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_game_apply(
+    game: *mut MonopolyGame,
+    command: *const c_char,
+) -> c_int {
+    guarded(-1, || {
+        if game.is_null() {
+            return Err("the game is NULL".to_string());
+        }
+        let command = serde_json::from_str(text(command)?).map_err(|error| error.to_string())?;
+        let game = &mut *game;
+        game.state
+            .apply(command)
+            .map_err(|error| error.to_string())?;
+        Ok(0)
+    })
+}
+
+// Why the last call to fail on this thread failed, into buffer; the empty
+// string if none has.
+#[no_mangle]
+pub unsafe extern "C" fn monopoly_last_error(buffer: *mut c_char, length: usize) -> isize {
+    LAST_ERROR.with(|last| {
+        let last = last.borrow();
+        copy_out(last.to_str().unwrap_or_default(), buffer, length)
+    })
+}
+
+// Runs body, turning an Err or a panic into failed and a last error.
+fn guarded<T>(failed: T, body: impl FnOnce() -> Result<T, String>) -> T {
+    let message = match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(message)) => message,
+        Err(payload) => format!("the engine panicked: {}", panic_message(&*payload)),
+    };
+    // Messages are ours or serde's, and neither has a NUL in it.
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    failed
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload
+            .downcast_ref::<String>()
+            .map_or("no message", String::as_str),
+    }
+}
+
+unsafe fn text<'a>(text: *const c_char) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err("a string is NULL".to_string());
+    }
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| "a string is not UTF-8".to_string())
+}
+
+unsafe fn borrow<'a>(game: *const MonopolyGame) -> Result<&'a MonopolyGame, String> {
+    game.as_ref().ok_or_else(|| "the game is NULL".to_string())
+}
+
+unsafe fn copy_out(text: &str, buffer: *mut c_char, length: usize) -> isize {
+    if !buffer.is_null() && text.len() < length {
+        ptr::copy_nonoverlapping(text.as_ptr(), buffer as *mut u8, text.len());
+        *buffer.add(text.len()) = 0;
+    }
+    text.len() as isize
+}
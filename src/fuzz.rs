@@ -0,0 +1,109 @@
+// Entry points for the targets under fuzz/. Each turns arbitrary bytes into
+// commands for a fresh game and applies them one by one. Refusals are fine;
+// what must never happen is a panic, or an accepted command leaving the game
+// in a state check_invariants turns away. Both panic when it does, which is
+// how a fuzzer learns of it.
+
+use crate::encoding::read_events;
+use crate::game::{
+    Command, GameConfig, GameState, Money, PlayerId, PropertyId, RollResult, MAX_PLAYERS,
+    MIN_PLAYERS,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::convert::TryInto;
+
+// Ids, amounts and dice are mostly in range, so that a fair share of what is
+// generated gets past the first checks, but now and then anything the type
+// can hold.
+impl<'a> Arbitrary<'a> for PlayerId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.ratio(9, 10)? {
+            true => Ok(PlayerId(u.int_in_range(0..=MAX_PLAYERS as i8 - 1)?)),
+            false => Ok(PlayerId(u.arbitrary()?)),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertyId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.ratio(9, 10)? {
+            true => Ok(PropertyId(u.int_in_range(0..=39)?)),
+            false => Ok(PropertyId(u.arbitrary()?)),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Money {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.ratio(9, 10)? {
+            true => Ok(Money(u.int_in_range(0..=2000)?)),
+            false => Ok(Money(u.arbitrary()?)),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for RollResult {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.ratio(9, 10)? {
+            true => Ok(RollResult(u.int_in_range(1..=6)?, u.int_in_range(1..=6)?)),
+            false => Ok(RollResult(u.arbitrary()?, u.arbitrary()?)),
+        }
+    }
+}
+
+// The game's setup, then commands until the bytes run out.
+pub fn fuzz_commands(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let config = match arbitrary_config(&mut u) {
+        Ok(config) => config,
+        Err(_) => return,
+    };
+    if let Ok(commands) = u.arbitrary_iter::<Command>() {
+        play(config, commands.map_while(Result::ok));
+    }
+}
+
+// A player count, a seed as eight little-endian bytes, then an event log as
+// write_events writes it. The logged commands are applied afresh rather than
+// replayed, so a log that is cut short or tampered with still gets as far as
+// its commands allow.
+pub fn fuzz_log(data: &[u8]) {
+    if data.len() < 9 {
+        return;
+    }
+    let (header, mut log) = data.split_at(9);
+    let config = GameConfig {
+        players: header[0] as usize,
+        seed: u64::from_le_bytes(header[1..].try_into().unwrap()),
+        ..GameConfig::default()
+    };
+    let records = match read_events(&mut log) {
+        Ok(records) => records,
+        Err(_) => return,
+    };
+    play(config, records.into_iter().map(|record| record.command));
+}
+
+fn arbitrary_config(u: &mut Unstructured) -> Result<GameConfig> {
+    Ok(GameConfig {
+        players: u.int_in_range(MIN_PLAYERS..=MAX_PLAYERS)?,
+        seed: u.arbitrary()?,
+        engine_dice: u.arbitrary()?,
+        ..GameConfig::default()
+    })
+}
+
+fn play(config: GameConfig, commands: impl Iterator<Item = Command>) {
+    let mut state = match GameState::new(config) {
+        Ok(state) => state,
+        Err(_) => return,
+    };
+    for command in commands {
+        if state.apply(command.clone()).is_err() {
+            continue;
+        }
+        if let Err(error) = state.check_invariants() {
+            panic!("after {:?}: {}", command, error);
+        }
+    }
+}
@@ -1,201 +1,4358 @@
-use std::convert::TryInto;
+use crate::rng::Rng;
+use log::{debug, trace};
+use std::collections::VecDeque;
+use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod board;
+#[cfg(any(test, feature = "testing"))]
+mod builder;
+mod clock;
+mod dice;
+mod diff;
+mod export;
+#[cfg(any(test, feature = "testing"))]
+mod generate;
+#[cfg(feature = "proptest")]
+mod generators;
+mod greedy;
+mod income;
+mod integrity;
+mod invariants;
+#[cfg(feature = "serde")]
+mod journal;
+mod landing;
+mod markov;
+mod observe;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+mod random;
+mod ratings;
+mod render;
+mod report;
+#[cfg(feature = "serde")]
+mod saved;
+mod script;
+mod simulate;
+mod tournament;
+mod transcript;
+mod viewer;
+pub use board::BoardBuilder;
+#[cfg(any(test, feature = "testing"))]
+pub use builder::GameStateBuilder;
+pub use clock::{Clock, SharedClock, SystemClock};
+pub use dice::{Dice, DiceRoller, SeededDice};
+pub use diff::{Difference, StateDiff};
+#[cfg(any(test, feature = "testing"))]
+pub use generate::generate_state;
+#[cfg(feature = "proptest")]
+pub use generators::game_states;
+pub use greedy::GreedyBuyer;
+#[cfg(feature = "serde")]
+pub use journal::{EventSink, FileSink, JournalError, Journaled};
+pub use landing::LandingStats;
+pub use markov::steady_state;
+pub use observe::{Observed, Observer};
+#[cfg(feature = "protobuf")]
+pub use protobuf::{proto, ProtoError};
+pub use random::RandomStrategy;
+pub use ratings::{Ratings, INITIAL_RATING};
+pub use report::{
+    play_seeded, run_many, run_many_parallel, seated, strategy_named, LengthBucket,
+    SimulationReport, StrategyFactory,
+};
+#[cfg(feature = "serde")]
+pub use saved::{GameLog, SaveError, LOG_VERSION, SAVE_VERSION};
+pub use script::ParseError;
+pub use simulate::{simulate, DoNothing, GameResult, Outcome, Strategy};
+pub use tournament::{tournament, TournamentGame, TournamentReport, DEFAULT_K};
 // Derived from https://www.hasbro.com/common/instruct/00009.pdf
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PlayerId(pub i8);
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Player {
-    pub id: PlayerId,
+    id: PlayerId,
+    cash: Money,
+    position: usize,
+    jail: Option<u8>, // failed attempts to roll out so far
+    jail_cards: Vec<Card>,
+    eliminated: Option<u32>, // the turn they went bankrupt on
+    token: Token,
+    // Shown in place of "Player N"; the id is still who they are.
+    #[cfg_attr(feature = "serde", serde(default))]
+    name: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct PropertyId(i8);
+impl Player {
+    pub fn id(&self) -> PlayerId {
+        self.id
+    }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Money(i16);
+    pub fn cash(&self) -> Money {
+        self.cash
+    }
 
-#[derive(Clone, Debug, PartialEq)]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn in_jail(&self) -> bool {
+        self.jail.is_some()
+    }
+
+    pub fn jail_cards(&self) -> usize {
+        self.jail_cards.len()
+    }
+
+    pub fn is_bankrupt(&self) -> bool {
+        self.eliminated.is_some()
+    }
+
+    pub fn status(&self) -> PlayerStatus {
+        match self.eliminated {
+            Some(on_turn) => PlayerStatus::Eliminated { on_turn },
+            None => PlayerStatus::Active,
+        }
+    }
+
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "Player {}", self.id.0),
+        }
+    }
+}
+
+pub const MAX_NAME_LENGTH: usize = 20; // in characters
+
+// Who sat where, as settled before the first roll, so a game rebuilt from
+// its config has the same people in it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Seat {
+    pub token: Token,
+    pub name: Option<String>,
+}
+
+// A bankrupt player keeps their seat, their id and what's left of their
+// record; they just take no more turns.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PlayerStatus {
+    Active,
+    Eliminated { on_turn: u32 },
+}
+
+// The pieces in the box, one to a player.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Token {
+    TopHat,
+    Thimble,
+    Boot,
+    Dog,
+    Battleship,
+    Cat,
+    Wheelbarrow,
+    RaceCar,
+}
+
+impl Token {
+    // In the order they're handed out to players who don't pick.
+    pub const ALL: [Token; MAX_PLAYERS] = [
+        Token::TopHat,
+        Token::Thimble,
+        Token::Boot,
+        Token::Dog,
+        Token::Battleship,
+        Token::Cat,
+        Token::Wheelbarrow,
+        Token::RaceCar,
+    ];
+
+    // What stands for it on the drawn board.
+    pub fn symbol(self) -> char {
+        match self {
+            Token::TopHat => 'H',
+            Token::Thimble => 'T',
+            Token::Boot => 'B',
+            Token::Dog => 'D',
+            Token::Battleship => 'S',
+            Token::Cat => 'C',
+            Token::Wheelbarrow => 'W',
+            Token::RaceCar => 'R',
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Token::TopHat => "top hat",
+            Token::Thimble => "thimble",
+            Token::Boot => "boot",
+            Token::Dog => "dog",
+            Token::Battleship => "battleship",
+            Token::Cat => "cat",
+            Token::Wheelbarrow => "wheelbarrow",
+            Token::RaceCar => "race car",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PropertyId(pub i8);
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Money(pub i32);
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ColorGroup {
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+}
+
+impl ColorGroup {
+    pub const ALL: [ColorGroup; 8] = [
+        ColorGroup::Brown,
+        ColorGroup::LightBlue,
+        ColorGroup::Pink,
+        ColorGroup::Orange,
+        ColorGroup::Red,
+        ColorGroup::Yellow,
+        ColorGroup::Green,
+        ColorGroup::DarkBlue,
+    ];
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Property {
     name: &'static str,
+    color: ColorGroup,
+    price: Money,
     base: Money,
     houses: [Money; 4],
     hotel: Money,
     mortgage: Money,
     house_cost: Money,
     hotel_cost: (Money, i8), // ($cost, num_houses)
-                             // TODO(emacs): double rent if player owns all lots on color?
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Railroad {
+    name: &'static str,
+    price: Money,
+    mortgage: Money,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Utility {
+    name: &'static str,
+    price: Money,
+    mortgage: Money,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Tax {
+    name: &'static str,
+    amount: Money,
+    percent: Option<i32>, // alternatively pay this share of total worth
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RollResult(pub i8, pub i8);
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct ChanceCard;
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum ChanceCard {
+    AdvanceToGo,
+    AdvanceToIllinoisAve,
+    AdvanceToStCharlesPlace,
+    AdvanceToNearestUtility,
+    AdvanceToNearestRailroad,
+    BankDividend,
+    GetOutOfJailFree,
+    GoBackThreeSpaces,
+    GoToJail,
+    GeneralRepairs,
+    PoorTax,
+    TripToReadingRailroad,
+    WalkOnTheBoardwalk,
+    ChairmanOfTheBoard,
+    BuildingLoanMatures,
+}
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct CommunityChestCard;
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum CommunityChestCard {
+    AdvanceToGo,
+    BankError,
+    DoctorsFee,
+    SaleOfStock,
+    GetOutOfJailFree,
+    GoToJail,
+    GrandOperaNight,
+    HolidayFund,
+    IncomeTaxRefund,
+    LifeInsurance,
+    HospitalFees,
+    SchoolFees,
+    ConsultancyFee,
+    StreetRepairs,
+    BeautyContest,
+    Inheritance,
+}
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum Card {
     Chance(ChanceCard),
     CommunityChest(CommunityChestCard),
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Bid(PlayerId, Money);
+// Both decks in printed order; they are shuffled when a game is created.
+static CHANCE_CARDS: &[ChanceCard] = &[
+    ChanceCard::AdvanceToGo,
+    ChanceCard::AdvanceToIllinoisAve,
+    ChanceCard::AdvanceToStCharlesPlace,
+    ChanceCard::AdvanceToNearestUtility,
+    ChanceCard::AdvanceToNearestRailroad,
+    ChanceCard::AdvanceToNearestRailroad,
+    ChanceCard::BankDividend,
+    ChanceCard::GetOutOfJailFree,
+    ChanceCard::GoBackThreeSpaces,
+    ChanceCard::GoToJail,
+    ChanceCard::GeneralRepairs,
+    ChanceCard::PoorTax,
+    ChanceCard::TripToReadingRailroad,
+    ChanceCard::WalkOnTheBoardwalk,
+    ChanceCard::ChairmanOfTheBoard,
+    ChanceCard::BuildingLoanMatures,
+];
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum TransactionType {
-    BuyProperty(PlayerId, PropertyId),
-    BuyGetOutOfJailFreeCard(PlayerId),
-    SellProperty(PlayerId, PropertyId),
-    PayRent(PlayerId),
+static COMMUNITY_CHEST_CARDS: &[CommunityChestCard] = &[
+    CommunityChestCard::AdvanceToGo,
+    CommunityChestCard::BankError,
+    CommunityChestCard::DoctorsFee,
+    CommunityChestCard::SaleOfStock,
+    CommunityChestCard::GetOutOfJailFree,
+    CommunityChestCard::GoToJail,
+    CommunityChestCard::GrandOperaNight,
+    CommunityChestCard::HolidayFund,
+    CommunityChestCard::IncomeTaxRefund,
+    CommunityChestCard::LifeInsurance,
+    CommunityChestCard::HospitalFees,
+    CommunityChestCard::SchoolFees,
+    CommunityChestCard::ConsultancyFee,
+    CommunityChestCard::StreetRepairs,
+    CommunityChestCard::BeautyContest,
+    CommunityChestCard::Inheritance,
+];
+
+// Where a card sends a player, by what stands there rather than by index, so
+// the cards work on any board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Landmark {
+    Go,
+    FirstLot(ColorGroup), // St. Charles Place
+    LastLot(ColorGroup),  // Illinois Ave and Boardwalk
+    FirstRailroad,        // Reading Railroad
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct Transaction {
-    ty: TransactionType,
-    cost: Money,
+enum CardEffect {
+    AdvanceTo(Landmark),
+    AdvanceToNearestUtility,  // pay ten times a fresh roll if owned
+    AdvanceToNearestRailroad, // pay twice the rent if owned
+    GoBack(usize),
+    Collect(Money),
+    Pay(Money),
+    CollectFromEachPlayer(Money),
+    PayEachPlayer(Money),
+    Repairs { per_house: Money, per_hotel: Money },
+    GetOutOfJailFree,
+    GoToJail,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum Action {
+impl ChanceCard {
+    fn effect(self) -> CardEffect {
+        match self {
+            ChanceCard::AdvanceToGo => CardEffect::AdvanceTo(Landmark::Go),
+            ChanceCard::AdvanceToIllinoisAve => {
+                CardEffect::AdvanceTo(Landmark::LastLot(ColorGroup::Red))
+            }
+            ChanceCard::AdvanceToStCharlesPlace => {
+                CardEffect::AdvanceTo(Landmark::FirstLot(ColorGroup::Pink))
+            }
+            ChanceCard::AdvanceToNearestUtility => CardEffect::AdvanceToNearestUtility,
+            ChanceCard::AdvanceToNearestRailroad => CardEffect::AdvanceToNearestRailroad,
+            ChanceCard::BankDividend => CardEffect::Collect(Money(50)),
+            ChanceCard::GetOutOfJailFree => CardEffect::GetOutOfJailFree,
+            ChanceCard::GoBackThreeSpaces => CardEffect::GoBack(3),
+            ChanceCard::GoToJail => CardEffect::GoToJail,
+            ChanceCard::GeneralRepairs => CardEffect::Repairs {
+                per_house: Money(25),
+                per_hotel: Money(100),
+            },
+            ChanceCard::PoorTax => CardEffect::Pay(Money(15)),
+            ChanceCard::TripToReadingRailroad => CardEffect::AdvanceTo(Landmark::FirstRailroad),
+            ChanceCard::WalkOnTheBoardwalk => {
+                CardEffect::AdvanceTo(Landmark::LastLot(ColorGroup::DarkBlue))
+            }
+            ChanceCard::ChairmanOfTheBoard => CardEffect::PayEachPlayer(Money(50)),
+            ChanceCard::BuildingLoanMatures => CardEffect::Collect(Money(150)),
+        }
+    }
+}
+
+impl CommunityChestCard {
+    fn effect(self) -> CardEffect {
+        match self {
+            CommunityChestCard::AdvanceToGo => CardEffect::AdvanceTo(Landmark::Go),
+            CommunityChestCard::BankError => CardEffect::Collect(Money(200)),
+            CommunityChestCard::DoctorsFee => CardEffect::Pay(Money(50)),
+            CommunityChestCard::SaleOfStock => CardEffect::Collect(Money(50)),
+            CommunityChestCard::GetOutOfJailFree => CardEffect::GetOutOfJailFree,
+            CommunityChestCard::GoToJail => CardEffect::GoToJail,
+            CommunityChestCard::GrandOperaNight => CardEffect::CollectFromEachPlayer(Money(50)),
+            CommunityChestCard::HolidayFund => CardEffect::Collect(Money(100)),
+            CommunityChestCard::IncomeTaxRefund => CardEffect::Collect(Money(20)),
+            CommunityChestCard::LifeInsurance => CardEffect::Collect(Money(100)),
+            CommunityChestCard::HospitalFees => CardEffect::Pay(Money(100)),
+            CommunityChestCard::SchoolFees => CardEffect::Pay(Money(150)),
+            CommunityChestCard::ConsultancyFee => CardEffect::Collect(Money(25)),
+            CommunityChestCard::StreetRepairs => CardEffect::Repairs {
+                per_house: Money(40),
+                per_hotel: Money(115),
+            },
+            CommunityChestCard::BeautyContest => CardEffect::Collect(Money(10)),
+            CommunityChestCard::Inheritance => CardEffect::Collect(Money(100)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Bid(pub PlayerId, pub Money);
+
+// An offer from one player to another. Nothing changes hands until the
+// recipient accepts it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Trade {
+    pub proposer: PlayerId,
+    pub recipient: PlayerId,
+    pub offered: Vec<PropertyId>,
+    pub requested: Vec<PropertyId>,
+    pub offered_cash: Money,
+    pub requested_cash: Money,
+    pub offered_jail_cards: u8,
+    pub requested_jail_cards: u8,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum TaxChoice {
+    Flat,
+    Percent,
+}
+
+// Something the game is waiting on before play can continue. Decisions are
+// resolved in the order they arose.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PendingDecision {
+    BuyOrDecline {
+        player: PlayerId,
+        property: PropertyId,
+    },
+    Auction {
+        property: PropertyId,
+        high_bid: Option<Bid>,
+        bidders: Vec<PlayerId>, // still in the running, in bidding order
+        next: usize,            // index into bidders
+    },
+    PayDebt {
+        debtor: PlayerId,
+        creditor: Option<PlayerId>, // None for the bank
+        amount: Money,
+    },
+    IncomeTax {
+        player: PlayerId,
+        worth: Money, // total worth when the player landed
+    },
+    UtilityRoll {
+        player: PlayerId,
+        property: PropertyId,
+    },
+    // A winning bid more than the bidder's cash, which they settle as a debt
+    // to the bank before the title is theirs.
+    PayBid {
+        debtor: PlayerId,
+        property: PropertyId,
+        amount: Money,
+    },
+}
+
+impl PendingDecision {
+    // The player who has to act to resolve this decision.
+    pub fn player(&self) -> PlayerId {
+        match self {
+            PendingDecision::BuyOrDecline { player, .. } => *player,
+            PendingDecision::Auction { bidders, next, .. } => bidders[*next],
+            PendingDecision::PayDebt { debtor, .. } => *debtor,
+            PendingDecision::IncomeTax { player, .. } => *player,
+            PendingDecision::UtilityRoll { player, .. } => *player,
+            PendingDecision::PayBid { debtor, .. } => *debtor,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Command {
     RollDice(PlayerId, RollResult),
+    Roll(PlayerId), // with the engine's dice; logged as the roll it became
     MoveForward(PlayerId, i8),
     BuyProperty(PlayerId, PropertyId),  // from the bank
+    DeclineToBuy(PlayerId),             // puts it up for auction
     SellProperty(PlayerId, PropertyId), // to the bank
     BuyHouse(PlayerId, PropertyId),     // from the bank
     SellHouse(PlayerId, PropertyId),    // to the bank
     BuyHotel(PlayerId, PropertyId),     // from the bank
     SellHotel(PlayerId, PropertyId),    // to the bank
     PayTaxes(PlayerId, Money),          // to the bank
-    ReceiveSalary(PlayerId),            // passing GO
+    PayIncomeTax(PlayerId, TaxChoice),
+    ReceiveSalary(PlayerId), // passing GO
     DrawCard(PlayerId, Card),
     GoToJail(PlayerId),
     PayJailFine(PlayerId),
-    AuctionProperty(PropertyId, Vec<Bid>),
+    UseGetOutOfJailFreeCard(PlayerId),
+    AuctionProperty(PropertyId, Vec<Bid>), // settles the whole auction at once
+    PlaceBid(PlayerId, Money),
+    PassAuction(PlayerId),
     MortgageProperty(PlayerId, PropertyId),
     UnmortgageProperty(PlayerId, PropertyId),
-    TransactWithPlayer(PlayerId, Transaction),
+    ProposeTrade(Trade),
+    AcceptTrade(PlayerId),
+    RejectTrade(PlayerId), // by either party
+    PayDebt(PlayerId),
     DeclareBankruptcy(PlayerId),
+    EndTurn(PlayerId),
+    Resign(PlayerId), // on their own turn, or instead of an unpayable debt
+    RollMany(PlayerId, Vec<i8>), // any dice but two, each as it landed
+}
+
+// Something the engine did while applying a command. The command itself is
+// what the player asked for; effects are everything that followed from it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Effect {
+    Moved {
+        player: PlayerId,
+        to: usize,
+    },
+    CollectedSalary {
+        player: PlayerId,
+        amount: Money,
+    },
+    Paid {
+        from: PlayerId,
+        to: Option<PlayerId>, // None for the bank
+        amount: Money,
+    },
+    Received {
+        player: PlayerId,
+        amount: Money,
+    }, // from the bank
+    DrewCard {
+        player: PlayerId,
+        card: Card,
+    },
+    WentToJail(PlayerId),
+    LeftJail(PlayerId),
+    Acquired {
+        player: PlayerId,
+        property: PropertyId,
+    },
+    DecisionPending(PendingDecision),
+    WentBankrupt(PlayerId),
+    TurnStarted(PlayerId),
+    // Each comes just before the salary it earns: a move that ends on GO
+    // lands on it, and any other that goes round passes it.
+    PassedGo(PlayerId),
+    LandedOnGo(PlayerId),
+    // Double ones under the snake eyes rule, just before the bonus is paid.
+    SnakeEyes(PlayerId),
+    // A bankrupt player's buildings on one lot going back to the bank, just
+    // before the half price is paid for them.
+    SoldBuildings {
+        player: PlayerId,
+        property: PropertyId,
+        houses: u8,
+        hotel: bool,
+    },
+}
+
+// Everything that happened, in order: each command the engine accepted,
+// followed by its effects.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Event {
+    Command(Command),
+    Effect(Effect),
+}
+
+impl From<Command> for Event {
+    fn from(command: Command) -> Event {
+        Event::Command(command)
+    }
+}
+
+impl From<Effect> for Event {
+    fn from(effect: Effect) -> Event {
+        Event::Effect(effect)
+    }
+}
+
+impl Command {
+    // Who is taking the action, if anyone is.
+    pub fn actor(&self) -> Option<PlayerId> {
+        match self {
+            Command::RollDice(id, _)
+            | Command::Roll(id)
+            | Command::MoveForward(id, _)
+            | Command::BuyProperty(id, _)
+            | Command::DeclineToBuy(id)
+            | Command::SellProperty(id, _)
+            | Command::BuyHouse(id, _)
+            | Command::SellHouse(id, _)
+            | Command::BuyHotel(id, _)
+            | Command::SellHotel(id, _)
+            | Command::PayTaxes(id, _)
+            | Command::PayIncomeTax(id, _)
+            | Command::ReceiveSalary(id)
+            | Command::DrawCard(id, _)
+            | Command::GoToJail(id)
+            | Command::PayJailFine(id)
+            | Command::UseGetOutOfJailFreeCard(id)
+            | Command::PlaceBid(id, _)
+            | Command::PassAuction(id)
+            | Command::MortgageProperty(id, _)
+            | Command::UnmortgageProperty(id, _)
+            | Command::AcceptTrade(id)
+            | Command::RejectTrade(id)
+            | Command::PayDebt(id)
+            | Command::DeclareBankruptcy(id)
+            | Command::EndTurn(id)
+            | Command::Resign(id)
+            | Command::RollMany(id, _) => Some(*id),
+            Command::ProposeTrade(trade) => Some(trade.proposer),
+            Command::AuctionProperty(..) => None,
+        }
+    }
+
+    // Property names live on the board, so rendering needs the game.
+    pub fn display_with<'a>(&'a self, state: &'a GameState) -> CommandDisplay<'a> {
+        CommandDisplay {
+            action: self,
+            state,
+        }
+    }
+}
+
+pub struct CommandDisplay<'a> {
+    action: &'a Command,
+    state: &'a GameState,
+}
+
+impl fmt::Display for CommandDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |property: &PropertyId| self.state.square_name(property.0 as usize);
+        let who = |id: &i8| self.state.display_name(PlayerId(*id));
+        match self.action {
+            Command::RollDice(PlayerId(id), RollResult(one, two)) => {
+                write!(f, "{} rolled {}+{} ({})", who(id), one, two, one + two)
+            }
+            Command::RollMany(PlayerId(id), dice) => {
+                let faces: Vec<_> = dice.iter().map(|die| die.to_string()).collect();
+                let total: i32 = dice.iter().map(|&die| die as i32).sum();
+                write!(f, "{} rolled {} ({})", who(id), faces.join("+"), total)
+            }
+            Command::Roll(PlayerId(id)) => write!(f, "{} rolls", who(id)),
+            Command::MoveForward(PlayerId(id), spaces) => {
+                write!(f, "{} moved forward {} spaces", who(id), spaces)
+            }
+            Command::BuyProperty(PlayerId(id), property) => write!(
+                f,
+                "{} bought {} for ${}",
+                who(id),
+                name(property),
+                self.state.price(property.0 as usize).0
+            ),
+            Command::DeclineToBuy(PlayerId(id)) => write!(f, "{} declined to buy", who(id)),
+            Command::SellProperty(PlayerId(id), property) => {
+                write!(f, "{} sold {}", who(id), name(property))
+            }
+            Command::BuyHouse(PlayerId(id), property) => {
+                write!(f, "{} built a house on {}", who(id), name(property))
+            }
+            Command::SellHouse(PlayerId(id), property) => {
+                write!(f, "{} sold a house on {}", who(id), name(property))
+            }
+            Command::BuyHotel(PlayerId(id), property) => {
+                write!(f, "{} built a hotel on {}", who(id), name(property))
+            }
+            Command::SellHotel(PlayerId(id), property) => {
+                write!(f, "{} sold the hotel on {}", who(id), name(property))
+            }
+            Command::PayTaxes(PlayerId(id), amount) => {
+                write!(f, "{} paid ${} in taxes", who(id), amount.0)
+            }
+            Command::PayIncomeTax(PlayerId(id), TaxChoice::Flat) => {
+                write!(f, "{} paid the flat income tax", who(id))
+            }
+            Command::PayIncomeTax(PlayerId(id), TaxChoice::Percent) => {
+                write!(f, "{} paid income tax as a percentage", who(id))
+            }
+            Command::ReceiveSalary(PlayerId(id)) => write!(f, "{} collected salary", who(id)),
+            Command::DrawCard(PlayerId(id), Card::Chance(card)) => {
+                write!(f, "{} drew Chance: {:?}", who(id), card)
+            }
+            Command::DrawCard(PlayerId(id), Card::CommunityChest(card)) => {
+                write!(f, "{} drew Community Chest: {:?}", who(id), card)
+            }
+            Command::GoToJail(PlayerId(id)) => write!(f, "{} went to jail", who(id)),
+            Command::PayJailFine(PlayerId(id)) => {
+                write!(f, "{} paid the fine to leave jail", who(id))
+            }
+            Command::UseGetOutOfJailFreeCard(PlayerId(id)) => {
+                write!(f, "{} used a Get Out of Jail Free card", who(id))
+            }
+            Command::AuctionProperty(property, bids) => {
+                write!(f, "{} was auctioned", name(property))?;
+                match bids.iter().max_by_key(|Bid(_, amount)| amount.0) {
+                    Some(Bid(PlayerId(id), amount)) => {
+                        write!(f, " to {} for ${}", who(id), amount.0)
+                    }
+                    None => write!(f, " without bids"),
+                }
+            }
+            Command::PlaceBid(PlayerId(id), amount) => {
+                write!(f, "{} bid ${}", who(id), amount.0)
+            }
+            Command::PassAuction(PlayerId(id)) => write!(f, "{} passed", who(id)),
+            Command::MortgageProperty(PlayerId(id), property) => {
+                write!(f, "{} mortgaged {}", who(id), name(property))
+            }
+            Command::UnmortgageProperty(PlayerId(id), property) => {
+                write!(f, "{} unmortgaged {}", who(id), name(property))
+            }
+            Command::ProposeTrade(trade) => {
+                let side = |properties: &[PropertyId], cash: Money, cards: u8| {
+                    let mut items: Vec<String> =
+                        properties.iter().map(|p| name(p).to_string()).collect();
+                    if cash.0 > 0 {
+                        items.push(format!("${}", cash.0));
+                    }
+                    if cards > 0 {
+                        items.push(format!("{} jail card(s)", cards));
+                    }
+                    if items.is_empty() {
+                        "nothing".to_string()
+                    } else {
+                        items.join(", ")
+                    }
+                };
+                write!(
+                    f,
+                    "{} offered {} {} for {}",
+                    who(&trade.proposer.0),
+                    who(&trade.recipient.0),
+                    side(&trade.offered, trade.offered_cash, trade.offered_jail_cards),
+                    side(
+                        &trade.requested,
+                        trade.requested_cash,
+                        trade.requested_jail_cards
+                    )
+                )
+            }
+            Command::AcceptTrade(PlayerId(id)) => write!(f, "{} accepted the trade", who(id)),
+            Command::RejectTrade(PlayerId(id)) => write!(f, "{} rejected the trade", who(id)),
+            Command::PayDebt(PlayerId(id)) => write!(f, "{} paid their debt", who(id)),
+            Command::DeclareBankruptcy(PlayerId(id)) => {
+                write!(f, "{} declared bankruptcy", who(id))
+            }
+            Command::EndTurn(PlayerId(id)) => write!(f, "{} ended their turn", who(id)),
+            Command::Resign(PlayerId(id)) => write!(f, "{} resigned", who(id)),
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 enum Square {
     Go,
     Property(Property),
+    Railroad(Railroad),
+    Utility(Utility),
+    Tax(Tax),
+    Chance,
+    CommunityChest,
+    Jail,
+    FreeParking,
+    GoToJail,
 }
 
-static SQUARES: &'static [Square] = &[
-    Square::Go,
-    Square::Property(Property {
-        name: "Mediterranean Ave",
-        base: Money(2),
-        houses: [Money(10), Money(30), Money(90), Money(160)],
-        hotel: Money(250),
-        mortgage: Money(30),
-        house_cost: Money(50),
-        hotel_cost: (Money(50), 4),
-    }),
-];
-
-#[derive(Clone)]
-pub struct GameState {
-    squares: Vec<Square>,
-    players: Vec<Player>,
-    events: Vec<Action>,
+// The squares a game is played on, shared between clones of a game, with
+// where GO and Jail stand on them and the lots of each color group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Board {
+    squares: Arc<[Square]>,
+    groups: Arc<[Vec<PropertyId>]>, // in board order, indexed by ColorGroup
+    go: usize,
+    jail: usize,
 }
 
-impl fmt::Debug for GameState {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("GameState")
-            .field("players", &self.players)
-            .field("events", &self.events)
-            .finish()
+impl Board {
+    // Each edition's squares are laid out once, and every game on that
+    // edition shares them.
+    pub fn for_edition(edition: BoardEdition) -> Board {
+        static STANDARD: OnceLock<Board> = OnceLock::new();
+        static FRENCH: OnceLock<Board> = OnceLock::new();
+        static GERMAN: OnceLock<Board> = OnceLock::new();
+        let (board, squares): (_, fn() -> Vec<Square>) = match edition {
+            BoardEdition::Standard => (&STANDARD, || board::standard_squares().to_vec()),
+            BoardEdition::French => (&FRENCH, board::french_squares),
+            BoardEdition::German => (&GERMAN, board::german_squares),
+        };
+        board
+            .get_or_init(|| match Board::from_squares(squares()) {
+                Ok(board) => board,
+                Err(error) => panic!("the {:?} board is misprinted: {}", edition, error),
+            })
+            .clone()
+    }
+
+    pub fn go(&self) -> usize {
+        self.go
+    }
+
+    pub fn jail(&self) -> usize {
+        self.jail
+    }
+
+    // Whether moving the spaces forward from the square passes or lands on GO.
+    fn reaches_go(&self, from: usize, spaces: usize) -> bool {
+        let len = self.squares.len();
+        let to_go = match (self.go + len - from) % len {
+            0 => len,
+            distance => distance,
+        };
+        spaces >= to_go
+    }
+
+    // Where a card sends a player on this board.
+    fn landmark(&self, landmark: Landmark) -> usize {
+        match landmark {
+            Landmark::Go => self.go,
+            Landmark::FirstLot(color) => self.groups[color as usize][0].0 as usize,
+            Landmark::LastLot(color) => self.groups[color as usize].last().unwrap().0 as usize,
+            Landmark::FirstRailroad => self
+                .squares
+                .iter()
+                .position(|square| matches!(square, Square::Railroad(_)))
+                .unwrap(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.squares.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.squares.is_empty()
+    }
+
+    pub fn name(&self, index: usize) -> Option<&'static str> {
+        let name = match self.squares.get(index)? {
+            Square::Go => "GO",
+            Square::Property(property) => property.name,
+            Square::Railroad(railroad) => railroad.name,
+            Square::Utility(utility) => utility.name,
+            Square::Tax(tax) => tax.name,
+            Square::Chance => "Chance",
+            Square::CommunityChest => "Community Chest",
+            Square::Jail => "Jail",
+            Square::FreeParking => "Free Parking",
+            Square::GoToJail => "Go To Jail",
+        };
+        Some(name)
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct StateError {
-    message: String,
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BoardEdition {
+    #[default]
+    Standard,
+    French, // Paris streets, from Boulevard de Belleville to Rue de la Paix
+    German, // from Badstraße to Schlossallee
 }
 
-impl StateError {
-    pub fn new(message: &str) -> Self {
-        StateError {
-            message: message.to_string(),
+impl BoardEdition {
+    // By the name it goes by on the command line.
+    pub fn named(name: &str) -> Option<BoardEdition> {
+        match name {
+            "standard" => Some(BoardEdition::Standard),
+            "french" => Some(BoardEdition::French),
+            "german" => Some(BoardEdition::German),
+            _ => None,
         }
     }
 }
 
-impl Error for StateError {
-    fn description(&self) -> &str {
-        &self.message
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HouseRules {
+    pub free_parking_pot: bool, // taxes and fines go to whoever lands on Free Parking
+    pub double_salary_on_go: bool, // landing exactly on GO pays twice the salary
+    pub auctions_enabled: bool, // off, what nobody buys stays with the bank
+    pub snake_eyes_bonus: Option<Money>, // paid for rolling double ones
+    pub unlimited_buildings: bool, // the bank never runs out of houses or hotels
+    pub trades_any_time: bool,  // off, only on the proposer's turn and outside auctions
+    pub jail_fine: Money,       // to leave early, or forced after the third failed roll
+    pub dice: Dice,             // thrown on every roll; two six-sided dice by default
+}
+
+// Every rule a game is played by, official or not.
+pub type GameRules = HouseRules;
+
+impl Default for HouseRules {
+    fn default() -> HouseRules {
+        HouseRules {
+            free_parking_pot: false,
+            double_salary_on_go: false,
+            auctions_enabled: true,
+            snake_eyes_bonus: None,
+            unlimited_buildings: false,
+            trades_any_time: true,
+            jail_fine: JAIL_FINE,
+            dice: Dice::CLASSIC,
+        }
     }
 }
 
-impl fmt::Display for StateError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
+// The rules as printed, with whichever house rules are wanted on top:
+// HouseRules::official().with_free_parking_pot(true).
+impl HouseRules {
+    pub fn official() -> HouseRules {
+        HouseRules::default()
+    }
+
+    pub fn with_free_parking_pot(self, free_parking_pot: bool) -> HouseRules {
+        HouseRules {
+            free_parking_pot,
+            ..self
+        }
+    }
+
+    pub fn with_double_salary_on_go(self, double_salary_on_go: bool) -> HouseRules {
+        HouseRules {
+            double_salary_on_go,
+            ..self
+        }
+    }
+
+    pub fn with_auctions_enabled(self, auctions_enabled: bool) -> HouseRules {
+        HouseRules {
+            auctions_enabled,
+            ..self
+        }
+    }
+
+    pub fn with_snake_eyes_bonus(self, snake_eyes_bonus: Option<Money>) -> HouseRules {
+        HouseRules {
+            snake_eyes_bonus,
+            ..self
+        }
+    }
+
+    pub fn with_unlimited_buildings(self, unlimited_buildings: bool) -> HouseRules {
+        HouseRules {
+            unlimited_buildings,
+            ..self
+        }
+    }
+
+    pub fn with_trades_any_time(self, trades_any_time: bool) -> HouseRules {
+        HouseRules {
+            trades_any_time,
+            ..self
+        }
+    }
+
+    pub fn with_jail_fine(self, jail_fine: Money) -> HouseRules {
+        HouseRules { jail_fine, ..self }
+    }
+
+    pub fn with_dice(self, dice: Dice) -> HouseRules {
+        HouseRules { dice, ..self }
     }
 }
 
-impl GameState {
-    pub fn init() -> GameState {
-        GameState {
-            squares: SQUARES.to_vec(),
-            players: Vec::new(),
-            events: Vec::new(),
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GameConfig {
+    pub players: usize,
+    pub starting_cash: Money,
+    pub salary: Money, // every payout for GO, whether by dice, card or house rule
+    pub edition: BoardEdition,
+    pub rules: HouseRules,
+    pub seed: u64,
+    // Stamps each event with when it happened; logs keep the stamps, but a
+    // loaded config has no clock until it is given one.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub clock: SharedClock,
+    pub hash_chain: bool, // give each event a digest chained to the last
+    // Players send Roll and the engine picks the dice; RollDice, with the
+    // dice given, is refused except when replaying a log.
+    pub engine_dice: bool,
+    pub turn_limit: u32, // where simulate gives up on a game nobody has won
+    // One to a seat once anyone joins, picks a token or takes a name; empty
+    // while everyone has the token dealt to their seat and no name.
+    pub seats: Vec<Seat>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            players: 4,
+            starting_cash: Money(1500),
+            salary: Money(200),
+            edition: BoardEdition::default(),
+            rules: HouseRules::default(),
+            seed: 0,
+            clock: SharedClock::default(),
+            hash_chain: false,
+            engine_dice: false,
+            turn_limit: 1000,
+            seats: Vec::new(),
         }
     }
+}
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
 
-    fn ensure_player(&self, player_id: i8) -> Result<(), StateError> {
-        if player_id >= self.players.len().try_into().unwrap() {
-            Err(StateError::new(
-                format!("player {:?} is not a valid player", player_id).as_str(),
-            ))
+const JAIL_FINE: Money = Money(50); // the printed fine; see rules.jail_fine
+pub const BANK_HOUSES: u8 = 32;
+pub const BANK_HOTELS: u8 = 12;
+// The streams a game's seed is split into besides the decks, which are
+// shuffled from the seed itself so existing games keep their cards: the
+// engine's dice, and one for each seat's strategy from SEAT_STREAMS on.
+const DICE_STREAM: u64 = 0;
+const SEAT_STREAMS: u64 = 1;
+const RAILROAD_RENTS: [Money; 4] = [Money(25), Money(50), Money(100), Money(200)];
+
+// The mutable half of an ownable square; indexed like the board.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Deed {
+    owner: Option<PlayerId>,
+    mortgaged: bool,
+    houses: u8,
+    hotel: bool,
+}
+
+impl Deed {
+    // Houses, counting a hotel as a fifth.
+    fn level(&self) -> u8 {
+        if self.hotel {
+            5
         } else {
-            Ok(())
+            self.houses
         }
     }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Turn {
+    number: u32,
+    player: PlayerId,
+    rolls: u8,      // dice rolls taken this turn
+    doubles: u8,    // consecutive doubles rolled this turn
+    may_roll: bool, // false once the player has rolled and is not owed another roll
+}
+
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "saved::SavedState", try_from = "saved::SavedState")
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GameState {
+    config: GameConfig,
+    board: Board,
+    deeds: Vec<Deed>,
+    players: Vec<Player>,
+    turn: Turn,
+    pending: VecDeque<PendingDecision>,
+    trade: Option<Trade>,
+    pot: Money, // Free Parking, when that house rule is on
+    houses: u8, // left in the bank
+    hotels: u8,
+    chance: VecDeque<ChanceCard>,
+    community_chest: VecDeque<CommunityChestCard>,
+    dice: SeededDice, // the engine's own; see next_roll
+    events: Vec<EventRecord>,
+    compacted: usize,              // events dropped from the front of the log
+    compacted_digest: Option<u64>, // of the last event dropped
+    effects: Vec<Effect>,          // gathered during apply
+    start: Option<Arc<GameState>>, // before the first kept event, for undo
+    checkpoints: Vec<(CheckpointId, Arc<GameState>)>,
+    next_checkpoint: u32,
+    snapshots: Vec<Arc<GameState>>, // without history, every snapshot_every events
+    snapshot_every: Option<usize>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CheckpointId(u32);
+
+// A logged command and what followed from it, along with where in the game
+// it happened.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EventRecord {
+    pub seq: usize, // position in the log, counting commands
+    pub turn: u32,
+    pub actor: Option<PlayerId>,
+    pub command: Command,
+    pub effects: Vec<Effect>,
+    // Milliseconds since the Unix epoch, when the game has a clock.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time: Option<u64>,
+    // When the config asks for a hash chain; see verify_log.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub digest: Option<u64>,
+}
+
+impl EventRecord {
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        std::iter::once(Event::Command(self.command.clone()))
+            .chain(self.effects.iter().cloned().map(Event::Effect))
+    }
+}
 
-    pub fn apply(&mut self, action: Action) -> Result<(), StateError> {
-        match action {
-            Action::RollDice(PlayerId(id), RollResult(one, two)) => {
-                self.ensure_player(id)?;
-                println!("player {:?} rolled {:?}", id, one + two);
-                self.events.push(action);
-                Ok(())
+// Two states are equal when they are the same position: same players,
+// titles, buildings, decks, dice, turn and bank. The event log is deliberately left
+// out so different move orders reaching the same position compare equal; use
+// eq_including_history to compare the logs as well.
+impl PartialEq for GameState {
+    fn eq(&self, other: &GameState) -> bool {
+        // Only the squares: the rest of the board follows from them. They
+        // are not always the config's edition's, as on_board may have
+        // been given others.
+        self.config == other.config
+            && self.board.squares == other.board.squares
+            && self.deeds == other.deeds
+            && self.players == other.players
+            && self.turn == other.turn
+            && self.pending == other.pending
+            && self.trade == other.trade
+            && self.pot == other.pot
+            && self.houses == other.houses
+            && self.hotels == other.hotels
+            && self.chance == other.chance
+            && self.community_chest == other.community_chest
+            && self.dice == other.dice
+    }
+}
+
+impl Eq for GameState {}
+
+impl Hash for GameState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.config.hash(state);
+        self.board.squares.hash(state);
+        self.deeds.hash(state);
+        self.players.hash(state);
+        self.turn.hash(state);
+        self.pending.hash(state);
+        self.trade.hash(state);
+        self.pot.hash(state);
+        self.houses.hash(state);
+        self.hotels.hash(state);
+        self.chance.hash(state);
+        self.community_chest.hash(state);
+        self.dice.hash(state);
+    }
+}
+
+impl fmt::Debug for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameState")
+            .field("players", &self.players)
+            .field("events", &self.events)
+            .finish()
+    }
+}
+
+// A snapshot for people rather than tests: who has what, and whose turn it is.
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.winner() {
+            Some(winner) => writeln!(f, "{} has won", self.display_name(winner))?,
+            None => writeln!(
+                f,
+                "Turn {}: {} to move",
+                self.turn.number,
+                self.display_name(self.turn.player)
+            )?,
+        }
+        match (self.houses_remaining(), self.hotels_remaining()) {
+            (Some(houses), Some(hotels)) => {
+                write!(f, "Bank: {} houses, {} hotels", houses, hotels)?
             }
-            _ => Err(StateError::new("foo")),
+            _ => write!(f, "Bank: unlimited houses and hotels")?,
         }
+        if self.config.rules.free_parking_pot {
+            write!(f, ", ${} in the Free Parking pot", self.pot.0)?;
+        }
+        writeln!(f)?;
+        for player in &self.players {
+            write!(f, "{} ({}): ", player, player.token)?;
+            if let Some(turn) = player.eliminated {
+                writeln!(f, "bankrupt on turn {}", turn)?;
+                continue;
+            }
+            write!(f, "${}", player.cash.0)?;
+            if player.jail.is_some() {
+                write!(f, ", in jail")?;
+            } else {
+                write!(f, ", on {}", self.square_name(player.position))?;
+            }
+            if !player.jail_cards.is_empty() {
+                write!(
+                    f,
+                    ", {} Get Out of Jail Free card(s)",
+                    player.jail_cards.len()
+                )?;
+            }
+            writeln!(f)?;
+            let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+            for (index, deed) in self.deeds.iter().enumerate() {
+                if deed.owner != Some(player.id) {
+                    continue;
+                }
+                let group = match &self.board.squares[index] {
+                    Square::Property(property) => format!("{:?}", property.color),
+                    Square::Railroad(_) => "Railroads".to_string(),
+                    Square::Utility(_) => "Utilities".to_string(),
+                    _ => continue,
+                };
+                let mut lot = self.square_name(index).to_string();
+                if deed.hotel {
+                    lot.push_str(" (hotel)");
+                } else if deed.houses > 0 {
+                    let plural = if deed.houses == 1 { "" } else { "s" };
+                    lot.push_str(&format!(" ({} house{})", deed.houses, plural));
+                }
+                if deed.mortgaged {
+                    lot.push_str(" [mortgaged]");
+                }
+                match groups.iter_mut().find(|(name, _)| *name == group) {
+                    Some((_, lots)) => lots.push(lot),
+                    None => groups.push((group, vec![lot])),
+                }
+            }
+            for (group, lots) in groups {
+                writeln!(f, "  {}: {}", group, lots.join(", "))?;
+            }
+        }
+        Ok(())
     }
 }
 
-mod tests {
-    use super::*;
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum GameError {
+    InvalidConfig(String),
+    InvalidState(String),
+    InvalidPlayer(PlayerId),
+    InvalidProperty(PropertyId),
+    TooManyPlayers,
+    NotYourTurn(PlayerId),
+    DecisionPending(PendingDecision),
+    InsufficientFunds { needed: Money, available: Money },
+    NotOwner(PlayerId, PropertyId),
+    IllegalAction(String),
+    RuleViolation(RuleViolation),
+    Unsupported(Command),
+    TokenTaken(Token),
+    InvalidName(String),
+    RosterClosed,
+    InvalidRoll { dice: Vec<i8>, allowed: Dice }, // not a throw the game's dice could make
+    GameOver { winner: PlayerId },
+    NameTaken(String),
+}
 
-    #[test]
-    fn roll_dice_with_invalid_player_raises() {
-        let mut state = GameState::init();
-        let result = state.apply(Action::RollDice(PlayerId(0), RollResult(1, 2)));
-        assert_eq!(
-            result,
-            Err(StateError::new("player 0 is not a valid player"))
-        );
+impl Error for GameError {}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::InvalidConfig(message) => write!(f, "invalid config: {}", message),
+            GameError::InvalidState(message) => write!(f, "invalid state: {}", message),
+            GameError::InvalidPlayer(PlayerId(id)) => {
+                write!(f, "player {} is not a valid player", id)
+            }
+            GameError::InvalidProperty(PropertyId(id)) => {
+                write!(f, "square {} is not a property", id)
+            }
+            GameError::TooManyPlayers => {
+                write!(f, "a game cannot have more than {} players", MAX_PLAYERS)
+            }
+            GameError::NotYourTurn(PlayerId(id)) => write!(f, "it is not player {}'s turn", id),
+            GameError::DecisionPending(decision) => {
+                write!(f, "waiting on {:?} first", decision)
+            }
+            GameError::InsufficientFunds { needed, available } => {
+                write!(f, "needs ${} but only has ${}", needed.0, available.0)
+            }
+            GameError::NotOwner(PlayerId(player), PropertyId(property)) => {
+                write!(f, "player {} does not own square {}", player, property)
+            }
+            GameError::IllegalAction(message) => write!(f, "{}", message),
+            GameError::RuleViolation(violation) => write!(f, "{}", violation),
+            GameError::Unsupported(action) => write!(f, "{:?} is not a player action", action),
+            GameError::TokenTaken(token) => write!(f, "the {} is already taken", token),
+            GameError::InvalidName(message) => write!(f, "invalid name: {}", message),
+            GameError::RosterClosed => write!(f, "players can only join before the first turn"),
+            GameError::InvalidRoll { dice, allowed } => {
+                let faces: Vec<_> = dice.iter().map(|die| die.to_string()).collect();
+                write!(
+                    f,
+                    "{} is not a roll of {}: {} dice of 1 to {}",
+                    faces.join("+"),
+                    allowed,
+                    allowed.count,
+                    allowed.faces
+                )
+            }
+            GameError::GameOver {
+                winner: PlayerId(id),
+            } => write!(f, "the game is over; player {} has won", id),
+            GameError::NameTaken(name) => write!(f, "{} is already someone's name", name),
+        }
+    }
+}
+
+// A move the rules of the game forbid, with what stands in the way, for
+// interfaces to explain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RuleViolation {
+    // Building needs every lot in the group. Of the lots the builder lacks,
+    // those in held_by belong to other players and the rest to the bank.
+    IncompleteColorGroup {
+        missing: Vec<PropertyId>,
+        held_by: Vec<(PropertyId, PlayerId)>,
+    },
+    // Nor can anyone build on a group while any lot in it is mortgaged, even
+    // a lot other than the one being built on.
+    MortgagedInGroup {
+        mortgaged: Vec<PropertyId>,
+    },
+}
+
+impl From<RuleViolation> for GameError {
+    fn from(violation: RuleViolation) -> GameError {
+        GameError::RuleViolation(violation)
+    }
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleViolation::IncompleteColorGroup { missing, held_by } => {
+                let lots: Vec<String> = missing
+                    .iter()
+                    .map(|lot| match held_by.iter().find(|(held, _)| held == lot) {
+                        Some((_, PlayerId(owner))) => {
+                            format!("square {} (player {}'s)", lot.0, owner)
+                        }
+                        None => format!("square {} (unowned)", lot.0),
+                    })
+                    .collect();
+                write!(
+                    f,
+                    "building requires owning every lot in the color group; missing {}",
+                    lots.join(", ")
+                )
+            }
+            RuleViolation::MortgagedInGroup { mortgaged } => {
+                let lots: Vec<String> = mortgaged
+                    .iter()
+                    .map(|lot| format!("square {}", lot.0))
+                    .collect();
+                write!(
+                    f,
+                    "every lot in the color group must be unmortgaged before building; {} {} mortgaged",
+                    lots.join(", "),
+                    if lots.len() == 1 { "is" } else { "are" }
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayError {
+    Config(GameError),
+    Event(Box<ReplayFailure>),
+    NoSuchEvent {
+        index: usize,
+        events: usize,
+    },
+    // The command at index applied, but not with the effects the log says.
+    Diverged {
+        index: usize,
+        logged: Vec<Effect>,
+        produced: Vec<Effect>,
+    },
+    Compacted {
+        index: usize,
+        first: usize,
+    }, // first is the earliest event kept
+    // The first event whose digest doesn't follow from the log before it.
+    Tampered {
+        index: usize,
+    },
+}
+
+impl Error for ReplayError {}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Config(error) => write!(f, "cannot start the game: {}", error),
+            ReplayError::Event(failure) => write!(
+                f,
+                "event {} (turn {}, {:?}): {}",
+                failure.index, failure.turn, failure.action, failure.underlying
+            ),
+            ReplayError::NoSuchEvent { index, events } => {
+                write!(f, "asked for event {} of a log of {}", index, events)
+            }
+            ReplayError::Diverged {
+                index,
+                logged,
+                produced,
+            } => write!(
+                f,
+                "event {} was logged as {:?} but now does {:?}",
+                index, logged, produced
+            ),
+            ReplayError::Compacted { index, first } => write!(
+                f,
+                "event {} was compacted away; the log starts at {}",
+                index, first
+            ),
+            ReplayError::Tampered { index } => {
+                write!(f, "event {} does not match its digest", index)
+            }
+        }
+    }
+}
+
+// Why an event in a log would not apply. The state just before it would
+// have accepted any of legal_actions_at_failure instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayFailure {
+    pub index: usize,
+    pub turn: u32,
+    pub action: Command,
+    pub underlying: GameError,
+    pub legal_actions_at_failure: Vec<Command>,
+}
+
+impl ReplayError {
+    fn at(state: &GameState, index: usize, action: &Command, underlying: GameError) -> ReplayError {
+        ReplayError::Event(Box::new(ReplayFailure {
+            index,
+            turn: state.turn.number,
+            action: action.clone(),
+            underlying,
+            legal_actions_at_failure: state.legal_actions(),
+        }))
+    }
+}
+
+fn illegal(message: &str) -> GameError {
+    GameError::IllegalAction(message.to_string())
+}
+
+impl GameState {
+    pub fn new(config: GameConfig) -> Result<GameState, GameError> {
+        let board = Board::for_edition(config.edition);
+        GameState::on_board(config, board)
+    }
+
+    // A game on a board of its own, such as one from BoardBuilder, in place
+    // of the config's edition. Saves name only the edition, so such a game
+    // can be undone and replayed but save_to refuses it.
+    pub fn on_board(config: GameConfig, board: Board) -> Result<GameState, GameError> {
+        if config.players < MIN_PLAYERS || config.players > MAX_PLAYERS {
+            return Err(GameError::InvalidConfig(format!(
+                "expected {} to {} players, got {}",
+                MIN_PLAYERS, MAX_PLAYERS, config.players
+            )));
+        }
+        if config.starting_cash.0 < 0 || config.salary.0 < 0 || config.rules.jail_fine.0 < 0 {
+            return Err(GameError::InvalidConfig(
+                "starting cash, salary and jail fine must not be negative".to_string(),
+            ));
+        }
+        // A throw may pass GO once, but never carry a player all the way round.
+        let dice = config.rules.dice;
+        if dice.count == 0 || dice.faces < 2 || dice.max_total() >= board.len() as i32 {
+            return Err(GameError::InvalidConfig(format!(
+                "{} can't be rolled: at least one die of two faces or more, and less than a lap",
+                dice
+            )));
+        }
+        let mut decks = Rng::new(config.seed);
+        let mut chance = CHANCE_CARDS.to_vec();
+        decks.shuffle(&mut chance);
+        let mut community_chest = COMMUNITY_CHEST_CARDS.to_vec();
+        decks.shuffle(&mut community_chest);
+        let mut state = GameState {
+            deeds: vec![Deed::default(); board.len()],
+            board,
+            players: Vec::new(),
+            turn: Turn {
+                number: 1,
+                player: PlayerId(0),
+                rolls: 0,
+                doubles: 0,
+                may_roll: true,
+            },
+            pending: VecDeque::new(),
+            trade: None,
+            pot: Money(0),
+            houses: BANK_HOUSES,
+            hotels: BANK_HOTELS,
+            chance: chance.into(),
+            community_chest: community_chest.into(),
+            dice: SeededDice::from_rng(Rng::derive(config.seed, DICE_STREAM)),
+            events: Vec::new(),
+            compacted: 0,
+            compacted_digest: None,
+            effects: Vec::new(),
+            start: None,
+            checkpoints: Vec::new(),
+            next_checkpoint: 0,
+            snapshots: Vec::new(),
+            snapshot_every: None,
+            config,
+        };
+        if state.config.seats.len() > state.config.players {
+            return Err(GameError::InvalidConfig(format!(
+                "{} seats settled for {} players",
+                state.config.seats.len(),
+                state.config.players
+            )));
+        }
+        // Each seat counts itself back into the config as it is taken.
+        for seat in 0..std::mem::take(&mut state.config.players) {
+            match state.config.seats.get(seat).cloned() {
+                Some(Seat { token, name }) => {
+                    let id = state.seat(token)?;
+                    if let Some(name) = name {
+                        state.name_seat(id, &name)?;
+                    }
+                }
+                None => {
+                    let token = state.dealt_token().ok_or(GameError::TooManyPlayers)?;
+                    state.seat(token)?;
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    // Rebuilds a game from its config and event log. Everything random was
+    // settled by the seed or is recorded in the events, so this reaches the
+    // same state the log came from. Each command is applied again, and the
+    // effects after it, if the log has any, must be the ones it has now.
+    pub fn replay(config: GameConfig, events: &[Event]) -> Result<GameState, ReplayError> {
+        let mut state = GameState::new(config).map_err(ReplayError::Config)?;
+        let mut events = events.iter().peekable();
+        let mut index: usize = 0;
+        loop {
+            let mut logged = Vec::new();
+            while let Some(Event::Effect(effect)) = events.peek() {
+                logged.push(effect.clone());
+                events.next();
+            }
+            let produced = match index {
+                0 => Vec::new(),
+                _ => state.events.last().unwrap().effects.clone(),
+            };
+            if !logged.is_empty() && logged != produced {
+                return Err(ReplayError::Diverged {
+                    index: index.saturating_sub(1),
+                    logged,
+                    produced,
+                });
+            }
+            let command = match events.next() {
+                Some(Event::Command(command)) => command,
+                _ => return Ok(state),
+            };
+            if let Err(error) = state.apply_logged(command.clone()) {
+                return Err(ReplayError::at(&state, index, command, error));
+            }
+            index += 1;
+        }
+    }
+
+    // Starts a game and applies each command in turn, failing like replay
+    // at the first that doesn't apply.
+    pub fn play(config: GameConfig, commands: &[Command]) -> Result<GameState, ReplayError> {
+        let mut state = GameState::new(config).map_err(ReplayError::Config)?;
+        for (index, command) in commands.iter().enumerate() {
+            if let Err(error) = state.apply(command.clone()) {
+                return Err(ReplayError::at(&state, index, command, error));
+            }
+        }
+        Ok(state)
+    }
+
+    // Takes back the most recent event by replaying everything before it, so
+    // deck order and anything else it disturbed comes back too.
+    pub fn undo(&mut self) -> Result<Command, GameError> {
+        if self.events.is_empty() {
+            return Err(illegal(match self.compacted {
+                0 => "there is nothing to undo",
+                _ => "the events before this were compacted away",
+            }));
+        }
+        let mut state = match self.state_at(self.event_count() - 1) {
+            Ok(state) => state,
+            Err(ReplayError::Config(error)) => return Err(error),
+            Err(error) => panic!("the log only holds actions that applied: {}", error),
+        };
+        // The replay may have started from a snapshot that knows nothing of
+        // the events before it.
+        let last = self.events.pop().unwrap();
+        state.events = std::mem::take(&mut self.events);
+        state.compacted = self.compacted;
+        state.start = self.start.take();
+        self.restore(state);
+        Ok(last.command)
+    }
+
+    // The state after the first `index` events, replayed from the latest
+    // checkpoint or snapshot before then. A state replayed from a snapshot
+    // only has the history since it.
+    pub fn state_at(&self, index: usize) -> Result<GameState, ReplayError> {
+        if index > self.event_count() {
+            return Err(ReplayError::NoSuchEvent {
+                index,
+                events: self.event_count(),
+            });
+        }
+        if index < self.compacted {
+            return Err(ReplayError::Compacted {
+                index,
+                first: self.compacted,
+            });
+        }
+        if index == self.event_count() {
+            return Ok(self.clone());
+        }
+        // Anything from before the kept log can't be replayed forward.
+        let usable =
+            |snapshot: &&Arc<GameState>| (self.compacted..=index).contains(&snapshot.event_count());
+        let base = self
+            .checkpoints
+            .iter()
+            .map(|(_, snapshot)| snapshot)
+            .chain(&self.snapshots)
+            .chain(&self.start)
+            .filter(usable)
+            .max_by_key(|snapshot| snapshot.event_count());
+        let mut state = match base {
+            Some(snapshot) => GameState::clone(snapshot),
+            // A state read back from a save has no start position, so it is
+            // assumed to have begun as a fresh game.
+            // Who sits where, and under what name, is in the config.
+            None if self.compacted == 0 => {
+                GameState::on_board(self.config.clone(), self.board.clone())
+                    .map_err(ReplayError::Config)?
+            }
+            None => {
+                return Err(ReplayError::Compacted {
+                    index,
+                    first: self.compacted,
+                })
+            }
+        };
+        for index in state.event_count()..index {
+            let action = &self.events[index - self.compacted].command;
+            if let Err(error) = state.apply_logged(action.clone()) {
+                return Err(ReplayError::at(&state, index, action, error));
+            }
+        }
+        Ok(state)
+    }
+
+    // Counting any that were compacted away.
+    pub fn event_count(&self) -> usize {
+        self.compacted + self.events.len()
+    }
+
+    // Takes a snapshot every `events` events from now on, so state_at and
+    // undo replay at most that many. None stops taking them.
+    pub fn set_snapshot_interval(&mut self, events: Option<usize>) {
+        self.snapshot_every = events.filter(|events| *events > 0);
+    }
+
+    // Drops all but the last `keep_last` events. The state they led to is
+    // kept as the new starting point, so everything after it can still be
+    // replayed; state_at refuses anything earlier.
+    pub fn compact(&mut self, keep_last: usize) {
+        let dropped = self.events.len().saturating_sub(keep_last);
+        if dropped == 0 {
+            return;
+        }
+        let baseline = self
+            .state_at(self.compacted + dropped)
+            .expect("the log only holds actions that applied");
+        self.start = Some(Arc::new(baseline.snapshot()));
+        self.compacted_digest = self.events[dropped - 1].digest;
+        self.events.drain(..dropped);
+        self.compacted += dropped;
+        let compacted = self.compacted;
+        self.snapshots
+            .retain(|snapshot| snapshot.event_count() >= compacted);
+    }
+
+    // This position with no history, standing in for every event so far.
+    // Built field by field so the log is never copied.
+    fn snapshot(&self) -> GameState {
+        GameState {
+            config: self.config.clone(),
+            board: self.board.clone(),
+            deeds: self.deeds.clone(),
+            players: self.players.clone(),
+            turn: self.turn.clone(),
+            pending: self.pending.clone(),
+            trade: self.trade.clone(),
+            pot: self.pot,
+            houses: self.houses,
+            hotels: self.hotels,
+            chance: self.chance.clone(),
+            community_chest: self.community_chest.clone(),
+            dice: self.dice.clone(),
+            events: Vec::new(),
+            compacted: self.event_count(),
+            compacted_digest: self.last_digest(),
+            effects: Vec::new(),
+            start: None,
+            checkpoints: Vec::new(),
+            next_checkpoint: self.next_checkpoint,
+            snapshots: Vec::new(),
+            snapshot_every: self.snapshot_every,
+        }
+    }
+
+    // Remembers this exact moment so rollback can return to it.
+    pub fn checkpoint(&mut self) -> CheckpointId {
+        let id = CheckpointId(self.next_checkpoint);
+        self.next_checkpoint += 1;
+        let mut snapshot = self.clone();
+        snapshot.checkpoints.clear();
+        self.checkpoints.push((id, Arc::new(snapshot)));
+        id
+    }
+
+    // Returns to a checkpoint, dropping every event since. Checkpoints taken
+    // after it no longer exist.
+    pub fn rollback(&mut self, id: CheckpointId) -> Result<(), GameError> {
+        let position = self
+            .checkpoints
+            .iter()
+            .position(|(checkpoint, _)| *checkpoint == id)
+            .ok_or_else(|| illegal("that checkpoint does not exist or was rolled back past"))?;
+        self.checkpoints.truncate(position + 1);
+        let snapshot = GameState::clone(&self.checkpoints[position].1);
+        self.restore(snapshot);
+        Ok(())
+    }
+
+    // Replaces the position with an earlier one, keeping the checkpoints that
+    // are still in its past.
+    fn restore(&mut self, mut state: GameState) {
+        let events = state.event_count();
+        state.checkpoints = std::mem::take(&mut self.checkpoints);
+        state
+            .checkpoints
+            .retain(|(_, snapshot)| snapshot.event_count() <= events);
+        state.next_checkpoint = self.next_checkpoint;
+        state.snapshots = std::mem::take(&mut self.snapshots);
+        state
+            .snapshots
+            .retain(|snapshot| snapshot.event_count() <= events);
+        state.snapshot_every = self.snapshot_every;
+        *self = state;
+    }
+
+    // Until the first command is applied, players may join and settle their
+    // names; after it the roster is fixed. Undoing back to the start opens
+    // it again.
+    pub fn has_started(&self) -> bool {
+        self.event_count() > 0
+    }
+
+    // Seats a player with the first token nobody has.
+    pub fn add_player(&mut self) -> Result<PlayerId, GameError> {
+        match self.dealt_token() {
+            Some(token) => self.add_player_as(token),
+            None => Err(GameError::TooManyPlayers),
+        }
+    }
+
+    pub fn add_player_as(&mut self, token: Token) -> Result<PlayerId, GameError> {
+        let id = self.seat(token)?;
+        self.record_seats();
+        Ok(id)
+    }
+
+    fn dealt_token(&self) -> Option<Token> {
+        Token::ALL
+            .iter()
+            .copied()
+            .find(|token| self.players.iter().all(|player| player.token != *token))
+    }
+
+    fn seat(&mut self, token: Token) -> Result<PlayerId, GameError> {
+        if self.has_started() {
+            return Err(GameError::RosterClosed);
+        }
+        if self.players.len() >= MAX_PLAYERS {
+            return Err(GameError::TooManyPlayers);
+        }
+        if self.players.iter().any(|player| player.token == token) {
+            return Err(GameError::TokenTaken(token));
+        }
+        let id = PlayerId(self.players.len().try_into().unwrap());
+        self.players.push(Player {
+            id,
+            cash: self.config.starting_cash,
+            position: self.board.go,
+            jail: None,
+            jail_cards: Vec::new(),
+            eliminated: None,
+            token,
+            name: None,
+        });
+        // Replays and undo seat the game from the config.
+        self.config.players += 1;
+        Ok(id)
+    }
+
+    // Names are settled before the first roll, like who sits where.
+    pub fn rename(&mut self, id: PlayerId, name: &str) -> Result<(), GameError> {
+        self.name_seat(id, name)?;
+        self.record_seats();
+        Ok(())
+    }
+
+    // Tokens and names go into the config with the count, for the same
+    // reason as in seat.
+    fn record_seats(&mut self) {
+        self.config.seats = self.seats();
+    }
+
+    pub(super) fn seats(&self) -> Vec<Seat> {
+        self.players
+            .iter()
+            .map(|player| Seat {
+                token: player.token,
+                name: player.name.clone(),
+            })
+            .collect()
+    }
+
+    fn name_seat(&mut self, id: PlayerId, name: &str) -> Result<(), GameError> {
+        self.player(id)?;
+        if self.has_started() {
+            return Err(illegal(
+                "players can only be renamed before the game begins",
+            ));
+        }
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(GameError::InvalidName("a name cannot be blank".to_string()));
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(GameError::InvalidName(format!(
+                "a name can be at most {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        // Every player goes by a name nobody else does, including the
+        // "Player N" of whoever takes seat N, seated yet or not.
+        let folded = name.to_lowercase();
+        let someone_elses = folded
+            .strip_prefix("player ")
+            .and_then(|seat| seat.parse::<i64>().ok())
+            .is_some_and(|seat| seat != id.0 as i64);
+        if someone_elses
+            || self
+                .players
+                .iter()
+                .any(|other| other.id != id && other.to_string().to_lowercase() == folded)
+        {
+            return Err(GameError::NameTaken(name.to_string()));
+        }
+        self.players[id.0 as usize].name = Some(name.to_string());
+        Ok(())
+    }
+
+    // The player's name, or "Player N" for those without one.
+    pub fn display_name(&self, id: PlayerId) -> String {
+        match self.player(id) {
+            Ok(player) => player.to_string(),
+            Err(_) => format!("Player {}", id.0),
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn player(&self, id: PlayerId) -> Result<&Player, GameError> {
+        usize::try_from(id.0)
+            .ok()
+            .and_then(|index| self.players.get(index))
+            .ok_or(GameError::InvalidPlayer(id))
+    }
+
+    // Everyone who sat down, bankrupt or not.
+    pub fn players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter()
+    }
+
+    // Those still playing, in seating order.
+    pub fn active_players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|player| !player.is_bankrupt())
+    }
+
+    pub fn cash(&self, id: PlayerId) -> Result<Money, GameError> {
+        self.player(id).map(Player::cash)
+    }
+
+    pub fn position(&self, id: PlayerId) -> Result<usize, GameError> {
+        self.player(id).map(Player::position)
+    }
+
+    pub fn current_player(&self) -> PlayerId {
+        self.turn.player
+    }
+
+    pub fn turn_number(&self) -> u32 {
+        self.turn.number
+    }
+
+    pub fn pending(&self) -> Option<&PendingDecision> {
+        self.pending.front()
+    }
+
+    pub fn trade_offer(&self) -> Option<&Trade> {
+        self.trade.as_ref()
+    }
+
+    pub fn free_parking_pot(&self) -> Money {
+        self.pot
+    }
+
+    // The last player standing, once everyone else is bankrupt.
+    pub fn winner(&self) -> Option<PlayerId> {
+        let mut solvent = self.active_players();
+        match (solvent.next(), solvent.next()) {
+            (Some(player), None) => Some(player.id),
+            _ => None,
+        }
+    }
+
+    // Cash plus every title at its printed price, less what it would take to
+    // lift any mortgage on it, plus what the buildings on it cost.
+    pub fn net_worth(&self, id: PlayerId) -> Result<Money, GameError> {
+        self.player(id)?;
+        let mortgages: i32 = (0..self.deeds.len())
+            .filter(|index| self.deeds[*index].owner == Some(id) && self.deeds[*index].mortgaged)
+            .map(|index| self.unmortgage_cost(index).0)
+            .sum();
+        Ok(Money(self.total_worth(id).0 - mortgages))
+    }
+
+    pub fn owner_of(&self, property: PropertyId) -> Option<PlayerId> {
+        let index = self.ensure_property(property).ok()?;
+        self.deeds[index].owner
+    }
+
+    // Every title the player holds in board order, with whether it is
+    // currently mortgaged.
+    pub fn properties_owned_by(&self, player: PlayerId) -> Vec<(PropertyId, bool)> {
+        self.deeds
+            .iter()
+            .enumerate()
+            .filter(|(_, deed)| deed.owner == Some(player))
+            .map(|(index, deed)| (PropertyId(index as i8), deed.mortgaged))
+            .collect()
+    }
+
+    pub fn mortgaged_properties(&self, player: PlayerId) -> Vec<PropertyId> {
+        self.properties_owned_by(player)
+            .into_iter()
+            .filter_map(|(property, mortgaged)| mortgaged.then_some(property))
+            .collect()
+    }
+
+    pub fn unmortgaged_properties(&self, player: PlayerId) -> Vec<PropertyId> {
+        self.properties_owned_by(player)
+            .into_iter()
+            .filter_map(|(property, mortgaged)| (!mortgaged).then_some(property))
+            .collect()
+    }
+
+    // What the player could raise without trading: every building sold back
+    // for half its cost and every title still clear mortgaged. Bankruptcy is
+    // only open to a debtor who couldn't pay even with all of it.
+    pub fn mortgage_capacity(&self, player: PlayerId) -> Money {
+        Money(
+            (0..self.deeds.len())
+                .filter(|index| self.deeds[*index].owner == Some(player))
+                .map(|index| {
+                    let mortgage = match self.deeds[index].mortgaged {
+                        true => 0,
+                        false => self.mortgage_value(index).0,
+                    };
+                    mortgage + self.building_cost(index).0 / 2
+                })
+                .sum(),
+        )
+    }
+
+    // A player holds a monopoly when they own every lot in the group.
+    // Mortgaged lots still count, just as they do for doubling rent.
+    pub fn is_monopoly(&self, id: PlayerId, color: ColorGroup) -> bool {
+        self.color_group_members(color)
+            .iter()
+            .all(|property| self.deeds[property.0 as usize].owner == Some(id))
+    }
+
+    pub fn color_group_members(&self, color: ColorGroup) -> &[PropertyId] {
+        &self.board.groups[color as usize]
+    }
+
+    pub fn monopolies_of(&self, id: PlayerId) -> Vec<ColorGroup> {
+        ColorGroup::ALL
+            .iter()
+            .copied()
+            .filter(|color| self.is_monopoly(id, *color))
+            .collect()
+    }
+
+    // None when the bank's stock is unlimited.
+    pub fn houses_remaining(&self) -> Option<u8> {
+        match self.config.rules.unlimited_buildings {
+            true => None,
+            false => Some(self.houses),
+        }
+    }
+
+    pub fn hotels_remaining(&self) -> Option<u8> {
+        match self.config.rules.unlimited_buildings {
+            true => None,
+            false => Some(self.hotels),
+        }
+    }
+
+    // Houses and hotels back to the bank, or out of it when negative. An
+    // unlimited stock isn't counted.
+    fn restock(&mut self, houses: i32, hotels: i32) {
+        if self.config.rules.unlimited_buildings {
+            return;
+        }
+        self.houses = (self.houses as i32 + houses) as u8;
+        self.hotels = (self.hotels as i32 + hotels) as u8;
+    }
+
+    // Each lot in the group with its house count and whether it has a hotel.
+    pub fn buildings_on(&self, color: ColorGroup) -> Vec<(PropertyId, u8, bool)> {
+        self.color_group_members(color)
+            .iter()
+            .map(|property| {
+                let deed = &self.deeds[property.0 as usize];
+                (*property, deed.houses, deed.hotel)
+            })
+            .collect()
+    }
+
+    // The log flattened into the command and effect stream replay takes.
+    pub fn event_stream(&self) -> Vec<Event> {
+        self.events.iter().flat_map(EventRecord::events).collect()
+    }
+
+    pub fn commands(&self) -> Vec<Command> {
+        self.events
+            .iter()
+            .map(|record| record.command.clone())
+            .collect()
+    }
+
+    // The log as it is held, without copying it.
+    pub fn events(&self) -> &[EventRecord] {
+        &self.events
+    }
+
+    // The command applied most recently, if the log still holds it.
+    pub fn last_event(&self) -> Option<&EventRecord> {
+        self.events.last()
+    }
+
+    pub fn events_in_turn(&self, turn: u32) -> impl Iterator<Item = &EventRecord> {
+        self.events.iter().filter(move |record| record.turn == turn)
+    }
+
+    // The commands the player sent, not those that merely affected them.
+    pub fn events_for(&self, player: PlayerId) -> impl Iterator<Item = &EventRecord> {
+        self.events
+            .iter()
+            .filter(move |record| record.actor == Some(player))
+    }
+
+    #[deprecated(note = "use events")]
+    pub fn records(&self) -> &[EventRecord] {
+        self.events()
+    }
+
+    #[deprecated(note = "use last_event")]
+    pub fn last_record(&self) -> Option<&EventRecord> {
+        self.last_event()
+    }
+
+    #[deprecated(note = "use events_in_turn")]
+    pub fn records_in_turn(&self, turn: u32) -> impl Iterator<Item = &EventRecord> {
+        self.events_in_turn(turn)
+    }
+
+    #[deprecated(note = "use events_for")]
+    pub fn records_by(&self, player: PlayerId) -> impl Iterator<Item = &EventRecord> {
+        self.events_for(player)
+    }
+
+    pub fn eq_including_history(&self, other: &GameState) -> bool {
+        self == other && self.events == other.events
+    }
+
+    // Stamps the events from here on; a loaded game needs its clock back.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.config.clock = clock;
+    }
+
+    // The dice the engine would roll next. Every roll that is applied moves
+    // them on, wherever it came from, so they follow from the seed and the
+    // log alone and carry through saves, replays and undo unchanged. As a
+    // pair of six-sided dice; next_throw follows the rules' dice.
+    pub fn next_roll(&self) -> RollResult {
+        self.dice.clone().roll()
+    }
+
+    // The same for whatever dice the rules throw, one face per die.
+    pub fn next_throw(&self) -> Vec<i8> {
+        self.dice.clone().throw(self.config.rules.dice)
+    }
+
+    // The command a throw is logged as: RollDice for two dice, RollMany for
+    // any other number.
+    fn rolled(&self, id: PlayerId, dice: Vec<i8>) -> Command {
+        match dice[..] {
+            [one, two] => Command::RollDice(id, RollResult(one, two)),
+            _ => Command::RollMany(id, dice),
+        }
+    }
+
+    // Rolls the engine's dice for the player.
+    pub fn roll(&mut self, player: PlayerId) -> Result<Vec<Effect>, GameError> {
+        self.apply(Command::Roll(player))
+    }
+
+    // Like apply, but leaves this state alone and returns the one that
+    // follows. The board is shared, so this is cheap enough for search.
+    pub fn applied(&self, action: Command) -> Result<GameState, GameError> {
+        let mut next = self.clone();
+        next.apply(action)?;
+        Ok(next)
+    }
+
+    // The effects applying the command would have, or why it would be
+    // refused, leaving this state as it is.
+    pub fn dry_run(&self, action: Command) -> Result<Vec<Effect>, GameError> {
+        self.clone().apply(action)
+    }
+
+    // Every action that would apply right now. Rolls are listed once, as 1+2
+    // or a throw of all ones, since the dice never decide whether a roll is
+    // allowed; bids once, at the lowest that would count; and trades not at
+    // all.
+    pub fn legal_actions(&self) -> Vec<Command> {
+        let mut candidates = Vec::new();
+        for player in self.active_players() {
+            let id = player.id;
+            candidates.push(match self.config.engine_dice {
+                true => Command::Roll(id),
+                false if self.config.rules.dice.is_pair() => {
+                    Command::RollDice(id, RollResult(1, 2))
+                }
+                false => Command::RollMany(id, vec![1; self.config.rules.dice.count as usize]),
+            });
+            candidates.extend([
+                Command::DeclineToBuy(id),
+                Command::PayIncomeTax(id, TaxChoice::Flat),
+                Command::PayIncomeTax(id, TaxChoice::Percent),
+                Command::PayJailFine(id),
+                Command::UseGetOutOfJailFreeCard(id),
+                Command::PassAuction(id),
+                Command::AcceptTrade(id),
+                Command::RejectTrade(id),
+                Command::PayDebt(id),
+                Command::DeclareBankruptcy(id),
+                Command::EndTurn(id),
+                Command::Resign(id),
+            ]);
+            if let Some(PendingDecision::Auction { high_bid, .. }) = self.pending.front() {
+                let high = high_bid.as_ref().map_or(0, |Bid(_, amount)| amount.0);
+                candidates.push(Command::PlaceBid(id, Money(high + 1)));
+            }
+            for index in 0..self.board.len() {
+                let property = PropertyId(index as i8);
+                if self.ensure_property(property).is_err() {
+                    continue;
+                }
+                candidates.extend([
+                    Command::BuyProperty(id, property),
+                    Command::BuyHouse(id, property),
+                    Command::SellHouse(id, property),
+                    Command::BuyHotel(id, property),
+                    Command::SellHotel(id, property),
+                    Command::MortgageProperty(id, property),
+                    Command::UnmortgageProperty(id, property),
+                ]);
+            }
+        }
+        // Tried on a copy without the log, which may be long.
+        let scratch = self.snapshot();
+        candidates
+            .into_iter()
+            .filter(|action| scratch.applied(action.clone()).is_ok())
+            .collect()
+    }
+
+    // A command as a player sends it. Roll becomes a RollDice (or RollMany)
+    // with the engine's dice, and in an engine_dice game that is the only way
+    // to roll.
+    pub fn apply(&mut self, action: Command) -> Result<Vec<Effect>, GameError> {
+        // Once someone has won there is nothing left to play.
+        if let Some(winner) = self.winner() {
+            return Err(GameError::GameOver { winner });
+        }
+        let action = match action {
+            Command::Roll(id) => self.rolled(id, self.next_throw()),
+            Command::RollDice(..) | Command::RollMany(..) if self.config.engine_dice => {
+                let error = illegal("in this game the engine rolls the dice");
+                debug!(target: "monopoly::apply", "rejected {:?}: {}", action, error);
+                return Err(error);
+            }
+            action => action,
+        };
+        self.apply_logged(action)
+    }
+
+    // A command as the log holds it, so with the dice already rolled.
+    fn apply_logged(&mut self, action: Command) -> Result<Vec<Effect>, GameError> {
+        self.effects.clear();
+        if self.event_count() == 0 {
+            self.start = None;
+            self.start = Some(Arc::new(self.clone()));
+        }
+        let waiting = self.pending.front().cloned();
+        let turn = self.turn.number;
+        let applied = match &action {
+            Command::RollDice(id, RollResult(one, two)) => self.roll_dice(*id, &[*one, *two]),
+            Command::RollMany(_, dice) if dice.len() == 2 => {
+                Err(illegal("two dice are rolled as a RollDice"))
+            }
+            Command::RollMany(id, dice) => self.roll_dice(*id, dice),
+            Command::BuyProperty(id, property) => self.buy_property(*id, *property),
+            Command::DeclineToBuy(id) => self.decline_to_buy(*id),
+            Command::PayIncomeTax(id, choice) => self.pay_income_tax(*id, *choice),
+            Command::PayJailFine(id) => self.pay_jail_fine(*id),
+            Command::UseGetOutOfJailFreeCard(id) => self.use_jail_card(*id),
+            Command::AuctionProperty(property, bids) => self.settle_auction(*property, bids),
+            Command::PlaceBid(id, amount) => self.place_bid(*id, *amount),
+            Command::PassAuction(id) => self.pass_auction(*id),
+            Command::BuyHouse(id, property) => self.buy_house(*id, *property),
+            Command::SellHouse(id, property) => self.sell_house(*id, *property),
+            Command::BuyHotel(id, property) => self.buy_hotel(*id, *property),
+            Command::SellHotel(id, property) => self.sell_hotel(*id, *property),
+            Command::MortgageProperty(id, property) => self.mortgage(*id, *property),
+            Command::UnmortgageProperty(id, property) => self.unmortgage(*id, *property),
+            Command::ProposeTrade(trade) => self.propose_trade(trade),
+            Command::AcceptTrade(id) => self.accept_trade(*id),
+            Command::RejectTrade(id) => self.reject_trade(*id),
+            Command::PayDebt(id) => self.pay_debt(*id),
+            Command::DeclareBankruptcy(id) => self.declare_bankruptcy(*id),
+            Command::EndTurn(id) => self.end_turn(*id),
+            Command::Resign(id) => self.resign(*id),
+            _ => Err(GameError::Unsupported(action.clone())),
+        };
+        if let Err(error) = applied {
+            debug!(target: "monopoly::apply", "rejected {:?}: {}", action, error);
+            return Err(error);
+        }
+        if let Command::RollDice(..) | Command::RollMany(..) = action {
+            self.dice.throw(self.config.rules.dice);
+        }
+        if let Some(decision) = self.pending.front() {
+            if waiting.as_ref() != Some(decision) {
+                self.effects.push(Effect::DecisionPending(decision.clone()));
+            }
+        }
+        let effects = std::mem::take(&mut self.effects);
+        trace!(target: "monopoly::apply", "applied {:?}: {:?}", action, effects);
+        let mut record = EventRecord {
+            seq: self.event_count(),
+            turn,
+            actor: action.actor(),
+            command: action,
+            effects: effects.clone(),
+            time: self.config.clock.now(),
+            digest: None,
+        };
+        if self.config.hash_chain {
+            record.digest = Some(record.chained(self.last_digest()));
+        }
+        self.events.push(record);
+        if let Some(every) = self.snapshot_every {
+            if self.event_count().is_multiple_of(every) {
+                let snapshot = self.snapshot();
+                self.snapshots.push(Arc::new(snapshot));
+            }
+        }
+        Ok(effects)
+    }
+
+    fn ensure_active(&self, id: PlayerId) -> Result<&Player, GameError> {
+        let player = self.player(id)?;
+        if player.is_bankrupt() {
+            return Err(illegal(&format!("player {} is bankrupt", id.0)));
+        }
+        Ok(player)
+    }
+
+    fn ensure_property(&self, property: PropertyId) -> Result<usize, GameError> {
+        match usize::try_from(property.0)
+            .ok()
+            .and_then(|index| self.board.squares.get(index).map(|square| (index, square)))
+        {
+            Some((index, Square::Property(_)))
+            | Some((index, Square::Railroad(_)))
+            | Some((index, Square::Utility(_))) => Ok(index),
+            _ => Err(GameError::InvalidProperty(property)),
+        }
+    }
+
+    fn ensure_owner(&self, id: PlayerId, property: PropertyId) -> Result<usize, GameError> {
+        let index = self.ensure_property(property)?;
+        if self.deeds[index].owner != Some(id) {
+            return Err(GameError::NotOwner(id, property));
+        }
+        Ok(index)
+    }
+
+    // Fails unless the front decision is one this player must resolve.
+    fn ensure_decision(&self, id: PlayerId) -> Result<&PendingDecision, GameError> {
+        self.ensure_active(id)?;
+        match self.pending.front() {
+            Some(decision) if decision.player() == id => Ok(decision),
+            Some(decision) => Err(GameError::DecisionPending(decision.clone())),
+            None => Err(illegal("there is no decision to make")),
+        }
+    }
+
+    fn ensure_current(&self, id: PlayerId) -> Result<&Player, GameError> {
+        let player = self.ensure_active(id)?;
+        if self.turn.player != id {
+            return Err(GameError::NotYourTurn(id));
+        }
+        Ok(player)
+    }
+
+    fn ensure_funds(&self, id: PlayerId, needed: Money) -> Result<(), GameError> {
+        let available = self.cash(id)?;
+        if available.0 < needed.0 {
+            return Err(GameError::InsufficientFunds { needed, available });
+        }
+        Ok(())
+    }
+
+    // As ensure_funds, but counting all that selling up would raise.
+    fn ensure_raisable(&self, id: PlayerId, needed: Money) -> Result<(), GameError> {
+        if self.could_pay(id, needed) {
+            return Ok(());
+        }
+        let cash = self.players[id.0 as usize].cash;
+        Err(GameError::InsufficientFunds {
+            needed,
+            available: Money(cash.0 + self.mortgage_capacity(id).0),
+        })
+    }
+
+    fn player_mut(&mut self, id: PlayerId) -> &mut Player {
+        &mut self.players[id.0 as usize]
+    }
+
+    // Money from the bank.
+    fn credit(&mut self, id: PlayerId, amount: Money) {
+        self.player_mut(id).cash.0 += amount.0;
+        self.effects.push(Effect::Received { player: id, amount });
+    }
+
+    // Money to the bank.
+    fn debit(&mut self, id: PlayerId, amount: Money) {
+        self.player_mut(id).cash.0 -= amount.0;
+        self.effects.push(Effect::Paid {
+            from: id,
+            to: None,
+            amount,
+        });
+    }
+
+    // Passing GO or landing on it, on the way to `to`.
+    fn go_round(&mut self, id: PlayerId, to: usize) {
+        self.effects.push(match to == self.board.go {
+            true => Effect::LandedOnGo(id),
+            false => Effect::PassedGo(id),
+        });
+        self.pay_salary(id);
+    }
+
+    fn pay_salary(&mut self, id: PlayerId) {
+        let amount = self.config.salary;
+        self.player_mut(id).cash.0 += amount.0;
+        self.effects
+            .push(Effect::CollectedSalary { player: id, amount });
+    }
+
+    fn acquire(&mut self, id: PlayerId, index: usize) {
+        self.deeds[index].owner = Some(id);
+        self.effects.push(Effect::Acquired {
+            player: id,
+            property: PropertyId(index as i8),
+        });
+    }
+
+    fn leave_jail(&mut self, id: PlayerId) {
+        self.player_mut(id).jail = None;
+        self.effects.push(Effect::LeftJail(id));
+    }
+
+    fn square_name(&self, index: usize) -> &'static str {
+        self.board.name(index).unwrap_or("an unknown square")
+    }
+
+    fn price(&self, index: usize) -> Money {
+        match self.board.squares.get(index) {
+            Some(Square::Property(property)) => property.price,
+            Some(Square::Railroad(railroad)) => railroad.price,
+            Some(Square::Utility(utility)) => utility.price,
+            _ => Money(0),
+        }
+    }
+
+    fn mortgage_value(&self, index: usize) -> Money {
+        match &self.board.squares[index] {
+            Square::Property(property) => property.mortgage,
+            Square::Railroad(railroad) => railroad.mortgage,
+            Square::Utility(utility) => utility.mortgage,
+            _ => Money(0),
+        }
+    }
+
+    // Cash plus the printed price of every title, mortgaged or not, plus what
+    // the buildings on them cost.
+    fn total_worth(&self, id: PlayerId) -> Money {
+        let titles: i32 = (0..self.deeds.len())
+            .filter(|index| self.deeds[*index].owner == Some(id))
+            .map(|index| self.price(index).0 + self.building_cost(index).0)
+            .sum();
+        Money(self.players[id.0 as usize].cash.0 + titles)
+    }
+
+    fn building_cost(&self, index: usize) -> Money {
+        let deed = &self.deeds[index];
+        match &self.board.squares[index] {
+            Square::Property(property) if deed.hotel => Money(
+                property.hotel_cost.0 .0 + property.house_cost.0 * property.hotel_cost.1 as i32,
+            ),
+            Square::Property(property) => Money(property.house_cost.0 * deed.houses as i32),
+            _ => Money(0),
+        }
+    }
+
+    fn street(&self, index: usize) -> Option<&Property> {
+        match &self.board.squares[index] {
+            Square::Property(property) => Some(property),
+            _ => None,
+        }
+    }
+
+    fn group_has_buildings(&self, index: usize) -> bool {
+        self.street(index).is_some_and(|property| {
+            self.color_group_members(property.color)
+                .iter()
+                .any(|lot| self.deeds[lot.0 as usize].level() > 0)
+        })
+    }
+
+    // The lowest and highest building level across the lot's color group.
+    fn group_levels(&self, index: usize) -> (u8, u8) {
+        let color = self.street(index).map(|street| street.color).unwrap();
+        let levels = self
+            .color_group_members(color)
+            .iter()
+            .map(|lot| self.deeds[lot.0 as usize].level());
+        (levels.clone().min().unwrap(), levels.max().unwrap())
+    }
+
+    fn group_has_mortgage(&self, color: ColorGroup) -> bool {
+        self.color_group_members(color)
+            .iter()
+            .any(|lot| self.deeds[lot.0 as usize].mortgaged)
+    }
+
+    fn count_owned<F: Fn(&Square) -> bool>(&self, id: PlayerId, kind: F) -> usize {
+        self.board
+            .squares
+            .iter()
+            .zip(&self.deeds)
+            .filter(|(square, deed)| kind(square) && deed.owner == Some(id))
+            .count()
+    }
+
+    // What landing on the property would cost anyone but its owner right now.
+    // Utility rent depends on the dice, so previewing it needs a roll.
+    pub fn rent_due(
+        &self,
+        property: PropertyId,
+        roll: Option<&RollResult>,
+    ) -> Result<Money, GameError> {
+        let total = roll.map(|RollResult(one, two)| *one as i32 + *two as i32);
+        self.rent_for(property, total)
+    }
+
+    // rent_due for a throw of any dice, given what they came to.
+    fn rent_for(&self, property: PropertyId, total: Option<i32>) -> Result<Money, GameError> {
+        let index = self.ensure_property(property)?;
+        let deed = &self.deeds[index];
+        let owner = match deed.owner {
+            Some(owner) if !deed.mortgaged => owner,
+            _ => return Ok(Money(0)),
+        };
+        let rent = match &self.board.squares[index] {
+            Square::Property(property) if deed.hotel => property.hotel,
+            Square::Property(property) if deed.houses > 0 => {
+                property.houses[deed.houses as usize - 1]
+            }
+            Square::Property(property) => {
+                if self.is_monopoly(owner, property.color) {
+                    Money(property.base.0 * 2)
+                } else {
+                    property.base
+                }
+            }
+            Square::Railroad(_) => {
+                let owned = self.count_owned(owner, |square| matches!(square, Square::Railroad(_)));
+                RAILROAD_RENTS[owned - 1]
+            }
+            Square::Utility(_) => {
+                let total = total
+                    .ok_or_else(|| illegal("utility rent depends on the dice; supply a roll"))?;
+                let owned = self.count_owned(owner, |square| matches!(square, Square::Utility(_)));
+                Money(total * if owned == 2 { 10 } else { 4 })
+            }
+            _ => unreachable!("ensure_property only accepts ownable squares"),
+        };
+        Ok(rent)
+    }
+
+    // Takes the amount now if the debtor can afford it, otherwise leaves a
+    // debt they must settle (by raising cash) or go bankrupt over.
+    fn charge(&mut self, debtor: PlayerId, creditor: Option<PlayerId>, amount: Money) {
+        let in_debt = self.pending.iter().any(|decision| match decision {
+            PendingDecision::PayDebt { debtor: d, .. }
+            | PendingDecision::PayBid { debtor: d, .. } => *d == debtor,
+            _ => false,
+        });
+        if !in_debt && self.players[debtor.0 as usize].cash.0 >= amount.0 {
+            self.transfer(debtor, creditor, amount);
+        } else {
+            self.pending.push_back(PendingDecision::PayDebt {
+                debtor,
+                creditor,
+                amount,
+            });
+        }
+    }
+
+    fn transfer(&mut self, debtor: PlayerId, creditor: Option<PlayerId>, amount: Money) {
+        self.player_mut(debtor).cash.0 -= amount.0;
+        match creditor {
+            Some(creditor) => self.player_mut(creditor).cash.0 += amount.0,
+            None if self.config.rules.free_parking_pot => self.pot.0 += amount.0,
+            None => {}
+        }
+        self.effects.push(Effect::Paid {
+            from: debtor,
+            to: creditor,
+            amount,
+        });
+    }
+
+    fn roll_dice(&mut self, id: PlayerId, dice: &[i8]) -> Result<(), GameError> {
+        self.ensure_active(id)?;
+        let allowed = self.config.rules.dice;
+        if !allowed.allows(dice) {
+            return Err(GameError::InvalidRoll {
+                dice: dice.to_vec(),
+                allowed,
+            });
+        }
+        let total: i32 = dice.iter().map(|&die| die as i32).sum();
+        if let Some(PendingDecision::UtilityRoll { player, property }) = self.pending.front() {
+            if *player != id {
+                return Err(GameError::NotYourTurn(id));
+            }
+            let property = *property;
+            self.pending.pop_front();
+            let owner = self.owner_of(property).unwrap();
+            let rent = Money(10 * total);
+            debug!(
+                target: "monopoly::rent",
+                "player {} owes player {} ten times the dice, ${}, on {}",
+                id.0,
+                owner.0,
+                rent.0,
+                self.square_name(property.0 as usize)
+            );
+            self.charge(id, Some(owner), rent);
+            return Ok(());
+        }
+        self.ensure_current(id)?;
+        if let Some(decision) = self.pending.front() {
+            return Err(GameError::DecisionPending(decision.clone()));
+        }
+        if !self.turn.may_roll {
+            return Err(illegal("no roll is owed this turn"));
+        }
+        // Only a pair can come up doubles, or snake eyes.
+        let doubles = matches!(dice, [one, two] if one == two);
+        self.turn.rolls += 1;
+        self.turn.may_roll = false;
+        if let ([1, 1], Some(bonus)) = (dice, self.config.rules.snake_eyes_bonus) {
+            self.effects.push(Effect::SnakeEyes(id));
+            self.credit(id, bonus);
+        }
+        if let Some(attempts) = self.players[id.0 as usize].jail {
+            if doubles {
+                self.leave_jail(id);
+            } else if attempts < 2 {
+                self.player_mut(id).jail = Some(attempts + 1);
+                return Ok(());
+            } else {
+                // The third failed attempt: pay up and move anyway.
+                self.leave_jail(id);
+                self.charge(id, None, self.config.rules.jail_fine);
+            }
+            self.advance(id, total, total);
+            return Ok(());
+        }
+        if doubles {
+            self.turn.doubles += 1;
+            if self.turn.doubles == 3 {
+                self.send_to_jail(id);
+                return Ok(());
+            }
+        }
+        self.advance(id, total, total);
+        // Landing may have sent the player to jail, which ends the turn.
+        self.turn.may_roll = doubles && !self.players[id.0 as usize].in_jail();
+        Ok(())
+    }
+
+    // thrown is what the dice came to this turn, for any utility rent.
+    fn advance(&mut self, id: PlayerId, spaces: i32, thrown: i32) {
+        let len = self.board.squares.len() as i32;
+        let from = self.players[id.0 as usize].position as i32;
+        let to = (from + spaces).rem_euclid(len) as usize;
+        if spaces > 0 && self.board.reaches_go(from as usize, spaces as usize) {
+            self.go_round(id, to);
+        }
+        self.player_mut(id).position = to;
+        self.effects.push(Effect::Moved { player: id, to });
+        self.land(id, thrown);
+    }
+
+    fn advance_to(&mut self, id: PlayerId, target: usize, thrown: i32) {
+        let len = self.board.squares.len();
+        let from = self.players[id.0 as usize].position;
+        let spaces = (target + len - from) % len;
+        self.advance(id, spaces as i32, thrown);
+    }
+
+    fn send_to_jail(&mut self, id: PlayerId) {
+        let jail = self.board.jail;
+        let player = self.player_mut(id);
+        player.position = jail;
+        player.jail = Some(0);
+        self.effects.push(Effect::WentToJail(id));
+        if self.turn.player == id {
+            self.turn.may_roll = false;
+        }
+    }
+
+    fn land(&mut self, id: PlayerId, thrown: i32) {
+        let index = self.players[id.0 as usize].position;
+        match &self.board.squares[index] {
+            Square::Go => {
+                if self.config.rules.double_salary_on_go {
+                    self.pay_salary(id);
+                }
+            }
+            Square::Property(_) | Square::Railroad(_) | Square::Utility(_) => {
+                match self.deeds[index].owner {
+                    None => self.pending.push_back(PendingDecision::BuyOrDecline {
+                        player: id,
+                        property: PropertyId(index as i8),
+                    }),
+                    Some(owner) if owner != id => {
+                        let rent = self
+                            .rent_for(PropertyId(index as i8), Some(thrown))
+                            .expect("every rent is known once the dice are");
+                        debug!(
+                            target: "monopoly::rent",
+                            "player {} owes player {} ${} on {}",
+                            id.0,
+                            owner.0,
+                            rent.0,
+                            self.square_name(index)
+                        );
+                        if rent.0 > 0 {
+                            self.charge(id, Some(owner), rent);
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+            Square::Tax(tax) => match tax.percent {
+                Some(_) => self.pending.push_back(PendingDecision::IncomeTax {
+                    player: id,
+                    worth: self.total_worth(id),
+                }),
+                None => {
+                    let amount = tax.amount;
+                    self.charge(id, None, amount);
+                }
+            },
+            Square::Chance => {
+                let card = self.chance.pop_front().unwrap();
+                self.resolve_card(id, Card::Chance(card), card.effect(), thrown);
+            }
+            Square::CommunityChest => {
+                let card = self.community_chest.pop_front().unwrap();
+                self.resolve_card(id, Card::CommunityChest(card), card.effect(), thrown);
+            }
+            Square::FreeParking => {
+                let pot = std::mem::replace(&mut self.pot, Money(0));
+                self.credit(id, pot);
+            }
+            Square::GoToJail => self.send_to_jail(id),
+            Square::Jail => {}
+        }
+    }
+
+    fn resolve_card(&mut self, id: PlayerId, card: Card, effect: CardEffect, thrown: i32) {
+        self.effects.push(Effect::DrewCard {
+            player: id,
+            card: card.clone(),
+        });
+        if effect == CardEffect::GetOutOfJailFree {
+            self.player_mut(id).jail_cards.push(card);
+            return;
+        }
+        self.return_card(card);
+        match effect {
+            CardEffect::AdvanceTo(landmark) => {
+                let target = self.board.landmark(landmark);
+                self.advance_to(id, target, thrown)
+            }
+            CardEffect::AdvanceToNearestRailroad => {
+                let target = self.nearest(id, |square| matches!(square, Square::Railroad(_)));
+                match self.deeds[target].owner {
+                    Some(owner) if owner != id => {
+                        self.advance_without_landing(id, target);
+                        let rent = self.rent_due(PropertyId(target as i8), None).unwrap();
+                        debug!(
+                            target: "monopoly::rent",
+                            "player {} owes player {} twice ${} on {}",
+                            id.0,
+                            owner.0,
+                            rent.0,
+                            self.square_name(target)
+                        );
+                        if rent.0 > 0 {
+                            self.charge(id, Some(owner), Money(rent.0 * 2));
+                        }
+                    }
+                    _ => self.advance_to(id, target, thrown),
+                }
+            }
+            CardEffect::AdvanceToNearestUtility => {
+                let target = self.nearest(id, |square| matches!(square, Square::Utility(_)));
+                match self.deeds[target].owner {
+                    Some(owner) if owner != id && !self.deeds[target].mortgaged => {
+                        // Pay ten times a fresh roll instead of the usual rent.
+                        self.advance_without_landing(id, target);
+                        self.pending.push_back(PendingDecision::UtilityRoll {
+                            player: id,
+                            property: PropertyId(target as i8),
+                        });
+                    }
+                    _ => self.advance_to(id, target, thrown),
+                }
+            }
+            CardEffect::GoBack(spaces) => self.advance(id, -(spaces as i32), thrown),
+            CardEffect::Collect(amount) => self.credit(id, amount),
+            CardEffect::Pay(amount) => self.charge(id, None, amount),
+            CardEffect::CollectFromEachPlayer(amount) => {
+                for other in self.other_active_players(id) {
+                    self.charge(other, Some(id), amount);
+                }
+            }
+            CardEffect::PayEachPlayer(amount) => {
+                for other in self.other_active_players(id) {
+                    self.charge(id, Some(other), amount);
+                }
+            }
+            CardEffect::Repairs {
+                per_house,
+                per_hotel,
+            } => {
+                let (houses, hotels) = self
+                    .deeds
+                    .iter()
+                    .filter(|deed| deed.owner == Some(id))
+                    .fold((0, 0), |(houses, hotels), deed| {
+                        (houses + deed.houses as i32, hotels + deed.hotel as i32)
+                    });
+                let amount = Money(houses * per_house.0 + hotels * per_hotel.0);
+                if amount.0 > 0 {
+                    self.charge(id, None, amount);
+                }
+            }
+            CardEffect::GoToJail => self.send_to_jail(id),
+            CardEffect::GetOutOfJailFree => unreachable!(),
+        }
+    }
+
+    // Moves forward to the target, collecting salary on the way, without
+    // resolving the square itself.
+    fn advance_without_landing(&mut self, id: PlayerId, target: usize) {
+        let (from, len) = (self.players[id.0 as usize].position, self.board.len());
+        if self.board.reaches_go(from, (target + len - from) % len) {
+            self.go_round(id, target);
+        }
+        self.player_mut(id).position = target;
+        self.effects.push(Effect::Moved {
+            player: id,
+            to: target,
+        });
+    }
+
+    fn nearest<F: Fn(&Square) -> bool>(&self, id: PlayerId, kind: F) -> usize {
+        let len = self.board.squares.len();
+        let from = self.players[id.0 as usize].position;
+        (1..=len)
+            .map(|offset| (from + offset) % len)
+            .find(|index| kind(&self.board.squares[*index]))
+            .unwrap()
+    }
+
+    fn return_card(&mut self, card: Card) {
+        match card {
+            Card::Chance(card) => self.chance.push_back(card),
+            Card::CommunityChest(card) => self.community_chest.push_back(card),
+        }
+    }
+
+    fn other_active_players(&self, id: PlayerId) -> Vec<PlayerId> {
+        self.active_players()
+            .filter(|player| player.id != id)
+            .map(|player| player.id)
+            .collect()
+    }
+
+    fn buy_property(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        match self.ensure_decision(id)? {
+            PendingDecision::BuyOrDecline {
+                property: offered, ..
+            } if *offered == property => {}
+            _ => return Err(illegal("that property is not for sale")),
+        }
+        let index = property.0 as usize;
+        let price = self.price(index);
+        self.ensure_funds(id, price)?;
+        self.pending.pop_front();
+        self.debit(id, price);
+        self.acquire(id, index);
+        Ok(())
+    }
+
+    fn decline_to_buy(&mut self, id: PlayerId) -> Result<(), GameError> {
+        let property = match self.ensure_decision(id)? {
+            PendingDecision::BuyOrDecline { property, .. } => *property,
+            _ => return Err(illegal("there is nothing to decline")),
+        };
+        self.pending.pop_front();
+        if !self.config.rules.auctions_enabled {
+            debug!(
+                target: "monopoly::auction",
+                "player {} declines {}, which stays with the bank",
+                id.0,
+                self.square_name(property.0 as usize)
+            );
+            return Ok(());
+        }
+        let auction = self.auction(property, id);
+        debug!(
+            target: "monopoly::auction",
+            "player {} declines {}, which goes to auction",
+            id.0,
+            self.square_name(property.0 as usize)
+        );
+        self.pending.push_front(auction);
+        Ok(())
+    }
+
+    // Everyone still in the game may bid, starting with the given player.
+    fn auction(&self, property: PropertyId, first: PlayerId) -> PendingDecision {
+        let count = self.players.len();
+        let bidders = (0..count)
+            .map(|offset| &self.players[(first.0 as usize + offset) % count])
+            .filter(|player| !player.is_bankrupt())
+            .map(|player| player.id)
+            .collect();
+        PendingDecision::Auction {
+            property,
+            high_bid: None,
+            bidders,
+            next: 0,
+        }
+    }
+
+    fn ensure_auctions(&self) -> Result<(), GameError> {
+        match self.config.rules.auctions_enabled {
+            true => Ok(()),
+            false => Err(illegal("auctions disabled")),
+        }
+    }
+
+    fn place_bid(&mut self, id: PlayerId, amount: Money) -> Result<(), GameError> {
+        self.ensure_auctions()?;
+        let high = match self.ensure_decision(id)? {
+            PendingDecision::Auction { high_bid, .. } => {
+                high_bid.as_ref().map_or(0, |bid| bid.1 .0)
+            }
+            _ => return Err(illegal("there is no auction running")),
+        };
+        if amount.0 <= high {
+            return Err(illegal(&format!("bids must be higher than ${}", high)));
+        }
+        self.ensure_raisable(id, amount)?;
+        debug!(target: "monopoly::auction", "player {} bids ${}", id.0, amount.0);
+        if let Some(PendingDecision::Auction {
+            high_bid,
+            bidders,
+            next,
+            ..
+        }) = self.pending.front_mut()
+        {
+            *high_bid = Some(Bid(id, amount));
+            *next = (*next + 1) % bidders.len();
+        }
+        self.continue_auction();
+        Ok(())
+    }
+
+    fn pass_auction(&mut self, id: PlayerId) -> Result<(), GameError> {
+        self.ensure_auctions()?;
+        match self.ensure_decision(id)? {
+            PendingDecision::Auction { .. } => {}
+            _ => return Err(illegal("there is no auction running")),
+        }
+        debug!(target: "monopoly::auction", "player {} drops out", id.0);
+        if let Some(PendingDecision::Auction { bidders, next, .. }) = self.pending.front_mut() {
+            bidders.remove(*next);
+            if !bidders.is_empty() {
+                *next %= bidders.len();
+            }
+        }
+        self.continue_auction();
+        Ok(())
+    }
+
+    // Ends the auction once nobody is left to outbid the high bidder, and
+    // otherwise skips over the high bidder, who has nothing to respond to.
+    fn continue_auction(&mut self) {
+        let (property, winner) = match self.pending.front_mut() {
+            Some(PendingDecision::Auction {
+                property,
+                high_bid,
+                bidders,
+                next,
+            }) => {
+                let leader = high_bid.as_ref().map(|bid| bid.0);
+                if bidders.is_empty() || (bidders.len() == 1 && leader == Some(bidders[0])) {
+                    (*property, high_bid.clone())
+                } else {
+                    if leader == Some(bidders[*next]) {
+                        *next = (*next + 1) % bidders.len();
+                    }
+                    return;
+                }
+            }
+            _ => return,
+        };
+        self.pending.pop_front();
+        self.sell_at_auction(property, winner);
+    }
+
+    // To the high bidder, if there was one; otherwise it stays with the bank.
+    fn sell_at_auction(&mut self, property: PropertyId, winner: Option<Bid>) {
+        let name = self.square_name(property.0 as usize);
+        match winner {
+            Some(Bid(winner, amount)) => {
+                debug!(
+                    target: "monopoly::auction",
+                    "player {} wins {} for ${}",
+                    winner.0,
+                    name,
+                    amount.0
+                );
+                if self.players[winner.0 as usize].cash.0 >= amount.0 {
+                    self.debit(winner, amount);
+                    self.acquire(winner, property.0 as usize);
+                } else {
+                    // The bid counted on selling up, which comes first.
+                    self.pending.push_front(PendingDecision::PayBid {
+                        debtor: winner,
+                        property,
+                        amount,
+                    });
+                }
+            }
+            None => debug!(target: "monopoly::auction", "nobody bid on {}", name),
+        }
+    }
+
+    fn settle_auction(&mut self, property: PropertyId, bids: &[Bid]) -> Result<(), GameError> {
+        self.ensure_auctions()?;
+        let bidders = match self.pending.front() {
+            Some(PendingDecision::Auction {
+                property: auctioned,
+                bidders,
+                ..
+            }) if *auctioned == property => bidders,
+            _ => return Err(illegal("that property is not being auctioned")),
+        };
+        let mut winner: Option<&Bid> = None;
+        for bid in bids {
+            if !bidders.contains(&bid.0) {
+                return Err(illegal(&format!("player {} is not bidding", (bid.0).0)));
+            }
+            if bid.1 .0 <= 0 {
+                return Err(illegal("bids must be positive"));
+            }
+            self.ensure_raisable(bid.0, bid.1)?;
+            if winner.is_none_or(|best| bid.1 .0 > best.1 .0) {
+                winner = Some(bid);
+            }
+        }
+        let winner = winner.cloned();
+        self.pending.pop_front();
+        self.sell_at_auction(property, winner);
+        Ok(())
+    }
+
+    fn pay_income_tax(&mut self, id: PlayerId, choice: TaxChoice) -> Result<(), GameError> {
+        let worth = match self.ensure_decision(id)? {
+            PendingDecision::IncomeTax { worth, .. } => *worth,
+            _ => return Err(illegal("no income tax is due")),
+        };
+        let index = self.players[id.0 as usize].position;
+        let (amount, percent) = match &self.board.squares[index] {
+            Square::Tax(tax) => (tax.amount, tax.percent.unwrap_or(0)),
+            _ => unreachable!("income tax is only owed on a tax square"),
+        };
+        self.pending.pop_front();
+        let due = match choice {
+            TaxChoice::Flat => amount,
+            TaxChoice::Percent => Money(worth.0 * percent / 100),
+        };
+        self.charge(id, None, due);
+        Ok(())
+    }
+
+    fn ensure_jail_exit(&self, id: PlayerId) -> Result<(), GameError> {
+        let player = self.ensure_current(id)?;
+        if !player.in_jail() {
+            return Err(illegal("only a jailed player can do that"));
+        }
+        if self.turn.rolls > 0 {
+            return Err(illegal("that must be done before rolling"));
+        }
+        Ok(())
+    }
+
+    fn pay_jail_fine(&mut self, id: PlayerId) -> Result<(), GameError> {
+        self.ensure_jail_exit(id)?;
+        let fine = self.config.rules.jail_fine;
+        self.ensure_funds(id, fine)?;
+        self.transfer(id, None, fine);
+        self.leave_jail(id);
+        Ok(())
+    }
+
+    fn use_jail_card(&mut self, id: PlayerId) -> Result<(), GameError> {
+        self.ensure_jail_exit(id)?;
+        let card = match self.player_mut(id).jail_cards.pop() {
+            Some(card) => card,
+            None => return Err(illegal("no Get Out of Jail Free card to use")),
+        };
+        self.return_card(card);
+        self.leave_jail(id);
+        Ok(())
+    }
+
+    fn mortgage(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        self.ensure_active(id)?;
+        let index = self.ensure_owner(id, property)?;
+        if self.deeds[index].mortgaged {
+            return Err(illegal("that property is already mortgaged"));
+        }
+        if self.group_has_buildings(index) {
+            return Err(illegal(
+                "buildings in the color group must be sold before mortgaging",
+            ));
+        }
+        self.deeds[index].mortgaged = true;
+        self.credit(id, self.mortgage_value(index));
+        Ok(())
+    }
+
+    // Lifting a mortgage costs its value plus 10% interest, rounded up.
+    fn unmortgage_cost(&self, index: usize) -> Money {
+        let value = self.mortgage_value(index).0;
+        Money(value + (value + 9) / 10)
+    }
+
+    fn unmortgage(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        self.ensure_active(id)?;
+        let index = self.ensure_owner(id, property)?;
+        if !self.deeds[index].mortgaged {
+            return Err(illegal("that property is not mortgaged"));
+        }
+        let cost = self.unmortgage_cost(index);
+        self.ensure_funds(id, cost)?;
+        self.debit(id, cost);
+        self.deeds[index].mortgaged = false;
+        Ok(())
+    }
+
+    // Checks the lot is a street the player may build on and returns it with
+    // the building levels across its group.
+    fn ensure_buildable(
+        &self,
+        id: PlayerId,
+        property: PropertyId,
+    ) -> Result<(usize, &Property, Vec<u8>), GameError> {
+        self.ensure_active(id)?;
+        let index = self.ensure_owner(id, property)?;
+        let street = self
+            .street(index)
+            .ok_or_else(|| illegal("only streets can have buildings"))?;
+        let missing: Vec<PropertyId> = self
+            .color_group_members(street.color)
+            .iter()
+            .copied()
+            .filter(|lot| self.deeds[lot.0 as usize].owner != Some(id))
+            .collect();
+        if !missing.is_empty() {
+            let held_by = missing
+                .iter()
+                .filter_map(|lot| Some((*lot, self.deeds[lot.0 as usize].owner?)))
+                .collect();
+            return Err(RuleViolation::IncompleteColorGroup { missing, held_by }.into());
+        }
+        let mortgaged: Vec<PropertyId> = self
+            .color_group_members(street.color)
+            .iter()
+            .copied()
+            .filter(|lot| self.deeds[lot.0 as usize].mortgaged)
+            .collect();
+        if !mortgaged.is_empty() {
+            return Err(RuleViolation::MortgagedInGroup { mortgaged }.into());
+        }
+        let levels = self
+            .color_group_members(street.color)
+            .iter()
+            .map(|lot| self.deeds[lot.0 as usize].level())
+            .collect();
+        Ok((index, street, levels))
+    }
+
+    // Every lot the player could put a house or hotel on right now, with
+    // what it would cost: the whole group held with nothing in it mortgaged,
+    // built up evenly, the bank able to supply it and the cash to pay.
+    pub fn buildable_properties(&self, id: PlayerId) -> Vec<(PropertyId, Money)> {
+        let cash = self
+            .players
+            .get(id.0 as usize)
+            .map_or(0, |player| player.cash.0);
+        let mut lots = Vec::new();
+        for (index, deed) in self.deeds.iter().enumerate() {
+            let street = match self.street(index) {
+                Some(street)
+                    if deed.owner == Some(id)
+                        && self.is_monopoly(id, street.color)
+                        && !self.group_has_mortgage(street.color) =>
+                {
+                    street
+                }
+                _ => continue,
+            };
+            let (lowest, _) = self.group_levels(index);
+            let needed = street.hotel_cost.1 as u8;
+            let cost = if deed.hotel {
+                continue;
+            } else if deed.houses < needed {
+                if deed.level() != lowest || self.houses_remaining() == Some(0) {
+                    continue;
+                }
+                street.house_cost
+            } else {
+                if lowest < needed || self.hotels_remaining() == Some(0) {
+                    continue;
+                }
+                street.hotel_cost.0
+            };
+            if cash >= cost.0 {
+                lots.push((PropertyId(index as i8), cost));
+            }
+        }
+        lots
+    }
+
+    fn buy_house(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        let (index, street, levels) = self.ensure_buildable(id, property)?;
+        let deed = &self.deeds[index];
+        if deed.hotel || deed.houses as i8 >= street.hotel_cost.1 {
+            return Err(illegal("that lot has all the houses it can take"));
+        }
+        if deed.level() > *levels.iter().min().unwrap() {
+            return Err(illegal("houses must be built evenly across the group"));
+        }
+        if self.houses_remaining() == Some(0) {
+            return Err(illegal("the bank has no houses left"));
+        }
+        let cost = street.house_cost;
+        self.ensure_funds(id, cost)?;
+        self.debit(id, cost);
+        self.restock(-1, 0);
+        self.deeds[index].houses += 1;
+        Ok(())
+    }
+
+    fn buy_hotel(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        let (index, street, levels) = self.ensure_buildable(id, property)?;
+        let needed = street.hotel_cost.1 as u8;
+        let deed = &self.deeds[index];
+        if deed.hotel || deed.houses != needed {
+            return Err(illegal(&format!(
+                "a hotel needs {} houses on the lot first",
+                needed
+            )));
+        }
+        if levels.iter().any(|level| *level < needed) {
+            return Err(illegal("houses must be built evenly across the group"));
+        }
+        if self.hotels_remaining() == Some(0) {
+            return Err(illegal("the bank has no hotels left"));
+        }
+        let cost = street.hotel_cost.0;
+        self.ensure_funds(id, cost)?;
+        self.debit(id, cost);
+        self.restock(needed as i32, -1);
+        self.deeds[index].houses = 0;
+        self.deeds[index].hotel = true;
+        Ok(())
+    }
+
+    // Buildings go back to the bank at half what they cost.
+    fn sell_house(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        let (index, street, levels) = self.ensure_buildable(id, property)?;
+        let deed = &self.deeds[index];
+        if deed.hotel || deed.houses == 0 {
+            return Err(illegal("there is no house on that lot to sell"));
+        }
+        if deed.level() < *levels.iter().max().unwrap() {
+            return Err(illegal("houses must be sold evenly across the group"));
+        }
+        let refund = Money(street.house_cost.0 / 2);
+        self.credit(id, refund);
+        self.restock(1, 0);
+        self.deeds[index].houses -= 1;
+        Ok(())
+    }
+
+    // A hotel breaks back down into houses if the bank has enough of them;
+    // whatever it can't supply is paid out as if those houses were sold too.
+    fn sell_hotel(&mut self, id: PlayerId, property: PropertyId) -> Result<(), GameError> {
+        let (index, street, _) = self.ensure_buildable(id, property)?;
+        if !self.deeds[index].hotel {
+            return Err(illegal("there is no hotel on that lot to sell"));
+        }
+        let needed = street.hotel_cost.1 as u8;
+        let replaced = self
+            .houses_remaining()
+            .map_or(needed, |houses| needed.min(houses));
+        let refund = Money(
+            street.hotel_cost.0 .0 / 2 + (needed - replaced) as i32 * street.house_cost.0 / 2,
+        );
+        self.credit(id, refund);
+        self.restock(-(replaced as i32), 1);
+        self.deeds[index].hotel = false;
+        self.deeds[index].houses = replaced;
+        Ok(())
+    }
+
+    // Sells every building the player owns back to the bank.
+    fn liquidate_buildings(&mut self, id: PlayerId) {
+        for index in 0..self.deeds.len() {
+            if self.deeds[index].owner != Some(id) || self.deeds[index].level() == 0 {
+                continue;
+            }
+            let refund = Money(self.building_cost(index).0 / 2);
+            let deed = &mut self.deeds[index];
+            let (houses, hotel) = (deed.houses, deed.hotel);
+            deed.hotel = false;
+            deed.houses = 0;
+            self.restock(houses as i32, hotel as i32);
+            self.effects.push(Effect::SoldBuildings {
+                player: id,
+                property: PropertyId(index as i8),
+                houses,
+                hotel,
+            });
+            self.credit(id, refund);
+        }
+    }
+
+    fn validate_trade(&self, trade: &Trade) -> Result<(), GameError> {
+        self.ensure_active(trade.proposer)?;
+        self.ensure_active(trade.recipient)?;
+        if trade.proposer == trade.recipient {
+            return Err(illegal("a player cannot trade with themselves"));
+        }
+        if trade.offered_cash.0 < 0 || trade.requested_cash.0 < 0 {
+            return Err(illegal("trade amounts must not be negative"));
+        }
+        let sides = [
+            (
+                trade.proposer,
+                &trade.offered,
+                trade.offered_cash,
+                trade.offered_jail_cards,
+            ),
+            (
+                trade.recipient,
+                &trade.requested,
+                trade.requested_cash,
+                trade.requested_jail_cards,
+            ),
+        ];
+        for (owner, properties, cash, jail_cards) in sides.iter() {
+            for (i, property) in properties.iter().enumerate() {
+                let index = self.ensure_owner(*owner, *property)?;
+                if self.group_has_buildings(index) {
+                    return Err(illegal(
+                        "buildings in the color group must be sold before trading",
+                    ));
+                }
+                if properties[..i].contains(property) {
+                    return Err(illegal("a property is listed twice"));
+                }
+            }
+            self.ensure_funds(*owner, *cash)?;
+            if self.players[owner.0 as usize].jail_cards.len() < *jail_cards as usize {
+                return Err(illegal(&format!(
+                    "player {} does not hold enough Get Out of Jail Free cards",
+                    owner.0
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn propose_trade(&mut self, trade: &Trade) -> Result<(), GameError> {
+        if self.trade.is_some() {
+            return Err(illegal("another trade is already on the table"));
+        }
+        self.ensure_trade_timing(trade)?;
+        self.validate_trade(trade)?;
+        self.trade = Some(trade.clone());
+        Ok(())
+    }
+
+    fn accept_trade(&mut self, id: PlayerId) -> Result<(), GameError> {
+        let trade = match &self.trade {
+            Some(trade) if trade.recipient == id => trade.clone(),
+            _ => return Err(illegal("there is no trade offer to accept")),
+        };
+        // Positions may have changed since the offer was made.
+        self.ensure_trade_timing(&trade)?;
+        self.validate_trade(&trade)?;
+        self.trade = None;
+        // Mortgaged titles change hands as they are; the 10% transfer
+        // interest is not charged.
+        for property in &trade.offered {
+            self.acquire(trade.recipient, property.0 as usize);
+        }
+        for property in &trade.requested {
+            self.acquire(trade.proposer, property.0 as usize);
+        }
+        self.transfer(trade.proposer, Some(trade.recipient), trade.offered_cash);
+        self.transfer(trade.recipient, Some(trade.proposer), trade.requested_cash);
+        self.hand_over_jail_cards(trade.proposer, trade.recipient, trade.offered_jail_cards);
+        self.hand_over_jail_cards(trade.recipient, trade.proposer, trade.requested_jail_cards);
+        Ok(())
+    }
+
+    // Under strict timing, trades are made and taken up only on the
+    // proposer's turn, and not while an auction is running.
+    fn ensure_trade_timing(&self, trade: &Trade) -> Result<(), GameError> {
+        if self.config.rules.trades_any_time {
+            return Ok(());
+        }
+        if trade.proposer != self.turn.player {
+            return Err(illegal(
+                "trades may only be made during the proposer's own turn",
+            ));
+        }
+        if let Some(PendingDecision::Auction { .. }) = self.pending.front() {
+            return Err(illegal(
+                "trades may not be made while an auction is running",
+            ));
+        }
+        Ok(())
+    }
+
+    fn hand_over_jail_cards(&mut self, from: PlayerId, to: PlayerId, count: u8) {
+        for _ in 0..count {
+            let card = self.player_mut(from).jail_cards.pop().unwrap();
+            self.player_mut(to).jail_cards.push(card);
+        }
+    }
+
+    fn reject_trade(&mut self, id: PlayerId) -> Result<(), GameError> {
+        match &self.trade {
+            Some(trade) if trade.recipient == id || trade.proposer == id => {
+                self.trade = None;
+                Ok(())
+            }
+            _ => Err(illegal("there is no trade offer to reject")),
+        }
+    }
+
+    fn pay_debt(&mut self, id: PlayerId) -> Result<(), GameError> {
+        match *self.ensure_decision(id)? {
+            PendingDecision::PayDebt {
+                creditor, amount, ..
+            } => {
+                self.ensure_funds(id, amount)?;
+                self.pending.pop_front();
+                self.transfer(id, creditor, amount);
+            }
+            // As the auction would have taken it, so never into the pot.
+            PendingDecision::PayBid {
+                property, amount, ..
+            } => {
+                self.ensure_funds(id, amount)?;
+                self.pending.pop_front();
+                self.debit(id, amount);
+                self.acquire(id, property.0 as usize);
+            }
+            _ => return Err(illegal("there is no debt to pay")),
+        }
+        Ok(())
+    }
+
+    fn declare_bankruptcy(&mut self, id: PlayerId) -> Result<(), GameError> {
+        let (creditor, amount) = match self.ensure_decision(id)? {
+            PendingDecision::PayDebt {
+                creditor, amount, ..
+            } => (*creditor, *amount),
+            // The lot stays with the bank.
+            PendingDecision::PayBid { amount, .. } => (None, *amount),
+            _ => {
+                return Err(illegal(
+                    "only a player who cannot pay a debt may go bankrupt",
+                ))
+            }
+        };
+        if self.could_pay(id, amount) {
+            return Err(illegal(&format!(
+                "selling and mortgaging would raise enough to pay ${}",
+                amount.0
+            )));
+        }
+        self.pending.pop_front();
+        self.go_bankrupt(id, creditor);
+        Ok(())
+    }
+
+    // Whether the player's cash and all they could sell or mortgage cover
+    // the amount, in which case they may not go bankrupt over it.
+    pub(super) fn could_pay(&self, id: PlayerId, amount: Money) -> bool {
+        self.players[id.0 as usize].cash.0 + self.mortgage_capacity(id).0 >= amount.0
+    }
+
+    // Conceding hands everything to the bank, as going bankrupt to it would.
+    // Facing a debt, it is declaring bankruptcy, with the same check that
+    // selling up couldn't pay.
+    fn resign(&mut self, id: PlayerId) -> Result<(), GameError> {
+        self.ensure_active(id)?;
+        match self.pending.front() {
+            Some(PendingDecision::PayDebt { debtor, .. })
+            | Some(PendingDecision::PayBid { debtor, .. })
+                if *debtor == id =>
+            {
+                return self.declare_bankruptcy(id)
+            }
+            _ => {}
+        }
+        if self.turn.player != id {
+            return Err(GameError::NotYourTurn(id));
+        }
+        if let Some(decision) = self.pending.front() {
+            return Err(GameError::DecisionPending(decision.clone()));
+        }
+        self.go_bankrupt(id, None);
+        Ok(())
+    }
+
+    // Everything the player holds goes to the creditor, or to the bank when
+    // there is none, and they leave the game.
+    fn go_bankrupt(&mut self, id: PlayerId, creditor: Option<PlayerId>) {
+        // Buildings are never handed over; the bank buys them back and the
+        // proceeds go with the rest of the debtor's cash.
+        self.liquidate_buildings(id);
+        let cash = self.players[id.0 as usize].cash;
+        let cards = std::mem::take(&mut self.player_mut(id).jail_cards);
+        let titles: Vec<usize> = (0..self.deeds.len())
+            .filter(|index| self.deeds[*index].owner == Some(id))
+            .collect();
+        match creditor {
+            Some(creditor) => {
+                // Everything goes to the creditor, mortgages and all.
+                self.transfer(id, Some(creditor), cash);
+                for index in titles {
+                    self.acquire(creditor, index);
+                }
+                self.player_mut(creditor).jail_cards.extend(cards);
+            }
+            None => {
+                // The bank auctions off the titles free of their mortgages,
+                // or keeps them when there are no auctions.
+                self.debit(id, cash);
+                for card in cards {
+                    self.return_card(card);
+                }
+                for index in &titles {
+                    self.deeds[*index] = Deed::default();
+                }
+                self.player_mut(id).eliminated = Some(self.turn.number);
+                let first = self.next_player(id);
+                let auctioned = match self.config.rules.auctions_enabled {
+                    true => titles,
+                    false => vec![],
+                };
+                for index in auctioned {
+                    let auction = self.auction(PropertyId(index as i8), first);
+                    self.pending.push_back(auction);
+                }
+            }
+        }
+        self.player_mut(id).eliminated = Some(self.turn.number);
+        self.effects.push(Effect::WentBankrupt(id));
+        // Auctions go on without them, and a bid of theirs can't be paid.
+        for decision in self.pending.iter_mut() {
+            if let PendingDecision::Auction {
+                high_bid,
+                bidders,
+                next,
+                ..
+            } = decision
+            {
+                if high_bid.as_ref().is_some_and(|bid| bid.0 == id) {
+                    *high_bid = None;
+                }
+                if let Some(index) = bidders.iter().position(|bidder| *bidder == id) {
+                    bidders.remove(index);
+                    if index < *next {
+                        *next -= 1;
+                    }
+                    if !bidders.is_empty() {
+                        *next %= bidders.len();
+                    }
+                }
+            }
+        }
+        self.pending.retain(|decision| match decision {
+            PendingDecision::PayDebt {
+                debtor, creditor, ..
+            } => *debtor != id && *creditor != Some(id),
+            PendingDecision::Auction { .. } => true,
+            _ => decision.player() != id,
+        });
+        self.continue_auction();
+        if self
+            .trade
+            .as_ref()
+            .is_some_and(|trade| trade.proposer == id || trade.recipient == id)
+        {
+            self.trade = None;
+        }
+        if self.turn.player == id && self.winner().is_none() {
+            self.start_turn(self.next_player(id));
+        }
+    }
+
+    // The first solvent player after the given one in seating order.
+    fn next_player(&self, id: PlayerId) -> PlayerId {
+        let count = self.players.len();
+        (1..=count)
+            .map(|offset| &self.players[(id.0 as usize + offset) % count])
+            .find(|player| !player.is_bankrupt())
+            .map_or(id, |player| player.id)
+    }
+
+    fn start_turn(&mut self, id: PlayerId) {
+        self.turn = Turn {
+            number: self.turn.number + 1,
+            player: id,
+            rolls: 0,
+            doubles: 0,
+            may_roll: true,
+        };
+        self.effects.push(Effect::TurnStarted(id));
+    }
+
+    fn end_turn(&mut self, id: PlayerId) -> Result<(), GameError> {
+        self.ensure_current(id)?;
+        if let Some(decision) = self.pending.front() {
+            return Err(GameError::DecisionPending(decision.clone()));
+        }
+        if self.turn.may_roll {
+            return Err(illegal("the player must roll before ending the turn"));
+        }
+        self.start_turn(self.next_player(id));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_game_gives_every_player_starting_cash_on_go() {
+        let state = GameState::new(GameConfig::default()).unwrap();
+        assert_eq!(state.players().count(), 4);
+        for player in state.players() {
+            assert_eq!(player.cash(), Money(1500));
+            assert_eq!(player.position(), 0);
+        }
+    }
+
+    #[test]
+    fn new_game_has_full_board_and_decks() {
+        let state = GameState::new(GameConfig::default()).unwrap();
+        assert_eq!(state.board.squares.len(), 40);
+        assert_eq!(state.chance.len(), 16);
+        assert_eq!(state.community_chest.len(), 16);
+    }
+
+    #[test]
+    fn games_share_their_board() {
+        let a = GameState::new(GameConfig::default()).unwrap();
+        let b = GameState::new(GameConfig {
+            seed: 1,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        assert!(Arc::ptr_eq(&a.board.squares, &b.board.squares));
+        assert!(Arc::ptr_eq(&a.board.squares, &a.clone().board.squares));
+    }
+
+    #[test]
+    fn new_game_shuffles_decks_by_seed() {
+        let config = GameConfig::default();
+        let a = GameState::new(config.clone()).unwrap();
+        let b = GameState::new(config.clone()).unwrap();
+        assert_eq!(a.chance, b.chance);
+        let c = GameState::new(GameConfig { seed: 1, ..config }).unwrap();
+        assert_ne!(a.chance, c.chance);
+    }
+
+    #[test]
+    fn every_roll_moves_the_dice_on() {
+        let mut state = GameState::new(GameConfig::default()).unwrap();
+        let first = state.next_roll();
+        assert_eq!(state.next_roll(), first);
+        let mut supplied = state.clone();
+        state.roll(PlayerId(0)).unwrap();
+        assert_eq!(
+            state.events()[0].command,
+            Command::RollDice(PlayerId(0), first.clone())
+        );
+        supplied
+            .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+            .unwrap();
+        assert_eq!(supplied.dice, state.dice);
+        supplied.undo().unwrap();
+        assert_eq!(supplied.next_roll(), first);
+    }
+
+    #[test]
+    fn the_dice_are_part_of_the_position() {
+        let state = GameState::new(GameConfig {
+            engine_dice: true,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        let mut diverged = state.clone();
+        diverged.dice = SeededDice::new(1);
+        assert_ne!(diverged.next_roll(), state.next_roll());
+        assert_ne!(diverged, state);
+        assert_eq!(state.diff(&diverged).to_string(), "engine dice differ\n");
+    }
+
+    #[test]
+    fn new_game_rejects_bad_player_counts() {
+        for players in &[0, 1, 9] {
+            let config = GameConfig {
+                players: *players,
+                ..GameConfig::default()
+            };
+            assert!(matches!(
+                GameState::new(config),
+                Err(GameError::InvalidConfig(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn add_player_hands_out_sequential_ids() {
+        let mut state = GameState::new(GameConfig {
+            players: 2,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        assert_eq!(state.add_player(), Ok(PlayerId(2)));
+        assert_eq!(state.add_player(), Ok(PlayerId(3)));
+        assert_eq!(state.players().count(), 4);
+        assert_eq!(state.cash(PlayerId(3)), Ok(Money(1500)));
+    }
+
+    fn two_player_game() -> GameState {
+        GameState::new(GameConfig {
+            players: 2,
+            ..GameConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn two_player_builder() -> GameStateBuilder {
+        GameStateBuilder::new(GameConfig {
+            players: 2,
+            ..GameConfig::default()
+        })
+    }
+
+    #[test]
+    fn without_auctions_the_bank_keeps_a_bankrupts_titles() {
+        let p0 = PlayerId(0);
+        let mut state = GameState::new(GameConfig {
+            players: 3,
+            rules: HouseRules {
+                auctions_enabled: false,
+                ..HouseRules::default()
+            },
+            ..GameConfig::default()
+        })
+        .unwrap();
+        state.deeds[3].owner = Some(p0);
+        state.deeds[3].mortgaged = true;
+        state.players[0].cash = Money(10);
+        state.players[0].position = 35;
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 2)))
+            .unwrap();
+        state.apply(Command::DeclareBankruptcy(p0)).unwrap();
+        assert_eq!(state.deeds[3], Deed::default());
+        assert_eq!(state.pending(), None);
+        assert_eq!(state.current_player(), PlayerId(1));
+    }
+
+    #[test]
+    fn bankruptcy_to_the_bank_puts_titles_up_for_auction() {
+        let (p0, p1, p2) = (PlayerId(0), PlayerId(1), PlayerId(2));
+        let mut state = GameState::new(GameConfig {
+            players: 3,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        state.deeds[3].owner = Some(p0);
+        state.deeds[3].mortgaged = true;
+        state.players[0].cash = Money(10);
+        state.players[0].position = 35;
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 2)))
+            .unwrap();
+        assert_eq!(
+            state.pending(),
+            Some(&PendingDecision::PayDebt {
+                debtor: p0,
+                creditor: None,
+                amount: Money(75),
+            })
+        );
+        state.apply(Command::DeclareBankruptcy(p0)).unwrap();
+        assert_eq!(state.deeds[3], Deed::default());
+        assert_eq!(state.current_player(), p1);
+        assert_eq!(
+            state.pending(),
+            Some(&PendingDecision::Auction {
+                property: PropertyId(3),
+                high_bid: None,
+                bidders: vec![p1, p2],
+                next: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn passing_go_pays_salary() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        state.players[0].position = 36;
+        state
+            .apply(Command::RollDice(p0, RollResult(2, 5)))
+            .unwrap();
+        assert_eq!(state.position(p0), Ok(3));
+        assert_eq!(state.cash(p0), Ok(Money(1700)));
+    }
+
+    #[test]
+    fn third_failed_jail_roll_pays_the_fine_and_moves() {
+        let (p0, p1) = (PlayerId(0), PlayerId(1));
+        let mut state = two_player_game();
+        state.send_to_jail(p0);
+        state.start_turn(p0);
+        for _ in 0..2 {
+            state
+                .apply(Command::RollDice(p0, RollResult(1, 2)))
+                .unwrap();
+            assert_eq!(state.position(p0), Ok(state.board.jail()));
+            state.apply(Command::EndTurn(p0)).unwrap();
+            state.start_turn(p0);
+        }
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 2)))
+            .unwrap();
+        assert_eq!(state.position(p0), Ok(13));
+        assert_eq!(state.cash(p0), Ok(Money(1450)));
+        assert!(!state.players[0].in_jail());
+        assert_eq!(state.current_player(), p0);
+        assert_eq!(state.cash(p1), Ok(Money(1500)));
+    }
+
+    #[test]
+    fn doubles_out_of_jail_move_without_another_roll() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_builder().in_jail(p0, 0).build().unwrap();
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 3)))
+            .unwrap();
+        assert_eq!(state.position(p0), Ok(16));
+        state.apply(Command::DeclineToBuy(p0)).unwrap();
+        state.apply(Command::PassAuction(p0)).unwrap();
+        state.apply(Command::PassAuction(PlayerId(1))).unwrap();
+        assert!(state
+            .apply(Command::RollDice(p0, RollResult(1, 2)))
+            .is_err());
+        state.apply(Command::EndTurn(p0)).unwrap();
+    }
+
+    #[test]
+    fn drawn_cards_go_to_the_bottom_of_the_deck() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        state.chance = vec![ChanceCard::BankDividend, ChanceCard::PoorTax].into();
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 4)))
+            .unwrap();
+        assert_eq!(state.cash(p0), Ok(Money(1550)));
+        assert_eq!(
+            state.chance,
+            [ChanceCard::PoorTax, ChanceCard::BankDividend]
+        );
+    }
+
+    #[test]
+    fn advancing_to_go_lands_on_it() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        state.config.rules.double_salary_on_go = true;
+        state.chance = vec![ChanceCard::AdvanceToGo].into();
+        let effects = state
+            .apply(Command::RollDice(p0, RollResult(3, 4)))
+            .unwrap();
+        let salary = Effect::CollectedSalary {
+            player: p0,
+            amount: Money(200),
+        };
+        assert_eq!(
+            effects[2..],
+            [
+                Effect::LandedOnGo(p0),
+                salary.clone(),
+                Effect::Moved { player: p0, to: 0 },
+                salary,
+            ]
+        );
+        assert_eq!(state.cash(p0), Ok(Money(1900)));
+    }
+
+    // Every way round the board pays the config's salary, never a
+    // hard-coded $200: each case is where player 0 starts, the dice, the
+    // Chance card on top and the salaries it should collect. Stopping on
+    // GO, by card or dice, pays twice under the house rule.
+    #[test]
+    fn every_way_past_go_pays_the_configured_salary() {
+        let p0 = PlayerId(0);
+        let cases = [
+            (36, RollResult(2, 5), None, 1),
+            (4, RollResult(1, 2), Some(ChanceCard::AdvanceToGo), 2),
+            (
+                33,
+                RollResult(1, 2),
+                Some(ChanceCard::AdvanceToStCharlesPlace),
+                1,
+            ),
+            (36, RollResult(1, 3), None, 2),
+        ];
+        for (from, roll, card, paid) in cases {
+            let mut state = GameState::new(GameConfig {
+                players: 2,
+                salary: Money(500),
+                rules: HouseRules {
+                    double_salary_on_go: true,
+                    ..HouseRules::default()
+                },
+                ..GameConfig::default()
+            })
+            .unwrap();
+            state.players[0].position = from;
+            state.chance.extend(card);
+            state.chance.rotate_right(card.iter().count());
+            let effects = state.apply(Command::RollDice(p0, roll)).unwrap();
+            let salaries: Vec<&Effect> = effects
+                .iter()
+                .filter(|effect| matches!(effect, Effect::CollectedSalary { .. }))
+                .collect();
+            let salary = Effect::CollectedSalary {
+                player: p0,
+                amount: Money(500),
+            };
+            assert_eq!(salaries, vec![&salary; paid], "from {}", from);
+            assert_eq!(state.cash(p0), Ok(Money(1500 + 500 * paid as i32)));
+        }
+    }
+
+    #[test]
+    fn the_standard_board_is_laid_out_from_its_deeds() {
+        let squares = board::standard_squares();
+        assert_eq!(board::check_standard(squares), Ok(()));
+        let board = Board::for_edition(BoardEdition::Standard);
+        let names = [
+            (1, "Mediterranean Ave"),
+            (5, "Reading Railroad"),
+            (28, "Water Works"),
+        ];
+        for (index, name) in names {
+            assert_eq!(board.name(index), Some(name));
+        }
+        match &squares[39] {
+            Square::Property(boardwalk) => {
+                assert_eq!(boardwalk.houses[3], Money(1700));
+                assert_eq!(boardwalk.mortgage, Money(200));
+                assert_eq!(boardwalk.hotel_cost, (Money(200), 4));
+            }
+            square => panic!("{:?} where Boardwalk should be", square),
+        }
+        assert!(matches!(
+            &squares[38],
+            Square::Tax(Tax {
+                amount: Money(75),
+                percent: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn a_misprinted_board_is_caught() {
+        let mut squares = board::standard_squares().to_vec();
+        // Playable with three railroads, but not as printed.
+        squares[5] = Square::FreeParking;
+        assert_eq!(board::check_board(&squares), Ok(()));
+        assert_eq!(
+            board::check_standard(&squares),
+            Err("3 railroads rather than 4".to_string())
+        );
+        if let Square::Property(baltic) = &mut squares[3] {
+            baltic.houses[2] = Money(20);
+        }
+        assert_eq!(
+            board::check_board(&squares),
+            Err("the rent on Baltic Ave does not rise".to_string())
+        );
+        squares[12] = Square::FreeParking;
+        squares[28] = Square::FreeParking;
+        assert_eq!(
+            board::check_board(&squares),
+            Err("0 utilities rather than 1 to 2".to_string())
+        );
+        squares[0] = Square::Jail;
+        assert_eq!(
+            board::check_board(&squares),
+            Err("0 GO rather than 1".to_string())
+        );
+        squares.pop();
+        assert_eq!(
+            board::check_board(&squares),
+            Err("39 squares, which don't make four equal sides".to_string())
+        );
+    }
+
+    #[test]
+    fn buying_boardwalk_costs_its_printed_price() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        assert_eq!(state.price(39), Money(400));
+        state.players[0].position = 35;
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 3)))
+            .unwrap();
+        state
+            .apply(Command::BuyProperty(p0, PropertyId(39)))
+            .unwrap();
+        assert_eq!(state.cash(p0), Ok(Money(1100)));
+        assert_eq!(state.net_worth(p0), Ok(Money(1500)));
+    }
+
+    #[test]
+    fn jail_cards_are_kept_until_used() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        state.chance = vec![ChanceCard::GetOutOfJailFree, ChanceCard::PoorTax].into();
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 4)))
+            .unwrap();
+        assert_eq!(state.players[0].jail_cards(), 1);
+        assert_eq!(state.chance, [ChanceCard::PoorTax]);
+        state.send_to_jail(p0);
+        state.start_turn(p0);
+        state.apply(Command::UseGetOutOfJailFreeCard(p0)).unwrap();
+        assert!(!state.players[0].in_jail());
+        assert_eq!(
+            state.chance,
+            [ChanceCard::PoorTax, ChanceCard::GetOutOfJailFree]
+        );
+    }
+
+    #[test]
+    fn nearest_railroad_card_charges_double_rent() {
+        let (p0, p1) = (PlayerId(0), PlayerId(1));
+        let mut state = two_player_game();
+        state.deeds[15].owner = Some(p1);
+        state.chance = vec![ChanceCard::AdvanceToNearestRailroad].into();
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 4)))
+            .unwrap();
+        assert_eq!(state.position(p0), Ok(15));
+        assert_eq!(state.cash(p1), Ok(Money(1550)));
+    }
+
+    #[test]
+    fn nearest_utility_card_charges_ten_times_a_fresh_roll() {
+        let (p0, p1) = (PlayerId(0), PlayerId(1));
+        let mut state = two_player_game();
+        state.deeds[12].owner = Some(p1);
+        state.chance = vec![ChanceCard::AdvanceToNearestUtility].into();
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 4)))
+            .unwrap();
+        assert_eq!(state.position(p0), Ok(12));
+        // The fresh roll is held to the dice like any other.
+        assert!(matches!(
+            state.apply(Command::RollDice(p0, RollResult(0, 9))),
+            Err(GameError::InvalidRoll { .. })
+        ));
+        state
+            .apply(Command::RollDice(p0, RollResult(2, 3)))
+            .unwrap();
+        assert_eq!(state.cash(p1), Ok(Money(1550)));
+        assert!(state.apply(Command::EndTurn(p0)).is_ok());
+    }
+
+    #[test]
+    fn color_groups_list_their_lots_in_board_order() {
+        let state = two_player_game();
+        let ids = |color| -> Vec<i8> {
+            state
+                .color_group_members(color)
+                .iter()
+                .map(|property| property.0)
+                .collect()
+        };
+        assert_eq!(ids(ColorGroup::Brown), [1, 3]);
+        assert_eq!(ids(ColorGroup::Orange), [16, 18, 19]);
+        assert_eq!(ids(ColorGroup::DarkBlue), [37, 39]);
+        let total: usize = ColorGroup::ALL
+            .iter()
+            .map(|color| state.color_group_members(*color).len())
+            .sum();
+        assert_eq!(total, 22);
+    }
+
+    #[test]
+    fn mortgaged_lots_still_complete_a_monopoly() {
+        let (p0, p1) = (PlayerId(0), PlayerId(1));
+        let mut state = two_player_game();
+        state.deeds[1].owner = Some(p0);
+        assert!(!state.is_monopoly(p0, ColorGroup::Brown));
+        state.deeds[3].owner = Some(p0);
+        state.deeds[3].mortgaged = true;
+        assert!(state.is_monopoly(p0, ColorGroup::Brown));
+        assert!(!state.is_monopoly(p1, ColorGroup::Brown));
+        assert_eq!(state.monopolies_of(p0), [ColorGroup::Brown]);
+        assert_eq!(state.monopolies_of(p1), []);
+        // The unmortgaged lot of a complete set charges double.
+        assert_eq!(state.rent_due(PropertyId(1), None), Ok(Money(4)));
+    }
+
+    #[test]
+    fn monopolies_of_lists_every_complete_group() {
+        let p0 = PlayerId(0);
+        let mut state = two_player_game();
+        for index in &[1, 3, 37, 39, 16, 18] {
+            state.deeds[*index].owner = Some(p0);
+        }
+        assert_eq!(
+            state.monopolies_of(p0),
+            [ColorGroup::Brown, ColorGroup::DarkBlue]
+        );
+    }
+
+    #[test]
+    fn bank_stock_follows_buildings_up_and_down() {
+        let p0 = PlayerId(0);
+        let (mediterranean, baltic) = (PropertyId(1), PropertyId(3));
+        let mut state = two_player_builder()
+            .owns(p0, mediterranean, 0)
+            .owns(p0, baltic, 0)
+            .build()
+            .unwrap();
+        state.apply(Command::BuyHouse(p0, mediterranean)).unwrap();
+        assert!(state.apply(Command::BuyHouse(p0, mediterranean)).is_err());
+        for _ in 0..3 {
+            state.apply(Command::BuyHouse(p0, baltic)).unwrap();
+            state.apply(Command::BuyHouse(p0, mediterranean)).unwrap();
+        }
+        state.apply(Command::BuyHouse(p0, baltic)).unwrap();
+        assert_eq!(state.houses_remaining(), Some(BANK_HOUSES - 8));
+        assert_eq!(state.rent_due(baltic, None), Ok(Money(320)));
+
+        state.apply(Command::BuyHotel(p0, baltic)).unwrap();
+        assert_eq!(state.houses_remaining(), Some(BANK_HOUSES - 4));
+        assert_eq!(state.hotels_remaining(), Some(BANK_HOTELS - 1));
+        assert_eq!(
+            state.buildings_on(ColorGroup::Brown),
+            [(mediterranean, 4, false), (baltic, 0, true)]
+        );
+        assert!(state.apply(Command::MortgageProperty(p0, baltic)).is_err());
+
+        state.apply(Command::SellHotel(p0, baltic)).unwrap();
+        assert_eq!(state.houses_remaining(), Some(BANK_HOUSES - 8));
+        assert_eq!(state.hotels_remaining(), Some(BANK_HOTELS));
+        // Sales have to stay even as well.
+        state.apply(Command::SellHouse(p0, baltic)).unwrap();
+        assert!(state.apply(Command::SellHouse(p0, baltic)).is_err());
+        state.apply(Command::SellHouse(p0, mediterranean)).unwrap();
+        assert_eq!(state.houses_remaining(), Some(BANK_HOUSES - 6));
+        // Five houses and a hotel bought, 25 back for each house, 25 for the
+        // hotel.
+        assert_eq!(state.cash(p0), Ok(Money(1500 - 9 * 50 + 25 * 2 + 25)));
+
+        assert!(state.apply(Command::BuyHotel(p0, mediterranean)).is_err());
+        state.charge(p0, None, Money(10_000));
+        state.apply(Command::DeclareBankruptcy(p0)).unwrap();
+        assert_eq!(state.houses_remaining(), Some(BANK_HOUSES));
+        assert_eq!(state.hotels_remaining(), Some(BANK_HOTELS));
+        assert_eq!(
+            state.buildings_on(ColorGroup::Brown),
+            [(mediterranean, 0, false), (baltic, 0, false)]
+        );
+    }
+
+    #[test]
+    fn summary_lists_cash_positions_and_holdings() {
+        let (p0, p1) = (PlayerId(0), PlayerId(1));
+        let state = GameStateBuilder::default()
+            .player(Money(1500), 0)
+            .player(Money(1320), 24)
+            .owns(p0, PropertyId(1), 2)
+            .owns(p0, PropertyId(3), 1)
+            .owns(p1, PropertyId(5), 0)
+            .mortgaged(PropertyId(5))
+            .build()
+            .unwrap();
+        let summary = state.to_string();
+        assert!(summary.contains("Turn 1: Player 0 to move"));
+        assert!(summary.contains("Bank: 29 houses, 12 hotels"));
+        assert!(summary.contains("Player 0 (top hat): $1500, on GO"));
+        assert!(summary.contains("  Brown: Mediterranean Ave (2 houses), Baltic Ave (1 house)"));
+        assert!(summary.contains("Player 1 (thimble): $1320, on Illinois Ave"));
+        assert!(summary.contains("  Railroads: Reading Railroad [mortgaged]"));
     }
 
     #[test]
-    fn roll_dice_with_valid_player_logs_roll() {
-        let mut state = GameState::init();
-        let id = PlayerId(0);
-        state.players.push(Player { id });
-        let result = state.apply(Action::RollDice(id, RollResult(1, 2)));
-        assert_eq!(result, Ok(()));
-        assert_eq!(state.events, [Action::RollDice(id, RollResult(1, 2))]);
+    fn add_player_refuses_a_ninth_player() {
+        let mut state = GameState::new(GameConfig {
+            players: MAX_PLAYERS,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        assert_eq!(state.add_player(), Err(GameError::TooManyPlayers));
     }
 }
 
@@ -0,0 +1,473 @@
+// The standard board, laid out from tables of what is printed on it rather
+// than spelled out square by square. Whatever is laid out is checked before
+// any game is played on it.
+
+use super::*;
+
+// Each street's deed: name, group, price, the rent bare, with one to four
+// houses and with a hotel, and what a house costs. A hotel costs as much as
+// a house on top of the four it replaces, and a mortgage is half the price.
+type StreetDeed = (&'static str, ColorGroup, i32, [i32; 6], i32);
+
+#[rustfmt::skip]
+static STREETS: &[StreetDeed] = &[
+    ("Mediterranean Ave", ColorGroup::Brown, 60, [2, 10, 30, 90, 160, 250], 50),
+    ("Baltic Ave", ColorGroup::Brown, 60, [4, 20, 60, 180, 320, 450], 50),
+    ("Oriental Ave", ColorGroup::LightBlue, 100, [6, 30, 90, 270, 400, 550], 50),
+    ("Vermont Ave", ColorGroup::LightBlue, 100, [6, 30, 90, 270, 400, 550], 50),
+    ("Connecticut Ave", ColorGroup::LightBlue, 120, [8, 40, 100, 300, 450, 600], 50),
+    ("St. Charles Place", ColorGroup::Pink, 140, [10, 50, 150, 450, 625, 750], 100),
+    ("States Ave", ColorGroup::Pink, 140, [10, 50, 150, 450, 625, 750], 100),
+    ("Virginia Ave", ColorGroup::Pink, 160, [12, 60, 180, 500, 700, 900], 100),
+    ("St. James Place", ColorGroup::Orange, 180, [14, 70, 200, 550, 750, 950], 100),
+    ("Tennessee Ave", ColorGroup::Orange, 180, [14, 70, 200, 550, 750, 950], 100),
+    ("New York Ave", ColorGroup::Orange, 200, [16, 80, 220, 600, 800, 1000], 100),
+    ("Kentucky Ave", ColorGroup::Red, 220, [18, 90, 250, 700, 875, 1050], 150),
+    ("Indiana Ave", ColorGroup::Red, 220, [18, 90, 250, 700, 875, 1050], 150),
+    ("Illinois Ave", ColorGroup::Red, 240, [20, 100, 300, 750, 925, 1100], 150),
+    ("Atlantic Ave", ColorGroup::Yellow, 260, [22, 110, 330, 800, 975, 1150], 150),
+    ("Ventnor Ave", ColorGroup::Yellow, 260, [22, 110, 330, 800, 975, 1150], 150),
+    ("Marvin Gardens", ColorGroup::Yellow, 280, [24, 120, 360, 850, 1025, 1200], 150),
+    ("Pacific Ave", ColorGroup::Green, 300, [26, 130, 390, 900, 1100, 1275], 200),
+    ("North Carolina Ave", ColorGroup::Green, 300, [26, 130, 390, 900, 1100, 1275], 200),
+    ("Pennsylvania Ave", ColorGroup::Green, 320, [28, 150, 450, 1000, 1200, 1400], 200),
+    ("Park Place", ColorGroup::DarkBlue, 350, [35, 175, 500, 1100, 1300, 1500], 200),
+    ("Boardwalk", ColorGroup::DarkBlue, 400, [50, 200, 600, 1400, 1700, 2000], 200),
+];
+
+// Every railroad costs $200 and every utility $150.
+static RAILROADS: &[&str] = &[
+    "Reading Railroad",
+    "Pennsylvania Railroad",
+    "B. & O. Railroad",
+    "Short Line",
+];
+static UTILITIES: &[&str] = &["Electric Company", "Water Works"];
+
+// What stands on each square, from GO round. Each street, railroad and
+// utility is the next one in its table.
+#[derive(Clone, Copy)]
+enum Slot {
+    Go,
+    Street,
+    Railroad,
+    Utility,
+    IncomeTax,
+    LuxuryTax,
+    Chance,
+    CommunityChest,
+    Jail,
+    FreeParking,
+    GoToJail,
+}
+
+#[rustfmt::skip]
+static LAYOUT: [Slot; 40] = {
+    use Slot::*;
+    [
+        Go, Street, CommunityChest, Street, IncomeTax,
+        Railroad, Street, Chance, Street, Street,
+        Jail, Street, Utility, Street, Street,
+        Railroad, Street, CommunityChest, Street, Street,
+        FreeParking, Street, Chance, Street, Street,
+        Railroad, Street, Street, Utility, Street,
+        GoToJail, Street, Street, CommunityChest, Street,
+        Railroad, Chance, Street, LuxuryTax, Street,
+    ]
+};
+
+fn street(&(name, color, price, rents, house_cost): &StreetDeed) -> Square {
+    Square::Property(Property {
+        name,
+        color,
+        price: Money(price),
+        base: Money(rents[0]),
+        houses: [rents[1], rents[2], rents[3], rents[4]].map(Money),
+        hotel: Money(rents[5]),
+        mortgage: Money(price / 2),
+        house_cost: Money(house_cost),
+        hotel_cost: (Money(house_cost), 4),
+    })
+}
+
+fn lay_out() -> Vec<Square> {
+    let mut streets = STREETS.iter().map(street);
+    let mut railroads = RAILROADS.iter().map(|name| {
+        Square::Railroad(Railroad {
+            name,
+            price: Money(200),
+            mortgage: Money(100),
+        })
+    });
+    let mut utilities = UTILITIES.iter().map(|name| {
+        Square::Utility(Utility {
+            name,
+            price: Money(150),
+            mortgage: Money(75),
+        })
+    });
+    let mut squares = Vec::with_capacity(LAYOUT.len());
+    for slot in LAYOUT {
+        squares.push(match slot {
+            Slot::Go => Square::Go,
+            Slot::Street => streets.next().expect("a deed for every street"),
+            Slot::Railroad => railroads.next().expect("a name for every railroad"),
+            Slot::Utility => utilities.next().expect("a name for every utility"),
+            Slot::IncomeTax => Square::Tax(Tax {
+                name: "Income Tax",
+                amount: Money(200),
+                percent: Some(10),
+            }),
+            Slot::LuxuryTax => Square::Tax(Tax {
+                name: "Luxury Tax",
+                amount: Money(75),
+                percent: None,
+            }),
+            Slot::Chance => Square::Chance,
+            Slot::CommunityChest => Square::CommunityChest,
+            Slot::Jail => Square::Jail,
+            Slot::FreeParking => Square::FreeParking,
+            Slot::GoToJail => Square::GoToJail,
+        });
+    }
+    squares
+}
+
+// What another edition prints on the same squares: its streets, railroads
+// and utilities in board order, then the income and luxury taxes. Prices and
+// rents are the standard board's.
+struct Names {
+    streets: [&'static str; 22],
+    railroads: [&'static str; 4],
+    utilities: [&'static str; 2],
+    taxes: [&'static str; 2],
+}
+
+static FRENCH: Names = Names {
+    streets: [
+        "Boulevard de Belleville",
+        "Rue Lecourbe",
+        "Rue de Vaugirard",
+        "Rue de Courcelles",
+        "Avenue de la République",
+        "Boulevard de la Villette",
+        "Avenue de Neuilly",
+        "Rue de Paradis",
+        "Avenue Mozart",
+        "Boulevard Saint-Michel",
+        "Place Pigalle",
+        "Avenue Matignon",
+        "Boulevard Malesherbes",
+        "Avenue Henri-Martin",
+        "Faubourg Saint-Honoré",
+        "Place de la Bourse",
+        "Rue La Fayette",
+        "Avenue de Breteuil",
+        "Avenue Foch",
+        "Boulevard des Capucines",
+        "Avenue des Champs-Élysées",
+        "Rue de la Paix",
+    ],
+    railroads: [
+        "Gare Montparnasse",
+        "Gare de Lyon",
+        "Gare du Nord",
+        "Gare Saint-Lazare",
+    ],
+    utilities: [
+        "Compagnie de distribution d'électricité",
+        "Compagnie de distribution des eaux",
+    ],
+    taxes: ["Impôts sur le revenu", "Taxe de luxe"],
+};
+
+static GERMAN: Names = Names {
+    streets: [
+        "Badstraße",
+        "Turmstraße",
+        "Chausseestraße",
+        "Elisenstraße",
+        "Poststraße",
+        "Seestraße",
+        "Hafenstraße",
+        "Neue Straße",
+        "Münchner Straße",
+        "Wiener Straße",
+        "Berliner Straße",
+        "Theaterstraße",
+        "Museumstraße",
+        "Opernplatz",
+        "Lessingstraße",
+        "Schillerstraße",
+        "Goethestraße",
+        "Rathausplatz",
+        "Hauptstraße",
+        "Bahnhofstraße",
+        "Parkstraße",
+        "Schlossallee",
+    ],
+    railroads: ["Südbahnhof", "Westbahnhof", "Nordbahnhof", "Hauptbahnhof"],
+    utilities: ["Elektrizitätswerk", "Wasserwerk"],
+    taxes: ["Einkommensteuer", "Zusatzsteuer"],
+};
+
+// The standard squares under another edition's names.
+fn renamed(names: &Names) -> Vec<Square> {
+    let mut streets = names.streets.iter();
+    let mut railroads = names.railroads.iter();
+    let mut utilities = names.utilities.iter();
+    let mut taxes = names.taxes.iter();
+    let mut squares = standard_squares().to_vec();
+    for square in &mut squares {
+        let (name, names) = match square {
+            Square::Property(property) => (&mut property.name, &mut streets),
+            Square::Railroad(railroad) => (&mut railroad.name, &mut railroads),
+            Square::Utility(utility) => (&mut utility.name, &mut utilities),
+            Square::Tax(tax) => (&mut tax.name, &mut taxes),
+            _ => continue,
+        };
+        *name = names.next().expect("a name for every square");
+    }
+    squares
+}
+
+pub(super) fn french_squares() -> Vec<Square> {
+    renamed(&FRENCH)
+}
+
+pub(super) fn german_squares() -> Vec<Square> {
+    renamed(&GERMAN)
+}
+
+// Laid out and checked the first time it is asked for.
+pub(super) fn standard_squares() -> &'static [Square] {
+    static SQUARES: OnceLock<Vec<Square>> = OnceLock::new();
+    SQUARES.get_or_init(|| {
+        let squares = lay_out();
+        if let Err(error) = check_standard(&squares) {
+            panic!("the standard board is misprinted: {}", error);
+        }
+        squares
+    })
+}
+
+// What any board must hold to be played on: four equal sides of squares,
+// one GO and one Jail, somewhere to be sent to jail from, streets in groups
+// of two or three whose rent rises with every building, and one to four
+// railroads and one or two utilities. Where each stands is up to the board.
+pub(super) fn check_board(squares: &[Square]) -> Result<(), String> {
+    if squares.len() < 8 || !squares.len().is_multiple_of(4) || squares.len() > i8::MAX as usize {
+        return Err(format!(
+            "{} squares, which don't make four equal sides",
+            squares.len()
+        ));
+    }
+    let count = |kind: fn(&Square) -> bool| squares.iter().filter(|square| kind(square)).count();
+    let found = [
+        ("GO", count(|square| matches!(square, Square::Go)), 1..=1),
+        (
+            "Jail",
+            count(|square| matches!(square, Square::Jail)),
+            1..=1,
+        ),
+        (
+            "Go To Jail",
+            count(|square| matches!(square, Square::GoToJail)),
+            1..=squares.len(),
+        ),
+        (
+            "railroads",
+            count(|square| matches!(square, Square::Railroad(_))),
+            1..=4,
+        ),
+        (
+            "utilities",
+            count(|square| matches!(square, Square::Utility(_))),
+            1..=2,
+        ),
+    ];
+    for (kind, found, expected) in found {
+        if !expected.contains(&found) {
+            let expected = match (expected.start(), expected.end()) {
+                (low, high) if low == high => low.to_string(),
+                (low, high) => format!("{} to {}", low, high),
+            };
+            return Err(format!("{} {} rather than {}", found, kind, expected));
+        }
+    }
+    for color in ColorGroup::ALL {
+        let lots = squares
+            .iter()
+            .filter(
+                |square| matches!(square, Square::Property(property) if property.color == color),
+            )
+            .count();
+        if !(2..=3).contains(&lots) {
+            return Err(format!("{} {:?} streets", lots, color));
+        }
+    }
+    for square in squares {
+        if let Square::Property(property) = square {
+            let mut rents = vec![property.base];
+            rents.extend(property.houses);
+            rents.push(property.hotel);
+            if rents.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+                return Err(format!("the rent on {} does not rise", property.name));
+            }
+        }
+    }
+    Ok(())
+}
+
+// The printed board, besides being playable, has exactly 22 streets, four
+// railroads and two utilities.
+pub(super) fn check_standard(squares: &[Square]) -> Result<(), String> {
+    check_board(squares)?;
+    let count = |kind: fn(&Square) -> bool| squares.iter().filter(|square| kind(square)).count();
+    let found = [
+        (
+            "streets",
+            count(|square| matches!(square, Square::Property(_))),
+            22,
+        ),
+        (
+            "railroads",
+            count(|square| matches!(square, Square::Railroad(_))),
+            4,
+        ),
+        (
+            "utilities",
+            count(|square| matches!(square, Square::Utility(_))),
+            2,
+        ),
+    ];
+    for (kind, found, expected) in found {
+        if found != expected {
+            return Err(format!("{} {} rather than {}", found, kind, expected));
+        }
+    }
+    Ok(())
+}
+
+impl Board {
+    // Checks the squares can be played on, then finds GO, Jail and the lots
+    // of each group.
+    pub(super) fn from_squares(squares: Vec<Square>) -> Result<Board, String> {
+        check_board(&squares)?;
+        let find = |kind: fn(&Square) -> bool| squares.iter().position(kind).unwrap();
+        let go = find(|square| matches!(square, Square::Go));
+        let jail = find(|square| matches!(square, Square::Jail));
+        let groups = ColorGroup::ALL
+            .iter()
+            .map(|color| {
+                (0..squares.len())
+                    .filter(|index| {
+                        matches!(&squares[*index], Square::Property(property) if property.color == *color)
+                    })
+                    .map(|index| PropertyId(index as i8))
+                    .collect()
+            })
+            .collect();
+        Ok(Board {
+            squares: Arc::from(squares),
+            groups,
+            go,
+            jail,
+        })
+    }
+}
+
+// Lays out a board of any size square by square, from GO round, for games
+// played with GameState::on_board:
+//
+//     BoardBuilder::new().go().street("Old Kent Road", ColorGroup::Brown, ..)
+//
+// Railroads cost $200 and utilities $150, as on the standard board, and a
+// mortgage is half the price. build checks the board as check_board does.
+#[derive(Clone, Debug, Default)]
+pub struct BoardBuilder {
+    squares: Vec<Square>,
+}
+
+impl BoardBuilder {
+    pub fn new() -> BoardBuilder {
+        BoardBuilder::default()
+    }
+
+    fn push(mut self, square: Square) -> BoardBuilder {
+        self.squares.push(square);
+        self
+    }
+
+    pub fn go(self) -> BoardBuilder {
+        self.push(Square::Go)
+    }
+
+    // The rent bare, with one to four houses and with a hotel.
+    pub fn street(
+        self,
+        name: &'static str,
+        color: ColorGroup,
+        price: Money,
+        rents: [Money; 6],
+        house_cost: Money,
+    ) -> BoardBuilder {
+        self.push(street(&(
+            name,
+            color,
+            price.0,
+            rents.map(|rent| rent.0),
+            house_cost.0,
+        )))
+    }
+
+    pub fn railroad(self, name: &'static str) -> BoardBuilder {
+        self.push(Square::Railroad(Railroad {
+            name,
+            price: Money(200),
+            mortgage: Money(100),
+        }))
+    }
+
+    pub fn utility(self, name: &'static str) -> BoardBuilder {
+        self.push(Square::Utility(Utility {
+            name,
+            price: Money(150),
+            mortgage: Money(75),
+        }))
+    }
+
+    // A flat tax; the income tax's choice of a share of worth is the
+    // standard board's alone.
+    pub fn tax(self, name: &'static str, amount: Money) -> BoardBuilder {
+        self.push(Square::Tax(Tax {
+            name,
+            amount,
+            percent: None,
+        }))
+    }
+
+    pub fn chance(self) -> BoardBuilder {
+        self.push(Square::Chance)
+    }
+
+    pub fn community_chest(self) -> BoardBuilder {
+        self.push(Square::CommunityChest)
+    }
+
+    pub fn jail(self) -> BoardBuilder {
+        self.push(Square::Jail)
+    }
+
+    pub fn free_parking(self) -> BoardBuilder {
+        self.push(Square::FreeParking)
+    }
+
+    pub fn go_to_jail(self) -> BoardBuilder {
+        self.push(Square::GoToJail)
+    }
+
+    pub fn build(self) -> Result<Board, String> {
+        Board::from_squares(self.squares)
+    }
+}
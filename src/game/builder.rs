@@ -0,0 +1,174 @@
+// Builds mid-game positions directly, for tests that would otherwise replay
+// dozens of actions to get there. Everything is checked in build(), so a
+// position the rules could never reach is an error rather than a surprise.
+
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct GameStateBuilder {
+    config: GameConfig,
+    players: Vec<(Money, usize)>,
+    holdings: Vec<(PlayerId, PropertyId, u8)>,
+    mortgaged: Vec<PropertyId>,
+    jailed: Vec<(PlayerId, u8)>,
+    bank_houses: Option<u8>,
+    bank_hotels: Option<u8>,
+    current: PlayerId,
+}
+
+impl Default for GameStateBuilder {
+    fn default() -> GameStateBuilder {
+        GameStateBuilder::new(GameConfig::default())
+    }
+}
+
+impl GameStateBuilder {
+    // The config's player count is ignored once any player is added.
+    pub fn new(config: GameConfig) -> GameStateBuilder {
+        GameStateBuilder {
+            config,
+            players: Vec::new(),
+            holdings: Vec::new(),
+            mortgaged: Vec::new(),
+            jailed: Vec::new(),
+            bank_houses: None,
+            bank_hotels: None,
+            current: PlayerId(0),
+        }
+    }
+
+    pub fn player(mut self, cash: Money, position: usize) -> GameStateBuilder {
+        self.players.push((cash, position));
+        self
+    }
+
+    // Five houses means a hotel.
+    pub fn owns(mut self, player: PlayerId, property: PropertyId, houses: u8) -> GameStateBuilder {
+        self.holdings.push((player, property, houses));
+        self
+    }
+
+    pub fn mortgaged(mut self, property: PropertyId) -> GameStateBuilder {
+        self.mortgaged.push(property);
+        self
+    }
+
+    // Turns is how many attempts to roll out have already failed.
+    pub fn in_jail(mut self, player: PlayerId, turns: u8) -> GameStateBuilder {
+        self.jailed.push((player, turns));
+        self
+    }
+
+    // What's left in the bank; defaults to whatever the board doesn't use.
+    pub fn bank_houses(mut self, houses: u8) -> GameStateBuilder {
+        self.bank_houses = Some(houses);
+        self
+    }
+
+    pub fn bank_hotels(mut self, hotels: u8) -> GameStateBuilder {
+        self.bank_hotels = Some(hotels);
+        self
+    }
+
+    pub fn current_player(mut self, player: PlayerId) -> GameStateBuilder {
+        self.current = player;
+        self
+    }
+
+    pub fn build(self) -> Result<GameState, GameError> {
+        let inconsistent = |message: String| Err(GameError::InvalidConfig(message));
+        let mut config = self.config;
+        if !self.players.is_empty() {
+            config.players = self.players.len();
+        }
+        let mut state = GameState::new(config)?;
+        for (player, (cash, position)) in state.players.iter_mut().zip(self.players) {
+            if position >= state.board.len() {
+                return inconsistent(format!("there is no square {}", position));
+            }
+            player.cash = cash;
+            player.position = position;
+        }
+        for (player, property, houses) in self.holdings {
+            state.player(player)?;
+            let index = state.ensure_property(property)?;
+            if state.deeds[index].owner.is_some() {
+                return inconsistent(format!("square {} is owned twice", property.0));
+            }
+            if houses > 5 {
+                return inconsistent(format!("{} houses cannot fit on a lot", houses));
+            }
+            if houses > 0 && state.street(index).is_none() {
+                return inconsistent(format!("square {} cannot have buildings", property.0));
+            }
+            let deed = &mut state.deeds[index];
+            deed.owner = Some(player);
+            deed.hotel = houses == 5;
+            deed.houses = if deed.hotel { 0 } else { houses };
+        }
+        for property in self.mortgaged {
+            let index = state.ensure_property(property)?;
+            if state.deeds[index].owner.is_none() {
+                return inconsistent(format!("square {} is mortgaged but unowned", property.0));
+            }
+            state.deeds[index].mortgaged = true;
+        }
+        for color in ColorGroup::ALL.iter() {
+            let levels: Vec<(PropertyId, &Deed)> = state
+                .color_group_members(*color)
+                .iter()
+                .map(|lot| (*lot, &state.deeds[lot.0 as usize]))
+                .collect();
+            let highest = levels.iter().map(|(_, deed)| deed.level()).max().unwrap();
+            if highest == 0 {
+                continue;
+            }
+            let owner = levels[0].1.owner;
+            if levels.iter().any(|(_, deed)| deed.owner != owner) {
+                return inconsistent(format!("{:?} has buildings without a monopoly", color));
+            }
+            if levels.iter().any(|(_, deed)| deed.level() + 1 < highest) {
+                return inconsistent(format!("{:?} is not built evenly", color));
+            }
+            if let Some((property, _)) = levels.iter().find(|(_, deed)| deed.mortgaged) {
+                return inconsistent(format!(
+                    "square {} is mortgaged in a group with buildings",
+                    property.0
+                ));
+            }
+        }
+        let houses: u32 = state.deeds.iter().map(|deed| deed.houses as u32).sum();
+        let hotels: u32 = state.deeds.iter().map(|deed| deed.hotel as u32).sum();
+        let bank_houses = self.bank_houses.map_or(0, u32::from);
+        let bank_hotels = self.bank_hotels.map_or(0, u32::from);
+        if houses + bank_houses > BANK_HOUSES as u32 {
+            return inconsistent(format!(
+                "{} houses placed and {} in the bank, but there are only {}",
+                houses, bank_houses, BANK_HOUSES
+            ));
+        }
+        if hotels + bank_hotels > BANK_HOTELS as u32 {
+            return inconsistent(format!(
+                "{} hotels placed and {} in the bank, but there are only {}",
+                hotels, bank_hotels, BANK_HOTELS
+            ));
+        }
+        state.houses = self.bank_houses.unwrap_or(BANK_HOUSES - houses as u8);
+        state.hotels = self.bank_hotels.unwrap_or(BANK_HOTELS - hotels as u8);
+        for (player, turns) in self.jailed {
+            state.player(player)?;
+            if turns > 2 {
+                return inconsistent(format!("no one stays in jail for {} turns", turns));
+            }
+            let jail = state.board.jail;
+            let player = state.player_mut(player);
+            player.position = jail;
+            player.jail = Some(turns);
+        }
+        if state.ensure_active(self.current).is_err() {
+            return Err(GameError::InvalidPlayer(self.current));
+        }
+        state.turn.player = self.current;
+        Ok(state)
+    }
+}
@@ -0,0 +1,77 @@
+// Wall-clock time for stamping events, for games played by people. Games
+// have no clock unless their config is given one, so simulations never read
+// the system time.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Milliseconds since the Unix epoch.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> u64;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        // A clock set before 1970 reads as the epoch.
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+// A clock as a GameConfig holds it, or none by default. Which clock a game
+// has doesn't change the game, so any two compare equal, with or without.
+#[derive(Clone, Default)]
+pub struct SharedClock(Option<Arc<dyn Clock>>);
+
+impl SharedClock {
+    pub fn new(clock: impl Clock + 'static) -> SharedClock {
+        SharedClock(Some(Arc::new(clock)))
+    }
+
+    pub fn now(&self) -> Option<u64> {
+        self.0.as_ref().map(|clock| clock.now())
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "SharedClock"),
+            None => write!(f, "NoClock"),
+        }
+    }
+}
+
+impl PartialEq for SharedClock {
+    fn eq(&self, _: &SharedClock) -> bool {
+        true
+    }
+}
+
+impl Eq for SharedClock {}
+
+impl Hash for SharedClock {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_system_clock_is_after_2020() {
+        assert!(SystemClock.now() > 1_577_836_800_000);
+    }
+
+    #[test]
+    fn no_clock_reads_nothing() {
+        assert_eq!(SharedClock::default().now(), None);
+        assert_eq!(SharedClock::default(), SharedClock::new(SystemClock));
+    }
+}
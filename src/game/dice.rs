@@ -0,0 +1,212 @@
+// Dice for the engine to roll itself, rather than trusting whatever a player
+// says they rolled.
+
+use super::*;
+
+// What is thrown on each roll: how many dice and how many faces each has.
+// Two dice are the classic game, rolled as a RollDice, and only two dice can
+// come up doubles; any other number is a RollMany.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Dice {
+    pub count: u8,
+    pub faces: u8,
+}
+
+impl Dice {
+    pub const CLASSIC: Dice = Dice { count: 2, faces: 6 };
+
+    // Whether a throw is a RollDice, and can come up doubles.
+    pub fn is_pair(&self) -> bool {
+        self.count == 2
+    }
+
+    // The most a throw can come to.
+    pub fn max_total(&self) -> i32 {
+        self.count as i32 * self.faces as i32
+    }
+
+    // Whether a throw could have come from these dice.
+    pub fn allows(&self, dice: &[i8]) -> bool {
+        dice.len() == self.count as usize
+            && dice
+                .iter()
+                .all(|&die| die >= 1 && die as i32 <= self.faces as i32)
+    }
+
+    // Every way a throw can come out: what it comes to, whether it is
+    // doubles, and how likely it is. A pair is listed die by die, the rest
+    // by total.
+    pub(super) fn outcomes(&self) -> Vec<(i32, bool, f64)> {
+        let faces = self.faces as i32;
+        if self.is_pair() {
+            let chance = 1.0 / (faces * faces) as f64;
+            return (1..=faces)
+                .flat_map(|one| (1..=faces).map(move |two| (one + two, one == two, chance)))
+                .collect();
+        }
+        // How many throws come to each total, adding a die at a time.
+        let mut ways = vec![1.0];
+        for _ in 0..self.count {
+            let mut next = vec![0.0; ways.len() + faces as usize];
+            for (total, count) in ways.iter().enumerate() {
+                for face in 1..=faces as usize {
+                    next[total + face] += count;
+                }
+            }
+            ways = next;
+        }
+        let throws = (faces as f64).powi(self.count as i32);
+        ways.iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0.0)
+            .map(|(total, count)| (total as i32, false, count / throws))
+            .collect()
+    }
+}
+
+impl Default for Dice {
+    fn default() -> Dice {
+        Dice::CLASSIC
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.faces)
+    }
+}
+
+impl std::str::FromStr for Dice {
+    type Err = String;
+
+    // As written on the command line: 3d6 is three six-sided dice.
+    fn from_str(text: &str) -> Result<Dice, String> {
+        let parsed = text.split_once('d').and_then(|(count, faces)| {
+            Some(Dice {
+                count: count.parse().ok()?,
+                faces: faces.parse().ok()?,
+            })
+        });
+        parsed.ok_or_else(|| format!("expected dice like 2d6, got {:?}", text))
+    }
+}
+
+pub trait DiceRoller {
+    fn roll(&mut self) -> RollResult;
+}
+
+// Two fair dice from a seeded generator, so a seed always rolls the same
+// sequence.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SeededDice(Rng);
+
+impl SeededDice {
+    pub fn new(seed: u64) -> SeededDice {
+        SeededDice(Rng::new(seed))
+    }
+
+    // Carries on from a generator that has already been drawn from.
+    pub(super) fn from_rng(rng: Rng) -> SeededDice {
+        SeededDice(rng)
+    }
+
+    // A throw of any dice. Two six-sided dice draw just as roll does, so the
+    // classic game rolls the same whichever is called.
+    pub fn throw(&mut self, dice: Dice) -> Vec<i8> {
+        (0..dice.count)
+            .map(|_| self.0.below(dice.faces as u64) as i8 + 1)
+            .collect()
+    }
+
+    // Where the dice are up to; SeededDice::new carries on from here.
+    #[cfg(feature = "protobuf")]
+    pub(super) fn state(&self) -> u64 {
+        self.0.state()
+    }
+}
+
+// Always the classic two six-sided dice, whatever the rules throw; games with
+// other dice roll with throw.
+impl DiceRoller for SeededDice {
+    fn roll(&mut self) -> RollResult {
+        let mut die = || self.0.below(6) as i8 + 1;
+        RollResult(die(), die())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dice_stay_on_the_faces() {
+        let mut dice = SeededDice::new(5);
+        for _ in 0..1000 {
+            let RollResult(one, two) = dice.roll();
+            assert!((1..=6).contains(&one) && (1..=6).contains(&two));
+        }
+    }
+
+    #[test]
+    fn any_dice_stay_on_their_faces() {
+        let mut dice = SeededDice::new(5);
+        let d4 = Dice { count: 3, faces: 4 };
+        for _ in 0..1000 {
+            assert!(d4.allows(&dice.throw(d4)));
+        }
+        let (mut a, mut b) = (SeededDice::new(8), SeededDice::new(8));
+        let RollResult(one, two) = a.roll();
+        assert_eq!(b.throw(Dice::CLASSIC), [one, two]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn outcomes_cover_every_throw() {
+        for dice in [
+            Dice::CLASSIC,
+            Dice { count: 2, faces: 8 },
+            Dice { count: 3, faces: 6 },
+        ] {
+            let outcomes = dice.outcomes();
+            assert!(
+                (outcomes.iter().map(|(_, _, chance)| chance).sum::<f64>() - 1.0).abs() < 1e-12
+            );
+            let mean: f64 = outcomes
+                .iter()
+                .map(|(total, _, chance)| *total as f64 * chance)
+                .sum();
+            assert!((mean - dice.count as f64 * (dice.faces as f64 + 1.0) / 2.0).abs() < 1e-9);
+            let doubles: f64 = outcomes
+                .iter()
+                .filter(|(_, doubles, _)| *doubles)
+                .map(|(_, _, chance)| chance)
+                .sum();
+            match dice.is_pair() {
+                true => assert!((doubles - 1.0 / dice.faces as f64).abs() < 1e-12),
+                false => assert_eq!(doubles, 0.0),
+            }
+        }
+    }
+
+    #[test]
+    fn dice_are_written_as_count_d_faces() {
+        assert_eq!("3d6".parse(), Ok(Dice { count: 3, faces: 6 }));
+        assert_eq!(Dice::default().to_string(), "2d6");
+        assert!("d6".parse::<Dice>().is_err());
+        assert!("2x6".parse::<Dice>().is_err());
+    }
+
+    #[test]
+    fn the_seed_decides_the_rolls() {
+        let rolls = |seed| {
+            let mut dice = SeededDice::new(seed);
+            (0..20).map(|_| dice.roll()).collect::<Vec<_>>()
+        };
+        assert_eq!(rolls(1), rolls(1));
+        assert_ne!(rolls(1), rolls(2));
+    }
+}
@@ -0,0 +1,513 @@
+// What separates two states, field by field, for when == says no and a test
+// needs to say why. Covers exactly what == compares, so an empty diff means
+// the states are equal; the event logs are left out for the same reason.
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference {
+    Config,
+    Board,
+    PlayerCount {
+        left: usize,
+        right: usize,
+    },
+    Cash {
+        player: PlayerId,
+        left: Money,
+        right: Money,
+    },
+    Position {
+        player: PlayerId,
+        left: usize,
+        right: usize,
+    },
+    Jail {
+        player: PlayerId,
+        left: Option<u8>,
+        right: Option<u8>,
+    },
+    JailCards {
+        player: PlayerId,
+        left: Vec<Card>,
+        right: Vec<Card>,
+    },
+    Status {
+        player: PlayerId,
+        left: PlayerStatus,
+        right: PlayerStatus,
+    },
+    Token {
+        player: PlayerId,
+        left: Token,
+        right: Token,
+    },
+    Name {
+        player: PlayerId,
+        left: Option<String>,
+        right: Option<String>,
+    },
+    Owner {
+        property: PropertyId,
+        left: Option<PlayerId>,
+        right: Option<PlayerId>,
+    },
+    Mortgaged {
+        property: PropertyId,
+        left: bool,
+        right: bool,
+    },
+    // Houses, counting a hotel as five.
+    Buildings {
+        property: PropertyId,
+        left: u8,
+        right: u8,
+    },
+    BankHouses {
+        left: u8,
+        right: u8,
+    },
+    BankHotels {
+        left: u8,
+        right: u8,
+    },
+    Pot {
+        left: Money,
+        right: Money,
+    },
+    // The first card where the decks disagree.
+    Chance {
+        from: usize,
+    },
+    CommunityChest {
+        from: usize,
+    },
+    Turn {
+        left: u32,
+        right: u32,
+    },
+    CurrentPlayer {
+        left: PlayerId,
+        right: PlayerId,
+    },
+    // Rolls and doubles this turn.
+    Rolls {
+        left: (u8, u8),
+        right: (u8, u8),
+    },
+    MayRoll {
+        left: bool,
+        right: bool,
+    },
+    Pending {
+        left: Vec<PendingDecision>,
+        right: Vec<PendingDecision>,
+    },
+    Trade {
+        left: Option<Trade>,
+        right: Option<Trade>,
+    },
+    Dice,
+}
+
+#[derive(Clone, Debug)]
+pub struct StateDiff {
+    board: Board,
+    differences: Vec<Difference>,
+}
+
+impl StateDiff {
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.differences.len()
+    }
+}
+
+fn first_difference<T: PartialEq>(left: &VecDeque<T>, right: &VecDeque<T>) -> Option<usize> {
+    if left == right {
+        return None;
+    }
+    Some(
+        left.iter()
+            .zip(right)
+            .position(|(left, right)| left != right)
+            .unwrap_or_else(|| left.len().min(right.len())),
+    )
+}
+
+impl GameState {
+    // Lists everything that differs, in the order a reader would check it:
+    // the players, then the board, then the bank and decks, then the turn.
+    pub fn diff(&self, other: &GameState) -> StateDiff {
+        let mut differences = Vec::new();
+        let mut note = |different: bool, difference: Difference| {
+            if different {
+                differences.push(difference);
+            }
+        };
+        note(self.config != other.config, Difference::Config);
+        note(self.board.squares != other.board.squares, Difference::Board);
+        note(
+            self.players.len() != other.players.len(),
+            Difference::PlayerCount {
+                left: self.players.len(),
+                right: other.players.len(),
+            },
+        );
+        for (left, right) in self.players.iter().zip(&other.players) {
+            let player = left.id;
+            note(
+                left.cash != right.cash,
+                Difference::Cash {
+                    player,
+                    left: left.cash,
+                    right: right.cash,
+                },
+            );
+            note(
+                left.position != right.position,
+                Difference::Position {
+                    player,
+                    left: left.position,
+                    right: right.position,
+                },
+            );
+            note(
+                left.jail != right.jail,
+                Difference::Jail {
+                    player,
+                    left: left.jail,
+                    right: right.jail,
+                },
+            );
+            note(
+                left.jail_cards != right.jail_cards,
+                Difference::JailCards {
+                    player,
+                    left: left.jail_cards.clone(),
+                    right: right.jail_cards.clone(),
+                },
+            );
+            note(
+                left.eliminated != right.eliminated,
+                Difference::Status {
+                    player,
+                    left: left.status(),
+                    right: right.status(),
+                },
+            );
+            note(
+                left.token != right.token,
+                Difference::Token {
+                    player,
+                    left: left.token,
+                    right: right.token,
+                },
+            );
+            note(
+                left.name != right.name,
+                Difference::Name {
+                    player,
+                    left: left.name.clone(),
+                    right: right.name.clone(),
+                },
+            );
+        }
+        for (index, (left, right)) in self.deeds.iter().zip(&other.deeds).enumerate() {
+            let property = PropertyId(index as i8);
+            note(
+                left.owner != right.owner,
+                Difference::Owner {
+                    property,
+                    left: left.owner,
+                    right: right.owner,
+                },
+            );
+            note(
+                left.mortgaged != right.mortgaged,
+                Difference::Mortgaged {
+                    property,
+                    left: left.mortgaged,
+                    right: right.mortgaged,
+                },
+            );
+            note(
+                left.level() != right.level(),
+                Difference::Buildings {
+                    property,
+                    left: left.level(),
+                    right: right.level(),
+                },
+            );
+        }
+        note(
+            self.houses != other.houses,
+            Difference::BankHouses {
+                left: self.houses,
+                right: other.houses,
+            },
+        );
+        note(
+            self.hotels != other.hotels,
+            Difference::BankHotels {
+                left: self.hotels,
+                right: other.hotels,
+            },
+        );
+        note(
+            self.pot != other.pot,
+            Difference::Pot {
+                left: self.pot,
+                right: other.pot,
+            },
+        );
+        if let Some(from) = first_difference(&self.chance, &other.chance) {
+            note(true, Difference::Chance { from });
+        }
+        if let Some(from) = first_difference(&self.community_chest, &other.community_chest) {
+            note(true, Difference::CommunityChest { from });
+        }
+        let (left, right) = (&self.turn, &other.turn);
+        note(
+            left.number != right.number,
+            Difference::Turn {
+                left: left.number,
+                right: right.number,
+            },
+        );
+        note(
+            left.player != right.player,
+            Difference::CurrentPlayer {
+                left: left.player,
+                right: right.player,
+            },
+        );
+        note(
+            (left.rolls, left.doubles) != (right.rolls, right.doubles),
+            Difference::Rolls {
+                left: (left.rolls, left.doubles),
+                right: (right.rolls, right.doubles),
+            },
+        );
+        note(
+            left.may_roll != right.may_roll,
+            Difference::MayRoll {
+                left: left.may_roll,
+                right: right.may_roll,
+            },
+        );
+        note(
+            self.pending != other.pending,
+            Difference::Pending {
+                left: self.pending.iter().cloned().collect(),
+                right: other.pending.iter().cloned().collect(),
+            },
+        );
+        note(
+            self.trade != other.trade,
+            Difference::Trade {
+                left: self.trade.clone(),
+                right: other.trade.clone(),
+            },
+        );
+        note(self.dice != other.dice, Difference::Dice);
+        StateDiff {
+            board: self.board.clone(),
+            differences,
+        }
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.differences.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        let square = |index: usize| self.board.name(index).unwrap_or("off the board");
+        let lot = |property: &PropertyId| square(property.0 as usize);
+        let owner = |owner: &Option<PlayerId>| match owner {
+            Some(PlayerId(id)) => format!("player {}", id),
+            None => "the bank".to_string(),
+        };
+        for difference in &self.differences {
+            match difference {
+                Difference::Config => writeln!(f, "config differs")?,
+                Difference::Board => writeln!(f, "board differs")?,
+                Difference::PlayerCount { left, right } => {
+                    writeln!(f, "players: {} vs {}", left, right)?
+                }
+                Difference::Cash {
+                    player,
+                    left,
+                    right,
+                } => writeln!(f, "player {} cash: ${} vs ${}", player.0, left.0, right.0)?,
+                Difference::Position {
+                    player,
+                    left,
+                    right,
+                } => writeln!(
+                    f,
+                    "player {} position: {} vs {}",
+                    player.0,
+                    square(*left),
+                    square(*right)
+                )?,
+                Difference::Jail {
+                    player,
+                    left,
+                    right,
+                } => writeln!(f, "player {} jail: {:?} vs {:?}", player.0, left, right)?,
+                Difference::JailCards {
+                    player,
+                    left,
+                    right,
+                } => writeln!(
+                    f,
+                    "player {} jail cards: {:?} vs {:?}",
+                    player.0, left, right
+                )?,
+                Difference::Status {
+                    player,
+                    left,
+                    right,
+                } => writeln!(f, "player {} status: {:?} vs {:?}", player.0, left, right)?,
+                Difference::Token {
+                    player,
+                    left,
+                    right,
+                } => writeln!(f, "player {} token: {} vs {}", player.0, left, right)?,
+                Difference::Name {
+                    player,
+                    left,
+                    right,
+                } => writeln!(f, "player {} name: {:?} vs {:?}", player.0, left, right)?,
+                Difference::Owner {
+                    property,
+                    left,
+                    right,
+                } => writeln!(
+                    f,
+                    "{} owner: {} vs {}",
+                    lot(property),
+                    owner(left),
+                    owner(right)
+                )?,
+                Difference::Mortgaged {
+                    property,
+                    left,
+                    right,
+                } => writeln!(f, "{} mortgaged: {} vs {}", lot(property), left, right)?,
+                Difference::Buildings {
+                    property,
+                    left,
+                    right,
+                } => writeln!(f, "{} buildings: {} vs {}", lot(property), left, right)?,
+                Difference::BankHouses { left, right } => {
+                    writeln!(f, "bank houses: {} vs {}", left, right)?
+                }
+                Difference::BankHotels { left, right } => {
+                    writeln!(f, "bank hotels: {} vs {}", left, right)?
+                }
+                Difference::Pot { left, right } => writeln!(f, "pot: ${} vs ${}", left.0, right.0)?,
+                Difference::Chance { from } => {
+                    writeln!(f, "Chance decks diverge at card {}", from)?
+                }
+                Difference::CommunityChest { from } => {
+                    writeln!(f, "Community Chest decks diverge at card {}", from)?
+                }
+                Difference::Turn { left, right } => writeln!(f, "turn: {} vs {}", left, right)?,
+                Difference::CurrentPlayer { left, right } => {
+                    writeln!(f, "to move: player {} vs player {}", left.0, right.0)?
+                }
+                Difference::Rolls { left, right } => writeln!(
+                    f,
+                    "rolls (doubles) this turn: {} ({}) vs {} ({})",
+                    left.0, left.1, right.0, right.1
+                )?,
+                Difference::MayRoll { left, right } => {
+                    writeln!(f, "may roll: {} vs {}", left, right)?
+                }
+                Difference::Pending { left, right } => {
+                    writeln!(f, "pending: {:?} vs {:?}", left, right)?
+                }
+                Difference::Trade { left, right } => {
+                    writeln!(f, "trade: {:?} vs {:?}", left, right)?
+                }
+                Difference::Dice => writeln!(f, "engine dice differ")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BALTIC: PropertyId = PropertyId(3);
+
+    fn board_with_baltic(mortgaged: bool) -> GameState {
+        let builder = GameStateBuilder::default().owns(PlayerId(0), BALTIC, 0);
+        match mortgaged {
+            true => builder.mortgaged(BALTIC),
+            false => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn equal_states_have_no_differences() {
+        let state = board_with_baltic(false);
+        assert!(state.diff(&state.clone()).is_empty());
+        assert_eq!(state.diff(&state).to_string(), "no differences\n");
+    }
+
+    #[test]
+    fn one_mortgage_is_one_difference() {
+        let diff = board_with_baltic(false).diff(&board_with_baltic(true));
+        assert_eq!(
+            diff.differences(),
+            [Difference::Mortgaged {
+                property: BALTIC,
+                left: false,
+                right: true,
+            }]
+        );
+        assert_eq!(diff.to_string(), "Baltic Ave mortgaged: false vs true\n");
+    }
+
+    #[test]
+    fn a_played_action_shows_up_in_the_diff() {
+        let before = GameState::new(GameConfig::default()).unwrap();
+        let after = before
+            .applied(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+            .unwrap();
+        let report = before.diff(&after).to_string();
+        assert!(
+            report.contains("player 0 position: GO vs Baltic Ave\n"),
+            "{}",
+            report
+        );
+        assert!(report.contains("may roll: true vs false\n"), "{}", report);
+    }
+
+    #[test]
+    fn seating_shows_up_in_the_diff() {
+        let before = GameState::new(GameConfig::default()).unwrap();
+        let mut after = before.clone();
+        after.rename(PlayerId(1), "Ada").unwrap();
+        after.players[2].token = Token::Cat;
+        assert_eq!(
+            before.diff(&after).to_string(),
+            "config differs\nplayer 1 name: None vs Some(\"Ada\")\nplayer 2 token: boot vs cat\n"
+        );
+    }
+}
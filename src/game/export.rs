@@ -0,0 +1,51 @@
+// Per-turn numbers for plotting elsewhere. The log is replayed to see how
+// each turn ended, so this works on any game with its history.
+
+use super::*;
+use std::io::{self, Write};
+
+impl GameState {
+    // One row per player for every finished turn, then for the turn in
+    // progress as it stands.
+    pub fn export_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "turn,player,cash,net_worth,position,properties_owned,houses,hotels,in_jail"
+        )?;
+        let mut state = self.state_at(self.compacted).map_err(io::Error::other)?;
+        for record in &self.events {
+            let turn = state.turn.number;
+            state
+                .apply_logged(record.command.clone())
+                .expect("the log only holds actions that applied");
+            if state.turn.number != turn {
+                state.write_rows(turn, out)?;
+            }
+        }
+        self.write_rows(self.turn.number, out)
+    }
+
+    fn write_rows(&self, turn: u32, out: &mut impl Write) -> io::Result<()> {
+        for player in &self.players {
+            let owned: Vec<&Deed> = self
+                .deeds
+                .iter()
+                .filter(|deed| deed.owner == Some(player.id))
+                .collect();
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{}",
+                turn,
+                player.id.0,
+                player.cash.0,
+                self.total_worth(player.id).0,
+                player.position,
+                owned.len(),
+                owned.iter().map(|deed| deed.houses as u32).sum::<u32>(),
+                owned.iter().filter(|deed| deed.hotel).count(),
+                player.jail.is_some(),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+// Mid-game positions for property tests and fuzzing, reached by playing
+// rather than built by hand, so they hold whatever the engine holds.
+
+use super::report::seated;
+use super::*;
+
+// The game after `turns` turns of random play from the seed, or sooner if
+// someone has already won. The seed also picks between two and six players.
+pub fn generate_state(seed: u64, turns: u32) -> GameState {
+    let players = MIN_PLAYERS + (seed % 5) as usize;
+    let mut state = GameState::new(GameConfig {
+        players,
+        seed,
+        engine_dice: true,
+        ..GameConfig::default()
+    })
+    .expect("the default config is valid for two to six players");
+    let random: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+    state.play_out(&mut seated(&vec![random; players], seed), turns);
+    state
+}
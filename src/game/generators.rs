@@ -0,0 +1,166 @@
+// Proptest generators. Ids, amounts and dice are mostly in range but now and
+// then anything the type can hold, since apply has to turn those away
+// cleanly too.
+
+use super::*;
+use proptest::collection::vec;
+use proptest::prelude::{any, prop_oneof, Arbitrary, BoxedStrategy, Just};
+use proptest::sample::select;
+use proptest::strategy::Strategy as _;
+
+impl Arbitrary for PlayerId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<PlayerId>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![9 => 0..MAX_PLAYERS as i8, 1 => any::<i8>()]
+            .prop_map(PlayerId)
+            .boxed()
+    }
+}
+
+impl Arbitrary for PropertyId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<PropertyId>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![9 => 0..board::standard_squares().len() as i8, 1 => any::<i8>()]
+            .prop_map(PropertyId)
+            .boxed()
+    }
+}
+
+impl Arbitrary for Money {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Money>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![9 => 0..2000, 1 => any::<i32>()]
+            .prop_map(Money)
+            .boxed()
+    }
+}
+
+impl Arbitrary for RollResult {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<RollResult>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![9 => (1..=6i8, 1..=6i8), 1 => any::<(i8, i8)>()]
+            .prop_map(|(one, two)| RollResult(one, two))
+            .boxed()
+    }
+}
+
+impl Arbitrary for TaxChoice {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<TaxChoice>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![Just(TaxChoice::Flat), Just(TaxChoice::Percent)].boxed()
+    }
+}
+
+impl Arbitrary for Card {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Card>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            select(CHANCE_CARDS).prop_map(Card::Chance),
+            select(COMMUNITY_CHEST_CARDS).prop_map(Card::CommunityChest),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Bid {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Bid>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<PlayerId>(), any::<Money>())
+            .prop_map(|(bidder, amount)| Bid(bidder, amount))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Trade {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Trade>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let side = || {
+            (
+                vec(any::<PropertyId>(), 0..4),
+                any::<Money>(),
+                prop_oneof![9 => 0..3u8, 1 => any::<u8>()],
+            )
+        };
+        (any::<PlayerId>(), any::<PlayerId>(), side(), side())
+            .prop_map(|(proposer, recipient, offer, request)| Trade {
+                proposer,
+                recipient,
+                offered: offer.0,
+                requested: request.0,
+                offered_cash: offer.1,
+                requested_cash: request.1,
+                offered_jail_cards: offer.2,
+                requested_jail_cards: request.2,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Command {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Command>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let player = any::<PlayerId>;
+        let property = any::<PropertyId>;
+        prop_oneof![
+            (player(), any::<RollResult>()).prop_map(|(id, roll)| Command::RollDice(id, roll)),
+            player().prop_map(Command::Roll),
+            (player(), any::<i8>()).prop_map(|(id, spaces)| Command::MoveForward(id, spaces)),
+            (player(), property()).prop_map(|(id, lot)| Command::BuyProperty(id, lot)),
+            player().prop_map(Command::DeclineToBuy),
+            (player(), property()).prop_map(|(id, lot)| Command::SellProperty(id, lot)),
+            (player(), property()).prop_map(|(id, lot)| Command::BuyHouse(id, lot)),
+            (player(), property()).prop_map(|(id, lot)| Command::SellHouse(id, lot)),
+            (player(), property()).prop_map(|(id, lot)| Command::BuyHotel(id, lot)),
+            (player(), property()).prop_map(|(id, lot)| Command::SellHotel(id, lot)),
+            (player(), any::<Money>()).prop_map(|(id, amount)| Command::PayTaxes(id, amount)),
+            (player(), any::<TaxChoice>())
+                .prop_map(|(id, choice)| Command::PayIncomeTax(id, choice)),
+            player().prop_map(Command::ReceiveSalary),
+            (player(), any::<Card>()).prop_map(|(id, card)| Command::DrawCard(id, card)),
+            player().prop_map(Command::GoToJail),
+            player().prop_map(Command::PayJailFine),
+            player().prop_map(Command::UseGetOutOfJailFreeCard),
+            (property(), vec(any::<Bid>(), 0..4))
+                .prop_map(|(lot, bids)| Command::AuctionProperty(lot, bids)),
+            (player(), any::<Money>()).prop_map(|(id, amount)| Command::PlaceBid(id, amount)),
+            player().prop_map(Command::PassAuction),
+            (player(), property()).prop_map(|(id, lot)| Command::MortgageProperty(id, lot)),
+            (player(), property()).prop_map(|(id, lot)| Command::UnmortgageProperty(id, lot)),
+            any::<Trade>().prop_map(Command::ProposeTrade),
+            player().prop_map(Command::AcceptTrade),
+            player().prop_map(Command::RejectTrade),
+            player().prop_map(Command::PayDebt),
+            player().prop_map(Command::DeclareBankruptcy),
+            player().prop_map(Command::EndTurn),
+            player().prop_map(Command::Resign),
+            (player(), vec(1..=6i8, 0..5)).prop_map(|(id, dice)| Command::RollMany(id, dice)),
+        ]
+        .boxed()
+    }
+}
+
+// Games part way through, from generate_state: up to two hundred turns of
+// random play from any seed.
+pub fn game_states() -> BoxedStrategy<GameState> {
+    (any::<u64>(), 0..200u32)
+        .prop_map(|(seed, turns)| generate_state(seed, turns))
+        .boxed()
+}
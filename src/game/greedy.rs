@@ -0,0 +1,133 @@
+// A strategy that buys everything it can, as a baseline any serious bot
+// should beat and RandomStrategy should not.
+
+use super::*;
+
+// Buys whatever it lands on and can afford, bids up to the printed price at
+// auction, and builds evenly on its monopolies while it has cash to spare.
+// It never offers trades, and takes one only if it completes a monopoly for
+// it without completing one for the other side.
+#[derive(Clone, Debug)]
+pub struct GreedyBuyer {
+    jail_floor: Money, // pays its way out of jail when it has more than this
+    reserve: Money,    // keeps this much back when building
+}
+
+impl Default for GreedyBuyer {
+    fn default() -> GreedyBuyer {
+        GreedyBuyer::new(Money(200), Money(300))
+    }
+}
+
+impl GreedyBuyer {
+    pub fn new(jail_floor: Money, reserve: Money) -> GreedyBuyer {
+        GreedyBuyer {
+            jail_floor,
+            reserve,
+        }
+    }
+}
+
+impl Strategy for GreedyBuyer {
+    fn buy_or_decline(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+    ) -> Command {
+        match state.players[player.0 as usize].cash.0 >= state.price(property.0 as usize).0 {
+            true => Command::BuyProperty(player, property),
+            false => Command::DeclineToBuy(player),
+        }
+    }
+
+    fn bid(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+        high_bid: Option<&Bid>,
+    ) -> Command {
+        let bid = high_bid.map_or(0, |Bid(_, amount)| amount.0) + 1;
+        let cash = state.players[player.0 as usize].cash.0;
+        match bid <= state.price(property.0 as usize).0 && bid <= cash {
+            true => Command::PlaceBid(player, Money(bid)),
+            false => Command::PassAuction(player),
+        }
+    }
+
+    fn income_tax(&mut self, state: &GameState, player: PlayerId, worth: Money) -> Command {
+        let position = state.players[player.0 as usize].position;
+        let choice = match &state.board.squares[position] {
+            Square::Tax(tax) if worth.0 * tax.percent.unwrap_or(100) / 100 < tax.amount.0 => {
+                TaxChoice::Percent
+            }
+            _ => TaxChoice::Flat,
+        };
+        Command::PayIncomeTax(player, choice)
+    }
+
+    fn leave_jail(&mut self, state: &GameState, player: PlayerId) -> Command {
+        let jailed = &state.players[player.0 as usize];
+        if !jailed.jail_cards.is_empty() {
+            Command::UseGetOutOfJailFreeCard(player)
+        } else if jailed.cash.0 > self.jail_floor.0
+            && jailed.cash.0 >= state.config.rules.jail_fine.0
+        {
+            Command::PayJailFine(player)
+        } else {
+            Command::Roll(player)
+        }
+    }
+
+    // Sells buildings and mortgages titles in board order until it can pay.
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        _: Option<PlayerId>,
+        amount: Money,
+    ) -> Command {
+        if state.players[player.0 as usize].cash.0 >= amount.0 {
+            return Command::PayDebt(player);
+        }
+        state
+            .ways_to_raise_cash(player)
+            .into_iter()
+            .next()
+            .unwrap_or(Command::DeclareBankruptcy(player))
+    }
+
+    fn answer_trade(&mut self, state: &GameState, player: PlayerId, trade: &Trade) -> Command {
+        let after = match state.applied(Command::AcceptTrade(player)) {
+            Ok(after) => after,
+            Err(_) => return Command::RejectTrade(player),
+        };
+        let gains = |id| after.monopolies_of(id).len() > state.monopolies_of(id).len();
+        match gains(player) && !gains(trade.proposer) {
+            true => Command::AcceptTrade(player),
+            false => Command::RejectTrade(player),
+        }
+    }
+
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command {
+        let cash = state.players[player.0 as usize].cash.0;
+        let affordable = |command: &Command| {
+            let cost = match command {
+                Command::BuyHouse(_, property) => {
+                    state.street(property.0 as usize).unwrap().house_cost
+                }
+                Command::BuyHotel(_, property) => {
+                    state.street(property.0 as usize).unwrap().hotel_cost.0
+                }
+                _ => return false,
+            };
+            cash - cost.0 >= self.reserve.0
+        };
+        state
+            .ways_to_build(player)
+            .into_iter()
+            .find(affordable)
+            .unwrap_or(Command::EndTurn(player))
+    }
+}
@@ -0,0 +1,71 @@
+// What titles can be expected to bring in, given how likely each square is
+// to be landed on in an opponent's turn: steady_state for the real odds, or
+// any other spread to ask what-if.
+
+use super::*;
+
+impl GameState {
+    // The rent the property collects in an average opponent turn as things
+    // stand. Utility rent is averaged over the rules' dice.
+    pub fn expected_income(&self, property: PropertyId, landing: &[f64]) -> Result<f64, GameError> {
+        let index = self.ensure_property(property)?;
+        let odds = self.landing_odds(index, landing)?;
+        let rent = match &self.board.squares[index] {
+            Square::Utility(_) => {
+                let mut rent = 0.0;
+                for (total, _, chance) in self.config.rules.dice.outcomes() {
+                    rent += self.rent_for(property, Some(total))?.0 as f64 * chance;
+                }
+                rent
+            }
+            _ => self.rent_due(property, None)?.0 as f64,
+        };
+        Ok(odds * rent)
+    }
+
+    // How many opponent turns the lot's next house, or its hotel, takes to
+    // pay for itself in extra rent, whether or not the rules would let it be
+    // built yet. None for lots that are not streets or already have a hotel.
+    pub fn payback_turns(
+        &self,
+        property: PropertyId,
+        landing: &[f64],
+    ) -> Result<Option<f64>, GameError> {
+        let index = self.ensure_property(property)?;
+        let odds = self.landing_odds(index, landing)?;
+        let (street, deed) = match self.street(index) {
+            Some(street) if !self.deeds[index].hotel => (street, &self.deeds[index]),
+            _ => return Ok(None),
+        };
+        let now = match deed.houses {
+            0 => self.rent_due(property, None)?,
+            houses => street.houses[houses as usize - 1],
+        };
+        let (cost, next) = match deed.houses {
+            houses if (houses as i8) < street.hotel_cost.1 => {
+                (street.house_cost, street.houses[houses as usize])
+            }
+            _ => (street.hotel_cost.0, street.hotel),
+        };
+        Ok(Some(cost.0 as f64 / (odds * (next.0 - now.0) as f64)))
+    }
+
+    // The expected income of every lot in the group, together.
+    pub fn group_income(&self, color: ColorGroup, landing: &[f64]) -> Result<f64, GameError> {
+        self.color_group_members(color)
+            .iter()
+            .map(|property| self.expected_income(*property, landing))
+            .sum()
+    }
+
+    fn landing_odds(&self, index: usize, landing: &[f64]) -> Result<f64, GameError> {
+        match landing.len() == self.board.len() {
+            true => Ok(landing[index]),
+            false => Err(GameError::InvalidConfig(format!(
+                "{} landing odds for {} squares",
+                landing.len(),
+                self.board.len()
+            ))),
+        }
+    }
+}
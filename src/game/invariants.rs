@@ -0,0 +1,241 @@
+// What must hold of any game the engine could have produced. Loading a save
+// relies on this to turn away a payload that claims the impossible.
+
+use super::*;
+
+fn invalid(message: String) -> GameError {
+    GameError::InvalidState(message)
+}
+
+impl GameState {
+    // Everything the engine keeps true of a game, from the cards in the
+    // decks down to the order of the log. A saved game is checked against
+    // these before it is loaded, and any state the engine hands back should
+    // pass them.
+    pub fn check_invariants(&self) -> Result<(), GameError> {
+        let count = self.players.len();
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&count) {
+            return Err(invalid(format!("{} players", count)));
+        }
+        let player = |id: PlayerId| match self.player(id) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(invalid(format!("player {} does not exist", id.0))),
+        };
+        let property = |id: PropertyId| {
+            self.ensure_property(id)
+                .map_err(|_| invalid(format!("square {} is not a property", id.0)))
+        };
+        for (index, player) in self.players.iter().enumerate() {
+            if player.id != PlayerId(index as i8) {
+                return Err(invalid(format!("player {} is out of order", player.id.0)));
+            }
+            if player.position >= self.board.squares.len() {
+                return Err(invalid(format!(
+                    "player {} is on square {}",
+                    index, player.position
+                )));
+            }
+            if player.cash.0 < 0 {
+                return Err(invalid(format!("player {} has negative cash", index)));
+            }
+            if player
+                .eliminated
+                .is_some_and(|turn| turn > self.turn.number)
+            {
+                return Err(invalid(format!(
+                    "player {} went bankrupt on a turn still to come",
+                    index
+                )));
+            }
+            if player.jail.is_some_and(|attempts| attempts > 2) {
+                return Err(invalid(format!(
+                    "player {} has been in jail too long",
+                    index
+                )));
+            }
+            if self.players[..index]
+                .iter()
+                .any(|other| other.token == player.token)
+            {
+                return Err(invalid(format!(
+                    "player {} has the {} already taken",
+                    index, player.token
+                )));
+            }
+            if let Some(name) = &player.name {
+                if name.trim() != name || name.is_empty() || name.chars().count() > MAX_NAME_LENGTH
+                {
+                    return Err(invalid(format!("player {} has the name {:?}", index, name)));
+                }
+            }
+        }
+        // The config is what replays and undo seat the game from.
+        if self.config.players != count {
+            return Err(invalid(format!(
+                "the config seats {} players, not {}",
+                self.config.players, count
+            )));
+        }
+        if !self.config.seats.is_empty() && self.config.seats != self.seats() {
+            return Err(invalid(
+                "the config's seats are not the players'".to_string(),
+            ));
+        }
+
+        if self.deeds.len() != self.board.squares.len() {
+            return Err(invalid(format!(
+                "{} deeds for {} squares",
+                self.deeds.len(),
+                self.board.squares.len()
+            )));
+        }
+        for (index, deed) in self.deeds.iter().enumerate() {
+            if property(PropertyId(index as i8)).is_err() {
+                if *deed != Deed::default() {
+                    return Err(invalid(format!("square {} cannot be owned", index)));
+                }
+                continue;
+            }
+            match deed.owner {
+                Some(owner) => {
+                    player(owner)?;
+                    if self.players[owner.0 as usize].is_bankrupt() {
+                        return Err(invalid(format!("bankrupt player {} owns titles", owner.0)));
+                    }
+                }
+                None if *deed != Deed::default() => {
+                    return Err(invalid(format!("unowned square {} is not clear", index)));
+                }
+                None => {}
+            }
+            if deed.level() > 0 && self.street(index).is_none() {
+                return Err(invalid(format!("square {} cannot have buildings", index)));
+            }
+            if deed.houses > 4 || (deed.hotel && deed.houses > 0) {
+                return Err(invalid(format!("square {} has too many buildings", index)));
+            }
+        }
+        // Buildings stand only on monopolies with nothing in the group
+        // mortgaged. They needn't be even: a hotel sold in a housing shortage
+        // leaves its lot short.
+        for color in ColorGroup::ALL.iter() {
+            let group: Vec<&Deed> = self
+                .color_group_members(*color)
+                .iter()
+                .map(|lot| &self.deeds[lot.0 as usize])
+                .collect();
+            if group.iter().all(|deed| deed.level() == 0) {
+                continue;
+            }
+            if group.iter().any(|deed| deed.owner != group[0].owner) {
+                return Err(invalid(format!(
+                    "{:?} has buildings without a monopoly",
+                    color
+                )));
+            }
+            if group.iter().any(|deed| deed.mortgaged) {
+                return Err(invalid(format!("{:?} has buildings and a mortgage", color)));
+            }
+        }
+        let houses: u32 = self.deeds.iter().map(|deed| deed.houses as u32).sum();
+        let hotels: u32 = self.deeds.iter().map(|deed| deed.hotel as u32).sum();
+        // Fewer is fine: tests stage shortages that way. An unlimited bank
+        // has no stock to count against.
+        if !self.config.rules.unlimited_buildings
+            && (houses + self.houses as u32 > BANK_HOUSES as u32
+                || hotels + self.hotels as u32 > BANK_HOTELS as u32)
+        {
+            return Err(invalid(
+                "there are more buildings than the bank owns".to_string(),
+            ));
+        }
+
+        // Every card is either in its deck or in someone's hand, exactly once.
+        let held: Vec<&Card> = self
+            .players
+            .iter()
+            .flat_map(|player| &player.jail_cards)
+            .collect();
+        let keepable = |card: &&Card| match card {
+            Card::Chance(card) => card.effect() == CardEffect::GetOutOfJailFree,
+            Card::CommunityChest(card) => card.effect() == CardEffect::GetOutOfJailFree,
+        };
+        if !held.iter().all(keepable) {
+            return Err(invalid("only jail cards can be kept".to_string()));
+        }
+        let mut chance: Vec<ChanceCard> = self.chance.iter().copied().collect();
+        let mut community_chest: Vec<CommunityChestCard> =
+            self.community_chest.iter().copied().collect();
+        for card in held {
+            match card {
+                Card::Chance(card) => chance.push(*card),
+                Card::CommunityChest(card) => community_chest.push(*card),
+            }
+        }
+        if !same_cards(&chance, CHANCE_CARDS)
+            || !same_cards(&community_chest, COMMUNITY_CHEST_CARDS)
+        {
+            return Err(invalid("the decks do not hold every card once".to_string()));
+        }
+
+        player(self.turn.player)?;
+        for decision in &self.pending {
+            player(decision.player())?;
+            match decision {
+                PendingDecision::BuyOrDecline { property: id, .. }
+                | PendingDecision::UtilityRoll { property: id, .. }
+                | PendingDecision::PayBid { property: id, .. } => {
+                    property(*id)?;
+                }
+                PendingDecision::Auction {
+                    property: id,
+                    high_bid,
+                    bidders,
+                    next,
+                } => {
+                    property(*id)?;
+                    for bidder in bidders {
+                        player(*bidder)?;
+                    }
+                    if let Some(Bid(bidder, _)) = high_bid {
+                        player(*bidder)?;
+                    }
+                    if *next >= bidders.len() {
+                        return Err(invalid("the auction has no next bidder".to_string()));
+                    }
+                }
+                PendingDecision::PayDebt { creditor, .. } => {
+                    if let Some(creditor) = creditor {
+                        player(*creditor)?;
+                    }
+                }
+                PendingDecision::IncomeTax { .. } => {}
+            }
+        }
+        if let Some(trade) = &self.trade {
+            player(trade.proposer)?;
+            player(trade.recipient)?;
+            for id in trade.offered.iter().chain(&trade.requested) {
+                property(*id)?;
+            }
+        }
+        for (seq, record) in self.events.iter().enumerate() {
+            if record.seq != self.compacted + seq {
+                return Err(invalid(format!("event {} is out of order", seq)));
+            }
+        }
+        if self.config.hash_chain {
+            self.verify_log()
+                .map_err(|error| invalid(error.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn same_cards<T: PartialEq>(cards: &[T], deck: &[T]) -> bool {
+    cards.len() == deck.len()
+        && deck.iter().all(|card| {
+            cards.iter().filter(|c| *c == card).count()
+                == deck.iter().filter(|c| *c == card).count()
+        })
+}
@@ -0,0 +1,197 @@
+// Writing a game down as it is played, so a crash loses at most the event
+// being written. A journal file is a header line holding the config, then
+// one event record per line:
+//
+//     {"config":{..},"version":3}
+//     {"seq":0,"turn":1,"actor":0,"command":{"RollDice":[0,[3,4]]},"effects":[..],"time":null,"digest":null}
+//
+// A process that dies mid-write leaves a partial last line, which loading
+// ignores.
+
+use super::*;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+// Somewhere to send events as they happen.
+pub trait EventSink {
+    // Called once, before any records.
+    fn start(&mut self, config: &GameConfig) -> io::Result<()>;
+    fn record(&mut self, record: &EventRecord) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+pub struct FileSink {
+    out: BufWriter<File>,
+    flush_every: usize,
+    unflushed: usize,
+}
+
+impl FileSink {
+    // Replaces whatever is at the path. Every record is flushed as it is
+    // written unless flush_every says otherwise.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<FileSink> {
+        Ok(FileSink {
+            out: BufWriter::new(File::create(path)?),
+            flush_every: 1,
+            unflushed: 0,
+        })
+    }
+
+    // Flushes after every `events` records; 0 leaves it to flush() and drop.
+    pub fn flush_every(mut self, events: usize) -> FileSink {
+        self.flush_every = events;
+        self
+    }
+}
+
+impl EventSink for FileSink {
+    fn start(&mut self, config: &GameConfig) -> io::Result<()> {
+        let header = serde_json::json!({
+            "version": LOG_VERSION,
+            "config": config,
+        });
+        writeln!(self.out, "{}", header)?;
+        self.flush()
+    }
+
+    fn record(&mut self, record: &EventRecord) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        writeln!(self.out)?;
+        self.unflushed += 1;
+        if self.flush_every > 0 && self.unflushed >= self.flush_every {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.unflushed = 0;
+        self.out.flush()
+    }
+}
+
+#[derive(Debug)]
+pub enum JournalError {
+    Io(io::Error),
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+    UnsupportedVersion {
+        found: Option<u64>,
+        supported: u64,
+    },
+    Game(GameError),
+    Replay(ReplayError),
+}
+
+impl Error for JournalError {}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JournalError::Io(error) => write!(f, "{}", error),
+            JournalError::Json { line, error } => write!(f, "line {}: {}", line, error),
+            JournalError::UnsupportedVersion {
+                found: Some(version),
+                supported,
+            } => write!(
+                f,
+                "version {} is not supported; this build reads version {}",
+                version, supported
+            ),
+            JournalError::UnsupportedVersion { found: None, .. } => write!(f, "missing version"),
+            JournalError::Game(error) => write!(f, "{}", error),
+            JournalError::Replay(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<io::Error> for JournalError {
+    fn from(error: io::Error) -> JournalError {
+        JournalError::Io(error)
+    }
+}
+
+// A game that sends every event it accepts to a sink.
+pub struct Journaled<S: EventSink> {
+    state: GameState,
+    sink: S,
+}
+
+impl<S: EventSink> Journaled<S> {
+    // Writes out the events the game already has, so the sink always holds
+    // the whole game.
+    pub fn new(state: GameState, mut sink: S) -> Result<Journaled<S>, JournalError> {
+        if state.compacted > 0 {
+            return Err(JournalError::Game(illegal(
+                "a compacted game cannot be journaled from the start",
+            )));
+        }
+        sink.start(&state.config)?;
+        for record in &state.events {
+            sink.record(record)?;
+        }
+        Ok(Journaled { state, sink })
+    }
+
+    // If the sink fails the command has still been applied; only the journal
+    // is behind.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<Effect>, JournalError> {
+        let effects = self.state.apply(command).map_err(JournalError::Game)?;
+        self.sink.record(self.state.events.last().unwrap())?;
+        Ok(effects)
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn sink(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    pub fn into_inner(mut self) -> Result<(GameState, S), JournalError> {
+        self.sink.flush()?;
+        Ok((self.state, self.sink))
+    }
+}
+
+impl GameState {
+    // Replays a journal up to its last complete record, checking the hash
+    // chain if the game has one.
+    pub fn load_journal(path: impl AsRef<Path>) -> Result<GameState, JournalError> {
+        let text = fs::read_to_string(path)?;
+        let complete = match text.rfind('\n') {
+            Some(end) => &text[..end],
+            None => "",
+        };
+        let mut lines = complete.lines().enumerate();
+        let header = lines.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::UnexpectedEof, "the journal has no header")
+        })?;
+        let json = |error| JournalError::Json { line: 1, error };
+        let mut header: serde_json::Value = serde_json::from_str(header.1).map_err(json)?;
+        let version = header["version"].as_u64();
+        if version != Some(LOG_VERSION) {
+            return Err(JournalError::UnsupportedVersion {
+                found: version,
+                supported: LOG_VERSION,
+            });
+        }
+        let config: GameConfig = serde_json::from_value(header["config"].take()).map_err(json)?;
+        let mut records = Vec::new();
+        for (index, line) in lines {
+            let record: EventRecord =
+                serde_json::from_str(line).map_err(|error| JournalError::Json {
+                    line: index + 1,
+                    error,
+                })?;
+            records.push(record);
+        }
+        GameLog { config, records }
+            .replay()
+            .map_err(JournalError::Replay)
+    }
+}
@@ -0,0 +1,172 @@
+// Which squares players end up on, counted from the log: every roll, every
+// card that moves them and every trip to jail. Trips round the board that
+// only pass GO are counted apart.
+
+use super::*;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LandingStats {
+    board: Board,
+    counts: Vec<u64>, // by square
+    passed_go: u64,
+}
+
+impl Default for LandingStats {
+    fn default() -> LandingStats {
+        LandingStats::new(BoardEdition::default())
+    }
+}
+
+impl LandingStats {
+    pub fn new(edition: BoardEdition) -> LandingStats {
+        LandingStats::on_board(Board::for_edition(edition))
+    }
+
+    fn on_board(board: Board) -> LandingStats {
+        LandingStats {
+            counts: vec![0; board.len()],
+            board,
+            passed_go: 0,
+        }
+    }
+
+    // Stats for counts gathered elsewhere, one per square of the board.
+    pub fn from_counts(edition: BoardEdition, counts: Vec<u64>) -> Option<LandingStats> {
+        let board = Board::for_edition(edition);
+        if counts.len() != board.len() {
+            return None;
+        }
+        Some(LandingStats {
+            board,
+            counts,
+            passed_go: 0,
+        })
+    }
+
+    pub fn for_game(state: &GameState) -> LandingStats {
+        let mut stats = LandingStats::on_board(state.board.clone());
+        for record in &state.events {
+            stats.record(record);
+        }
+        stats
+    }
+
+    // Counts each square a player moved to, and Jail for each trip there. A
+    // move onto Go To Jail counts only as the trip to jail it turned into,
+    // since no one stays on that square.
+    pub fn record(&mut self, record: &EventRecord) {
+        let mut effects = record.effects.iter().peekable();
+        while let Some(effect) = effects.next() {
+            match effect {
+                Effect::Moved { player, to }
+                    if effects.peek() != Some(&&Effect::WentToJail(*player)) =>
+                {
+                    self.counts[*to] += 1
+                }
+                Effect::WentToJail(_) => self.counts[self.board.jail] += 1,
+                Effect::PassedGo(_) => self.passed_go += 1,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &LandingStats) {
+        for (count, more) in self.counts.iter_mut().zip(&other.counts) {
+            *count += more;
+        }
+        self.passed_go += other.passed_go;
+    }
+
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    // How often players went round the board without stopping on GO, and
+    // how often they stopped there.
+    pub fn passed_go(&self) -> u64 {
+        self.passed_go
+    }
+
+    pub fn landed_on_go(&self) -> u64 {
+        self.counts[self.board.go]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    // Each square's share of all landings; all zero before any.
+    pub fn frequencies(&self) -> Vec<f64> {
+        let total = self.total().max(1) as f64;
+        self.counts
+            .iter()
+            .map(|count| *count as f64 / total)
+            .collect()
+    }
+
+    // Squares by how often they were landed on, most first, board order
+    // breaking ties.
+    pub fn ranked(&self) -> Vec<usize> {
+        let mut squares: Vec<usize> = (0..self.counts.len()).collect();
+        squares.sort_by_key(|square| std::cmp::Reverse(self.counts[*square]));
+        squares
+    }
+
+    // The board as render_board draws it, each square shaded by how often it
+    // was landed on against the average square, then the ten busiest.
+    pub fn render_heatmap(&self) -> String {
+        let board = &self.board;
+        let frequencies = self.frequencies();
+        let average = 1.0 / board.len() as f64;
+        let cells: Vec<[String; 3]> = (0..board.len())
+            .map(|square| {
+                let ratio = frequencies[square] / average;
+                let shade = SHADES
+                    .iter()
+                    .find(|(below, _)| ratio < *below)
+                    .map_or('@', |(_, shade)| *shade);
+                [
+                    render::fit(&render::abbreviate(board.name(square).unwrap())),
+                    shade.to_string().repeat(render::WIDTH),
+                    render::fit(&format!("{:.2}%", frequencies[square] * 100.0)),
+                ]
+            })
+            .collect();
+        let mut out = render::draw_ring(&cells);
+        out.push_str("\nMost landed on:\n");
+        for (rank, square) in self.ranked().into_iter().take(10).enumerate() {
+            out.push_str(&format!(
+                "{:>2}. {:<22}{:>6.2}%{:>8}\n",
+                rank + 1,
+                board.name(square).unwrap(),
+                frequencies[square] * 100.0,
+                self.counts[square]
+            ));
+        }
+        out
+    }
+}
+
+// Lightest to darkest, by how many times the average a square is landed on:
+// under half of it, under 0.9, within a tenth of it, under one and a half
+// times and, past all these, '@'.
+const SHADES: [(f64, char); 4] = [(0.5, ' '), (0.9, '.'), (1.1, ':'), (1.5, '#')];
+
+//  3.21%  Jail
+//  2.98%  Illinois Ave
+// ...
+impl fmt::Display for LandingStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let board = &self.board;
+        let frequencies = self.frequencies();
+        for square in self.ranked() {
+            writeln!(
+                f,
+                "{:>6.2}%  {}",
+                frequencies[square] * 100.0,
+                board.name(square).unwrap()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+// Where players end up in the long run, worked out exactly from the dice
+// and the cards rather than sampled by playing games.
+
+use super::*;
+
+// Where a player can be between rolls: free, with the doubles rolled so far
+// this turn, or in jail, with the rolls already tried there.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Place {
+    Free { square: usize, doubles: u8 },
+    Jailed { attempts: u8 },
+}
+
+impl Place {
+    // The jail states come first, then three for each square.
+    fn index(self) -> usize {
+        match self {
+            Place::Jailed { attempts } => attempts as usize,
+            Place::Free { square, doubles } => 3 + square * 3 + doubles as usize,
+        }
+    }
+}
+
+// One way a roll can go: where it leaves the player, where they wait for
+// the next roll, and how likely it is.
+struct Step {
+    to: usize, // a Place index
+    lands: usize,
+    chance: f64,
+}
+
+// Each square's share of the rolls that leave a player on it, after any
+// card or Go To Jail has moved them on, for a player who never pays to
+// leave jail. Cards are drawn as though the decks were reshuffled every
+// time with all their cards in them.
+//
+// The rules' dice decide the moves. Only a pair comes up doubles, so with
+// any other number of dice nobody rolls again or is jailed for speeding,
+// and a jailed player stays in for the full three tries.
+pub fn steady_state(board: &Board, rules: &HouseRules) -> Vec<f64> {
+    let len = board.len();
+    let count = 3 + len * 3;
+    let places: Vec<Place> = (0..3)
+        .map(|attempts| Place::Jailed { attempts })
+        .chain(
+            (0..len).flat_map(|square| (0..3).map(move |doubles| Place::Free { square, doubles })),
+        )
+        .collect();
+    let throws = rules.dice.outcomes();
+    let steps: Vec<Vec<Step>> = places
+        .iter()
+        .map(|place| moves(board, &throws, *place))
+        .collect();
+    let mut odds = vec![0.0; count];
+    odds[Place::Free {
+        square: board.go,
+        doubles: 0,
+    }
+    .index()] = 1.0;
+    let mut landings = vec![0.0; len];
+    // A thousand rolls is far more than it takes to settle; the chain mixes
+    // within a few dozen.
+    for _ in 0..1000 {
+        let mut next = vec![0.0; count];
+        landings = vec![0.0; len];
+        for (from, ways) in steps.iter().enumerate() {
+            for step in ways {
+                next[step.to] += odds[from] * step.chance;
+                landings[step.lands] += odds[from] * step.chance;
+            }
+        }
+        let change: f64 = next.iter().zip(&odds).map(|(a, b)| (a - b).abs()).sum();
+        odds = next;
+        if change < 1e-15 {
+            break;
+        }
+    }
+    landings
+}
+
+// Every way the next roll from a place can go, given every way the dice
+// can come out.
+fn moves(board: &Board, throws: &[(i32, bool, f64)], place: Place) -> Vec<Step> {
+    let jailed = Place::Jailed { attempts: 0 }.index();
+    let mut steps = Vec::new();
+    for &(spaces, doubles, chance) in throws {
+        let (from, rolled) = match place {
+            Place::Free { doubles: 2, .. } if doubles => {
+                steps.push(Step {
+                    to: jailed,
+                    lands: board.jail,
+                    chance,
+                });
+                continue;
+            }
+            Place::Free {
+                square,
+                doubles: so_far,
+            } => (square, if doubles { so_far + 1 } else { 0 }),
+            Place::Jailed { attempts } if !doubles && attempts < 2 => {
+                steps.push(Step {
+                    to: attempts as usize + 1,
+                    lands: board.jail,
+                    chance,
+                });
+                continue;
+            }
+            // Out on doubles, or on the third try after paying, either
+            // way with no roll to follow.
+            Place::Jailed { .. } => (board.jail, 0),
+        };
+        let to = (from + spaces as usize) % board.len();
+        for (lands, sent_to_jail, odds) in settle(board, to) {
+            steps.push(Step {
+                to: match sent_to_jail {
+                    true => jailed,
+                    false => Place::Free {
+                        square: lands,
+                        doubles: rolled,
+                    }
+                    .index(),
+                },
+                lands,
+                chance: chance * odds,
+            });
+        }
+    }
+    steps
+}
+
+// Where a player who lands on the square ends up once any card or Go To
+// Jail has had its way, whether they went to jail, and how likely each is.
+fn settle(board: &Board, square: usize) -> Vec<(usize, bool, f64)> {
+    let effects: Vec<CardEffect> = match &board.squares[square] {
+        Square::GoToJail => return vec![(board.jail, true, 1.0)],
+        Square::Chance => CHANCE_CARDS.iter().map(|card| card.effect()).collect(),
+        Square::CommunityChest => COMMUNITY_CHEST_CARDS
+            .iter()
+            .map(|card| card.effect())
+            .collect(),
+        _ => return vec![(square, false, 1.0)],
+    };
+    let len = board.len();
+    let share = 1.0 / effects.len() as f64;
+    let mut ends = Vec::new();
+    for effect in effects {
+        let target = match effect {
+            CardEffect::AdvanceTo(landmark) => board.landmark(landmark),
+            CardEffect::AdvanceToNearestRailroad => nearest(board, square, |square| {
+                matches!(square, Square::Railroad(_))
+            }),
+            CardEffect::AdvanceToNearestUtility => {
+                nearest(board, square, |square| matches!(square, Square::Utility(_)))
+            }
+            CardEffect::GoBack(spaces) => (square + len - spaces) % len,
+            CardEffect::GoToJail => {
+                ends.push((board.jail, true, share));
+                continue;
+            }
+            _ => {
+                ends.push((square, false, share));
+                continue;
+            }
+        };
+        for (end, sent_to_jail, odds) in settle(board, target) {
+            ends.push((end, sent_to_jail, share * odds));
+        }
+    }
+    ends
+}
+
+fn nearest<F: Fn(&Square) -> bool>(board: &Board, from: usize, kind: F) -> usize {
+    let len = board.len();
+    (1..=len)
+        .map(|offset| (from + offset) % len)
+        .find(|index| kind(&board.squares[*index]))
+        .unwrap()
+}
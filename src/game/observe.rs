@@ -0,0 +1,91 @@
+// Telling interested parties about a game as it is played, rather than having
+// them poll it. Observers are called in the order they were added, straight
+// after each command the game accepts, with the state it left behind:
+//
+//     on_event, then on_game_over if that command won the game, or else
+//     on_turn_start if it began a new turn, then on_decision_required if it
+//     left a new decision waiting.
+//
+// The command is applied before any observer hears of it, so an observer that
+// panics cannot leave the game half-changed. The panic is caught, the
+// observer is dropped, and the rest are told as usual.
+
+use super::*;
+use std::panic::{self, AssertUnwindSafe};
+
+// Every callback does nothing unless overridden.
+pub trait Observer {
+    fn on_event(&mut self, _record: &EventRecord, _state: &GameState) {}
+    fn on_turn_start(&mut self, _turn: u32, _player: PlayerId, _state: &GameState) {}
+    fn on_decision_required(&mut self, _decision: &PendingDecision, _state: &GameState) {}
+    fn on_game_over(&mut self, _winner: PlayerId, _state: &GameState) {}
+}
+
+// A game that tells its observers about every command it accepts. Nothing
+// is said about what happened before an observer was added.
+pub struct Observed {
+    state: GameState,
+    observers: Vec<Box<dyn Observer>>,
+    dropped: usize,
+}
+
+impl Observed {
+    pub fn new(state: GameState) -> Observed {
+        Observed {
+            state,
+            observers: Vec::new(),
+            dropped: 0,
+        }
+    }
+
+    pub fn add_observer(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+
+    // Observers are told nothing about a refused command.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<Effect>, GameError> {
+        let turn = (self.state.turn_number(), self.state.current_player());
+        let decision = self.state.pending().cloned();
+        let effects = self.state.apply(command)?;
+
+        let state = &self.state;
+        let record = state.events.last().unwrap();
+        let winner = state.winner();
+        let new_turn = (state.turn_number(), state.current_player());
+        let new_decision = state
+            .pending()
+            .filter(|now| decision.as_ref() != Some(*now));
+        let before = self.observers.len();
+        self.observers.retain_mut(|observer| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                observer.on_event(record, state);
+                match winner {
+                    Some(winner) => observer.on_game_over(winner, state),
+                    None if new_turn != turn => {
+                        observer.on_turn_start(new_turn.0, new_turn.1, state)
+                    }
+                    None => {}
+                }
+                if let (None, Some(decision)) = (winner, new_decision) {
+                    observer.on_decision_required(decision, state);
+                }
+            }))
+            .is_ok()
+        });
+        self.dropped += before - self.observers.len();
+        Ok(effects)
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    // How many observers have been dropped for panicking.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn into_inner(self) -> GameState {
+        self.state
+    }
+}
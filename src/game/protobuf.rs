@@ -0,0 +1,916 @@
+// Protobuf for configs, commands, logged events and whole games, with the
+// messages in proto/monopoly.proto and the types prost generates from them
+// in proto. Going out can't fail; coming in is checked field by field, and
+// an enum value this build doesn't know, a number too big for its field or a
+// required message left out is refused rather than guessed at.
+
+use super::*;
+use prost::Message;
+
+#[allow(clippy::all)]
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/monopoly.v1.rs"));
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProtoError {
+    Decode(prost::DecodeError),
+    Missing(&'static str), // a message or oneof that has to be set
+    UnknownEnumValue { field: &'static str, value: i32 },
+    OutOfRange(&'static str),
+    // Everything decoded, but it doesn't make a game.
+    Invalid(GameError),
+}
+
+impl Error for ProtoError {}
+
+impl fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtoError::Decode(error) => write!(f, "malformed protobuf: {}", error),
+            ProtoError::Missing(field) => write!(f, "{} is missing", field),
+            ProtoError::UnknownEnumValue { field, value } => {
+                write!(f, "{} has unknown value {}", field, value)
+            }
+            ProtoError::OutOfRange(field) => write!(f, "{} is out of range", field),
+            ProtoError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<prost::DecodeError> for ProtoError {
+    fn from(error: prost::DecodeError) -> ProtoError {
+        ProtoError::Decode(error)
+    }
+}
+
+impl GameState {
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        proto::GameState::from(self).encode_to_vec()
+    }
+
+    pub fn from_protobuf(bytes: &[u8]) -> Result<GameState, ProtoError> {
+        proto::GameState::decode(bytes)?.try_into()
+    }
+}
+
+impl EventRecord {
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        proto::EventRecord::from(self).encode_to_vec()
+    }
+
+    pub fn from_protobuf(bytes: &[u8]) -> Result<EventRecord, ProtoError> {
+        proto::EventRecord::decode(bytes)?.try_into()
+    }
+}
+
+fn required<T>(field: &'static str, value: Option<T>) -> Result<T, ProtoError> {
+    value.ok_or(ProtoError::Missing(field))
+}
+
+fn narrow<T: TryFrom<U>, U>(field: &'static str, value: U) -> Result<T, ProtoError> {
+    T::try_from(value).map_err(|_| ProtoError::OutOfRange(field))
+}
+
+fn player(field: &'static str, id: i32) -> Result<PlayerId, ProtoError> {
+    narrow(field, id).map(PlayerId)
+}
+
+fn property(field: &'static str, id: i32) -> Result<PropertyId, ProtoError> {
+    narrow(field, id).map(PropertyId)
+}
+
+fn optional_player(field: &'static str, id: Option<i32>) -> Result<Option<PlayerId>, ProtoError> {
+    id.map(|id| player(field, id)).transpose()
+}
+
+// Enums whose variants match their messages' one for one. The messages'
+// UNSPECIFIED is unknown like any other value without a variant here.
+macro_rules! same_variants {
+    ($native:ident, $read:ident, $($variant:ident),+ $(,)?) => {
+        impl From<$native> for proto::$native {
+            fn from(value: $native) -> proto::$native {
+                match value {
+                    $($native::$variant => proto::$native::$variant,)+
+                }
+            }
+        }
+
+        fn $read(field: &'static str, value: i32) -> Result<$native, ProtoError> {
+            match proto::$native::try_from(value) {
+                $(Ok(proto::$native::$variant) => Ok($native::$variant),)+
+                _ => Err(ProtoError::UnknownEnumValue { field, value }),
+            }
+        }
+    };
+}
+
+same_variants!(BoardEdition, board_edition, Standard, French, German);
+
+same_variants!(TaxChoice, tax_choice, Flat, Percent);
+
+same_variants!(
+    Token,
+    token,
+    TopHat,
+    Thimble,
+    Boot,
+    Dog,
+    Battleship,
+    Cat,
+    Wheelbarrow,
+    RaceCar,
+);
+
+same_variants!(
+    ChanceCard,
+    chance_card,
+    AdvanceToGo,
+    AdvanceToIllinoisAve,
+    AdvanceToStCharlesPlace,
+    AdvanceToNearestUtility,
+    AdvanceToNearestRailroad,
+    BankDividend,
+    GetOutOfJailFree,
+    GoBackThreeSpaces,
+    GoToJail,
+    GeneralRepairs,
+    PoorTax,
+    TripToReadingRailroad,
+    WalkOnTheBoardwalk,
+    ChairmanOfTheBoard,
+    BuildingLoanMatures,
+);
+
+same_variants!(
+    CommunityChestCard,
+    community_chest_card,
+    AdvanceToGo,
+    BankError,
+    DoctorsFee,
+    SaleOfStock,
+    GetOutOfJailFree,
+    GoToJail,
+    GrandOperaNight,
+    HolidayFund,
+    IncomeTaxRefund,
+    LifeInsurance,
+    HospitalFees,
+    SchoolFees,
+    ConsultancyFee,
+    StreetRepairs,
+    BeautyContest,
+    Inheritance,
+);
+
+// The shapes commands and effects share, read into whichever variant they
+// belong to.
+
+impl From<PlayerId> for proto::PlayerRef {
+    fn from(player: PlayerId) -> proto::PlayerRef {
+        proto::PlayerRef {
+            player: player.0.into(),
+        }
+    }
+}
+
+impl proto::PlayerRef {
+    fn read<T>(self, make: impl FnOnce(PlayerId) -> T) -> Result<T, ProtoError> {
+        Ok(make(player("player", self.player)?))
+    }
+}
+
+impl From<(PlayerId, PropertyId)> for proto::PlayerProperty {
+    fn from((player, property): (PlayerId, PropertyId)) -> proto::PlayerProperty {
+        proto::PlayerProperty {
+            player: player.0.into(),
+            property: property.0.into(),
+        }
+    }
+}
+
+impl proto::PlayerProperty {
+    fn read<T>(self, make: impl FnOnce(PlayerId, PropertyId) -> T) -> Result<T, ProtoError> {
+        Ok(make(
+            player("player", self.player)?,
+            property("property", self.property)?,
+        ))
+    }
+}
+
+impl From<(PlayerId, Money)> for proto::PlayerMoney {
+    fn from((player, amount): (PlayerId, Money)) -> proto::PlayerMoney {
+        proto::PlayerMoney {
+            player: player.0.into(),
+            amount: amount.0,
+        }
+    }
+}
+
+impl proto::PlayerMoney {
+    fn read<T>(self, make: impl FnOnce(PlayerId, Money) -> T) -> Result<T, ProtoError> {
+        Ok(make(player("player", self.player)?, Money(self.amount)))
+    }
+}
+
+impl From<&GameConfig> for proto::GameConfig {
+    fn from(config: &GameConfig) -> proto::GameConfig {
+        proto::GameConfig {
+            players: config.players as u32,
+            starting_cash: config.starting_cash.0,
+            salary: config.salary.0,
+            edition: proto::BoardEdition::from(config.edition).into(),
+            rules: Some(proto::HouseRules {
+                free_parking_pot: config.rules.free_parking_pot,
+                double_salary_on_go: config.rules.double_salary_on_go,
+                auctions_disabled: !config.rules.auctions_enabled,
+                snake_eyes_bonus: config.rules.snake_eyes_bonus.map(|bonus| bonus.0),
+                unlimited_buildings: config.rules.unlimited_buildings,
+                strict_trades: !config.rules.trades_any_time,
+                jail_fine: Some(config.rules.jail_fine.0).filter(|fine| *fine != JAIL_FINE.0),
+                dice: Some(config.rules.dice)
+                    .filter(|dice| *dice != Dice::CLASSIC)
+                    .map(|dice| proto::Dice {
+                        count: dice.count.into(),
+                        faces: dice.faces.into(),
+                    }),
+            }),
+            seed: config.seed,
+            hash_chain: config.hash_chain,
+            engine_dice: config.engine_dice,
+            turn_limit: config.turn_limit,
+            seats: config
+                .seats
+                .iter()
+                .map(|seat| proto::Seat {
+                    token: proto::Token::from(seat.token).into(),
+                    name: seat.name.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// The clock isn't sent; a decoded config has none until it is given one.
+impl TryFrom<proto::GameConfig> for GameConfig {
+    type Error = ProtoError;
+
+    fn try_from(config: proto::GameConfig) -> Result<GameConfig, ProtoError> {
+        let rules = required("GameConfig.rules", config.rules)?;
+        Ok(GameConfig {
+            players: narrow("GameConfig.players", config.players)?,
+            starting_cash: Money(config.starting_cash),
+            salary: Money(config.salary),
+            edition: board_edition("GameConfig.edition", config.edition)?,
+            rules: HouseRules {
+                free_parking_pot: rules.free_parking_pot,
+                double_salary_on_go: rules.double_salary_on_go,
+                auctions_enabled: !rules.auctions_disabled,
+                snake_eyes_bonus: rules.snake_eyes_bonus.map(Money),
+                unlimited_buildings: rules.unlimited_buildings,
+                trades_any_time: !rules.strict_trades,
+                jail_fine: rules.jail_fine.map_or(JAIL_FINE, Money),
+                dice: match rules.dice {
+                    Some(dice) => Dice {
+                        count: narrow("Dice.count", dice.count)?,
+                        faces: narrow("Dice.faces", dice.faces)?,
+                    },
+                    None => Dice::CLASSIC,
+                },
+            },
+            seed: config.seed,
+            clock: SharedClock::default(),
+            hash_chain: config.hash_chain,
+            engine_dice: config.engine_dice,
+            turn_limit: config.turn_limit,
+            seats: config
+                .seats
+                .into_iter()
+                .map(|seat| {
+                    Ok(Seat {
+                        token: token("Seat.token", seat.token)?,
+                        name: seat.name,
+                    })
+                })
+                .collect::<Result<_, ProtoError>>()?,
+        })
+    }
+}
+
+impl From<&Card> for proto::Card {
+    fn from(card: &Card) -> proto::Card {
+        let card = match *card {
+            Card::Chance(card) => proto::card::Card::Chance(proto::ChanceCard::from(card).into()),
+            Card::CommunityChest(card) => {
+                proto::card::Card::CommunityChest(proto::CommunityChestCard::from(card).into())
+            }
+        };
+        proto::Card { card: Some(card) }
+    }
+}
+
+impl TryFrom<proto::Card> for Card {
+    type Error = ProtoError;
+
+    fn try_from(card: proto::Card) -> Result<Card, ProtoError> {
+        Ok(match required("Card.card", card.card)? {
+            proto::card::Card::Chance(card) => Card::Chance(chance_card("Card.chance", card)?),
+            proto::card::Card::CommunityChest(card) => {
+                Card::CommunityChest(community_chest_card("Card.community_chest", card)?)
+            }
+        })
+    }
+}
+
+impl From<&Bid> for proto::Bid {
+    fn from(Bid(player, amount): &Bid) -> proto::Bid {
+        proto::Bid {
+            player: player.0.into(),
+            amount: amount.0,
+        }
+    }
+}
+
+impl TryFrom<proto::Bid> for Bid {
+    type Error = ProtoError;
+
+    fn try_from(bid: proto::Bid) -> Result<Bid, ProtoError> {
+        Ok(Bid(player("Bid.player", bid.player)?, Money(bid.amount)))
+    }
+}
+
+impl From<&Trade> for proto::Trade {
+    fn from(trade: &Trade) -> proto::Trade {
+        let ids = |ids: &[PropertyId]| ids.iter().map(|id| id.0.into()).collect();
+        proto::Trade {
+            proposer: trade.proposer.0.into(),
+            recipient: trade.recipient.0.into(),
+            offered: ids(&trade.offered),
+            requested: ids(&trade.requested),
+            offered_cash: trade.offered_cash.0,
+            requested_cash: trade.requested_cash.0,
+            offered_jail_cards: trade.offered_jail_cards.into(),
+            requested_jail_cards: trade.requested_jail_cards.into(),
+        }
+    }
+}
+
+impl TryFrom<proto::Trade> for Trade {
+    type Error = ProtoError;
+
+    fn try_from(trade: proto::Trade) -> Result<Trade, ProtoError> {
+        let ids = |field, ids: Vec<i32>| -> Result<Vec<PropertyId>, ProtoError> {
+            ids.into_iter().map(|id| property(field, id)).collect()
+        };
+        Ok(Trade {
+            proposer: player("Trade.proposer", trade.proposer)?,
+            recipient: player("Trade.recipient", trade.recipient)?,
+            offered: ids("Trade.offered", trade.offered)?,
+            requested: ids("Trade.requested", trade.requested)?,
+            offered_cash: Money(trade.offered_cash),
+            requested_cash: Money(trade.requested_cash),
+            offered_jail_cards: narrow("Trade.offered_jail_cards", trade.offered_jail_cards)?,
+            requested_jail_cards: narrow("Trade.requested_jail_cards", trade.requested_jail_cards)?,
+        })
+    }
+}
+
+impl From<&Command> for proto::Command {
+    fn from(command: &Command) -> proto::Command {
+        use proto::command::{self, Command as C};
+
+        let command = match command {
+            Command::RollDice(player, RollResult(first, second)) => {
+                C::RollDice(command::RollDice {
+                    player: player.0.into(),
+                    roll: Some(proto::RollResult {
+                        first: (*first).into(),
+                        second: (*second).into(),
+                    }),
+                })
+            }
+            Command::Roll(player) => C::Roll((*player).into()),
+            Command::MoveForward(player, spaces) => C::MoveForward(command::MoveForward {
+                player: player.0.into(),
+                spaces: (*spaces).into(),
+            }),
+            Command::BuyProperty(player, id) => C::BuyProperty((*player, *id).into()),
+            Command::DeclineToBuy(player) => C::DeclineToBuy((*player).into()),
+            Command::SellProperty(player, id) => C::SellProperty((*player, *id).into()),
+            Command::BuyHouse(player, id) => C::BuyHouse((*player, *id).into()),
+            Command::SellHouse(player, id) => C::SellHouse((*player, *id).into()),
+            Command::BuyHotel(player, id) => C::BuyHotel((*player, *id).into()),
+            Command::SellHotel(player, id) => C::SellHotel((*player, *id).into()),
+            Command::PayTaxes(player, amount) => C::PayTaxes((*player, *amount).into()),
+            Command::PayIncomeTax(player, choice) => C::PayIncomeTax(command::PayIncomeTax {
+                player: player.0.into(),
+                choice: proto::TaxChoice::from(*choice).into(),
+            }),
+            Command::ReceiveSalary(player) => C::ReceiveSalary((*player).into()),
+            Command::DrawCard(player, card) => C::DrawCard(command::DrawCard {
+                player: player.0.into(),
+                card: Some(card.into()),
+            }),
+            Command::GoToJail(player) => C::GoToJail((*player).into()),
+            Command::PayJailFine(player) => C::PayJailFine((*player).into()),
+            Command::UseGetOutOfJailFreeCard(player) => {
+                C::UseGetOutOfJailFreeCard((*player).into())
+            }
+            Command::AuctionProperty(id, bids) => C::AuctionProperty(command::AuctionProperty {
+                property: id.0.into(),
+                bids: bids.iter().map(proto::Bid::from).collect(),
+            }),
+            Command::PlaceBid(player, amount) => C::PlaceBid((*player, *amount).into()),
+            Command::PassAuction(player) => C::PassAuction((*player).into()),
+            Command::MortgageProperty(player, id) => C::MortgageProperty((*player, *id).into()),
+            Command::UnmortgageProperty(player, id) => C::UnmortgageProperty((*player, *id).into()),
+            Command::ProposeTrade(trade) => C::ProposeTrade(trade.into()),
+            Command::AcceptTrade(player) => C::AcceptTrade((*player).into()),
+            Command::RejectTrade(player) => C::RejectTrade((*player).into()),
+            Command::PayDebt(player) => C::PayDebt((*player).into()),
+            Command::DeclareBankruptcy(player) => C::DeclareBankruptcy((*player).into()),
+            Command::EndTurn(player) => C::EndTurn((*player).into()),
+            Command::Resign(player) => C::Resign((*player).into()),
+            Command::RollMany(player, dice) => C::RollMany(command::RollMany {
+                player: player.0.into(),
+                dice: dice.iter().map(|&die| die.into()).collect(),
+            }),
+        };
+        proto::Command {
+            command: Some(command),
+        }
+    }
+}
+
+impl TryFrom<proto::Command> for Command {
+    type Error = ProtoError;
+
+    fn try_from(command: proto::Command) -> Result<Command, ProtoError> {
+        use proto::command::Command as C;
+
+        match required("Command.command", command.command)? {
+            C::RollDice(roll) => {
+                let dice = required("RollDice.roll", roll.roll)?;
+                Ok(Command::RollDice(
+                    player("RollDice.player", roll.player)?,
+                    RollResult(
+                        narrow("RollResult.first", dice.first)?,
+                        narrow("RollResult.second", dice.second)?,
+                    ),
+                ))
+            }
+            C::Roll(at) => at.read(Command::Roll),
+            C::MoveForward(step) => Ok(Command::MoveForward(
+                player("MoveForward.player", step.player)?,
+                narrow("MoveForward.spaces", step.spaces)?,
+            )),
+            C::BuyProperty(at) => at.read(Command::BuyProperty),
+            C::DeclineToBuy(at) => at.read(Command::DeclineToBuy),
+            C::SellProperty(at) => at.read(Command::SellProperty),
+            C::BuyHouse(at) => at.read(Command::BuyHouse),
+            C::SellHouse(at) => at.read(Command::SellHouse),
+            C::BuyHotel(at) => at.read(Command::BuyHotel),
+            C::SellHotel(at) => at.read(Command::SellHotel),
+            C::PayTaxes(at) => at.read(Command::PayTaxes),
+            C::PayIncomeTax(tax) => Ok(Command::PayIncomeTax(
+                player("PayIncomeTax.player", tax.player)?,
+                tax_choice("PayIncomeTax.choice", tax.choice)?,
+            )),
+            C::ReceiveSalary(at) => at.read(Command::ReceiveSalary),
+            C::DrawCard(draw) => Ok(Command::DrawCard(
+                player("DrawCard.player", draw.player)?,
+                required("DrawCard.card", draw.card)?.try_into()?,
+            )),
+            C::GoToJail(at) => at.read(Command::GoToJail),
+            C::PayJailFine(at) => at.read(Command::PayJailFine),
+            C::UseGetOutOfJailFreeCard(at) => at.read(Command::UseGetOutOfJailFreeCard),
+            C::AuctionProperty(auction) => Ok(Command::AuctionProperty(
+                property("AuctionProperty.property", auction.property)?,
+                auction
+                    .bids
+                    .into_iter()
+                    .map(Bid::try_from)
+                    .collect::<Result<_, _>>()?,
+            )),
+            C::PlaceBid(at) => at.read(Command::PlaceBid),
+            C::PassAuction(at) => at.read(Command::PassAuction),
+            C::MortgageProperty(at) => at.read(Command::MortgageProperty),
+            C::UnmortgageProperty(at) => at.read(Command::UnmortgageProperty),
+            C::ProposeTrade(trade) => Ok(Command::ProposeTrade(trade.try_into()?)),
+            C::AcceptTrade(at) => at.read(Command::AcceptTrade),
+            C::RejectTrade(at) => at.read(Command::RejectTrade),
+            C::PayDebt(at) => at.read(Command::PayDebt),
+            C::DeclareBankruptcy(at) => at.read(Command::DeclareBankruptcy),
+            C::EndTurn(at) => at.read(Command::EndTurn),
+            C::Resign(at) => at.read(Command::Resign),
+            C::RollMany(roll) => Ok(Command::RollMany(
+                player("RollMany.player", roll.player)?,
+                roll.dice
+                    .into_iter()
+                    .map(|die| narrow("RollMany.dice", die))
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+impl From<&PendingDecision> for proto::PendingDecision {
+    fn from(decision: &PendingDecision) -> proto::PendingDecision {
+        use proto::pending_decision::{self, Decision as D};
+
+        let decision = match decision {
+            PendingDecision::BuyOrDecline { player, property } => {
+                D::BuyOrDecline((*player, *property).into())
+            }
+            PendingDecision::Auction {
+                property,
+                high_bid,
+                bidders,
+                next,
+            } => D::Auction(pending_decision::Auction {
+                property: property.0.into(),
+                high_bid: high_bid.as_ref().map(proto::Bid::from),
+                bidders: bidders.iter().map(|bidder| bidder.0.into()).collect(),
+                next: *next as u32,
+            }),
+            PendingDecision::PayDebt {
+                debtor,
+                creditor,
+                amount,
+            } => D::PayDebt(pending_decision::PayDebt {
+                debtor: debtor.0.into(),
+                creditor: creditor.map(|creditor| creditor.0.into()),
+                amount: amount.0,
+            }),
+            PendingDecision::IncomeTax { player, worth } => {
+                D::IncomeTax(pending_decision::IncomeTax {
+                    player: player.0.into(),
+                    worth: worth.0,
+                })
+            }
+            PendingDecision::UtilityRoll { player, property } => {
+                D::UtilityRoll((*player, *property).into())
+            }
+            PendingDecision::PayBid {
+                debtor,
+                property,
+                amount,
+            } => D::PayBid(pending_decision::PayBid {
+                debtor: debtor.0.into(),
+                property: property.0.into(),
+                amount: amount.0,
+            }),
+        };
+        proto::PendingDecision {
+            decision: Some(decision),
+        }
+    }
+}
+
+impl TryFrom<proto::PendingDecision> for PendingDecision {
+    type Error = ProtoError;
+
+    fn try_from(decision: proto::PendingDecision) -> Result<PendingDecision, ProtoError> {
+        use proto::pending_decision::Decision as D;
+
+        match required("PendingDecision.decision", decision.decision)? {
+            D::BuyOrDecline(at) => {
+                at.read(|player, property| PendingDecision::BuyOrDecline { player, property })
+            }
+            D::Auction(auction) => Ok(PendingDecision::Auction {
+                property: property("Auction.property", auction.property)?,
+                high_bid: auction.high_bid.map(Bid::try_from).transpose()?,
+                bidders: auction
+                    .bidders
+                    .into_iter()
+                    .map(|bidder| player("Auction.bidders", bidder))
+                    .collect::<Result<_, _>>()?,
+                next: narrow("Auction.next", auction.next)?,
+            }),
+            D::PayDebt(debt) => Ok(PendingDecision::PayDebt {
+                debtor: player("PayDebt.debtor", debt.debtor)?,
+                creditor: optional_player("PayDebt.creditor", debt.creditor)?,
+                amount: Money(debt.amount),
+            }),
+            D::IncomeTax(tax) => Ok(PendingDecision::IncomeTax {
+                player: player("IncomeTax.player", tax.player)?,
+                worth: Money(tax.worth),
+            }),
+            D::UtilityRoll(at) => {
+                at.read(|player, property| PendingDecision::UtilityRoll { player, property })
+            }
+            D::PayBid(bid) => Ok(PendingDecision::PayBid {
+                debtor: player("PayBid.debtor", bid.debtor)?,
+                property: property("PayBid.property", bid.property)?,
+                amount: Money(bid.amount),
+            }),
+        }
+    }
+}
+
+impl From<&Effect> for proto::Effect {
+    fn from(effect: &Effect) -> proto::Effect {
+        use proto::effect::{self, Effect as E};
+
+        let effect = match effect {
+            Effect::Moved { player, to } => E::Moved(effect::Moved {
+                player: player.0.into(),
+                to: *to as u32,
+            }),
+            Effect::CollectedSalary { player, amount } => {
+                E::CollectedSalary((*player, *amount).into())
+            }
+            Effect::Paid { from, to, amount } => E::Paid(effect::Paid {
+                from: from.0.into(),
+                to: to.map(|to| to.0.into()),
+                amount: amount.0,
+            }),
+            Effect::Received { player, amount } => E::Received((*player, *amount).into()),
+            Effect::DrewCard { player, card } => E::DrewCard(effect::DrewCard {
+                player: player.0.into(),
+                card: Some(card.into()),
+            }),
+            Effect::WentToJail(player) => E::WentToJail((*player).into()),
+            Effect::LeftJail(player) => E::LeftJail((*player).into()),
+            Effect::Acquired { player, property } => E::Acquired((*player, *property).into()),
+            Effect::DecisionPending(decision) => E::DecisionPending(decision.into()),
+            Effect::WentBankrupt(player) => E::WentBankrupt((*player).into()),
+            Effect::TurnStarted(player) => E::TurnStarted((*player).into()),
+            Effect::PassedGo(player) => E::PassedGo((*player).into()),
+            Effect::LandedOnGo(player) => E::LandedOnGo((*player).into()),
+            Effect::SnakeEyes(player) => E::SnakeEyes((*player).into()),
+            Effect::SoldBuildings {
+                player,
+                property,
+                houses,
+                hotel,
+            } => E::SoldBuildings(effect::SoldBuildings {
+                player: player.0.into(),
+                property: property.0.into(),
+                houses: (*houses).into(),
+                hotel: *hotel,
+            }),
+        };
+        proto::Effect {
+            effect: Some(effect),
+        }
+    }
+}
+
+impl TryFrom<proto::Effect> for Effect {
+    type Error = ProtoError;
+
+    fn try_from(effect: proto::Effect) -> Result<Effect, ProtoError> {
+        use proto::effect::Effect as E;
+
+        match required("Effect.effect", effect.effect)? {
+            E::Moved(moved) => Ok(Effect::Moved {
+                player: player("Moved.player", moved.player)?,
+                to: narrow("Moved.to", moved.to)?,
+            }),
+            E::CollectedSalary(at) => {
+                at.read(|player, amount| Effect::CollectedSalary { player, amount })
+            }
+            E::Paid(paid) => Ok(Effect::Paid {
+                from: player("Paid.from", paid.from)?,
+                to: optional_player("Paid.to", paid.to)?,
+                amount: Money(paid.amount),
+            }),
+            E::Received(at) => at.read(|player, amount| Effect::Received { player, amount }),
+            E::DrewCard(drew) => Ok(Effect::DrewCard {
+                player: player("DrewCard.player", drew.player)?,
+                card: required("DrewCard.card", drew.card)?.try_into()?,
+            }),
+            E::WentToJail(at) => at.read(Effect::WentToJail),
+            E::LeftJail(at) => at.read(Effect::LeftJail),
+            E::Acquired(at) => at.read(|player, property| Effect::Acquired { player, property }),
+            E::DecisionPending(decision) => Ok(Effect::DecisionPending(decision.try_into()?)),
+            E::WentBankrupt(at) => at.read(Effect::WentBankrupt),
+            E::TurnStarted(at) => at.read(Effect::TurnStarted),
+            E::PassedGo(at) => at.read(Effect::PassedGo),
+            E::LandedOnGo(at) => at.read(Effect::LandedOnGo),
+            E::SnakeEyes(at) => at.read(Effect::SnakeEyes),
+            E::SoldBuildings(sold) => Ok(Effect::SoldBuildings {
+                player: player("SoldBuildings.player", sold.player)?,
+                property: property("SoldBuildings.property", sold.property)?,
+                houses: narrow("SoldBuildings.houses", sold.houses)?,
+                hotel: sold.hotel,
+            }),
+        }
+    }
+}
+
+impl From<&EventRecord> for proto::EventRecord {
+    fn from(record: &EventRecord) -> proto::EventRecord {
+        proto::EventRecord {
+            seq: record.seq as u64,
+            turn: record.turn,
+            actor: record.actor.map(|actor| actor.0.into()),
+            command: Some((&record.command).into()),
+            effects: record.effects.iter().map(proto::Effect::from).collect(),
+            time: record.time,
+            digest: record.digest,
+        }
+    }
+}
+
+impl TryFrom<proto::EventRecord> for EventRecord {
+    type Error = ProtoError;
+
+    fn try_from(record: proto::EventRecord) -> Result<EventRecord, ProtoError> {
+        Ok(EventRecord {
+            seq: narrow("EventRecord.seq", record.seq)?,
+            turn: record.turn,
+            actor: optional_player("EventRecord.actor", record.actor)?,
+            command: required("EventRecord.command", record.command)?.try_into()?,
+            effects: record
+                .effects
+                .into_iter()
+                .map(Effect::try_from)
+                .collect::<Result<_, _>>()?,
+            time: record.time,
+            digest: record.digest,
+        })
+    }
+}
+
+impl From<&GameState> for proto::GameState {
+    fn from(state: &GameState) -> proto::GameState {
+        use proto::game_state;
+
+        proto::GameState {
+            config: Some((&state.config).into()),
+            deeds: state
+                .deeds
+                .iter()
+                .map(|deed| game_state::Deed {
+                    owner: deed.owner.map(|owner| owner.0.into()),
+                    mortgaged: deed.mortgaged,
+                    houses: deed.houses.into(),
+                    hotel: deed.hotel,
+                })
+                .collect(),
+            players: state
+                .players
+                .iter()
+                .zip(Token::ALL.iter())
+                .map(|(player, &dealt)| game_state::Player {
+                    id: player.id.0.into(),
+                    cash: player.cash.0,
+                    position: player.position as u32,
+                    jail: player.jail.map(u32::from),
+                    jail_cards: player.jail_cards.iter().map(proto::Card::from).collect(),
+                    bankrupt: player.is_bankrupt(),
+                    eliminated_on: player.eliminated.unwrap_or(0),
+                    token: Some(player.token)
+                        .filter(|&token| token != dealt)
+                        .map(|token| proto::Token::from(token).into()),
+                    name: player.name.clone(),
+                })
+                .collect(),
+            turn: Some(game_state::Turn {
+                number: state.turn.number,
+                player: state.turn.player.0.into(),
+                rolls: state.turn.rolls.into(),
+                doubles: state.turn.doubles.into(),
+                may_roll: state.turn.may_roll,
+            }),
+            pending: state
+                .pending
+                .iter()
+                .map(proto::PendingDecision::from)
+                .collect(),
+            trade: state.trade.as_ref().map(proto::Trade::from),
+            pot: state.pot.0,
+            houses: state.houses.into(),
+            hotels: state.hotels.into(),
+            chance: state
+                .chance
+                .iter()
+                .map(|&card| proto::ChanceCard::from(card).into())
+                .collect(),
+            community_chest: state
+                .community_chest
+                .iter()
+                .map(|&card| proto::CommunityChestCard::from(card).into())
+                .collect(),
+            dice: state.dice.state(),
+            events: state.events.iter().map(proto::EventRecord::from).collect(),
+            compacted: state.compacted as u64,
+            compacted_digest: state.compacted_digest,
+        }
+    }
+}
+
+// Checked the way a loaded save is: the board comes from the edition, and
+// the game has to hold together.
+impl TryFrom<proto::GameState> for GameState {
+    type Error = ProtoError;
+
+    fn try_from(saved: proto::GameState) -> Result<GameState, ProtoError> {
+        let config: GameConfig = required("GameState.config", saved.config)?.try_into()?;
+        let mut state = GameState::new(GameConfig {
+            players: MIN_PLAYERS,
+            seats: Vec::new(),
+            ..config.clone()
+        })
+        .map_err(ProtoError::Invalid)?;
+        state.config = config;
+        state.deeds = saved
+            .deeds
+            .into_iter()
+            .map(|deed| {
+                Ok(Deed {
+                    owner: optional_player("Deed.owner", deed.owner)?,
+                    mortgaged: deed.mortgaged,
+                    houses: narrow("Deed.houses", deed.houses)?,
+                    hotel: deed.hotel,
+                })
+            })
+            .collect::<Result<_, ProtoError>>()?;
+        // Games from before the turn was kept count as going bankrupt on
+        // the turn they were saved on.
+        let saved_on = saved.turn.as_ref().map_or(0, |turn| turn.number);
+        state.players = saved
+            .players
+            .into_iter()
+            .enumerate()
+            .map(|(seat, player)| {
+                let dealt = Token::ALL.get(seat).copied();
+                Ok(Player {
+                    id: self::player("Player.id", player.id)?,
+                    cash: Money(player.cash),
+                    position: narrow("Player.position", player.position)?,
+                    jail: player
+                        .jail
+                        .map(|tries| narrow("Player.jail", tries))
+                        .transpose()?,
+                    jail_cards: player
+                        .jail_cards
+                        .into_iter()
+                        .map(Card::try_from)
+                        .collect::<Result<_, _>>()?,
+                    eliminated: match (player.bankrupt, player.eliminated_on) {
+                        (false, _) => None,
+                        (true, 0) => Some(saved_on),
+                        (true, turn) => Some(turn),
+                    },
+                    token: match player.token {
+                        Some(value) => token("Player.token", value)?,
+                        None => dealt.ok_or(ProtoError::OutOfRange("GameState.players"))?,
+                    },
+                    name: player.name,
+                })
+            })
+            .collect::<Result<_, ProtoError>>()?;
+        let turn = required("GameState.turn", saved.turn)?;
+        state.turn = Turn {
+            number: turn.number,
+            player: player("Turn.player", turn.player)?,
+            rolls: narrow("Turn.rolls", turn.rolls)?,
+            doubles: narrow("Turn.doubles", turn.doubles)?,
+            may_roll: turn.may_roll,
+        };
+        state.pending = saved
+            .pending
+            .into_iter()
+            .map(PendingDecision::try_from)
+            .collect::<Result<_, _>>()?;
+        state.trade = saved.trade.map(Trade::try_from).transpose()?;
+        state.pot = Money(saved.pot);
+        state.houses = narrow("GameState.houses", saved.houses)?;
+        state.hotels = narrow("GameState.hotels", saved.hotels)?;
+        state.chance = saved
+            .chance
+            .into_iter()
+            .map(|card| chance_card("GameState.chance", card))
+            .collect::<Result<_, _>>()?;
+        state.community_chest = saved
+            .community_chest
+            .into_iter()
+            .map(|card| community_chest_card("GameState.community_chest", card))
+            .collect::<Result<_, _>>()?;
+        state.dice = SeededDice::new(saved.dice);
+        state.events = saved
+            .events
+            .into_iter()
+            .map(EventRecord::try_from)
+            .collect::<Result<_, _>>()?;
+        state.compacted = narrow("GameState.compacted", saved.compacted)?;
+        state.compacted_digest = saved.compacted_digest;
+        // Encodings from before the config kept the seats only have them in
+        // the players, and counted none who joined.
+        if state.config.seats.is_empty() {
+            state.config.players = state.players.len();
+            let dealt = state.players.iter().zip(Token::ALL.iter());
+            if !dealt
+                .clone()
+                .all(|(player, &token)| player.token == token && player.name.is_none())
+            {
+                state.config.seats = state.seats();
+            }
+        }
+        state.check_invariants().map_err(ProtoError::Invalid)?;
+        Ok(state)
+    }
+}
@@ -0,0 +1,160 @@
+// A strategy that plays at random, for smoke tests: it is no good at the
+// game, but it wanders into corners of the rules that sensible players avoid.
+
+use super::*;
+
+// Picks uniformly among the moves the rules allow at each decision, from its
+// own seeded generator, so a game it plays can always be played again.
+#[derive(Clone, Debug)]
+pub struct RandomStrategy {
+    rng: Rng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: u64) -> RandomStrategy {
+        RandomStrategy {
+            rng: Rng::new(seed),
+        }
+    }
+
+    fn pick(&mut self, mut choices: Vec<Command>) -> Command {
+        let index = self.rng.below(choices.len() as u64) as usize;
+        choices.swap_remove(index)
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn buy_or_decline(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+    ) -> Command {
+        let mut choices = vec![Command::DeclineToBuy(player)];
+        if state.players[player.0 as usize].cash.0 >= state.price(property.0 as usize).0 {
+            choices.push(Command::BuyProperty(player, property));
+        }
+        self.pick(choices)
+    }
+
+    // Half the time it drops out; otherwise it bids anything it can afford.
+    fn bid(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        _: PropertyId,
+        high_bid: Option<&Bid>,
+    ) -> Command {
+        let high = high_bid.map_or(0, |Bid(_, amount)| amount.0);
+        let cash = state.players[player.0 as usize].cash.0;
+        if cash <= high || self.rng.below(2) == 0 {
+            return Command::PassAuction(player);
+        }
+        let raise = self.rng.below((cash - high) as u64) as i32 + 1;
+        Command::PlaceBid(player, Money(high + raise))
+    }
+
+    fn income_tax(&mut self, _: &GameState, player: PlayerId, _: Money) -> Command {
+        self.pick(vec![
+            Command::PayIncomeTax(player, TaxChoice::Flat),
+            Command::PayIncomeTax(player, TaxChoice::Percent),
+        ])
+    }
+
+    fn leave_jail(&mut self, state: &GameState, player: PlayerId) -> Command {
+        let mut choices = vec![Command::Roll(player)];
+        let jailed = &state.players[player.0 as usize];
+        if jailed.cash.0 >= state.config.rules.jail_fine.0 {
+            choices.push(Command::PayJailFine(player));
+        }
+        if !jailed.jail_cards.is_empty() {
+            choices.push(Command::UseGetOutOfJailFreeCard(player));
+        }
+        self.pick(choices)
+    }
+
+    // Goes bankrupt only once there is nothing left to sell or mortgage.
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        _: Option<PlayerId>,
+        amount: Money,
+    ) -> Command {
+        let mut choices = state.ways_to_raise_cash(player);
+        if state.players[player.0 as usize].cash.0 >= amount.0 {
+            choices.push(Command::PayDebt(player));
+        }
+        if choices.is_empty() {
+            return Command::DeclareBankruptcy(player);
+        }
+        self.pick(choices)
+    }
+
+    fn answer_trade(&mut self, state: &GameState, player: PlayerId, trade: &Trade) -> Command {
+        let mut choices = vec![Command::RejectTrade(player)];
+        if state.validate_trade(trade).is_ok() {
+            choices.push(Command::AcceptTrade(player));
+        }
+        self.pick(choices)
+    }
+
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command {
+        let mut choices = state.ways_to_build(player);
+        choices.extend(state.ways_to_unmortgage(player));
+        choices.push(Command::EndTurn(player));
+        self.pick(choices)
+    }
+}
+
+// The moves a strategy might make with its money, worked out from the rules
+// directly rather than by trying each one, which would copy the game for
+// every candidate.
+impl GameState {
+    // Every building the player could sell and every title they could
+    // mortgage right now.
+    pub(super) fn ways_to_raise_cash(&self, id: PlayerId) -> Vec<Command> {
+        let mut ways = Vec::new();
+        for (index, deed) in self.deeds.iter().enumerate() {
+            let property = PropertyId(index as i8);
+            if deed.owner != Some(id) || deed.mortgaged {
+                continue;
+            }
+            if !self.group_has_buildings(index) {
+                ways.push(Command::MortgageProperty(id, property));
+            } else if deed.hotel {
+                ways.push(Command::SellHotel(id, property));
+            } else if deed.houses > 0 && deed.level() == self.group_levels(index).1 {
+                ways.push(Command::SellHouse(id, property));
+            }
+        }
+        ways
+    }
+
+    // Every house and hotel the player could buy and afford right now.
+    pub(super) fn ways_to_build(&self, id: PlayerId) -> Vec<Command> {
+        self.buildable_properties(id)
+            .into_iter()
+            .map(|(property, _)| {
+                let index = property.0 as usize;
+                let needed = self.street(index).map_or(0, |street| street.hotel_cost.1);
+                match (self.deeds[index].houses as i8) < needed {
+                    true => Command::BuyHouse(id, property),
+                    false => Command::BuyHotel(id, property),
+                }
+            })
+            .collect()
+    }
+
+    // Every mortgage the player could afford to lift right now.
+    pub(super) fn ways_to_unmortgage(&self, id: PlayerId) -> Vec<Command> {
+        let cash = self.players[id.0 as usize].cash.0;
+        (0..self.deeds.len())
+            .filter(|index| {
+                let deed = &self.deeds[*index];
+                deed.owner == Some(id) && deed.mortgaged && cash >= self.unmortgage_cost(*index).0
+            })
+            .map(|index| Command::UnmortgageProperty(id, PropertyId(index as i8)))
+            .collect()
+    }
+}
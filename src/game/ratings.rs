@@ -0,0 +1,47 @@
+// Elo ratings, for ranking strategies by how they do against each other
+// rather than by raw win rate, which depends on who else was at the table.
+
+// Everyone starts level. A game with several losers counts as the winner
+// beating each of them, all scored from the ratings before the game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ratings {
+    k: f64, // the most one pairing can move a rating
+    ratings: Vec<f64>,
+}
+
+pub const INITIAL_RATING: f64 = 1500.0;
+
+impl Ratings {
+    pub fn new(players: usize, k: f64) -> Ratings {
+        Ratings {
+            k,
+            ratings: vec![INITIAL_RATING; players],
+        }
+    }
+
+    pub fn update(&mut self, winner: usize, losers: &[usize]) {
+        let mut changes = vec![0.0; self.ratings.len()];
+        for loser in losers {
+            let gain = self.k * (1.0 - self.expected(winner, *loser));
+            changes[winner] += gain;
+            changes[*loser] -= gain;
+        }
+        for (rating, change) in self.ratings.iter_mut().zip(changes) {
+            *rating += change;
+        }
+    }
+
+    // The chance the first player beats the second, going by their ratings.
+    pub fn expected(&self, player: usize, against: usize) -> f64 {
+        let gap = self.ratings[against] - self.ratings[player];
+        1.0 / (1.0 + 10f64.powf(gap / 400.0))
+    }
+
+    pub fn rating(&self, player: usize) -> f64 {
+        self.ratings[player]
+    }
+
+    pub fn ratings(&self) -> &[f64] {
+        &self.ratings
+    }
+}
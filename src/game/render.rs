@@ -0,0 +1,126 @@
+// The board drawn as text, square by square around the ring the way it is
+// printed, GO at the bottom right. Each square is a cell of three lines: a
+// short name; the owner, then the houses or H for a hotel, and M when it is
+// mortgaged; and the tokens of the players standing there. On Jail, those
+// serving time are shown apart from those just visiting.
+
+use super::*;
+
+pub(super) const WIDTH: usize = 8; // inside a cell; enough for all eight players
+
+impl GameState {
+    pub fn render_board(&self) -> String {
+        let cells: Vec<[String; 3]> = (0..self.board.len())
+            .map(|index| self.cell(index))
+            .collect();
+        draw_ring(&cells)
+    }
+
+    fn cell(&self, index: usize) -> [String; 3] {
+        let deed = &self.deeds[index];
+        let mut held = String::new();
+        if let Some(PlayerId(owner)) = deed.owner {
+            held.push_str(&format!("P{}", owner));
+            if deed.hotel {
+                held.push_str(" H");
+            } else if deed.houses > 0 {
+                held.push_str(&format!(" {}", deed.houses));
+            }
+            if deed.mortgaged {
+                held.push_str(" M");
+            }
+        }
+        let here = |jailed: bool| -> String {
+            self.players
+                .iter()
+                .filter(|player| !player.is_bankrupt() && player.position == index)
+                .filter(|player| index != self.board.jail || player.in_jail() == jailed)
+                .map(|player| player.token.symbol())
+                .collect()
+        };
+        if index == self.board.jail {
+            let jailed = here(true);
+            if !jailed.is_empty() {
+                held = format!("in {}", jailed);
+            }
+        }
+        [
+            fit(&abbreviate(self.square_name(index))),
+            fit(&held),
+            fit(&here(false)),
+        ]
+    }
+}
+
+// Cells, one per square, laid out around the ring: corner to corner along
+// the top, then down both sides, then the bottom back towards GO.
+pub(super) fn draw_ring(cells: &[[String; 3]]) -> String {
+    let side = cells.len() / 4;
+    let top: Vec<usize> = (2 * side..=3 * side).collect();
+    let bottom: Vec<usize> = (0..=side).rev().collect();
+    let inside = (side - 1) * (WIDTH + 1) - 1;
+
+    let mut out = String::new();
+    let rule = |out: &mut String, cells: usize| {
+        out.push('+');
+        for _ in 0..cells {
+            out.push_str(&"-".repeat(WIDTH));
+            out.push('+');
+        }
+        out.push('\n');
+    };
+    let row = |out: &mut String, squares: &[usize]| {
+        let lines = (0..3).map(|line| {
+            squares
+                .iter()
+                .map(|square| cells[*square][line].as_str())
+                .collect::<Vec<_>>()
+        });
+        for texts in lines {
+            out.push_str(&format!("|{}|\n", texts.join("|")));
+        }
+    };
+    rule(&mut out, side + 1);
+    row(&mut out, &top);
+    for step in 1..side {
+        let (left, right) = (2 * side - step, 3 * side + step);
+        if step > 1 {
+            out.push_str(&format!(
+                "+{}+{}+{}+\n",
+                "-".repeat(WIDTH),
+                " ".repeat(inside),
+                "-".repeat(WIDTH)
+            ));
+        } else {
+            rule(&mut out, side + 1);
+        }
+        for (left, right) in cells[left].iter().zip(&cells[right]) {
+            out.push_str(&format!("|{}|{}|{}|\n", left, " ".repeat(inside), right));
+        }
+    }
+    rule(&mut out, side + 1);
+    row(&mut out, &bottom);
+    rule(&mut out, side + 1);
+    out
+}
+
+// The name whole if it fits, or else its first word cut short and the
+// initials of the rest: "Mediterranean Ave" is "Medite A".
+pub(super) fn abbreviate(name: &str) -> String {
+    if name.chars().count() <= WIDTH {
+        return name.to_string();
+    }
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let initials: String = words[1..]
+        .iter()
+        .flat_map(|word| std::iter::once(' ').chain(word.chars().take(1)))
+        .collect();
+    let room = WIDTH.saturating_sub(initials.chars().count()).max(1);
+    let first: String = words[0].chars().take(room).collect();
+    format!("{}{}", first, initials)
+}
+
+pub(super) fn fit(text: &str) -> String {
+    let text: String = text.chars().take(WIDTH).collect();
+    format!("{:<width$}", text, width = WIDTH)
+}
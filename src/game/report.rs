@@ -0,0 +1,372 @@
+// Many simulated games, boiled down to who won, how long it took and how
+// much everyone ended up with.
+
+use super::*;
+use std::io::{self, Write};
+
+// The games whose length fell between from and to, inclusive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LengthBucket {
+    pub from: u32,
+    pub to: u32,
+    pub games: usize,
+    pub wins: Vec<usize>, // by seat
+    pub unfinished: usize,
+}
+
+// Makes a seat's strategy for one game, from a seed of its own.
+pub type StrategyFactory = fn(u64) -> Box<dyn Strategy>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulationReport {
+    pub games: usize,
+    pub strategies: Vec<String>,     // by seat
+    pub wins: Vec<usize>,            // by seat
+    pub unfinished: usize,           // games that reached the turn limit
+    pub lengths: Vec<u32>,           // in turns, in the order the games were played
+    pub rolls: Vec<u32>,             // dice rolled in each game, in the same order
+    pub winners: Vec<Option<usize>>, // the winning seat of each game, if any
+    pub bankruptcies: Vec<u32>,      // the turn of each one, in every game
+    pub total_net_worth: Vec<i64>,   // by seat, summed over the games
+    pub landings: LandingStats,
+}
+
+// Plays n_games games with the given strategy in each seat, game i from
+// base_seed + i, and adds them up. Each seat's strategy gets a seed drawn
+// from the game's, so a game plays the same here as in play_seeded.
+pub fn run_many(
+    config: GameConfig,
+    strategies: &[(&str, StrategyFactory)],
+    n_games: usize,
+    base_seed: u64,
+) -> Result<SimulationReport, GameError> {
+    let mut report = SimulationReport::empty(&config, strategies);
+    let factories: Vec<StrategyFactory> = strategies.iter().map(|(_, make)| *make).collect();
+    for game in 0..n_games as u64 {
+        report.add(&play_seeded(
+            &config,
+            &factories,
+            base_seed.wrapping_add(game),
+        )?);
+    }
+    Ok(report)
+}
+
+// One game from a seed, with each seat's strategy seeded from a stream of
+// its own, so another player joining changes nobody else's seed.
+pub fn play_seeded(
+    config: &GameConfig,
+    strategies: &[StrategyFactory],
+    seed: u64,
+) -> Result<GameResult, GameError> {
+    simulate(config.clone(), &mut seated(strategies, seed), seed)
+}
+
+// The built-in strategies by the names the command line knows them by.
+pub fn strategy_named(name: &str) -> Option<StrategyFactory> {
+    let factory: StrategyFactory = match name {
+        "greedy" => |_| Box::new(GreedyBuyer::default()),
+        "random" => |seed| Box::new(RandomStrategy::new(seed)),
+        "nothing" => |_| Box::new(DoNothing),
+        _ => return None,
+    };
+    Some(factory)
+}
+
+// Each seat's strategy for the game with the seed, as play_seeded seats them.
+pub fn seated(strategies: &[StrategyFactory], seed: u64) -> Vec<Box<dyn Strategy>> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(seat, make)| make(Rng::derive(seed, SEAT_STREAMS + seat as u64).next_u64()))
+        .collect()
+}
+
+// Like run_many, with the games split into a run of consecutive seeds per
+// thread. The runs are merged back in seed order, so the report is the same
+// however many threads played it.
+pub fn run_many_parallel(
+    config: GameConfig,
+    strategies: &[(&str, StrategyFactory)],
+    n_games: usize,
+    base_seed: u64,
+    threads: usize,
+) -> Result<SimulationReport, GameError> {
+    let per_thread = n_games.div_ceil(threads.max(1)).max(1);
+    let reports: Vec<Result<SimulationReport, GameError>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..n_games)
+            .step_by(per_thread)
+            .map(|first| {
+                let config = config.clone();
+                let games = per_thread.min(n_games - first);
+                let seed = base_seed.wrapping_add(first as u64);
+                scope.spawn(move || run_many(config, strategies, games, seed))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("a simulation thread panicked"))
+            .collect()
+    });
+    let mut report = SimulationReport::empty(&config, strategies);
+    for part in reports {
+        report.merge(&part?);
+    }
+    Ok(report)
+}
+
+impl SimulationReport {
+    fn empty(config: &GameConfig, strategies: &[(&str, StrategyFactory)]) -> SimulationReport {
+        let seats = strategies.len();
+        SimulationReport {
+            games: 0,
+            strategies: strategies
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            wins: vec![0; seats],
+            unfinished: 0,
+            lengths: Vec::new(),
+            rolls: Vec::new(),
+            winners: Vec::new(),
+            bankruptcies: Vec::new(),
+            total_net_worth: vec![0; seats],
+            landings: LandingStats::new(config.edition),
+        }
+    }
+
+    // Adds in the games from another report with the same seats, as though
+    // they were played after these.
+    pub fn merge(&mut self, other: &SimulationReport) {
+        self.games += other.games;
+        for (wins, more) in self.wins.iter_mut().zip(&other.wins) {
+            *wins += more;
+        }
+        self.unfinished += other.unfinished;
+        self.lengths.extend(&other.lengths);
+        self.rolls.extend(&other.rolls);
+        self.winners.extend(&other.winners);
+        self.bankruptcies.extend(&other.bankruptcies);
+        for (total, more) in self.total_net_worth.iter_mut().zip(&other.total_net_worth) {
+            *total += more;
+        }
+        self.landings.merge(&other.landings);
+    }
+
+    fn add(&mut self, result: &GameResult) {
+        self.games += 1;
+        match result.winner {
+            Some(PlayerId(seat)) => self.wins[seat as usize] += 1,
+            None => self.unfinished += 1,
+        }
+        self.lengths.push(result.turns);
+        self.winners
+            .push(result.winner.map(|PlayerId(seat)| seat as usize));
+        let rolls = result
+            .log
+            .iter()
+            .filter(|record| {
+                matches!(
+                    record.command,
+                    Command::RollDice(..) | Command::RollMany(..)
+                )
+            })
+            .count();
+        self.rolls.push(rolls as u32);
+        for record in &result.log {
+            self.landings.record(record);
+            let went = record
+                .effects
+                .iter()
+                .filter(|effect| matches!(effect, Effect::WentBankrupt(_)))
+                .count();
+            self.bankruptcies
+                .extend(std::iter::repeat_n(record.turn, went));
+        }
+        for (total, worth) in self.total_net_worth.iter_mut().zip(&result.net_worths) {
+            *total += worth.0 as i64;
+        }
+    }
+
+    // Wins over games, by seat.
+    pub fn win_rate_by_seat(&self) -> Vec<f64> {
+        self.wins
+            .iter()
+            .map(|wins| *wins as f64 / self.games.max(1) as f64)
+            .collect()
+    }
+
+    // Wins over games, by strategy, whichever seats it played from. A
+    // strategy in two seats can win twice as often.
+    pub fn win_rate_by_strategy(&self) -> Vec<(String, f64)> {
+        let mut rates: Vec<(String, f64)> = Vec::new();
+        for (name, rate) in self.strategies.iter().zip(self.win_rate_by_seat()) {
+            match rates.iter_mut().find(|(seen, _)| seen == name) {
+                Some((_, total)) => *total += rate,
+                None => rates.push((name.clone(), rate)),
+            }
+        }
+        rates
+    }
+
+    pub fn mean_length(&self) -> f64 {
+        self.lengths.iter().map(|turns| *turns as f64).sum::<f64>() / self.games.max(1) as f64
+    }
+
+    // The middle length, or the mean of the middle two.
+    pub fn median_length(&self) -> f64 {
+        let mut lengths = self.lengths.clone();
+        lengths.sort_unstable();
+        match lengths.len() {
+            0 => 0.0,
+            len if len % 2 == 1 => lengths[len / 2] as f64,
+            len => (lengths[len / 2 - 1] + lengths[len / 2]) as f64 / 2.0,
+        }
+    }
+
+    pub fn mean_net_worth(&self) -> Vec<f64> {
+        self.total_net_worth
+            .iter()
+            .map(|total| *total as f64 / self.games.max(1) as f64)
+            .collect()
+    }
+
+    // The share of games that reached the turn limit without a winner.
+    pub fn unfinished_rate(&self) -> f64 {
+        self.unfinished as f64 / self.games.max(1) as f64
+    }
+
+    // The games in spans of `width` turns, from the first turn to the
+    // longest game, with who won the games in each.
+    pub fn length_histogram(&self, width: u32) -> Vec<LengthBucket> {
+        self.histogram(&self.lengths, width)
+    }
+
+    // The same, by how many times the dice were rolled.
+    pub fn roll_histogram(&self, width: u32) -> Vec<LengthBucket> {
+        self.histogram(&self.rolls, width)
+    }
+
+    fn histogram(&self, lengths: &[u32], width: u32) -> Vec<LengthBucket> {
+        let mut buckets: Vec<LengthBucket> = Vec::new();
+        for (length, winner) in lengths.iter().zip(&self.winners) {
+            let bucket = (length.saturating_sub(1) / width) as usize;
+            while buckets.len() <= bucket {
+                buckets.push(LengthBucket {
+                    from: buckets.len() as u32 * width + 1,
+                    to: (buckets.len() as u32 + 1) * width,
+                    games: 0,
+                    wins: vec![0; self.strategies.len()],
+                    unfinished: 0,
+                });
+            }
+            let bucket = &mut buckets[bucket];
+            bucket.games += 1;
+            match winner {
+                Some(seat) => bucket.wins[*seat] += 1,
+                None => bucket.unfinished += 1,
+            }
+        }
+        buckets
+    }
+
+    // One row per game, in the order they were played.
+    pub fn export_lengths_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "game,turns,rolls,winner,strategy,unfinished")?;
+        for (game, winner) in self.winners.iter().enumerate() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                game,
+                self.lengths[game],
+                self.rolls[game],
+                winner.map_or(String::new(), |seat| seat.to_string()),
+                winner.map_or("", |seat| self.strategies[seat].as_str()),
+                winner.is_none()
+            )?;
+        }
+        Ok(())
+    }
+
+    // How many players went bankrupt in each span of `width` turns, from the
+    // first turn to the last span anyone did.
+    pub fn bankruptcy_histogram(&self, width: u32) -> Vec<usize> {
+        let mut buckets = Vec::new();
+        for turn in &self.bankruptcies {
+            let bucket = ((turn - 1) / width) as usize;
+            if buckets.len() <= bucket {
+                buckets.resize(bucket + 1, 0);
+            }
+            buckets[bucket] += 1;
+        }
+        buckets
+    }
+}
+
+// 1000 games, 12 unfinished (1.2%); 312.4 turns on average, 290 the median
+//
+// seat  strategy    wins  win rate  mean worth
+// 0     greedy       512     51.2%       $2103
+// ...
+//
+// turns        games  unfinished  wins by seat
+// 1-100           41           0  30 4 5 2
+// ...
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} games, {} unfinished ({:.1}%); {:.1} turns on average, {} the median",
+            self.games,
+            self.unfinished,
+            self.unfinished_rate() * 100.0,
+            self.mean_length(),
+            self.median_length()
+        )?;
+        writeln!(f)?;
+        writeln!(f, "seat  strategy    wins  win rate  mean worth")?;
+        let rates = self.win_rate_by_seat();
+        let worths = self.mean_net_worth();
+        for seat in 0..self.strategies.len() {
+            writeln!(
+                f,
+                "{:<4}  {:<10} {:>5}  {:>7.1}%  {:>10}",
+                seat,
+                self.strategies[seat],
+                self.wins[seat],
+                rates[seat] * 100.0,
+                format!("${:.0}", worths[seat])
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "strategy    win rate")?;
+        for (name, rate) in self.win_rate_by_strategy() {
+            writeln!(f, "{:<10}  {:>7.1}%", name, rate * 100.0)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "turns        games  unfinished  wins by seat")?;
+        for bucket in self.length_histogram(100) {
+            let wins: Vec<String> = bucket.wins.iter().map(|wins| wins.to_string()).collect();
+            writeln!(
+                f,
+                "{:<11} {:>6}  {:>10}  {}",
+                format!("{}-{}", bucket.from, bucket.to),
+                bucket.games,
+                bucket.unfinished,
+                wins.join(" ")
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "bankruptcies   turns")?;
+        for (bucket, count) in self.bankruptcy_histogram(100).iter().enumerate() {
+            let from = bucket * 100 + 1;
+            writeln!(f, "{:>12}   {}-{}", count, from, from + 99)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "most landed on")?;
+        for line in self.landings.to_string().lines().take(10) {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,499 @@
+// The serialized form of a game. Everything that follows from the config
+// (the board) is left out, and everything read back in is checked before it
+// becomes a GameState, since a payload can claim anything.
+
+use super::integrity::verify_chain;
+use super::*;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Bumped whenever a save written by this version can't be read by the last.
+// Version 2 logs each command's effects alongside it, version 3 saves the
+// engine's dice, version 4 logs passing GO apart from landing on it,
+// version 5 the buildings sold off in a bankruptcy, version 6 saves each
+// player's token, version 7 the turn each bankrupt player went out on,
+// version 8 only holds bankruptcies that selling up couldn't have avoided
+// and version 9 keeps the seats in the config.
+pub const SAVE_VERSION: u64 = 9;
+// Version 1 logs held bare commands; version 2 added the record metadata,
+// version 3 the effects, version 4 PassedGo and LandedOnGo, version 5
+// SoldBuildings and version 6 refuses bankruptcies as version 8 saves do.
+pub const LOG_VERSION: u64 = 6;
+
+// Commands as logged by version 1 saves and version 2 logs, where each
+// record held just the one.
+fn old_commands(records: serde_json::Value) -> Result<Vec<Command>, serde_json::Error> {
+    #[derive(Deserialize)]
+    struct OldRecord {
+        action: Command,
+    }
+    let records: Vec<OldRecord> = serde_json::from_value(records)?;
+    Ok(records.into_iter().map(|record| record.action).collect())
+}
+
+// Everything before effects were logged and the dice saved can be brought
+// up to date by playing the commands through again.
+fn migrate(config: &GameConfig, commands: &[Command], from: u64) -> Result<GameState, SaveError> {
+    let failed = |error| SaveError::Migration { from, error };
+    let mut state =
+        GameState::new(config.clone()).map_err(|error| failed(ReplayError::Config(error)))?;
+    for (index, command) in commands.iter().enumerate() {
+        if let Err(error) = state.apply(command.clone()) {
+            return Err(failed(ReplayError::at(&state, index, command, error)));
+        }
+    }
+    Ok(state)
+}
+
+// Version 3 logged the salary for going round without saying whether GO was
+// passed or landed on. The move just after the salary tells which; the
+// salary the house rule pays for stopping on GO comes after its move, and
+// needs no marker. Digests are checked before and worked out again after,
+// starting from `previous`, the digest before the first record.
+// Every board a version 3 log could have been played on starts from GO.
+const GO: usize = 0;
+
+fn mark_go(
+    records: &mut [EventRecord],
+    hash_chain: bool,
+    mut previous: Option<u64>,
+) -> Result<(), SaveError> {
+    if hash_chain {
+        verify_chain(records, previous).map_err(|error| SaveError::Migration { from: 3, error })?;
+    }
+    for record in records {
+        let mut effects = Vec::with_capacity(record.effects.len());
+        for (index, effect) in record.effects.iter().enumerate() {
+            if let Effect::CollectedSalary { player, .. } = effect {
+                if let Some(Effect::Moved { player: moved, to }) = record.effects.get(index + 1) {
+                    if moved == player {
+                        effects.push(match *to {
+                            GO => Effect::LandedOnGo(*player),
+                            _ => Effect::PassedGo(*player),
+                        });
+                    }
+                }
+            }
+            effects.push(effect.clone());
+        }
+        record.effects = effects;
+        if hash_chain {
+            record.digest = Some(record.chained(previous));
+            previous = record.digest;
+        }
+    }
+    Ok(())
+}
+
+// Version 4 paid a bankrupt player for their buildings without saying which
+// were sold, and only the game as it stood then can tell. So a log with any
+// such bankruptcy is played through again from `config`, and each one takes
+// the effects the engine gives it now; a compacted one can't be, and is
+// refused. Digests are checked and worked out again as for mark_go.
+fn mark_sales(
+    config: &GameConfig,
+    records: &mut [EventRecord],
+    mut previous: Option<u64>,
+) -> Result<(), SaveError> {
+    let sold = |record: &EventRecord| {
+        matches!(record.command, Command::DeclareBankruptcy(_))
+            && matches!(record.effects.first(), Some(Effect::Received { .. }))
+    };
+    if !records.iter().any(sold) {
+        return Ok(());
+    }
+    if records[0].seq > 0 {
+        return Err(SaveError::UnsupportedVersion {
+            found: Some(4),
+            supported: SAVE_VERSION,
+        });
+    }
+    let failed = |error| SaveError::Migration { from: 4, error };
+    if config.hash_chain {
+        verify_chain(records, previous).map_err(failed)?;
+    }
+    let mut state =
+        GameState::new(config.clone()).map_err(|error| failed(ReplayError::Config(error)))?;
+    for (index, record) in records.iter_mut().enumerate() {
+        let marked = sold(record);
+        let effects = state
+            .apply_logged(record.command.clone())
+            .map_err(|error| failed(ReplayError::at(&state, index, &record.command, error)))?;
+        if marked {
+            record.effects = effects;
+        }
+        if config.hash_chain {
+            record.digest = Some(record.chained(previous));
+            previous = record.digest;
+        }
+    }
+    Ok(())
+}
+
+// Any bankruptcy left from before version 6 logs and version 8 saves might
+// be one the engine now refuses, where selling up would have paid, and only
+// playing the game through again can tell. There is no honest way to bring
+// such a game up to date, so it is refused; so is a compacted log, as for
+// mark_sales.
+fn check_bankruptcies(
+    config: &GameConfig,
+    records: &[EventRecord],
+    previous: Option<u64>,
+    from: u64,
+) -> Result<(), SaveError> {
+    let bankrupt = |record: &EventRecord| matches!(record.command, Command::DeclareBankruptcy(_));
+    if !records.iter().any(bankrupt) {
+        return Ok(());
+    }
+    if records[0].seq > 0 {
+        return Err(SaveError::UnsupportedVersion {
+            found: Some(from),
+            supported: SAVE_VERSION,
+        });
+    }
+    let failed = |error| SaveError::Migration { from, error };
+    if config.hash_chain {
+        verify_chain(records, previous).map_err(failed)?;
+    }
+    let mut state =
+        GameState::new(config.clone()).map_err(|error| failed(ReplayError::Config(error)))?;
+    for (index, record) in records.iter().enumerate() {
+        state
+            .apply_logged(record.command.clone())
+            .map_err(|error| failed(ReplayError::at(&state, index, &record.command, error)))?;
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub(super) struct SavedState {
+    config: GameConfig,
+    deeds: Vec<Deed>,
+    players: Vec<Player>,
+    turn: Turn,
+    pending: VecDeque<PendingDecision>,
+    trade: Option<Trade>,
+    pot: Money,
+    houses: u8,
+    hotels: u8,
+    chance: VecDeque<ChanceCard>,
+    community_chest: VecDeque<CommunityChestCard>,
+    dice: SeededDice,
+    events: Vec<EventRecord>,
+    #[serde(default)]
+    compacted: usize,
+    #[serde(default)]
+    compacted_digest: Option<u64>,
+}
+
+impl From<GameState> for SavedState {
+    fn from(state: GameState) -> SavedState {
+        SavedState {
+            config: state.config,
+            deeds: state.deeds,
+            players: state.players,
+            turn: state.turn,
+            pending: state.pending,
+            trade: state.trade,
+            pot: state.pot,
+            houses: state.houses,
+            hotels: state.hotels,
+            chance: state.chance,
+            community_chest: state.community_chest,
+            dice: state.dice,
+            events: state.events,
+            compacted: state.compacted,
+            compacted_digest: state.compacted_digest,
+        }
+    }
+}
+
+impl TryFrom<SavedState> for GameState {
+    type Error = GameError;
+
+    fn try_from(saved: SavedState) -> Result<GameState, GameError> {
+        // Start from a fresh game so the config is validated the usual way
+        // and the board comes from the edition.
+        let mut state = GameState::new(GameConfig {
+            players: MIN_PLAYERS,
+            seats: Vec::new(),
+            ..saved.config.clone()
+        })?;
+        state.config = saved.config;
+        state.players = saved.players;
+        state.deeds = saved.deeds;
+        state.turn = saved.turn;
+        state.pending = saved.pending;
+        state.trade = saved.trade;
+        state.pot = saved.pot;
+        state.houses = saved.houses;
+        state.hotels = saved.hotels;
+        state.chance = saved.chance;
+        state.community_chest = saved.community_chest;
+        state.dice = saved.dice;
+        state.events = saved.events;
+        state.compacted = saved.compacted;
+        state.compacted_digest = saved.compacted_digest;
+        state.check_invariants()?;
+        Ok(state)
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    UnsupportedVersion { found: Option<u64>, supported: u64 },
+    Migration { from: u64, error: ReplayError },
+    CustomBoard, // saves name only the edition, which can't bring the board back
+}
+
+impl Error for SaveError {}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::Json(error) => write!(f, "malformed save: {}", error),
+            SaveError::UnsupportedVersion {
+                found: Some(version),
+                supported,
+            } => write!(
+                f,
+                "version {} is not supported; this build reads up to version {}",
+                version, supported
+            ),
+            SaveError::UnsupportedVersion { found: None, .. } => write!(f, "missing version"),
+            SaveError::Migration { from, error } => {
+                write!(f, "migrating from version {}: {}", from, error)
+            }
+            SaveError::CustomBoard => write!(f, "a game on a board of its own can't be saved"),
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> SaveError {
+        SaveError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> SaveError {
+        SaveError::Json(error)
+    }
+}
+
+// Saves from before tokens seated everyone with the one they'd have been
+// handed in turn.
+fn deal_tokens(state: &mut serde_json::Value) {
+    if let Some(players) = state["players"].as_array_mut() {
+        for (player, token) in players.iter_mut().zip(Token::ALL.iter()) {
+            player["token"] = serde_json::json!(token);
+        }
+    }
+}
+
+// Saves from before version 9 left the seats out of the config, and didn't
+// count those who joined in it. The players have them all.
+fn seat_config(state: &mut serde_json::Value) {
+    let players = state["players"].as_array().cloned().unwrap_or_default();
+    state["config"]["players"] = serde_json::json!(players.len());
+    let dealt = players
+        .iter()
+        .zip(Token::ALL.iter())
+        .all(|(player, token)| {
+            player["token"] == serde_json::json!(token) && player["name"].is_null()
+        });
+    if !dealt {
+        let seats: Vec<_> = players
+            .iter()
+            .map(|player| serde_json::json!({ "token": player["token"], "name": player["name"] }))
+            .collect();
+        state["config"]["seats"] = serde_json::json!(seats);
+    }
+}
+
+// Saves from before version 7 only said who was bankrupt. The turn is the
+// one the log shows them going bankrupt on or, if that was compacted away,
+// the turn the game was saved on.
+fn date_eliminations(state: &mut serde_json::Value) {
+    let saved_on = state["turn"]["number"].clone();
+    let mut out_on = Vec::new();
+    for record in state["events"].as_array().into_iter().flatten() {
+        for effect in record["effects"].as_array().into_iter().flatten() {
+            if let Some(player) = effect["WentBankrupt"].as_u64() {
+                out_on.push((player, record["turn"].clone()));
+            }
+        }
+    }
+    if let Some(players) = state["players"].as_array_mut() {
+        for (seat, player) in players.iter_mut().enumerate() {
+            let bankrupt = player["bankrupt"].as_bool() == Some(true);
+            let turn = out_on
+                .iter()
+                .find(|(out, _)| *out == seat as u64)
+                .map_or(&saved_on, |(_, turn)| turn);
+            player["eliminated"] = match bankrupt {
+                true => turn.clone(),
+                false => serde_json::Value::Null,
+            };
+        }
+    }
+}
+
+impl GameState {
+    // Writes {"version": .., "state": ..}.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        if self.board != Board::for_edition(self.config.edition) {
+            return Err(SaveError::CustomBoard);
+        }
+        let envelope = serde_json::json!({
+            "version": SAVE_VERSION,
+            "state": self,
+        });
+        fs::write(path, serde_json::to_string(&envelope)?)?;
+        Ok(())
+    }
+
+    // The version is checked before the state is looked at, so a save from
+    // some other version is refused rather than half read.
+    pub fn load_from(path: impl AsRef<Path>) -> Result<GameState, SaveError> {
+        let mut envelope: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let mut state = envelope["state"].take();
+        let version = envelope["version"].as_u64();
+        match version {
+            Some(from @ (1 | 2)) => {
+                // A compacted log can't be played from the start; that came
+                // late enough in version 1 to leave unmigrated. Playing the
+                // rest again fills in the effects and the dice.
+                if state["compacted"]
+                    .as_u64()
+                    .is_some_and(|compacted| compacted > 0)
+                {
+                    return Err(SaveError::UnsupportedVersion {
+                        found: Some(from),
+                        supported: SAVE_VERSION,
+                    });
+                }
+                let config: GameConfig = serde_json::from_value(state["config"].clone())?;
+                let commands = match from {
+                    1 => old_commands(state["events"].take())?,
+                    _ => serde_json::from_value::<Vec<EventRecord>>(state["events"].take())?
+                        .into_iter()
+                        .map(|record| record.command)
+                        .collect(),
+                };
+                let migrated = migrate(&config, &commands, from)?;
+                state["dice"] = serde_json::to_value(&migrated.dice)?;
+                state["events"] = serde_json::to_value(&migrated.events)?;
+            }
+            Some(from @ (3 | 4)) => {
+                let config: GameConfig = serde_json::from_value(state["config"].clone())?;
+                let previous = serde_json::from_value(state["compacted_digest"].clone())?;
+                let mut records: Vec<EventRecord> = serde_json::from_value(state["events"].take())?;
+                if from == 3 {
+                    mark_go(&mut records, config.hash_chain, previous)?;
+                }
+                mark_sales(&config, &mut records, previous)?;
+                check_bankruptcies(&config, &records, previous, from)?;
+                state["events"] = serde_json::to_value(&records)?;
+            }
+            Some(from @ 5..=7) => {
+                let config: GameConfig = serde_json::from_value(state["config"].clone())?;
+                let previous = serde_json::from_value(state["compacted_digest"].clone())?;
+                let records: Vec<EventRecord> = serde_json::from_value(state["events"].clone())?;
+                check_bankruptcies(&config, &records, previous, from)?;
+            }
+            Some(8 | SAVE_VERSION) => {}
+            found => {
+                return Err(SaveError::UnsupportedVersion {
+                    found,
+                    supported: SAVE_VERSION,
+                })
+            }
+        }
+        if version < Some(6) {
+            deal_tokens(&mut state);
+        }
+        if version < Some(7) {
+            date_eliminations(&mut state);
+        }
+        if version < Some(9) {
+            seat_config(&mut state);
+        }
+        Ok(serde_json::from_value(state)?)
+    }
+
+    pub fn log(&self) -> GameLog {
+        GameLog {
+            config: self.config.clone(),
+            records: self.events.clone(),
+        }
+    }
+}
+
+// Just enough to replay a game: how it was set up and what happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameLog {
+    pub config: GameConfig,
+    pub records: Vec<EventRecord>,
+}
+
+impl GameLog {
+    // Writes {"version": .., "config": .., "events": [..]}.
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        let envelope = serde_json::json!({
+            "version": LOG_VERSION,
+            "config": self.config,
+            "events": self.records,
+        });
+        Ok(serde_json::to_string(&envelope)?)
+    }
+
+    // Reads this version or any earlier one, migrating as it goes.
+    pub fn from_json(json: &str) -> Result<GameLog, SaveError> {
+        let mut envelope: serde_json::Value = serde_json::from_str(json)?;
+        let config: GameConfig = serde_json::from_value(envelope["config"].take())?;
+        let events = envelope["events"].take();
+        let records = match envelope["version"].as_u64() {
+            Some(1) => {
+                migrate(&config, &serde_json::from_value::<Vec<Command>>(events)?, 1)?.events
+            }
+            Some(2) => migrate(&config, &old_commands(events)?, 2)?.events,
+            Some(from @ (3 | 4)) => {
+                let mut records: Vec<EventRecord> = serde_json::from_value(events)?;
+                if from == 3 {
+                    mark_go(&mut records, config.hash_chain, None)?;
+                }
+                mark_sales(&config, &mut records, None)?;
+                check_bankruptcies(&config, &records, None, from)?;
+                records
+            }
+            Some(5) => {
+                let records: Vec<EventRecord> = serde_json::from_value(events)?;
+                check_bankruptcies(&config, &records, None, 5)?;
+                records
+            }
+            Some(LOG_VERSION) => serde_json::from_value(events)?,
+            found => {
+                return Err(SaveError::UnsupportedVersion {
+                    found,
+                    supported: LOG_VERSION,
+                })
+            }
+        };
+        Ok(GameLog { config, records })
+    }
+
+    // A log from a game with hash_chain set has to verify first.
+    pub fn replay(&self) -> Result<GameState, ReplayError> {
+        if self.config.hash_chain {
+            return GameState::replay_verified(self.config.clone(), &self.records);
+        }
+        let events: Vec<Event> = self.records.iter().flat_map(EventRecord::events).collect();
+        GameState::replay(self.config.clone(), &events)
+    }
+}
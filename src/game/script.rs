@@ -0,0 +1,444 @@
+// A plain-text form for actions, one per line, for writing scenarios by hand:
+//
+//     # Player 0 buys Baltic and offers it on
+//     P0 roll 1 2
+//     P0 buy baltic
+//     P0 offer P1 baltic $50 for st-charles-place
+//     P0 endturn
+//
+// Properties may be named by any unambiguous prefix of their words, in any
+// case, with hyphens or spaces between words. Trade items are separated by
+// spaces, so names there need hyphens. Any number of dice but two is thrown
+// rather than rolled: P0 throw 2 5 3.
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize, // from 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+// Lowercase words, whatever separated them.
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+impl Board {
+    // The name as scripts write it: "St. James Place" is st-james-place.
+    pub fn slug(&self, index: usize) -> Option<String> {
+        self.name(index).map(|name| words(name).join("-"))
+    }
+
+    // Finds an ownable square by exact name, or failing that by a unique
+    // prefix of its words.
+    pub fn property_named(&self, query: &str) -> Result<PropertyId, String> {
+        let query = words(query);
+        let ownable = (0..self.len()).filter(|index| {
+            matches!(
+                self.squares[*index],
+                Square::Property(_) | Square::Railroad(_) | Square::Utility(_)
+            )
+        });
+        let mut prefixed = Vec::new();
+        for index in ownable {
+            let name = words(self.name(index).unwrap());
+            if name == query {
+                return Ok(PropertyId(index as i8));
+            }
+            if !query.is_empty()
+                && query.len() <= name.len()
+                && query
+                    .iter()
+                    .zip(&name)
+                    .all(|(query, word)| word.starts_with(query.as_str()))
+            {
+                prefixed.push(index);
+            }
+        }
+        match prefixed.as_slice() {
+            [index] => Ok(PropertyId(*index as i8)),
+            [] => Err(format!("no property is called '{}'", query.join(" "))),
+            several => Err(format!(
+                "'{}' could be {}",
+                query.join(" "),
+                several
+                    .iter()
+                    .map(|index| self.name(*index).unwrap())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    column: usize,
+}
+
+struct Line<'a> {
+    number: usize,
+    tokens: Vec<Token<'a>>,
+    next: usize,
+    end: usize, // column after the last token, for complaints about a missing one
+}
+
+impl<'a> Line<'a> {
+    fn error(&self, column: usize, message: String) -> ParseError {
+        ParseError {
+            line: self.number,
+            column,
+            message,
+        }
+    }
+
+    fn next(&mut self, what: &str) -> Result<Token<'a>, ParseError> {
+        match self.tokens.get(self.next) {
+            Some(_) => {
+                self.next += 1;
+                Ok(self.tokens[self.next - 1])
+            }
+            None => Err(self.error(self.end, format!("expected {}", what))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.next)
+    }
+
+    fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(token) => Err(self.error(token.column, format!("unexpected '{}'", token.text))),
+            None => Ok(()),
+        }
+    }
+
+    fn player(&mut self) -> Result<PlayerId, ParseError> {
+        let token = self.next("a player like P0")?;
+        let (text, column) = (token.text, token.column);
+        text.strip_prefix(['P', 'p'])
+            .and_then(|number| number.parse().ok())
+            .map(PlayerId)
+            .ok_or_else(|| self.error(column, format!("expected a player like P0, got '{}'", text)))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, ParseError> {
+        let token = self.next(what)?;
+        let (text, column) = (token.text, token.column);
+        text.parse()
+            .map_err(|_| self.error(column, format!("expected {}, got '{}'", what, text)))
+    }
+
+    fn money(&mut self) -> Result<Money, ParseError> {
+        let token = self.next("an amount")?;
+        let (text, column) = (token.text, token.column);
+        text.trim_start_matches('$')
+            .parse()
+            .map(Money)
+            .map_err(|_| self.error(column, format!("expected an amount, got '{}'", text)))
+    }
+
+    // Everything left on the line names one property.
+    fn property(&mut self, board: &Board) -> Result<PropertyId, ParseError> {
+        let column = self.next("a property")?.column;
+        let name: Vec<&str> = self.tokens[self.next - 1..]
+            .iter()
+            .map(|token| token.text)
+            .collect();
+        self.next = self.tokens.len();
+        board
+            .property_named(&name.join(" "))
+            .map_err(|message| self.error(column, message))
+    }
+
+    fn one_property(&mut self, board: &Board) -> Result<PropertyId, ParseError> {
+        let token = self.next("a property")?;
+        let (text, column) = (token.text, token.column);
+        board
+            .property_named(text)
+            .map_err(|message| self.error(column, message))
+    }
+}
+
+fn tokenize(number: usize, text: &str) -> Line<'_> {
+    let code = text.split('#').next().unwrap();
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (offset, c) in code
+        .char_indices()
+        .chain(std::iter::once((code.len(), ' ')))
+    {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(offset),
+            (Some(from), true) => {
+                tokens.push(Token {
+                    text: &code[from..offset],
+                    column: code[..from].chars().count() + 1,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    let end = code.trim_end().chars().count() + 1;
+    Line {
+        number,
+        tokens,
+        next: 0,
+        end,
+    }
+}
+
+fn parse_card(line: &mut Line, deck: &str) -> Result<Card, ParseError> {
+    let token = line.next("a card")?;
+    let (text, column) = (token.text, token.column);
+    let card = match deck {
+        "chance" => CHANCE_CARDS
+            .iter()
+            .find(|card| format!("{:?}", card).eq_ignore_ascii_case(text))
+            .map(|card| Card::Chance(*card)),
+        _ => COMMUNITY_CHEST_CARDS
+            .iter()
+            .find(|card| format!("{:?}", card).eq_ignore_ascii_case(text))
+            .map(|card| Card::CommunityChest(*card)),
+    };
+    card.ok_or_else(|| line.error(column, format!("no {} card is called '{}'", deck, text)))
+}
+
+// One side of a trade: property names, $cash and jail-card, up to `stop`.
+fn parse_side(
+    line: &mut Line,
+    board: &Board,
+    stop: Option<&str>,
+) -> Result<(Vec<PropertyId>, Money, u8), ParseError> {
+    let (mut properties, mut cash, mut cards) = (Vec::new(), Money(0), 0);
+    while let Some(token) = line.peek() {
+        if Some(token.text) == stop {
+            break;
+        }
+        if token.text == "nothing" {
+            line.next += 1;
+        } else if token.text.starts_with('$') {
+            cash.0 += line.money()?.0;
+        } else if token.text == "jail-card" {
+            line.next += 1;
+            cards += 1;
+        } else {
+            properties.push(line.one_property(board)?);
+        }
+    }
+    Ok((properties, cash, cards))
+}
+
+fn parse_line(line: &mut Line, board: &Board) -> Result<Command, ParseError> {
+    if line.peek().map(|token| token.text) == Some("auction") {
+        line.next += 1;
+        let property = line.one_property(board)?;
+        let mut bids = Vec::new();
+        while line.peek().is_some() {
+            let bidder = line.player()?;
+            bids.push(Bid(bidder, line.money()?));
+        }
+        return Ok(Command::AuctionProperty(property, bids));
+    }
+    let id = line.player()?;
+    let verb = line.next("an action")?;
+    let (verb, column) = (verb.text.to_lowercase(), verb.column);
+    let action = match verb.as_str() {
+        "roll" if line.peek().is_none() => Command::Roll(id),
+        "roll" => Command::RollDice(id, RollResult(line.number("a die")?, line.number("a die")?)),
+        "throw" => {
+            let mut dice = vec![line.number("a die")?];
+            while line.peek().is_some() {
+                dice.push(line.number("a die")?);
+            }
+            Command::RollMany(id, dice)
+        }
+        "move" => Command::MoveForward(id, line.number("a number of spaces")?),
+        "buy" => Command::BuyProperty(id, line.property(board)?),
+        "decline" => Command::DeclineToBuy(id),
+        "sell" => Command::SellProperty(id, line.property(board)?),
+        "buy-house" | "build" => Command::BuyHouse(id, line.property(board)?),
+        "sell-house" => Command::SellHouse(id, line.property(board)?),
+        "buy-hotel" => Command::BuyHotel(id, line.property(board)?),
+        "sell-hotel" => Command::SellHotel(id, line.property(board)?),
+        "pay-taxes" => Command::PayTaxes(id, line.money()?),
+        "income-tax" => {
+            let token = line.next("flat or percent")?;
+            let choice = match token.text {
+                "flat" => TaxChoice::Flat,
+                "percent" => TaxChoice::Percent,
+                other => {
+                    let message = format!("expected flat or percent, got '{}'", other);
+                    return Err(line.error(token.column, message));
+                }
+            };
+            Command::PayIncomeTax(id, choice)
+        }
+        "salary" => Command::ReceiveSalary(id),
+        "draw" => {
+            let token = line.next("chance or community-chest")?;
+            let deck = match token.text {
+                "chance" => "chance",
+                "community-chest" => "community-chest",
+                other => {
+                    let message = format!("expected chance or community-chest, got '{}'", other);
+                    return Err(line.error(token.column, message));
+                }
+            };
+            Command::DrawCard(id, parse_card(line, deck)?)
+        }
+        "go-to-jail" => Command::GoToJail(id),
+        "pay-fine" => Command::PayJailFine(id),
+        "use-card" => Command::UseGetOutOfJailFreeCard(id),
+        "bid" => Command::PlaceBid(id, line.money()?),
+        "pass" => Command::PassAuction(id),
+        "mortgage" => Command::MortgageProperty(id, line.property(board)?),
+        "unmortgage" => Command::UnmortgageProperty(id, line.property(board)?),
+        "offer" => {
+            let recipient = line.player()?;
+            let (offered, offered_cash, offered_jail_cards) = parse_side(line, board, Some("for"))?;
+            line.next("for")?;
+            let (requested, requested_cash, requested_jail_cards) = parse_side(line, board, None)?;
+            Command::ProposeTrade(Trade {
+                proposer: id,
+                recipient,
+                offered,
+                requested,
+                offered_cash,
+                requested_cash,
+                offered_jail_cards,
+                requested_jail_cards,
+            })
+        }
+        "accept" => Command::AcceptTrade(id),
+        "reject" => Command::RejectTrade(id),
+        "pay-debt" => Command::PayDebt(id),
+        "bankrupt" => Command::DeclareBankruptcy(id),
+        "resign" => Command::Resign(id),
+        "endturn" | "end-turn" | "end" => Command::EndTurn(id),
+        other => return Err(line.error(column, format!("unknown action '{}'", other))),
+    };
+    line.finish()?;
+    Ok(action)
+}
+
+impl Command {
+    pub fn parse_script(source: &str, board: &Board) -> Result<Vec<Command>, ParseError> {
+        let mut actions = Vec::new();
+        for (index, text) in source.lines().enumerate() {
+            let mut line = tokenize(index + 1, text);
+            if line.tokens.is_empty() {
+                continue;
+            }
+            actions.push(parse_line(&mut line, board)?);
+        }
+        Ok(actions)
+    }
+
+    pub fn write_script(actions: &[Command], board: &Board) -> String {
+        actions
+            .iter()
+            .map(|action| action.to_script_line(board) + "\n")
+            .collect()
+    }
+
+    pub fn to_script_line(&self, board: &Board) -> String {
+        let name = |property: &PropertyId| {
+            usize::try_from(property.0)
+                .ok()
+                .and_then(|index| board.slug(index))
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+        let side = |properties: &[PropertyId], cash: Money, cards: u8| {
+            let mut items: Vec<String> = properties.iter().map(name).collect();
+            if cash.0 != 0 {
+                items.push(format!("${}", cash.0));
+            }
+            items.extend((0..cards).map(|_| "jail-card".to_string()));
+            if items.is_empty() {
+                "nothing".to_string()
+            } else {
+                items.join(" ")
+            }
+        };
+        let (id, rest) = match self {
+            Command::AuctionProperty(property, bids) => {
+                let mut line = format!("auction {}", name(property));
+                for Bid(PlayerId(bidder), amount) in bids {
+                    line.push_str(&format!(" P{} ${}", bidder, amount.0));
+                }
+                return line;
+            }
+            Command::RollDice(id, RollResult(one, two)) => (id, format!("roll {} {}", one, two)),
+            Command::Roll(id) => (id, "roll".to_string()),
+            Command::RollMany(id, dice) => {
+                let faces: Vec<_> = dice.iter().map(|die| die.to_string()).collect();
+                (id, format!("throw {}", faces.join(" ")))
+            }
+            Command::MoveForward(id, spaces) => (id, format!("move {}", spaces)),
+            Command::BuyProperty(id, property) => (id, format!("buy {}", name(property))),
+            Command::DeclineToBuy(id) => (id, "decline".to_string()),
+            Command::SellProperty(id, property) => (id, format!("sell {}", name(property))),
+            Command::BuyHouse(id, property) => (id, format!("buy-house {}", name(property))),
+            Command::SellHouse(id, property) => (id, format!("sell-house {}", name(property))),
+            Command::BuyHotel(id, property) => (id, format!("buy-hotel {}", name(property))),
+            Command::SellHotel(id, property) => (id, format!("sell-hotel {}", name(property))),
+            Command::PayTaxes(id, amount) => (id, format!("pay-taxes ${}", amount.0)),
+            Command::PayIncomeTax(id, TaxChoice::Flat) => (id, "income-tax flat".to_string()),
+            Command::PayIncomeTax(id, TaxChoice::Percent) => (id, "income-tax percent".to_string()),
+            Command::ReceiveSalary(id) => (id, "salary".to_string()),
+            Command::DrawCard(id, Card::Chance(card)) => (id, format!("draw chance {:?}", card)),
+            Command::DrawCard(id, Card::CommunityChest(card)) => {
+                (id, format!("draw community-chest {:?}", card))
+            }
+            Command::GoToJail(id) => (id, "go-to-jail".to_string()),
+            Command::PayJailFine(id) => (id, "pay-fine".to_string()),
+            Command::UseGetOutOfJailFreeCard(id) => (id, "use-card".to_string()),
+            Command::PlaceBid(id, amount) => (id, format!("bid ${}", amount.0)),
+            Command::PassAuction(id) => (id, "pass".to_string()),
+            Command::MortgageProperty(id, property) => (id, format!("mortgage {}", name(property))),
+            Command::UnmortgageProperty(id, property) => {
+                (id, format!("unmortgage {}", name(property)))
+            }
+            Command::ProposeTrade(trade) => (
+                &trade.proposer,
+                format!(
+                    "offer P{} {} for {}",
+                    trade.recipient.0,
+                    side(&trade.offered, trade.offered_cash, trade.offered_jail_cards),
+                    side(
+                        &trade.requested,
+                        trade.requested_cash,
+                        trade.requested_jail_cards
+                    )
+                ),
+            ),
+            Command::AcceptTrade(id) => (id, "accept".to_string()),
+            Command::RejectTrade(id) => (id, "reject".to_string()),
+            Command::PayDebt(id) => (id, "pay-debt".to_string()),
+            Command::DeclareBankruptcy(id) => (id, "bankrupt".to_string()),
+            Command::EndTurn(id) => (id, "endturn".to_string()),
+            Command::Resign(id) => (id, "resign".to_string()),
+        };
+        format!("P{} {}", id.0, rest)
+    }
+}
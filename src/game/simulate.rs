@@ -0,0 +1,279 @@
+// Whole games played without anyone at the keyboard: the engine rolls and
+// ends turns, and each seat's strategy settles the decisions that fall to it.
+
+use super::*;
+
+// How a player makes the choices the game leaves to them. Each answer goes
+// through the engine like any other command, and one that doesn't apply is
+// replaced with what DoNothing would say. A method is asked again until its
+// decision is settled, so a strategy short of cash may mortgage or sell
+// first and answer properly on a later call. Strategies are Send so a game
+// and its seats can be handed to another thread together.
+pub trait Strategy: Send {
+    // BuyProperty or DeclineToBuy, which puts it up for auction.
+    fn buy_or_decline(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+    ) -> Command;
+    // PlaceBid over the high bid, or PassAuction to drop out.
+    fn bid(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+        high_bid: Option<&Bid>,
+    ) -> Command;
+    // PayIncomeTax; worth is what the percentage would be taken from.
+    fn income_tax(&mut self, state: &GameState, player: PlayerId, worth: Money) -> Command;
+    // Before rolling on a turn in jail: PayJailFine, UseGetOutOfJailFreeCard,
+    // or Roll to try for doubles.
+    fn leave_jail(&mut self, state: &GameState, player: PlayerId) -> Command;
+    // Until the debt is gone: sell or mortgage to raise cash, then PayDebt,
+    // or DeclareBankruptcy.
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        creditor: Option<PlayerId>,
+        amount: Money,
+    ) -> Command;
+    // AcceptTrade or RejectTrade, for the player the offer was made to.
+    fn answer_trade(&mut self, state: &GameState, player: PlayerId, trade: &Trade) -> Command;
+    // Once the dice are done with for the turn, until it answers EndTurn:
+    // build, unmortgage or propose trades.
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command;
+}
+
+// Turns everything down and never spends a dollar it doesn't owe. Its
+// answers always apply, which makes it the fallback for every other strategy.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DoNothing;
+
+impl Strategy for DoNothing {
+    fn buy_or_decline(&mut self, _: &GameState, player: PlayerId, _: PropertyId) -> Command {
+        Command::DeclineToBuy(player)
+    }
+
+    fn bid(&mut self, _: &GameState, player: PlayerId, _: PropertyId, _: Option<&Bid>) -> Command {
+        Command::PassAuction(player)
+    }
+
+    fn income_tax(&mut self, _: &GameState, player: PlayerId, _: Money) -> Command {
+        Command::PayIncomeTax(player, TaxChoice::Flat)
+    }
+
+    fn leave_jail(&mut self, _: &GameState, player: PlayerId) -> Command {
+        Command::Roll(player)
+    }
+
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        _: Option<PlayerId>,
+        amount: Money,
+    ) -> Command {
+        if state.players[player.0 as usize].cash.0 >= amount.0 {
+            return Command::PayDebt(player);
+        }
+        // Bankruptcy isn't open while selling up would cover the debt.
+        match state.could_pay(player, amount) {
+            true => state.ways_to_raise_cash(player).remove(0),
+            false => Command::DeclareBankruptcy(player),
+        }
+    }
+
+    fn answer_trade(&mut self, _: &GameState, player: PlayerId, _: &Trade) -> Command {
+        Command::RejectTrade(player)
+    }
+
+    fn end_turn(&mut self, _: &GameState, player: PlayerId) -> Command {
+        Command::EndTurn(player)
+    }
+}
+
+// How a simulated game ended. Without a winner the turn limit came first,
+// and the outcome says who was ahead then.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameResult {
+    pub winner: Option<PlayerId>,
+    pub outcome: Outcome,
+    pub turns: u32,
+    pub net_worths: Vec<Money>, // by seat, as the game ended
+    pub log: Vec<EventRecord>,
+    pub fallbacks: usize, // answers that didn't apply and were replaced
+}
+
+// The last player standing, or at the turn limit whoever is worth the most.
+// A tie in net worth goes to whoever has more cash, then to whoever holds
+// more unmortgaged titles, and players level on all three share a draw.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Won(PlayerId),
+    Draw(Vec<PlayerId>), // in seat order
+}
+
+// Commands a strategy may spend without the game moving on, say mortgaging
+// and unmortgaging the same lot, before DoNothing takes over from it.
+const TRIES_PER_DECISION: usize = 100;
+
+impl GameState {
+    // The player the game is waiting on: whoever the front decision is for,
+    // then whoever a trade was offered to, then whoever's turn it is.
+    pub fn to_act(&self) -> PlayerId {
+        match (self.pending.front(), &self.trade) {
+            (Some(decision), _) => decision.player(),
+            (None, Some(trade)) => trade.recipient,
+            (None, None) => self.turn.player,
+        }
+    }
+
+    // What happens next, with the strategy deciding for to_act(). Rolls that
+    // are no one's choice are made without asking it.
+    pub fn ask(&self, strategy: &mut dyn Strategy) -> Command {
+        let id = self.to_act();
+        match self.pending.front() {
+            Some(PendingDecision::BuyOrDecline { player, property }) => {
+                return strategy.buy_or_decline(self, *player, *property)
+            }
+            Some(PendingDecision::Auction {
+                property, high_bid, ..
+            }) => return strategy.bid(self, id, *property, high_bid.as_ref()),
+            Some(PendingDecision::PayDebt {
+                debtor,
+                creditor,
+                amount,
+            }) => return strategy.pay_debt(self, *debtor, *creditor, *amount),
+            Some(PendingDecision::PayBid { debtor, amount, .. }) => {
+                return strategy.pay_debt(self, *debtor, None, *amount)
+            }
+            Some(PendingDecision::IncomeTax { player, worth }) => {
+                return strategy.income_tax(self, *player, *worth)
+            }
+            Some(PendingDecision::UtilityRoll { player, .. }) => return Command::Roll(*player),
+            None => {}
+        }
+        if let Some(trade) = &self.trade {
+            return strategy.answer_trade(self, id, trade);
+        }
+        match self.turn.may_roll {
+            true if self.players[id.0 as usize].in_jail() && self.turn.rolls == 0 => {
+                strategy.leave_jail(self, id)
+            }
+            true => Command::Roll(id),
+            false => strategy.end_turn(self, id),
+        }
+    }
+
+    // None while there is neither a winner nor a turn limit passed.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if let Some(winner) = self.winner() {
+            return Some(Outcome::Won(winner));
+        }
+        if self.turn.number <= self.config.turn_limit {
+            return None;
+        }
+        let standing = |player: &Player| {
+            let unmortgaged = self
+                .deeds
+                .iter()
+                .filter(|deed| deed.owner == Some(player.id) && !deed.mortgaged)
+                .count();
+            (
+                self.net_worth(player.id).unwrap().0,
+                player.cash.0,
+                unmortgaged,
+            )
+        };
+        let solvent = self.players.iter().filter(|player| !player.is_bankrupt());
+        let best = solvent.clone().map(standing).max()?;
+        let leaders: Vec<PlayerId> = solvent
+            .filter(|player| standing(player) == best)
+            .map(|player| player.id)
+            .collect();
+        Some(match leaders[..] {
+            [leader] => Outcome::Won(leader),
+            _ => Outcome::Draw(leaders),
+        })
+    }
+
+    // Plays on until someone wins or turn `limit` is over, and returns how
+    // many answers had to be replaced.
+    pub(super) fn play_out(&mut self, strategies: &mut [Box<dyn Strategy>], limit: u32) -> usize {
+        let mut tries = 0;
+        let mut waiting = None;
+        let mut fallbacks = 0;
+        while self.winner().is_none() && self.turn.number <= limit {
+            let now = (self.turn.clone(), self.pending.front().cloned());
+            if waiting.as_ref() != Some(&now) {
+                waiting = Some(now);
+                tries = 0;
+            }
+            tries += 1;
+            let command = match tries > TRIES_PER_DECISION {
+                true => self.ask(&mut DoNothing),
+                false => self.ask(&mut *strategies[self.to_act().0 as usize]),
+            };
+            if let Err(error) = self.apply(command) {
+                debug!(
+                    target: "monopoly::simulate",
+                    "player {}'s answer was refused ({}); DoNothing answers instead",
+                    self.to_act().0,
+                    error
+                );
+                fallbacks += 1;
+                let fallback = self.ask(&mut DoNothing);
+                self.apply(fallback).expect("DoNothing always applies");
+            }
+        }
+        debug!(
+            target: "monopoly::simulate",
+            "stopped on turn {} with winner {:?} after {} fallbacks",
+            self.turn.number,
+            self.winner(),
+            fallbacks
+        );
+        fallbacks
+    }
+}
+
+// Plays a game from start to finish, or to the config's turn limit, with
+// strategies[n] deciding for player n. The seed stands in for the config's,
+// so one config can be played out many ways, and the engine rolls the dice.
+pub fn simulate(
+    config: GameConfig,
+    strategies: &mut [Box<dyn Strategy>],
+    rng_seed: u64,
+) -> Result<GameResult, GameError> {
+    if strategies.len() != config.players {
+        return Err(GameError::InvalidConfig(format!(
+            "{} players but {} strategies",
+            config.players,
+            strategies.len()
+        )));
+    }
+    let limit = config.turn_limit;
+    let mut state = GameState::new(GameConfig {
+        seed: rng_seed,
+        engine_dice: true,
+        ..config
+    })?;
+    let fallbacks = state.play_out(strategies, limit);
+    let net_worths = state
+        .players
+        .iter()
+        .map(|player| state.net_worth(player.id).unwrap())
+        .collect();
+    Ok(GameResult {
+        winner: state.winner(),
+        outcome: state
+            .outcome()
+            .expect("play_out stops at a winner or the turn limit"),
+        turns: state.turn.number.min(limit),
+        net_worths,
+        log: std::mem::take(&mut state.events),
+        fallbacks,
+    })
+}
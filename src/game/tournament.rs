@@ -0,0 +1,191 @@
+// Strategies played off against each other: every lineup of them that fits
+// the table, in every seating order, since going first is worth something.
+
+use super::*;
+
+// One game of a tournament: which strategy sat in each seat, by index into
+// the tournament's strategies, and which of them won.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TournamentGame {
+    pub seats: Vec<usize>,
+    pub winner: Option<usize>, // None if the turn limit came first
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TournamentReport {
+    pub strategies: Vec<String>,
+    pub games: Vec<TournamentGame>, // in the order they were played
+}
+
+// The K-factor the printed report rates with.
+pub const DEFAULT_K: f64 = 16.0;
+
+// Plays every choice of config.players strategies, each rotated through
+// every seat, games_per_seating times apiece. Every seating plays the same
+// seeds, base_seed onwards, so each lineup faces the same dice.
+pub fn tournament(
+    config: GameConfig,
+    strategies: &[(&str, StrategyFactory)],
+    games_per_seating: usize,
+    base_seed: u64,
+) -> Result<TournamentReport, GameError> {
+    let table = config.players;
+    if strategies.len() < table {
+        return Err(GameError::InvalidConfig(format!(
+            "{} strategies cannot fill {} seats",
+            strategies.len(),
+            table
+        )));
+    }
+    let mut report = TournamentReport {
+        strategies: strategies
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        games: Vec::new(),
+    };
+    for lineup in combinations(strategies.len(), table) {
+        for rotation in 0..table {
+            let mut seats = lineup.clone();
+            seats.rotate_left(rotation);
+            let factories: Vec<StrategyFactory> =
+                seats.iter().map(|index| strategies[*index].1).collect();
+            for game in 0..games_per_seating as u64 {
+                let result = play_seeded(&config, &factories, base_seed.wrapping_add(game))?;
+                report.games.push(TournamentGame {
+                    winner: result.winner.map(|PlayerId(seat)| seats[seat as usize]),
+                    seats: seats.clone(),
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+// Every way to choose k of 0..n, each in increasing order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    (k - 1..n)
+        .flat_map(|last| {
+            combinations(last, k - 1).into_iter().map(move |mut rest| {
+                rest.push(last);
+                rest
+            })
+        })
+        .collect()
+}
+
+impl TournamentReport {
+    pub fn played(&self, strategy: usize) -> usize {
+        self.games
+            .iter()
+            .filter(|game| game.seats.contains(&strategy))
+            .count()
+    }
+
+    pub fn wins(&self, strategy: usize) -> usize {
+        self.games
+            .iter()
+            .filter(|game| game.winner == Some(strategy))
+            .count()
+    }
+
+    pub fn win_rate(&self, strategy: usize) -> f64 {
+        self.wins(strategy) as f64 / self.played(strategy).max(1) as f64
+    }
+
+    // The 95% Wilson score interval around the win rate, which unlike the
+    // textbook one stays inside 0..1 for strategies that win always or never.
+    pub fn confidence_interval(&self, strategy: usize) -> (f64, f64) {
+        let n = self.played(strategy) as f64;
+        if n == 0.0 {
+            return (0.0, 1.0);
+        }
+        let (p, z) = (self.win_rate(strategy), 1.96);
+        let center = p + z * z / (2.0 * n);
+        let spread = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        let scale = 1.0 + z * z / n;
+        // Rounding can put an end a hair past a rate of 0 or 1.
+        (
+            ((center - spread) / scale).clamp(0.0, p),
+            ((center + spread) / scale).clamp(p, 1.0),
+        )
+    }
+
+    // Ratings from every decided game, in the order they were played.
+    pub fn ratings(&self, k: f64) -> Ratings {
+        let mut ratings = Ratings::new(self.strategies.len(), k);
+        for game in &self.games {
+            if let Some(winner) = game.winner {
+                let losers: Vec<usize> = game
+                    .seats
+                    .iter()
+                    .copied()
+                    .filter(|seat| *seat != winner)
+                    .collect();
+                ratings.update(winner, &losers);
+            }
+        }
+        ratings
+    }
+
+    // How often the first strategy won the games the second sat in on, as
+    // (wins, games).
+    pub fn head_to_head(&self, strategy: usize, against: usize) -> (usize, usize) {
+        let shared = self
+            .games
+            .iter()
+            .filter(|game| game.seats.contains(&strategy) && game.seats.contains(&against));
+        shared.fold((0, 0), |(wins, games), game| {
+            (wins + (game.winner == Some(strategy)) as usize, games + 1)
+        })
+    }
+}
+
+// strategy    games  wins  win rate  95% interval  rating
+// greedy        200   197     98.5%  95.7%-99.5%    1843
+// random        200   101     50.5%  43.6%-57.4%    1502
+//
+// head to head    greedy    random
+// greedy               -     97.0%
+// random            3.0%         -
+impl fmt::Display for TournamentReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "strategy    games  wins  win rate  95% interval  rating")?;
+        let ratings = self.ratings(DEFAULT_K);
+        for (index, name) in self.strategies.iter().enumerate() {
+            let (low, high) = self.confidence_interval(index);
+            let interval = format!("{:.1}%-{:.1}%", low * 100.0, high * 100.0);
+            writeln!(
+                f,
+                "{:<10} {:>6} {:>5}  {:>7.1}%  {:<12} {:>7.0}",
+                name,
+                self.played(index),
+                self.wins(index),
+                self.win_rate(index) * 100.0,
+                interval,
+                ratings.rating(index)
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "head to head")?;
+        for name in &self.strategies {
+            write!(f, "  {:>8}", name)?;
+        }
+        writeln!(f)?;
+        for (row, name) in self.strategies.iter().enumerate() {
+            write!(f, "{:<12}", name)?;
+            for column in 0..self.strategies.len() {
+                match self.head_to_head(row, column) {
+                    _ if row == column => write!(f, "  {:>8}", "-")?,
+                    (_, 0) => write!(f, "  {:>8}", "")?,
+                    (wins, games) => write!(f, "  {:>7.1}%", wins as f64 * 100.0 / games as f64)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+// The whole game as text, for golden tests of rule changes: one line per
+// command with its turn, who took it, what it was and whose cash it moved,
+// then how the game stands.
+//
+//     turn 1, Player 0: Player 0 bought St. Charles Place for $140 [Player 0 -$140]
+//
+// It is built from the log alone, in seat order, with nothing hashed or
+// floating, so the same game always gives the same bytes.
+
+use super::*;
+
+impl GameState {
+    pub fn transcript(&self) -> String {
+        let mut out = String::new();
+        for record in self.events() {
+            let actor = match record.actor {
+                Some(id) => self.display_name(id),
+                None => "Bank".to_string(),
+            };
+            out.push_str(&format!(
+                "turn {}, {}: {}",
+                record.turn,
+                actor,
+                record.command.display_with(self)
+            ));
+            let deltas = self.cash_deltas(&record.effects);
+            if !deltas.is_empty() {
+                out.push_str(&format!(" [{}]", deltas));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+        match self.outcome() {
+            Some(Outcome::Won(winner)) => {
+                out.push_str(&format!("{} won\n", self.display_name(winner)))
+            }
+            Some(Outcome::Draw(leaders)) => {
+                let names: Vec<String> = leaders.iter().map(|id| self.display_name(*id)).collect();
+                out.push_str(&format!("Drawn between {}\n", names.join(", ")))
+            }
+            None => out.push_str(&format!("Unfinished on turn {}\n", self.turn.number)),
+        }
+        for player in &self.players {
+            out.push_str(&match player.eliminated {
+                Some(turn) => format!("{}: bankrupt on turn {}\n", player, turn),
+                None => format!(
+                    "{}: ${}, worth ${}\n",
+                    player,
+                    player.cash.0,
+                    self.net_worth(player.id).unwrap().0
+                ),
+            });
+        }
+        out
+    }
+
+    // "Player 0 -$140, Player 1 +$140", netted per player; the bank and the
+    // pot are left out.
+    fn cash_deltas(&self, effects: &[Effect]) -> String {
+        let mut net = vec![0; self.players.len()];
+        for effect in effects {
+            match effect {
+                Effect::Paid { from, to, amount } => {
+                    net[from.0 as usize] -= amount.0;
+                    if let Some(to) = to {
+                        net[to.0 as usize] += amount.0;
+                    }
+                }
+                Effect::Received { player, amount }
+                | Effect::CollectedSalary { player, amount } => net[player.0 as usize] += amount.0,
+                _ => {}
+            }
+        }
+        self.players
+            .iter()
+            .zip(net)
+            .filter(|(_, change)| *change != 0)
+            .map(|(player, change)| match change > 0 {
+                true => format!("{} +${}", player, change),
+                false => format!("{} -${}", player, -change),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
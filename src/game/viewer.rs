@@ -0,0 +1,78 @@
+// Plays a log back one event at a time, saying what each did. Meant for
+// reading a game by eye, say to find where a rule change made it go wrong.
+//
+//     1. Player 0 rolled 3+4 (7)
+//        player 0: GO -> St. Charles Place
+//     2. Player 0 bought St. Charles Place for $140
+//        player 0: $1500 -> $1360
+
+use super::*;
+use std::io::{self, Write};
+
+impl GameState {
+    // Writes every command with the cash and position changes it caused. With
+    // summary_every, the full summary follows every that many events.
+    pub fn write_replay(
+        config: GameConfig,
+        commands: &[Command],
+        summary_every: Option<usize>,
+        out: &mut impl Write,
+    ) -> io::Result<GameState> {
+        let mut state = GameState::new(config).map_err(io::Error::other)?;
+        for (index, command) in commands.iter().enumerate() {
+            writeln!(out, "{}. {}", index + 1, command.display_with(&state))?;
+            let before = state.clone();
+            if let Err(error) = state.apply_logged(command.clone()) {
+                return Err(io::Error::other(ReplayError::at(
+                    &state, index, command, error,
+                )));
+            }
+            let delta = state.delta_since(&before);
+            if !delta.is_empty() {
+                writeln!(out, "   {}", delta)?;
+            }
+            if summary_every.is_some_and(|every| every > 0 && (index + 1) % every == 0) {
+                writeln!(out)?;
+                write!(out, "{}", state)?;
+                writeln!(out)?;
+            }
+        }
+        Ok(state)
+    }
+
+    // "player 0: $1500 -> $1440, GO -> Baltic Ave; player 1: ..."
+    fn delta_since(&self, before: &GameState) -> String {
+        let mut players: Vec<(PlayerId, Vec<String>)> = Vec::new();
+        for difference in before.diff(self).differences() {
+            let (player, change) = match difference {
+                Difference::Cash {
+                    player,
+                    left,
+                    right,
+                } => (*player, format!("${} -> ${}", left.0, right.0)),
+                Difference::Position {
+                    player,
+                    left,
+                    right,
+                } => (
+                    *player,
+                    format!(
+                        "{} -> {}",
+                        self.square_name(*left),
+                        self.square_name(*right)
+                    ),
+                ),
+                _ => continue,
+            };
+            match players.iter_mut().find(|(id, _)| *id == player) {
+                Some((_, changes)) => changes.push(change),
+                None => players.push((player, vec![change])),
+            }
+        }
+        players
+            .iter()
+            .map(|(player, changes)| format!("player {}: {}", player.0, changes.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
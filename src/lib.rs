@@ -0,0 +1,77 @@
+#[cfg(feature = "binary")]
+mod encoding;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "fuzzing")]
+mod fuzz;
+mod game;
+#[cfg(feature = "serde")]
+mod protocol;
+mod rng;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "multiplayer")]
+mod websocket;
+
+pub use crate::game::{
+    play_seeded, run_many, run_many_parallel, seated, simulate, steady_state, strategy_named,
+    tournament, Bid, Board, BoardBuilder, BoardEdition, Card, ChanceCard, CheckpointId, Clock,
+    ColorGroup, Command, CommandDisplay, CommunityChestCard, Dice, DiceRoller, Difference,
+    DoNothing, Effect, Event, EventRecord, GameConfig, GameError, GameResult, GameRules, GameState,
+    GreedyBuyer, HouseRules, LandingStats, LengthBucket, Money, Observed, Observer, Outcome,
+    ParseError, PendingDecision, Player, PlayerId, PlayerStatus, Property, PropertyId, Railroad,
+    RandomStrategy, Ratings, ReplayError, ReplayFailure, RollResult, RuleViolation, Seat,
+    SeededDice, SharedClock, SimulationReport, StateDiff, Strategy, StrategyFactory, SystemClock,
+    Tax, TaxChoice, Token, TournamentGame, TournamentReport, Trade, Utility, BANK_HOTELS,
+    BANK_HOUSES, DEFAULT_K, INITIAL_RATING, MAX_NAME_LENGTH, MAX_PLAYERS, MIN_PLAYERS,
+};
+
+#[cfg(feature = "testing")]
+pub use crate::game::{generate_state, GameStateBuilder};
+
+#[cfg(feature = "proptest")]
+pub use crate::game::game_states;
+
+#[cfg(feature = "serde")]
+pub use crate::game::{
+    EventSink, FileSink, GameLog, JournalError, Journaled, SaveError, LOG_VERSION, SAVE_VERSION,
+};
+
+#[cfg(feature = "protobuf")]
+pub use crate::game::{proto, ProtoError};
+
+#[cfg(feature = "serde")]
+pub use crate::protocol::{ClientMessage, Outbox, Recipient, ServerMessage, Table};
+
+#[cfg(feature = "binary")]
+pub use crate::encoding::{read_events, write_events};
+
+#[cfg(feature = "ffi")]
+pub use crate::ffi::{
+    monopoly_game_apply, monopoly_game_free, monopoly_game_legal_actions, monopoly_game_new,
+    monopoly_game_state, monopoly_last_error, MonopolyGame,
+};
+
+#[cfg(feature = "fuzzing")]
+pub use crate::fuzz::{fuzz_commands, fuzz_log};
+
+#[cfg(feature = "schema")]
+pub use crate::schema::{schema, SCHEMA_TYPES};
+
+#[cfg(feature = "server")]
+pub use crate::server::Server;
+
+#[cfg(feature = "tui")]
+pub use crate::tui::{draw_game, BoardWidget, LogWidget, PlayersWidget, PromptWidget};
+
+#[cfg(feature = "multiplayer")]
+pub use crate::websocket::serve_table;
+
+#[cfg(feature = "wasm")]
+pub use crate::wasm::WasmGame;
@@ -1,9 +1,540 @@
-mod game;
-use game::{Action, GameState, PlayerId, RollResult, StateError};
+#[cfg(feature = "serde")]
+use monopoly::GameLog;
+use monopoly::{
+    play_seeded, run_many_parallel, seated, tournament, BoardEdition, Command, DoNothing,
+    GameConfig, GameState, Money, PlayerId, RollResult, SharedClock, StrategyFactory, SystemClock,
+};
+use std::env;
 use std::error::Error;
-fn main() -> Result<(), StateError> {
-    let mut state = GameState::init();
-    state.apply(Action::RollDice(PlayerId(0), RollResult(1, 2)))?;
-    println!("state: {:?}", state);
+use std::fs::{self, File};
+use std::io;
+use std::process;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod repl;
+#[cfg(feature = "tui")]
+mod terminal;
+
+// monopoly [--load FILE] [--script FILE] [--save FILE] [--export-csv FILE] [--timestamps]
+// monopoly --strategy NAME,... [--seed N] [--players N] [RULES]
+// monopoly play [--load FILE] [--seed N] [--players N] [--humans N] [--bots NAME,...]
+//               [--show-board] [--tui] [RULES]
+// monopoly replay LOG [--every N]
+// monopoly validate LOG
+// monopoly simulate [--games N] [--seed N] [--strategies NAME,...] [--threads N]
+//                   [--output FILE] [--lengths-csv FILE] [--heatmap] [RULES]
+// monopoly tournament [--games N] [--seed N] [--players N] [RULES]
+// monopoly serve [--port N] [--threads N]
+// monopoly host [--port N] [--players N] [--seed N] [--takeover SECONDS] [RULES]
+// monopoly schema --type action|config|state|effect|event|client-message|server-message
+//
+// where RULES, for a new game, are any of
+//
+//     --edition standard|french|german --free-parking-pot --double-salary-on-go
+//     --no-auctions --unlimited-buildings --strict-trades --snake-eyes-bonus AMOUNT
+//     --jail-fine AMOUNT --dice COUNTdFACES
+//
+// Built with the logger feature, RUST_LOG picks the engine's diagnostics to
+// print, by target: for example
+// RUST_LOG=monopoly::rent=debug,monopoly::auction=debug.
+fn main() -> Result<(), Box<dyn Error>> {
+    #[cfg(feature = "logger")]
+    env_logger::init();
+    match env::args().nth(1).as_deref() {
+        Some("play") => return play(env::args().skip(2)),
+        Some("replay") => return replay(env::args().skip(2)),
+        Some("validate") => return validate(env::args().skip(2)),
+        Some("simulate") => return simulate(env::args().skip(2)),
+        Some("tournament") => return play_tournament(env::args().skip(2)),
+        Some("serve") => return serve(env::args().skip(2)),
+        Some("host") => return host(env::args().skip(2)),
+        Some("schema") => return print_schema(env::args().skip(2)),
+        _ => {}
+    }
+    let mut load = None;
+    let mut script = None;
+    let mut save = None;
+    let mut csv = None;
+    let mut timestamps = false;
+    let mut seed = None;
+    let mut players = None;
+    let mut bots = None;
+    let mut config = GameConfig::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--load" => load = Some(args.next().ok_or("--load needs a file")?),
+            "--script" => script = Some(args.next().ok_or("--script needs a file")?),
+            "--save" => save = Some(args.next().ok_or("--save needs a file")?),
+            "--export-csv" => csv = Some(args.next().ok_or("--export-csv needs a file")?),
+            "--timestamps" => timestamps = true,
+            "--seed" => seed = Some(args.next().ok_or("--seed needs a number")?.parse()?),
+            "--players" => players = Some(args.next().ok_or("--players needs a number")?.parse()?),
+            "--strategy" => bots = Some(args.next().ok_or("--strategy needs a list")?),
+            _ if rule_flag(&arg, &mut args, &mut config)? => {}
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    if let Some(bots) = bots {
+        return play_bots(&bots, seed, players, config);
+    }
+
+    let mut state = match &load {
+        Some(path) => resume(path)?,
+        None => GameState::new(GameConfig {
+            players: players.unwrap_or(config.players),
+            seed: seed.unwrap_or(config.seed),
+            ..config
+        })?,
+    };
+    if timestamps {
+        state.set_clock(SharedClock::new(SystemClock));
+    }
+    let actions = match &script {
+        Some(path) => Command::parse_script(&fs::read_to_string(path)?, state.board())
+            .map_err(|error| format!("{}: {}", path, error))?,
+        None if load.is_none() => vec![Command::RollDice(PlayerId(0), RollResult(1, 2))],
+        None => vec![],
+    };
+    for action in actions {
+        let effects = state.apply(action.clone())?;
+        println!("{}", action.display_with(&state));
+        for effect in effects {
+            println!("  {:?}", effect);
+        }
+    }
+    print!("{}", state);
+    if let Some(path) = save {
+        persist(&state, &path)?;
+    }
+    if let Some(path) = csv {
+        state.export_csv(&mut File::create(path)?)?;
+    }
     Ok(())
 }
+
+// A game at the terminal, new or loaded, saved on the way out if asked. The
+// first seats are for the people playing and the rest for the bots named.
+fn play(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut load = None;
+    let mut seed = None;
+    let mut players = None;
+    let mut humans = None;
+    let mut bots = Vec::new();
+    let mut show_board = false;
+    let mut tui = false;
+    let mut config = GameConfig {
+        engine_dice: true,
+        ..GameConfig::default()
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--load" => load = Some(args.next().ok_or("--load needs a file")?),
+            "--show-board" => show_board = true,
+            "--tui" => tui = true,
+            "--seed" => seed = Some(args.next().ok_or("--seed needs a number")?.parse()?),
+            "--players" => players = Some(args.next().ok_or("--players needs a number")?.parse()?),
+            "--humans" => humans = Some(args.next().ok_or("--humans needs a number")?.parse()?),
+            "--bots" => {
+                bots = args
+                    .next()
+                    .ok_or("--bots needs a list")?
+                    .split(',')
+                    .map(strategy_named)
+                    .collect::<Result<_, _>>()?
+            }
+            _ if rule_flag(&arg, &mut args, &mut config)? => {}
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    let mut state = match &load {
+        Some(path) => resume(path)?,
+        None => {
+            let humans = match (humans, players) {
+                (Some(humans), _) => humans,
+                (None, Some(players)) => players - bots.len().min(players),
+                (None, None) if bots.is_empty() => GameConfig::default().players,
+                (None, None) => 1,
+            };
+            GameState::new(GameConfig {
+                players: humans + bots.len(),
+                seed: match seed {
+                    Some(seed) => seed,
+                    None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+                },
+                ..config
+            })?
+        }
+    };
+    // Bots take the last seats, seeded as play_seeded would seed them.
+    let seats = state.players().count();
+    let first_bot = seats
+        .checked_sub(bots.len())
+        .ok_or("more bots than seats")?;
+    let mut factories: Vec<StrategyFactory> = vec![|_| Box::new(DoNothing); first_bot];
+    factories.extend(&bots);
+    let mut bots = repl::Bots::new(
+        seated(&factories, state.config().seed)
+            .into_iter()
+            .enumerate()
+            .map(|(seat, bot)| Some(bot).filter(|_| seat >= first_bot))
+            .collect(),
+    );
+    let save = match tui {
+        true => full_screen(&mut state, &mut bots)?,
+        false => repl::run(
+            &mut state,
+            &mut bots,
+            show_board,
+            &mut io::stdin().lock(),
+            &mut io::stdout().lock(),
+        )?,
+    };
+    if let Some(path) = save {
+        persist(&state, &path)?;
+        println!("Saved to {}", path);
+    }
+    Ok(())
+}
+
+// Prints a log event by event, with the summary every N events if asked.
+fn replay(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut log = None;
+    let mut every = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--every" => every = Some(args.next().ok_or("--every needs a number")?.parse()?),
+            _ if log.is_none() => log = Some(arg),
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    let (config, actions) = read_log(&log.ok_or("replay needs a log")?)?;
+    GameState::write_replay(config, &actions, every, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+// Replays a log without printing it, and says whether every event in it
+// still stands, or why the log can't be read; the exit status says so too.
+fn validate(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let path = args.next().ok_or("validate needs a log")?;
+    if let Some(arg) = args.next() {
+        return Err(format!("unknown argument {}", arg).into());
+    }
+    match check_log(&path) {
+        Ok(events) => println!("OK: {} events", events),
+        Err(error) => {
+            println!("{}: {}", path, error);
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+// Plays one game between bots and prints it. The seed is printed first, and
+// the same seed and bots always play the same game.
+fn play_bots(
+    bots: &str,
+    seed: Option<u64>,
+    players: Option<usize>,
+    config: GameConfig,
+) -> Result<(), Box<dyn Error>> {
+    let mut strategies = bots
+        .split(',')
+        .map(strategy_named)
+        .collect::<Result<Vec<_>, _>>()?;
+    // One bot named for several players plays all of them.
+    if let (Some(players), [bot]) = (players, strategies.as_slice()) {
+        strategies = vec![*bot; players];
+    }
+    let seed = match seed {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+    };
+    let config = GameConfig {
+        players: players.unwrap_or(strategies.len()),
+        seed,
+        ..config
+    };
+    println!("seed {}", seed);
+    let result = play_seeded(&config, &strategies, seed)?;
+    let names = GameState::new(config)?;
+    for record in &result.log {
+        println!("{}", record.command.display_with(&names));
+        for effect in &record.effects {
+            println!("  {:?}", effect);
+        }
+    }
+    match result.winner {
+        Some(PlayerId(winner)) => println!("player {} wins after {} turns", winner, result.turns),
+        None => println!("no winner after {} turns", result.turns),
+    }
+    Ok(())
+}
+
+// The flags for the board and house rules that every new game takes. False
+// if arg isn't one of them.
+fn rule_flag(
+    arg: &str,
+    args: &mut impl Iterator<Item = String>,
+    config: &mut GameConfig,
+) -> Result<bool, Box<dyn Error>> {
+    match arg {
+        "--edition" => {
+            let name = args.next().ok_or("--edition needs a name")?;
+            config.edition =
+                BoardEdition::named(&name).ok_or_else(|| format!("no edition called {}", name))?;
+        }
+        "--free-parking-pot" => config.rules.free_parking_pot = true,
+        "--double-salary-on-go" => config.rules.double_salary_on_go = true,
+        "--no-auctions" => config.rules.auctions_enabled = false,
+        "--unlimited-buildings" => config.rules.unlimited_buildings = true,
+        "--strict-trades" => config.rules.trades_any_time = false,
+        "--snake-eyes-bonus" => {
+            let amount = args.next().ok_or("--snake-eyes-bonus needs an amount")?;
+            config.rules.snake_eyes_bonus = Some(Money(amount.parse()?));
+        }
+        "--jail-fine" => {
+            let amount = args.next().ok_or("--jail-fine needs an amount")?;
+            config.rules.jail_fine = Money(amount.parse()?);
+        }
+        "--dice" => {
+            let dice = args.next().ok_or("--dice needs dice like 3d6")?;
+            config.rules.dice = dice.parse()?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn strategy_named(name: &str) -> Result<StrategyFactory, Box<dyn Error>> {
+    monopoly::strategy_named(name).ok_or_else(|| format!("no strategy called {}", name).into())
+}
+
+// Plays the strategies named, a seat each, and prints the statistics; by
+// default a greedy bot against three random ones.
+fn simulate(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut games = 1000;
+    let mut seed = 0;
+    let mut names = "greedy,random,random,random".to_string();
+    let mut threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let mut output = None;
+    let mut csv = None;
+    let mut heatmap = false;
+    let mut config = GameConfig::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => games = args.next().ok_or("--games needs a number")?.parse()?,
+            "--seed" => seed = args.next().ok_or("--seed needs a number")?.parse()?,
+            "--strategies" => names = args.next().ok_or("--strategies needs a list")?,
+            "--threads" => threads = args.next().ok_or("--threads needs a number")?.parse()?,
+            "--output" => output = Some(args.next().ok_or("--output needs a file")?),
+            "--lengths-csv" => csv = Some(args.next().ok_or("--lengths-csv needs a file")?),
+            "--heatmap" => heatmap = true,
+            _ if rule_flag(&arg, &mut args, &mut config)? => {}
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    let lineup = names
+        .split(',')
+        .map(|name| Ok((name, strategy_named(name)?)))
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    config.players = lineup.len();
+    let report = run_many_parallel(config, &lineup, games, seed, threads)?;
+    let mut text = report.to_string();
+    if heatmap {
+        text.push('\n');
+        text.push_str(&report.landings.render_heatmap());
+    }
+    match output {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    if let Some(path) = csv {
+        report.export_lengths_csv(&mut File::create(path)?)?;
+    }
+    Ok(())
+}
+
+// Plays the built-in strategies off against each other, with the given
+// number of games for each seating.
+fn play_tournament(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut games = 100;
+    let mut seed = 0;
+    let mut config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => games = args.next().ok_or("--games needs a number")?.parse()?,
+            "--seed" => seed = args.next().ok_or("--seed needs a number")?.parse()?,
+            "--players" => {
+                config.players = args.next().ok_or("--players needs a number")?.parse()?
+            }
+            _ if rule_flag(&arg, &mut args, &mut config)? => {}
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    let greedy = strategy_named("greedy")?;
+    let random = strategy_named("random")?;
+    let nothing = strategy_named("nothing")?;
+    let entrants = [("greedy", greedy), ("random", random), ("nothing", nothing)];
+    print!("{}", tournament(config, &entrants, games, seed)?);
+    Ok(())
+}
+
+// Plays games for HTTP clients until the process is killed.
+fn serve(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut port: u16 = 8000;
+    let mut threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => port = args.next().ok_or("--port needs a number")?.parse()?,
+            "--threads" => threads = args.next().ok_or("--threads needs a number")?.parse()?,
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    listen(port, threads)
+}
+
+#[cfg(feature = "server")]
+fn listen(port: u16, threads: usize) -> Result<(), Box<dyn Error>> {
+    let server = monopoly::Server::bind(("127.0.0.1", port))?;
+    println!("Serving games at http://{}", server.local_addr());
+    server.run(threads);
+    Ok(())
+}
+
+#[cfg(not(feature = "server"))]
+fn listen(_: u16, _: usize) -> Result<(), Box<dyn Error>> {
+    Err("serving games needs the server feature".into())
+}
+
+// Hosts one game for WebSocket clients, who each take a seat.
+fn host(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut port: u16 = 8001;
+    let mut config = GameConfig::default();
+    let mut takeover = 60;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => port = args.next().ok_or("--port needs a number")?.parse()?,
+            "--players" => {
+                config.players = args.next().ok_or("--players needs a number")?.parse()?
+            }
+            "--seed" => config.seed = args.next().ok_or("--seed needs a number")?.parse()?,
+            "--takeover" => takeover = args.next().ok_or("--takeover needs a number")?.parse()?,
+            _ if rule_flag(&arg, &mut args, &mut config)? => {}
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    host_table(port, GameState::new(config)?, takeover)
+}
+
+#[cfg(feature = "multiplayer")]
+fn host_table(port: u16, state: GameState, takeover: u64) -> Result<(), Box<dyn Error>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+        println!("Hosting a game at ws://{}", listener.local_addr()?);
+        let table = monopoly::Table::new(state, std::time::Duration::from_secs(takeover));
+        Ok(monopoly::serve_table(listener, table).await?)
+    })
+}
+
+#[cfg(not(feature = "multiplayer"))]
+fn host_table(_: u16, _: GameState, _: u64) -> Result<(), Box<dyn Error>> {
+    Err("hosting a game needs the multiplayer feature".into())
+}
+
+// Prints the JSON Schema for one of the types the engine speaks in JSON.
+fn print_schema(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut name = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => name = Some(args.next().ok_or("--type needs a name")?),
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    println!("{}", schema_of(&name.ok_or("schema needs a --type")?)?);
+    Ok(())
+}
+
+#[cfg(feature = "schema")]
+fn schema_of(name: &str) -> Result<String, Box<dyn Error>> {
+    let schema = monopoly::schema(name).ok_or_else(|| {
+        format!(
+            "no schema for {}; there are {}",
+            name,
+            monopoly::SCHEMA_TYPES.join(", ")
+        )
+    })?;
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
+
+#[cfg(not(feature = "schema"))]
+fn schema_of(_: &str) -> Result<String, Box<dyn Error>> {
+    Err("schemas need the schema feature".into())
+}
+
+#[cfg(feature = "serde")]
+fn read_log(path: &str) -> Result<(GameConfig, Vec<Command>), Box<dyn Error>> {
+    let log = GameLog::from_json(&fs::read_to_string(path)?)?;
+    let actions = log
+        .records
+        .into_iter()
+        .map(|record| record.command)
+        .collect();
+    Ok((log.config, actions))
+}
+
+// The number of events in the log, if they all still stand.
+#[cfg(feature = "serde")]
+fn check_log(path: &str) -> Result<usize, Box<dyn Error>> {
+    let log = GameLog::from_json(&fs::read_to_string(path)?)?;
+    log.replay()?;
+    Ok(log.records.len())
+}
+
+#[cfg(feature = "serde")]
+fn resume(path: &str) -> Result<GameState, Box<dyn Error>> {
+    Ok(GameState::load_from(path)?)
+}
+
+#[cfg(feature = "serde")]
+fn persist(state: &GameState, path: &str) -> Result<(), Box<dyn Error>> {
+    Ok(state.save_to(path)?)
+}
+
+#[cfg(feature = "tui")]
+fn full_screen(
+    state: &mut GameState,
+    bots: &mut repl::Bots,
+) -> Result<Option<String>, Box<dyn Error>> {
+    Ok(terminal::run(state, bots)?)
+}
+
+#[cfg(not(feature = "serde"))]
+fn read_log(_: &str) -> Result<(GameConfig, Vec<Command>), Box<dyn Error>> {
+    Err("event logs need the serde feature".into())
+}
+
+#[cfg(not(feature = "serde"))]
+fn check_log(_: &str) -> Result<usize, Box<dyn Error>> {
+    Err("event logs need the serde feature".into())
+}
+
+#[cfg(not(feature = "serde"))]
+fn resume(_: &str) -> Result<GameState, Box<dyn Error>> {
+    Err("saved games need the serde feature".into())
+}
+
+#[cfg(not(feature = "serde"))]
+fn persist(_: &GameState, _: &str) -> Result<(), Box<dyn Error>> {
+    Err("saved games need the serde feature".into())
+}
+
+#[cfg(not(feature = "tui"))]
+fn full_screen(_: &mut GameState, _: &mut repl::Bots) -> Result<Option<String>, Box<dyn Error>> {
+    Err("the full-screen interface needs the tui feature".into())
+}
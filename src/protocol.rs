@@ -0,0 +1,263 @@
+// The messages of a multiplayer game, and the table that answers them, with
+// no transport in sight: whatever carries the messages hands each one to the
+// table along with the seat of the client that sent it, and delivers what
+// comes back. Messages are JSON objects tagged by "type":
+//
+//     {"type":"join","seat":1}                   seat may be null for any
+//     {"type":"action","command":{"Roll":1}}
+//
+// and the table answers with seat-assignment, state-snapshot, event,
+// action-request, action-result and error messages. Everyone hears every
+// event; an action-request goes to the seat the game is waiting on. A seat
+// whose client goes away is held for it until the takeover time passes, and
+// then a bot plays it for the rest of the game.
+
+use crate::game::{
+    Command, DoNothing, Effect, EventRecord, GameError, GameState, GreedyBuyer, PendingDecision,
+    PlayerId, Strategy,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ClientMessage {
+    Join { seat: Option<PlayerId> }, // None for the first seat free
+    Action { command: Command },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ServerMessage {
+    SeatAssignment {
+        seat: PlayerId,
+    },
+    StateSnapshot {
+        state: Box<GameState>,
+    },
+    Event {
+        record: EventRecord,
+    },
+    // The game is waiting on this seat.
+    ActionRequest {
+        seat: PlayerId,
+        decision: Option<PendingDecision>,
+        legal_actions: Vec<Command>,
+    },
+    // To the sender of an action the game accepted.
+    ActionResult {
+        effects: Vec<Effect>,
+    },
+    // error is the engine's, when it was the engine that refused.
+    Error {
+        message: String,
+        error: Option<GameError>,
+    },
+}
+
+// Who a message is for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Recipient {
+    Sender,
+    Seat(PlayerId),
+    Everyone,
+}
+
+pub type Outbox = Vec<(Recipient, ServerMessage)>;
+
+// Commands a bot may spend without the game moving on before DoNothing
+// answers for it, as in simulate.
+const BOT_TRIES: usize = 100;
+
+enum Seat {
+    Open,
+    Taken,
+    Away(Instant), // since then
+    Bot(Box<dyn Strategy>),
+}
+
+pub struct Table {
+    state: GameState,
+    seats: Vec<Seat>,
+    takeover: Duration,
+}
+
+impl Table {
+    // Every seat starts open. A seat whose client has been gone for
+    // `takeover` goes to a bot.
+    pub fn new(state: GameState, takeover: Duration) -> Table {
+        let seats = (0..state.config().players).map(|_| Seat::Open).collect();
+        Table {
+            state,
+            seats,
+            takeover,
+        }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    // Answers a message from the client sitting at `seat`, or from one not
+    // yet seated; a join fills in the seat.
+    pub fn handle(&mut self, seat: &mut Option<PlayerId>, message: ClientMessage) -> Outbox {
+        let mut out = Outbox::new();
+        match (message, *seat) {
+            (ClientMessage::Join { .. }, Some(PlayerId(id))) => {
+                refuse(&mut out, format!("already sitting in seat {}", id))
+            }
+            (ClientMessage::Join { seat: wanted }, None) => match self.sit(wanted) {
+                Ok(taken) => {
+                    *seat = Some(taken);
+                    out.push((
+                        Recipient::Sender,
+                        ServerMessage::SeatAssignment { seat: taken },
+                    ));
+                    out.push((
+                        Recipient::Sender,
+                        ServerMessage::StateSnapshot {
+                            state: Box::new(self.state.clone()),
+                        },
+                    ));
+                    self.carry_on(&mut out);
+                }
+                Err(message) => refuse(&mut out, message),
+            },
+            (ClientMessage::Action { .. }, None) => {
+                refuse(&mut out, "join a seat first".to_string())
+            }
+            (ClientMessage::Action { command }, Some(seat)) => {
+                if command.actor() != Some(seat) {
+                    refuse(&mut out, "that is not a command for your seat".to_string());
+                    return out;
+                }
+                match self.state.apply(command) {
+                    Ok(effects) => {
+                        out.push((Recipient::Sender, ServerMessage::ActionResult { effects }));
+                        self.announce(&mut out);
+                        self.carry_on(&mut out);
+                    }
+                    Err(error) => out.push((
+                        Recipient::Sender,
+                        ServerMessage::Error {
+                            message: error.to_string(),
+                            error: Some(error),
+                        },
+                    )),
+                }
+            }
+        }
+        out
+    }
+
+    // The client at the seat has gone; it keeps the seat until the takeover
+    // time is up.
+    pub fn leave(&mut self, seat: PlayerId, now: Instant) {
+        if let Some(place @ Seat::Taken) = self.seats.get_mut(seat.0 as usize) {
+            *place = Seat::Away(now);
+        }
+    }
+
+    // Hands seats left too long to bots, and lets them play.
+    pub fn tick(&mut self, now: Instant) -> Outbox {
+        let mut out = Outbox::new();
+        let mut changed = false;
+        for place in &mut self.seats {
+            if let Seat::Away(since) = place {
+                if now.duration_since(*since) >= self.takeover {
+                    *place = Seat::Bot(Box::new(GreedyBuyer::default()));
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.carry_on(&mut out);
+        }
+        out
+    }
+
+    // The seat asked for, or the first open one; a seat whose client has
+    // gone can be taken back before a bot has it.
+    fn sit(&mut self, wanted: Option<PlayerId>) -> Result<PlayerId, String> {
+        let free = |place: &Seat| matches!(place, Seat::Open | Seat::Away(_));
+        let index = match wanted {
+            Some(PlayerId(id)) => match self.seats.get(id as usize) {
+                Some(place) if free(place) => id as usize,
+                Some(_) => return Err(format!("seat {} is taken", id)),
+                None => return Err(format!("there is no seat {}", id)),
+            },
+            None => self
+                .seats
+                .iter()
+                .position(|place| matches!(place, Seat::Open))
+                .ok_or("every seat is taken")?,
+        };
+        self.seats[index] = Seat::Taken;
+        Ok(PlayerId(index as i8))
+    }
+
+    // Plays the bots' moves, then asks whoever the game is waiting on.
+    fn carry_on(&mut self, out: &mut Outbox) {
+        let mut tries = 0;
+        let mut waiting = None;
+        while self.state.winner().is_none() {
+            let seat = self.state.to_act();
+            let bot = match &mut self.seats[seat.0 as usize] {
+                Seat::Bot(bot) => bot,
+                _ => break,
+            };
+            let now = (self.state.turn_number(), self.state.pending().cloned());
+            if waiting.as_ref() != Some(&now) {
+                waiting = Some(now);
+                tries = 0;
+            }
+            tries += 1;
+            let command = match tries > BOT_TRIES {
+                true => self.state.ask(&mut DoNothing),
+                false => self.state.ask(&mut **bot),
+            };
+            if self.state.apply(command).is_err() {
+                let fallback = self.state.ask(&mut DoNothing);
+                self.state
+                    .apply(fallback)
+                    .expect("DoNothing always applies");
+            }
+            self.announce(out);
+        }
+        let seat = self.state.to_act();
+        if self.state.winner().is_none() && matches!(self.seats[seat.0 as usize], Seat::Taken) {
+            let legal_actions = self
+                .state
+                .legal_actions()
+                .into_iter()
+                .filter(|command| command.actor() == Some(seat))
+                .collect();
+            out.push((
+                Recipient::Seat(seat),
+                ServerMessage::ActionRequest {
+                    seat,
+                    decision: self.state.pending().cloned(),
+                    legal_actions,
+                },
+            ));
+        }
+    }
+
+    // The command just applied, to everyone.
+    fn announce(&self, out: &mut Outbox) {
+        let record = self.state.last_event().unwrap().clone();
+        out.push((Recipient::Everyone, ServerMessage::Event { record }));
+    }
+}
+
+fn refuse(out: &mut Outbox, message: String) {
+    out.push((
+        Recipient::Sender,
+        ServerMessage::Error {
+            message,
+            error: None,
+        },
+    ));
+}
@@ -0,0 +1,187 @@
+// Playing a game at the terminal. Each prompt is for whoever has to act next;
+// what they type is a line of the script language with their own player
+// left off, so `buy`, `build st-james` and `mortgage boardwalk` all work, and
+// `P1 accept` speaks for someone else. Anything the engine refuses is
+// explained and asked again. Seats with a bot in them play themselves, and
+// what they do is printed as it happens. `board` draws the board.
+
+use monopoly::{Command, DoNothing, Effect, GameState, PendingDecision, PlayerId, Strategy};
+use std::io::{self, BufRead, Write};
+
+// Commands a bot may spend without the game moving on before DoNothing
+// answers for it, as in simulate.
+const BOT_TRIES: usize = 100;
+
+// The seats with bots in them, and how long the bot playing has been at the
+// decision in front of it.
+pub struct Bots {
+    seats: Vec<Option<Box<dyn Strategy>>>,
+    waiting: Option<(u32, Option<PendingDecision>)>,
+    tries: usize,
+}
+
+impl Bots {
+    // seats[n] decides for player n, where there is a bot.
+    pub fn new(seats: Vec<Option<Box<dyn Strategy>>>) -> Bots {
+        Bots {
+            seats,
+            waiting: None,
+            tries: 0,
+        }
+    }
+
+    // Makes the next move if it falls to a bot, and returns what followed;
+    // None when the game is waiting on a person.
+    pub fn play(&mut self, state: &mut GameState) -> Option<Vec<Effect>> {
+        let bot = self.seats.get_mut(state.to_act().0 as usize)?.as_mut()?;
+        let now = (state.turn_number(), state.pending().cloned());
+        if self.waiting.as_ref() != Some(&now) {
+            self.waiting = Some(now);
+            self.tries = 0;
+        }
+        self.tries += 1;
+        let command = match self.tries > BOT_TRIES {
+            true => state.ask(&mut DoNothing),
+            false => state.ask(&mut **bot),
+        };
+        match state.apply(command) {
+            Ok(effects) => Some(effects),
+            Err(_) => {
+                let fallback = state.ask(&mut DoNothing);
+                Some(state.apply(fallback).expect("DoNothing always applies"))
+            }
+        }
+    }
+}
+
+// Plays until someone wins or the players stop, with the bots playing their
+// own seats, and the board drawn before each prompt when something has
+// changed if `show_board`. Returns where to save the game, if the players
+// asked for it to be saved on the way out.
+pub fn run(
+    state: &mut GameState,
+    bots: &mut Bots,
+    show_board: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<Option<String>> {
+    let mut changed = true;
+    loop {
+        if let Some(PlayerId(winner)) = state.winner() {
+            writeln!(output, "Player {} wins!", winner)?;
+            return Ok(None);
+        }
+        if let Some(effects) = bots.play(state) {
+            show(state, effects, output)?;
+            changed = true;
+            continue;
+        }
+        let actor = state.to_act();
+        if changed && show_board {
+            write!(output, "{}", state.render_board())?;
+        }
+        if changed {
+            write!(output, "{}", state)?;
+        }
+        writeln!(output, "Player {} can: {}", actor.0, choices(state, actor))?;
+        write!(output, "P{}> ", actor.0)?;
+        output.flush()?;
+        let line = match read_line(input)? {
+            Some(line) => line,
+            None => return offer_save(input, output),
+        };
+        changed = false;
+        match line.as_str() {
+            "" => continue,
+            "quit" | "exit" => return offer_save(input, output),
+            "board" => {
+                write!(output, "{}", state.render_board())?;
+                continue;
+            }
+            _ => {}
+        }
+        let command = match parse(state, actor, &line) {
+            Ok(command) => command,
+            Err(message) => {
+                writeln!(output, "{}", message)?;
+                continue;
+            }
+        };
+        match state.apply(command) {
+            Ok(effects) => {
+                show(state, effects, output)?;
+                changed = true;
+            }
+            Err(error) => writeln!(output, "Can't do that: {}", error)?,
+        }
+    }
+}
+
+// The command just applied and what followed from it. The record is shown,
+// since a roll is logged with the dice it got.
+fn show(state: &GameState, effects: Vec<Effect>, output: &mut impl Write) -> io::Result<()> {
+    let record = state.last_event().unwrap();
+    writeln!(output, "{}", record.command.display_with(state))?;
+    for effect in effects {
+        writeln!(output, "  {:?}", effect)?;
+    }
+    Ok(())
+}
+
+// What the player could type, as they would type it.
+pub fn choices(state: &GameState, actor: PlayerId) -> String {
+    let prefix = format!("P{} ", actor.0);
+    let lines: Vec<String> = state
+        .legal_actions()
+        .iter()
+        .filter(|command| command.actor() == Some(actor))
+        .map(|command| {
+            let line = command.to_script_line(state.board());
+            line.strip_prefix(&prefix).unwrap_or(&line).to_string()
+        })
+        .collect();
+    lines.join(", ")
+}
+
+// A line as the player typed it, as the command it stands for.
+pub fn parse(state: &GameState, actor: PlayerId, line: &str) -> Result<Command, String> {
+    let named = line
+        .strip_prefix(['P', 'p'])
+        .and_then(|rest| rest.split_whitespace().next())
+        .is_some_and(|number| number.parse::<i8>().is_ok());
+    let mut script = match named {
+        true => line.to_string(),
+        false => format!("P{} {}", actor.0, line),
+    };
+    // A bare buy is for the square just landed on.
+    if let (["buy"], Some(PendingDecision::BuyOrDecline { property, .. })) = (
+        line.split_whitespace().collect::<Vec<_>>().as_slice(),
+        state.pending(),
+    ) {
+        script.push(' ');
+        script.push_str(&state.board().slug(property.0 as usize).unwrap());
+    }
+    match Command::parse_script(&script, state.board()) {
+        Ok(commands) if commands.len() == 1 => Ok(commands.into_iter().next().unwrap()),
+        Ok(_) => Err("one command at a time".to_string()),
+        Err(error) => Err(error.message),
+    }
+}
+
+// None at the end of the input.
+fn read_line(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    match input.read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line.trim().to_string())),
+    }
+}
+
+fn offer_save(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Option<String>> {
+    write!(
+        output,
+        "\nSave the game? Give a file name, or nothing to quit: "
+    )?;
+    output.flush()?;
+    Ok(read_line(input)?.filter(|path| !path.is_empty()))
+}
@@ -0,0 +1,90 @@
+// SplitMix64, as described in https://prng.di.unimi.it/splitmix64.c. It is not
+// cryptographically secure, but it is fast, has a single u64 of state, and is
+// fully determined by its seed, which is all a board game needs.
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    // A generator of its own for one use of a seed. Streams with different
+    // numbers share no draws, so taking more from one never shifts another.
+    pub fn derive(seed: u64, stream: u64) -> Rng {
+        let tag = Rng::new(stream).next_u64();
+        Rng::new(Rng::new(seed ^ tag).next_u64())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Where the generator is up to; Rng::new carries on from here.
+    #[cfg(feature = "protobuf")]
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    // Uniform in 0..bound, using rejection sampling to avoid modulo bias.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    // Fisher-Yates.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_produces_same_stream() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn derived_streams_differ() {
+        let mut dice = Rng::derive(42, 0);
+        let mut decks = Rng::derive(42, 1);
+        let mut plain = Rng::new(42);
+        let first = dice.next_u64();
+        assert_ne!(first, decks.next_u64());
+        assert_ne!(first, plain.next_u64());
+        assert_eq!(first, Rng::derive(42, 0).next_u64());
+    }
+
+    #[test]
+    fn below_stays_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(6) < 6);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// JSON Schemas for the JSON the engine reads and writes, for clients written
+// in other languages. Each is generated from the type itself, following its
+// serde attributes, so the schemas can't drift from what is sent.
+
+use crate::game::{Command, Effect, EventRecord, GameConfig, GameState};
+use crate::protocol::{ClientMessage, ServerMessage};
+use schemars::schema_for;
+use serde_json::Value;
+
+// The names schema knows: a command, a GameConfig, a whole game as saved, one
+// effect, one logged event, and the multiplayer messages each way.
+pub const SCHEMA_TYPES: [&str; 7] = [
+    "action",
+    "config",
+    "state",
+    "effect",
+    "event",
+    "client-message",
+    "server-message",
+];
+
+// The schema for the named type, as a JSON Schema 2020-12 document.
+pub fn schema(name: &str) -> Option<Value> {
+    let schema = match name {
+        "action" => schema_for!(Command),
+        "config" => schema_for!(GameConfig),
+        "state" => schema_for!(GameState),
+        "effect" => schema_for!(Effect),
+        "event" => schema_for!(EventRecord),
+        "client-message" => schema_for!(ClientMessage),
+        "server-message" => schema_for!(ServerMessage),
+        _ => return None,
+    };
+    Some(schema.to_value())
+}
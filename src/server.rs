@@ -0,0 +1,167 @@
+// Games played over HTTP, with JSON in and out, for frontends that would
+// rather not link the engine. Everything is kept in memory; each game has its
+// own lock, so moves in one game never wait on another.
+//
+//     POST /games                 a GameConfig     -> 201 {"id": 0}
+//     GET  /games/{id}            the state, whose move it is, the winner
+//                                 and the legal actions
+//     POST /games/{id}/actions    a Command        -> {"effects": [..]}
+//     GET  /games/{id}/events     the log, as EventRecords
+//
+// A command the engine refuses is a 400 with the GameError as "error" and
+// what it says as "message"; anything else that goes wrong has a message
+// alone.
+
+use crate::game::{Command, GameConfig, GameState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use tiny_http::{Header, Method, Request, Response};
+
+pub struct Server {
+    http: tiny_http::Server,
+    games: Mutex<HashMap<u64, Arc<Mutex<GameState>>>>,
+    next_id: AtomicU64,
+    stopping: AtomicBool,
+}
+
+// A response yet to be sent: the status and the JSON body.
+type Reply = (u16, Value);
+
+impl Server {
+    // Port 0 picks a free one; local_addr says which.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Server> {
+        let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(Server {
+            http,
+            games: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            stopping: AtomicBool::new(false),
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.http
+            .server_addr()
+            .to_ip()
+            .expect("bound to an IP address")
+    }
+
+    // Answers requests on `threads` threads until stop is called.
+    pub fn run(&self, threads: usize) {
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| {
+                    loop {
+                        match self.http.recv() {
+                            // A client that hangs up early is no concern
+                            // of ours.
+                            Ok(request) => drop(self.answer(request)),
+                            Err(_) if self.stopping.load(Ordering::SeqCst) => break,
+                            Err(_) => continue,
+                        }
+                    }
+                    // Pass the stop on to the next thread still waiting.
+                    self.http.unblock();
+                });
+            }
+        });
+    }
+
+    // Lets run return once the requests in hand are answered.
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        self.http.unblock();
+    }
+
+    fn answer(&self, mut request: Request) -> io::Result<()> {
+        let mut body = String::new();
+        let (status, value) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => self.route(request.method(), request.url(), &body),
+            Err(error) => (400, message(&error)),
+        };
+        let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        request.respond(response)
+    }
+
+    fn route(&self, method: &Method, url: &str, body: &str) -> Reply {
+        let path = url.split('?').next().unwrap_or_default();
+        let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+        match (method, parts.as_slice()) {
+            (Method::Post, ["games"]) => self.create(body),
+            (Method::Get, ["games", id]) => self.with_game(id, |state| (200, describe(state))),
+            (Method::Post, ["games", id, "actions"]) => match serde_json::from_str(body) {
+                Ok(command) => self.with_game(id, |state| apply(state, command)),
+                Err(error) => (400, message(&error)),
+            },
+            (Method::Get, ["games", id, "events"]) => {
+                self.with_game(id, |state| (200, json!(state.events())))
+            }
+            (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "actions" | "events"]) => {
+                (405, message(&format!("{} is not allowed here", method)))
+            }
+            _ => (404, message(&format!("nothing at {}", path))),
+        }
+    }
+
+    fn create(&self, body: &str) -> Reply {
+        let config: GameConfig = match serde_json::from_str(body) {
+            Ok(config) => config,
+            Err(error) => return (400, message(&error)),
+        };
+        let state = match GameState::new(config) {
+            Ok(state) => state,
+            Err(error) => return (400, json!({ "error": error, "message": error.to_string() })),
+        };
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.games().insert(id, Arc::new(Mutex::new(state)));
+        (201, json!({ "id": id }))
+    }
+
+    // Holds the game's own lock, and not the map's, while `f` runs.
+    fn with_game(&self, id: &str, f: impl FnOnce(&mut GameState) -> Reply) -> Reply {
+        let game = match id
+            .parse()
+            .ok()
+            .and_then(|id: u64| self.games().get(&id).cloned())
+        {
+            Some(game) => game,
+            None => return (404, message(&format!("no game {}", id))),
+        };
+        // A panic mid-command leaves the game as apply left it, which is no
+        // worse than a refused command.
+        let mut state = game.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut state)
+    }
+
+    fn games(&self) -> MutexGuard<'_, HashMap<u64, Arc<Mutex<GameState>>>> {
+        self.games.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn describe(state: &GameState) -> Value {
+    json!({
+        "state": state,
+        "to_act": state.to_act(),
+        "winner": state.winner(),
+        "legal_actions": state.legal_actions(),
+    })
+}
+
+fn apply(state: &mut GameState, command: Command) -> Reply {
+    match state.apply(command) {
+        Ok(effects) => (200, json!({ "effects": effects })),
+        Err(error) => (400, json!({ "error": error, "message": error.to_string() })),
+    }
+}
+
+fn message(error: &impl ToString) -> Value {
+    json!({ "message": error.to_string() })
+}
@@ -0,0 +1,96 @@
+// Playing full-screen: the same game as the prompt, drawn afresh after every
+// move instead of scrolling. Keys are read without blocking, so the bots play
+// on while nobody is typing; Esc, or `quit`, asks whether to save.
+
+use crate::repl::{self, Bots};
+use monopoly::{draw_game, GameState, PlayerId, PromptWidget};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::io;
+use std::time::Duration;
+
+// How long a bot's move stays on screen before the next.
+const BOT_PAUSE: Duration = Duration::from_millis(150);
+
+enum Mode {
+    Playing,
+    Saving, // asking for a file name
+}
+
+pub fn run(state: &mut GameState, bots: &mut Bots) -> io::Result<Option<String>> {
+    let mut terminal = ratatui::init();
+    let result = play(&mut terminal, state, bots);
+    ratatui::restore();
+    result
+}
+
+fn play(
+    terminal: &mut DefaultTerminal,
+    state: &mut GameState,
+    bots: &mut Bots,
+) -> io::Result<Option<String>> {
+    let mut mode = Mode::Playing;
+    let mut input = String::new();
+    let mut message = String::new();
+    loop {
+        let actor = state.to_act();
+        let prompt = match (&mode, state.winner()) {
+            (Mode::Saving, _) => "Save as (Enter alone to quit without saving): ".to_string(),
+            (Mode::Playing, Some(PlayerId(winner))) => {
+                format!("Player {} wins! Esc to leave. ", winner)
+            }
+            (Mode::Playing, None) => format!("P{}> ", actor.0),
+        };
+        if message.is_empty() {
+            if let (Mode::Playing, None) = (&mode, state.winner()) {
+                message = format!("Player {} can: {}", actor.0, repl::choices(state, actor));
+            }
+        }
+        terminal.draw(|frame| {
+            let prompt = PromptWidget {
+                prompt: &prompt,
+                input: &input,
+                message: &message,
+            };
+            draw_game(frame, state, prompt)
+        })?;
+
+        let bot_to_move = matches!(mode, Mode::Playing) && state.winner().is_none();
+        if bot_to_move && !event::poll(BOT_PAUSE)? && bots.play(state).is_some() {
+            message.clear();
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => match mode {
+                Mode::Playing => mode = Mode::Saving,
+                Mode::Saving => return Ok(None),
+            },
+            KeyCode::Enter => {
+                let line = std::mem::take(&mut input).trim().to_string();
+                match mode {
+                    Mode::Saving => return Ok(Some(line).filter(|path| !path.is_empty())),
+                    Mode::Playing if line == "quit" || line == "exit" => mode = Mode::Saving,
+                    Mode::Playing if line.is_empty() || state.winner().is_some() => {}
+                    Mode::Playing => {
+                        message = match repl::parse(state, actor, &line) {
+                            Ok(command) => match state.apply(command) {
+                                Ok(_) => String::new(),
+                                Err(error) => format!("Can't do that: {}", error),
+                            },
+                            Err(error) => error,
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
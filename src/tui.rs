@@ -0,0 +1,85 @@
+// Widgets for drawing a game full-screen with ratatui: the board on the left,
+// the players and the log on the right, and the line being typed at the
+// bottom. They draw from the state alone, so they can be rendered into a
+// buffer and checked without a terminal.
+
+use crate::game::GameState;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::Frame;
+
+// The board as render_board draws it.
+pub struct BoardWidget<'a>(pub &'a GameState);
+
+impl Widget for BoardWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.0.render_board()).render(area, buf);
+    }
+}
+
+// The summary the game displays as: whose turn, the bank, and every
+// player's cash, square and titles.
+pub struct PlayersWidget<'a>(pub &'a GameState);
+
+impl Widget for PlayersWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.0.to_string())
+            .block(Block::bordered().title(" Players "))
+            .render(area, buf);
+    }
+}
+
+// The latest commands, newest at the bottom, as many as fit.
+pub struct LogWidget<'a>(pub &'a GameState);
+
+impl Widget for LogWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.0;
+        let room = area.height.saturating_sub(2) as usize;
+        let records = state.events();
+        let lines: Vec<String> = records[records.len().saturating_sub(room)..]
+            .iter()
+            .map(|record| record.command.display_with(state).to_string())
+            .collect();
+        Paragraph::new(lines.join("\n"))
+            .block(Block::bordered().title(" Log "))
+            .render(area, buf);
+    }
+}
+
+// The line being typed, after a prompt for whoever is to act, with the
+// answer to the last line above it.
+pub struct PromptWidget<'a> {
+    pub prompt: &'a str,
+    pub input: &'a str,
+    pub message: &'a str,
+}
+
+impl Widget for PromptWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text = format!("{}\n{}{}", self.message, self.prompt, self.input);
+        Paragraph::new(text).render(area, buf);
+    }
+}
+
+// The whole screen, with the cursor at the end of the input.
+pub fn draw_game(frame: &mut Frame, state: &GameState, prompt: PromptWidget) {
+    let [main, bottom] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(frame.area());
+    let board_width = state.render_board().lines().next().map_or(0, str::len) as u16;
+    let [board, side] =
+        Layout::horizontal([Constraint::Length(board_width), Constraint::Min(0)]).areas(main);
+    let players = 3 + state.to_string().lines().count() as u16;
+    let [players_area, log] =
+        Layout::vertical([Constraint::Length(players), Constraint::Min(0)]).areas(side);
+    let cursor = (
+        bottom.x + (prompt.prompt.len() + prompt.input.chars().count()) as u16,
+        bottom.y + 1,
+    );
+    frame.render_widget(BoardWidget(state), board);
+    frame.render_widget(PlayersWidget(state), players_area);
+    frame.render_widget(LogWidget(state), log);
+    frame.render_widget(prompt, bottom);
+    frame.set_cursor_position(cursor);
+}
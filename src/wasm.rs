@@ -0,0 +1,103 @@
+// The engine for JavaScript, through wasm-bindgen. Everything goes in and
+// comes out as JSON in the same shapes as the save files and the HTTP API,
+// so a page can hold on to plain objects:
+//
+//     const game = new Game('{"players": 2, "seed": 7}');
+//     JSON.parse(game.legalActions());
+//     JSON.parse(game.apply('{"Roll": 0}'));   // {"effects": [..]}
+//     JSON.parse(game.playBot("greedy"));       // {"command": .., "effects": [..]}
+//
+// A refused command is answered with {"error": .., "message": ..} rather
+// than thrown. The seed in the config is the only source of chance, and no
+// clock is read unless one is set, so a game plays the same in the browser
+// as anywhere else.
+//
+// Built with wasm-pack, and tested in node with
+//
+//     wasm-pack test --node --features wasm -- --test wasm
+
+use crate::game::{seated, strategy_named, Command, GameConfig, GameState, Strategy};
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    state: GameState,
+    bots: Vec<Option<(String, Box<dyn Strategy>)>>, // by seat, once asked for
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    // From a GameConfig; fields left out take their defaults.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &str) -> Result<WasmGame, String> {
+        let config: GameConfig = serde_json::from_str(config).map_err(|error| error.to_string())?;
+        let state = GameState::new(config).map_err(|error| error.to_string())?;
+        let bots = (0..state.config().players).map(|_| None).collect();
+        Ok(WasmGame { state, bots })
+    }
+
+    pub fn state(&self) -> String {
+        serde_json::to_string(&self.state).expect("games always serialize")
+    }
+
+    #[wasm_bindgen(js_name = legalActions)]
+    pub fn legal_actions(&self) -> String {
+        json!(self.state.legal_actions()).to_string()
+    }
+
+    // The seat the game is waiting on.
+    #[wasm_bindgen(js_name = toAct)]
+    pub fn to_act(&self) -> i8 {
+        self.state.to_act().0
+    }
+
+    pub fn winner(&self) -> Option<i8> {
+        self.state.winner().map(|winner| winner.0)
+    }
+
+    pub fn apply(&mut self, command: &str) -> String {
+        match serde_json::from_str(command) {
+            Ok(command) => self.applied(command),
+            Err(error) => json!({ "message": error.to_string() }),
+        }
+        .to_string()
+    }
+
+    // Lets the named strategy decide for whoever is to act, and applies what
+    // it decides. Each seat keeps its bot from one call to the next, seeded
+    // as play_seeded would seat it; naming another strategy replaces it. An
+    // answer the engine refuses comes back like any other refusal.
+    #[wasm_bindgen(js_name = playBot)]
+    pub fn play_bot(&mut self, strategy: &str) -> String {
+        let factory = match strategy_named(strategy) {
+            Some(factory) => factory,
+            None => {
+                return json!({ "message": format!("no strategy called {}", strategy) }).to_string()
+            }
+        };
+        let seat = self.state.to_act().0 as usize;
+        let players = self.state.config().players;
+        let bot = match &mut self.bots[seat] {
+            Some((name, bot)) if name == strategy => bot,
+            place => {
+                let bot =
+                    seated(&vec![factory; players], self.state.config().seed).swap_remove(seat);
+                &mut place.insert((strategy.to_string(), bot)).1
+            }
+        };
+        let command = self.state.ask(&mut **bot);
+        let mut reply = self.applied(command.clone());
+        reply["command"] = json!(command);
+        reply.to_string()
+    }
+}
+
+impl WasmGame {
+    fn applied(&mut self, command: Command) -> Value {
+        match self.state.apply(command) {
+            Ok(effects) => json!({ "effects": effects }),
+            Err(error) => json!({ "error": error, "message": error.to_string() }),
+        }
+    }
+}
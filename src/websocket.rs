@@ -0,0 +1,111 @@
+// A Table served over WebSockets, one JSON message to a text frame. The table
+// lives in a task of its own; each connection's task passes on what its
+// client sends and writes out what the table has for it, so the table only
+// ever hears from one client at a time.
+
+use crate::game::PlayerId;
+use crate::protocol::{ClientMessage, Recipient, ServerMessage, Table};
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::io;
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_tungstenite::tungstenite::Message;
+
+// How often seats left empty are checked for a bot to take over.
+const TICK: Duration = Duration::from_millis(100);
+
+enum Input {
+    Connected(u64, UnboundedSender<ServerMessage>),
+    Message(u64, ClientMessage),
+    Closed(u64),
+}
+
+// Plays the table with whoever connects, until accepting fails.
+pub async fn serve_table(listener: TcpListener, table: Table) -> io::Result<()> {
+    let (inputs, received) = mpsc::unbounded_channel();
+    tokio::spawn(run_table(table, received));
+    let mut next_id = 0;
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(connection(stream, next_id, inputs.clone()));
+        next_id += 1;
+    }
+}
+
+async fn run_table(mut table: Table, mut received: UnboundedReceiver<Input>) {
+    // Each connection's seat, if it has one, and where its messages go.
+    let mut clients: HashMap<u64, (Option<PlayerId>, UnboundedSender<ServerMessage>)> =
+        HashMap::new();
+    let mut ticks = tokio::time::interval(TICK);
+    loop {
+        let (from, out) = tokio::select! {
+            input = received.recv() => match input {
+                Some(Input::Connected(id, sender)) => {
+                    clients.insert(id, (None, sender));
+                    continue;
+                }
+                Some(Input::Message(id, message)) => match clients.get_mut(&id) {
+                    Some((seat, _)) => (Some(id), table.handle(seat, message)),
+                    None => continue,
+                },
+                Some(Input::Closed(id)) => {
+                    if let Some((Some(seat), _)) = clients.remove(&id) {
+                        table.leave(seat, Instant::now());
+                    }
+                    continue;
+                }
+                None => return,
+            },
+            _ = ticks.tick() => (None, table.tick(Instant::now())),
+        };
+        for (recipient, message) in out {
+            for (id, (seat, sender)) in &clients {
+                let wanted = match recipient {
+                    Recipient::Sender => from == Some(*id),
+                    Recipient::Seat(to) => *seat == Some(to),
+                    Recipient::Everyone => true,
+                };
+                if wanted {
+                    // A client that has gone is told nothing more.
+                    let _ = sender.send(message.clone());
+                }
+            }
+        }
+    }
+}
+
+async fn connection(stream: TcpStream, id: u64, inputs: UnboundedSender<Input>) {
+    let socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(_) => return,
+    };
+    let (mut write, mut read) = socket.split();
+    let (sender, mut outgoing) = mpsc::unbounded_channel();
+    let _ = inputs.send(Input::Connected(id, sender.clone()));
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            let text = serde_json::to_string(&message).expect("messages always serialize");
+            if write.send(Message::text(text)).await.is_err() {
+                break;
+            }
+        }
+    });
+    while let Some(Ok(frame)) = read.next().await {
+        let text = match frame {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        match serde_json::from_str(&text) {
+            Ok(message) => drop(inputs.send(Input::Message(id, message))),
+            Err(error) => drop(sender.send(ServerMessage::Error {
+                message: error.to_string(),
+                error: None,
+            })),
+        }
+    }
+    let _ = inputs.send(Input::Closed(id));
+    writer.abort();
+}
@@ -0,0 +1,31 @@
+use monopoly::{Command, GameConfig, GameState, PlayerId, PropertyId, RollResult};
+
+#[test]
+fn applied_leaves_the_original_untouched() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let original = GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    let before = original.clone();
+    let child = original
+        .applied(Command::RollDice(p0, RollResult(1, 2)))
+        .and_then(|state| state.applied(Command::BuyProperty(p0, PropertyId(3))))
+        .and_then(|state| state.applied(Command::EndTurn(p0)))
+        .and_then(|state| state.applied(Command::RollDice(p1, RollResult(2, 3))))
+        .unwrap();
+    assert_eq!(child.owner_of(PropertyId(3)), Some(p0));
+    assert_eq!(child.position(p1), Ok(5));
+    assert_eq!(child.events().len(), 4);
+    assert!(original.eq_including_history(&before));
+    assert_eq!(original.owner_of(PropertyId(3)), None);
+    assert_eq!(original.position(p0), Ok(0));
+}
+
+#[test]
+fn applied_reports_errors_without_a_new_state() {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    assert!(state.applied(Command::EndTurn(PlayerId(1))).is_err());
+    assert!(state.events().is_empty());
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use monopoly::{
+    BoardBuilder, ColorGroup, Command, Effect, GameConfig, GameState, Money, PlayerId, RollResult,
+};
+
+fn street(board: BoardBuilder, name: &'static str, color: ColorGroup, price: i32) -> BoardBuilder {
+    let rents = [2, 10, 30, 90, 160, 250].map(|rent| Money(rent * price / 60));
+    board.street(name, color, Money(price), rents, Money(50))
+}
+
+// Twelve squares a side, with Jail four squares further on than usual and a
+// Go To Jail a roll of seven from GO.
+fn wide_board() -> BoardBuilder {
+    use ColorGroup::*;
+    let board = BoardBuilder::new().go();
+    let board = street(board, "Ash Row", Brown, 60).community_chest();
+    let board = street(board, "Birch Row", Brown, 60)
+        .tax("Income Tax", Money(200))
+        .railroad("North Line");
+    let board = street(board, "Cedar Lane", LightBlue, 100).go_to_jail();
+    let board = street(board, "Dove Lane", LightBlue, 100);
+    let board = street(board, "Elm Lane", LightBlue, 120).free_parking();
+    let board = street(board, "Fir Street", Pink, 140).utility("Power Station");
+    let board = street(board, "Gorse Street", Pink, 140).jail();
+    let board = street(board, "Hazel Street", Pink, 160).railroad("East Line");
+    let board = street(board, "Ivy Road", Orange, 180).community_chest();
+    let board = street(board, "Juniper Road", Orange, 180);
+    let board = street(board, "Kale Road", Orange, 200).chance();
+    let board = street(board, "Larch Avenue", Red, 220);
+    let board = street(board, "Maple Avenue", Red, 220).free_parking();
+    let board = street(board, "Nettle Avenue", Red, 240).railroad("South Line");
+    let board = street(board, "Oak Square", Yellow, 260);
+    let board = street(board, "Pine Square", Yellow, 260).utility("Water Tower");
+    let board = street(board, "Quince Square", Yellow, 280).chance();
+    let board = street(board, "Rowan Park", Green, 300);
+    let board = street(board, "Sorrel Park", Green, 300).community_chest();
+    let board = street(board, "Thyme Park", Green, 320)
+        .chance()
+        .railroad("West Line")
+        .chance();
+    let board = street(board, "Upper Heights", DarkBlue, 350).tax("Luxury Tax", Money(75));
+    let board = street(board, "Vale Heights", DarkBlue, 400);
+    board
+        .free_parking()
+        .community_chest()
+        .chance()
+        .free_parking()
+        .go_to_jail()
+        .community_chest()
+}
+
+fn wide_game(seed: u64) -> GameState {
+    let config = GameConfig {
+        players: 4,
+        seed,
+        ..GameConfig::default()
+    };
+    GameState::on_board(config, wide_board().build().unwrap()).unwrap()
+}
+
+#[test]
+fn a_wide_board_is_laid_out_as_built() {
+    let state = wide_game(0);
+    let board = state.board();
+    assert_eq!(board.len(), 48);
+    assert_eq!((board.go(), board.jail()), (0, 14));
+    assert_eq!(board.name(47), Some("Community Chest"));
+    assert_eq!(board.name(41), Some("Vale Heights"));
+    assert_eq!(state.color_group_members(ColorGroup::DarkBlue).len(), 2);
+    assert!(state.render_board().contains("|Vale H  |"));
+}
+
+#[test]
+fn jail_is_wherever_the_board_puts_it() {
+    let mut state = wide_game(0);
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(3, 4)))
+        .unwrap();
+    let player = state.player(PlayerId(0)).unwrap();
+    assert!(player.in_jail());
+    assert_eq!(player.position(), 14);
+    assert!(state.render_board().contains("in H"));
+}
+
+#[test]
+fn twenty_turns_play_out_on_a_wide_board() {
+    for seed in 0..3 {
+        let mut state = wide_game(seed);
+        common::play_turns(&mut state, 20);
+        assert!(state.turn_number() > 20 || state.winner().is_some());
+        state.check_invariants().unwrap();
+        let effects: Vec<&Effect> = state
+            .events()
+            .iter()
+            .flat_map(|record| &record.effects)
+            .collect();
+        // Round the far side and past GO, which is further away than on
+        // the standard board.
+        assert!(effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Moved { to, .. } if *to >= 40)));
+        assert!(effects
+            .iter()
+            .all(|effect| !matches!(effect, Effect::Moved { to, .. } if *to >= 48)));
+        assert!(effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::PassedGo(_))));
+        for player in state.players() {
+            assert!(!player.in_jail() || player.position() == 14);
+        }
+
+        // Undo rebuilds the game on the same board.
+        let mut undone = state.clone();
+        let last = undone.undo().unwrap();
+        undone.apply(last).unwrap();
+        common::assert_same(&undone, &state);
+    }
+}
+
+#[test]
+fn the_board_is_part_of_the_position() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash_of = |state: &GameState| {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+        hasher.finish()
+    };
+    let wide = wide_game(0);
+    assert_eq!(wide, wide_game(0));
+    assert_eq!(hash_of(&wide), hash_of(&wide_game(0)));
+    // The same config on the standard board.
+    let standard = GameState::new(wide.config().clone()).unwrap();
+    assert_ne!(wide, standard);
+    assert_ne!(hash_of(&wide), hash_of(&standard));
+    assert_eq!(wide.diff(&standard).to_string(), "board differs\n");
+}
@@ -0,0 +1,388 @@
+use monopoly::{
+    ColorGroup, Command, Effect, GameConfig, GameError, GameState, GameStateBuilder, Money,
+    PlayerId, PropertyId, RollResult, RuleViolation, BANK_HOUSES,
+};
+
+const P0: PlayerId = PlayerId(0);
+const P1: PlayerId = PlayerId(1);
+const MEDITERRANEAN: PropertyId = PropertyId(1);
+const BALTIC: PropertyId = PropertyId(3);
+
+fn brown_hotels() -> GameStateBuilder {
+    GameStateBuilder::default()
+        .player(Money(100), 0)
+        .player(Money(1500), 0)
+        .owns(P0, MEDITERRANEAN, 5)
+        .owns(P0, BALTIC, 5)
+}
+
+#[test]
+fn selling_a_hotel_during_a_shortage_pays_for_missing_houses() {
+    let mut state = brown_hotels().bank_houses(2).build().unwrap();
+    state.apply(Command::SellHotel(P0, BALTIC)).unwrap();
+    assert_eq!(state.houses_remaining(), Some(0));
+    // Half the hotel, plus half of each of the two houses the bank lacked.
+    assert_eq!(state.cash(P0), Ok(Money(100 + 25 + 2 * 25)));
+    let baltic = state.buildings_on(ColorGroup::Brown)[1];
+    assert_eq!(baltic, (BALTIC, 2, false));
+}
+
+#[test]
+fn building_needs_the_whole_color_group() {
+    let (st_james, tennessee, new_york) = (PropertyId(16), PropertyId(18), PropertyId(19));
+    let builder = GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .owns(P0, st_james, 0)
+        .owns(P0, tennessee, 0);
+    let mut unowned = builder.clone().build().unwrap();
+    assert_eq!(
+        unowned.apply(Command::BuyHouse(P0, st_james)),
+        Err(GameError::RuleViolation(
+            RuleViolation::IncompleteColorGroup {
+                missing: vec![new_york],
+                held_by: vec![],
+            }
+        ))
+    );
+
+    let mut held = builder.owns(PlayerId(2), new_york, 0).build().unwrap();
+    let refused = held.apply(Command::BuyHouse(P0, tennessee)).unwrap_err();
+    assert_eq!(
+        refused,
+        GameError::RuleViolation(RuleViolation::IncompleteColorGroup {
+            missing: vec![new_york],
+            held_by: vec![(new_york, PlayerId(2))],
+        })
+    );
+    assert_eq!(
+        refused.to_string(),
+        "building requires owning every lot in the color group; missing square 19 (player 2's)"
+    );
+    assert_eq!(held.cash(P0), Ok(Money(1500)));
+    assert!(!held
+        .legal_actions()
+        .contains(&Command::BuyHouse(P0, tennessee)));
+}
+
+#[test]
+fn an_empty_bank_stops_building() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .owns(P0, MEDITERRANEAN, 0)
+        .owns(P0, BALTIC, 0)
+        .bank_houses(0)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        state.apply(Command::BuyHouse(P0, MEDITERRANEAN)),
+        Err(GameError::IllegalAction(_))
+    ));
+}
+
+#[test]
+fn a_mortgage_in_the_group_stops_building() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .owns(P0, MEDITERRANEAN, 0)
+        .owns(P0, BALTIC, 0)
+        .mortgaged(BALTIC)
+        .build()
+        .unwrap();
+    for lot in [MEDITERRANEAN, BALTIC] {
+        assert_eq!(
+            state.apply(Command::BuyHouse(P0, lot)),
+            Err(GameError::RuleViolation(RuleViolation::MortgagedInGroup {
+                mortgaged: vec![BALTIC]
+            }))
+        );
+    }
+    state
+        .apply(Command::UnmortgageProperty(P0, BALTIC))
+        .unwrap();
+    state.apply(Command::BuyHouse(P0, MEDITERRANEAN)).unwrap();
+    assert_eq!(state.check_invariants(), Ok(()));
+}
+
+#[test]
+fn unmortgaging_the_lot_unblocks_the_group_at_once() {
+    let (st_james, tennessee, new_york) = (PropertyId(16), PropertyId(18), PropertyId(19));
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .owns(P0, st_james, 0)
+        .owns(P0, tennessee, 0)
+        .owns(P0, new_york, 0)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::MortgageProperty(P0, new_york))
+        .unwrap();
+    let refused = state.apply(Command::BuyHouse(P0, tennessee)).unwrap_err();
+    assert_eq!(
+        refused.to_string(),
+        "every lot in the color group must be unmortgaged before building; square 19 is mortgaged"
+    );
+    for command in [
+        Command::BuyHouse(P0, new_york),
+        Command::BuyHotel(P0, st_james),
+        Command::BuyHouse(P0, tennessee),
+    ] {
+        assert_eq!(
+            state.apply(command),
+            Err(GameError::RuleViolation(RuleViolation::MortgagedInGroup {
+                mortgaged: vec![new_york]
+            }))
+        );
+    }
+    state
+        .apply(Command::UnmortgageProperty(P0, new_york))
+        .unwrap();
+    state.apply(Command::BuyHouse(P0, new_york)).unwrap();
+    assert_eq!(
+        state.buildings_on(ColorGroup::Orange)[2],
+        (new_york, 1, false)
+    );
+}
+
+#[test]
+fn bankruptcy_to_a_player_sells_buildings_for_the_creditor() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(100), 35)
+        .player(Money(1500), 0)
+        .owns(P0, MEDITERRANEAN, 5)
+        .owns(P0, BALTIC, 5)
+        .owns(P1, PropertyId(37), 5)
+        .owns(P1, PropertyId(39), 5)
+        .build()
+        .unwrap();
+    assert_eq!(state.hotels_remaining(), Some(8));
+    state
+        .apply(Command::RollDice(P0, RollResult(1, 3)))
+        .unwrap();
+    state.apply(Command::DeclareBankruptcy(P0)).unwrap();
+    assert_eq!(state.hotels_remaining(), Some(10));
+    // Each brown hotel cost $250 in all and goes back for half.
+    assert_eq!(state.cash(P1), Ok(Money(1500 + 100 + 2 * 125)));
+    assert_eq!(state.owner_of(BALTIC), Some(P1));
+    assert_eq!(state.buildings_on(ColorGroup::Brown)[1], (BALTIC, 0, false));
+}
+
+// The houses are sold back before anything is handed over, so their price
+// goes to the creditor along with the rest of the debtor's cash.
+#[test]
+fn bankruptcy_sells_houses_back_before_paying_the_creditor() {
+    let light_blues = [PropertyId(6), PropertyId(8), PropertyId(9)];
+    let mut builder = GameStateBuilder::default()
+        .player(Money(100), 35)
+        .player(Money(1500), 0)
+        .owns(P1, PropertyId(37), 5)
+        .owns(P1, PropertyId(39), 5);
+    for lot in light_blues {
+        builder = builder.owns(P0, lot, 1);
+    }
+    let mut state = builder.build().unwrap();
+    let houses = state.houses_remaining().unwrap();
+    state
+        .apply(Command::RollDice(P0, RollResult(1, 3)))
+        .unwrap();
+    let effects = state.apply(Command::DeclareBankruptcy(P0)).unwrap();
+    assert_eq!(state.houses_remaining(), Some(houses + 3));
+    // Each house cost $50 and goes back for $25.
+    assert_eq!(state.cash(P1), Ok(Money(1500 + 100 + 3 * 25)));
+    let mut sales = Vec::new();
+    for lot in light_blues {
+        sales.push(Effect::SoldBuildings {
+            player: P0,
+            property: lot,
+            houses: 1,
+            hotel: false,
+        });
+        sales.push(Effect::Received {
+            player: P0,
+            amount: Money(25),
+        });
+    }
+    assert_eq!(effects[..6], sales[..]);
+    assert_eq!(
+        effects[6],
+        Effect::Paid {
+            from: P0,
+            to: Some(P1),
+            amount: Money(175)
+        }
+    );
+    for lot in light_blues {
+        assert_eq!(state.owner_of(lot), Some(P1));
+    }
+    assert_eq!(
+        state.buildings_on(ColorGroup::LightBlue)[0],
+        (light_blues[0], 0, false)
+    );
+}
+
+#[test]
+fn impossible_positions_are_rejected() {
+    let too_many_houses = GameStateBuilder::default()
+        .owns(P0, MEDITERRANEAN, 4)
+        .owns(P0, BALTIC, 4)
+        .bank_houses(25);
+    assert!(matches!(
+        too_many_houses.build(),
+        Err(GameError::InvalidConfig(_))
+    ));
+    let uneven = GameStateBuilder::default()
+        .owns(P0, MEDITERRANEAN, 3)
+        .owns(P0, BALTIC, 1);
+    assert!(uneven.build().is_err());
+    let without_monopoly = GameStateBuilder::default()
+        .owns(P0, MEDITERRANEAN, 1)
+        .owns(P1, BALTIC, 1);
+    assert!(without_monopoly.build().is_err());
+    let hotel_on_a_railroad = GameStateBuilder::default().owns(P0, PropertyId(5), 5);
+    assert!(hotel_on_a_railroad.build().is_err());
+}
+
+// Player 0 holds every street, with all the cash they could want.
+fn every_street(unlimited_buildings: bool) -> (GameState, Vec<PropertyId>) {
+    let mut config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    config.rules.unlimited_buildings = unlimited_buildings;
+    let mut builder = GameStateBuilder::new(config)
+        .player(Money(100_000), 0)
+        .player(Money(1500), 0);
+    let board = builder.clone().build().unwrap();
+    let streets: Vec<PropertyId> = ColorGroup::ALL
+        .iter()
+        .flat_map(|color| board.color_group_members(*color).to_vec())
+        .collect();
+    for street in &streets {
+        builder = builder.owns(P0, *street, 0);
+    }
+    (builder.build().unwrap(), streets)
+}
+
+#[test]
+fn an_unlimited_bank_never_runs_short() {
+    let (mut state, streets) = every_street(true);
+    assert_eq!(state.houses_remaining(), None);
+    for _ in 0..4 {
+        for street in &streets {
+            state.apply(Command::BuyHouse(P0, *street)).unwrap();
+        }
+    }
+    let houses: u8 = ColorGroup::ALL
+        .iter()
+        .flat_map(|color| state.buildings_on(*color))
+        .map(|(_, houses, _)| houses)
+        .sum();
+    assert_eq!(houses, 88);
+    for street in &streets {
+        state.apply(Command::BuyHotel(P0, *street)).unwrap();
+    }
+    assert_eq!(state.hotels_remaining(), None);
+    assert_eq!(state.check_invariants(), Ok(()));
+    // The other rules still hold.
+    assert!(state.apply(Command::BuyHouse(P0, streets[0])).is_err());
+    state.apply(Command::SellHotel(P0, streets[0])).unwrap();
+    assert_eq!(
+        state.buildings_on(ColorGroup::Brown)[0],
+        (streets[0], 4, false)
+    );
+    assert!(state.apply(Command::SellHouse(P0, streets[0])).is_err());
+    assert!(state
+        .to_string()
+        .contains("Bank: unlimited houses and hotels\n"));
+}
+
+#[test]
+fn a_limited_bank_runs_out_at_32_houses() {
+    let (mut state, streets) = every_street(false);
+    let mut built = 0;
+    for _ in 0..4 {
+        for street in &streets {
+            if state.apply(Command::BuyHouse(P0, *street)).is_ok() {
+                built += 1;
+            }
+        }
+    }
+    assert_eq!(built, BANK_HOUSES);
+    assert_eq!(state.houses_remaining(), Some(0));
+}
+
+// Each lot buildable_properties lists takes a building at the cost it gives,
+// and each of the player's other streets refuses both kinds.
+fn assert_agrees(state: &GameState, player: PlayerId) -> Vec<(PropertyId, Money)> {
+    let listed = state.buildable_properties(player);
+    for (property, _) in state.properties_owned_by(player) {
+        let applied: Vec<Money> = vec![
+            Command::BuyHouse(player, property),
+            Command::BuyHotel(player, property),
+        ]
+        .into_iter()
+        .filter_map(|command| {
+            let mut tried = state.clone();
+            tried.apply(command).ok()?;
+            Some(Money(
+                state.cash(player).unwrap().0 - tried.cash(player).unwrap().0,
+            ))
+        })
+        .collect();
+        let cost = listed
+            .iter()
+            .find(|(lot, _)| *lot == property)
+            .map(|(_, cost)| *cost);
+        assert_eq!(
+            applied,
+            cost.into_iter().collect::<Vec<_>>(),
+            "{:?}",
+            property
+        );
+    }
+    listed
+}
+
+#[test]
+fn buildable_properties_agree_with_what_applies() {
+    let light_blues = [PropertyId(6), PropertyId(8), PropertyId(9)];
+    // Oriental is a house ahead and the oranges aren't all held; the dark
+    // blues have a mortgage.
+    let holding = |cash| {
+        GameStateBuilder::default()
+            .player(Money(cash), 0)
+            .player(Money(1500), 0)
+            .owns(P0, MEDITERRANEAN, 5)
+            .owns(P0, BALTIC, 4)
+            .owns(P0, light_blues[0], 1)
+            .owns(P0, light_blues[1], 0)
+            .owns(P0, light_blues[2], 0)
+            .owns(P0, PropertyId(16), 0)
+            .owns(P0, PropertyId(18), 0)
+            .owns(P1, PropertyId(19), 0)
+            .owns(P1, PropertyId(37), 0)
+            .owns(P1, PropertyId(39), 0)
+            .mortgaged(PropertyId(39))
+    };
+    let state = holding(1500).build().unwrap();
+    assert_eq!(
+        assert_agrees(&state, P0),
+        [
+            (BALTIC, Money(50)),
+            (light_blues[1], Money(50)),
+            (light_blues[2], Money(50))
+        ]
+    );
+    assert_eq!(assert_agrees(&state, P1), []);
+
+    let no_houses = holding(1500).bank_houses(0).build().unwrap();
+    assert_eq!(assert_agrees(&no_houses, P0), [(BALTIC, Money(50))]);
+    let empty = holding(1500).bank_houses(0).bank_hotels(0).build().unwrap();
+    assert_eq!(assert_agrees(&empty, P0), []);
+    let broke = holding(49).build().unwrap();
+    assert_eq!(assert_agrees(&broke, P0), []);
+}
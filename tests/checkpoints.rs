@@ -0,0 +1,58 @@
+mod common;
+
+use monopoly::{Command, GameState, Money, PlayerId, PropertyId};
+
+#[test]
+fn rollback_restores_the_position_after_divergent_play() {
+    let (config, actions) = common::scripted_game();
+    let (before, after) = actions.split_at(9);
+    let mut state = GameState::play(config, before).unwrap();
+    let saved = state.clone();
+    let checkpoint = state.checkpoint();
+    // Player 0 is on Kentucky Ave with a roll still owed.
+    state.apply(Command::DeclineToBuy(PlayerId(0))).unwrap();
+    state
+        .apply(Command::PlaceBid(PlayerId(0), Money(10)))
+        .unwrap();
+    assert!(!state.eq_including_history(&saved));
+    state.rollback(checkpoint).unwrap();
+    common::assert_same(&state, &saved);
+    for action in after {
+        state.apply(action.clone()).unwrap();
+    }
+    assert_eq!(state.owner_of(PropertyId(21)), Some(PlayerId(0)));
+}
+
+#[test]
+fn rolling_back_invalidates_later_checkpoints() {
+    let (config, actions) = common::scripted_game();
+    let mut state = GameState::new(config).unwrap();
+    let first = state.checkpoint();
+    for action in &actions[..3] {
+        state.apply(action.clone()).unwrap();
+    }
+    let second = state.checkpoint();
+    for action in &actions[3..6] {
+        state.apply(action.clone()).unwrap();
+    }
+    let third = state.checkpoint();
+    state.rollback(second).unwrap();
+    assert_eq!(state.commands(), &actions[..3]);
+    assert!(state.rollback(third).is_err());
+    state.rollback(first).unwrap();
+    assert!(state.events().is_empty());
+    assert!(state.rollback(second).is_err());
+    // The earliest checkpoint survives being returned to.
+    state.apply(actions[0].clone()).unwrap();
+    state.rollback(first).unwrap();
+    assert!(state.events().is_empty());
+}
+
+#[test]
+fn undo_drops_checkpoints_taken_after_the_undone_event() {
+    let (config, actions) = common::scripted_game();
+    let mut state = GameState::play(config, &actions[..2]).unwrap();
+    let checkpoint = state.checkpoint();
+    state.undo().unwrap();
+    assert!(state.rollback(checkpoint).is_err());
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{BoardEdition, Effect, GameConfig, GameLog, GameState, PlayerId, PropertyId};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command as Process, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Process::new(env!("CARGO_BIN_EXE_monopoly"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("monopoly-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn write_log(name: &str, log: &GameLog) -> PathBuf {
+    let path = scratch(name);
+    fs::write(&path, log.to_json().unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn editions_go_by_their_names() {
+    assert_eq!(
+        BoardEdition::named("standard"),
+        Some(BoardEdition::Standard)
+    );
+    assert_eq!(BoardEdition::named("german"), Some(BoardEdition::German));
+    assert_eq!(BoardEdition::named("deluxe"), None);
+}
+
+#[test]
+fn play_takes_the_house_rules() {
+    let path = scratch("play.json");
+    // Under seed 2 the first roll is 2+3, onto Reading Railroad.
+    let input = format!("roll\nbuy\nend\nquit\n{}\n", path.display());
+    let args = [
+        "play",
+        "--seed",
+        "2",
+        "--players",
+        "2",
+        "--free-parking-pot",
+    ];
+    let output = stdout(&run(&args, &input));
+    assert!(output.contains("Player 0 bought Reading Railroad for $200\n"));
+    let saved = GameState::load_from(&path).unwrap();
+    assert!(saved.config().rules.free_parking_pot);
+    assert!(!saved.config().rules.double_salary_on_go);
+    assert_eq!(saved.owner_of(PropertyId(5)), Some(PlayerId(0)));
+}
+
+#[test]
+fn a_new_game_takes_the_flags() {
+    let path = scratch("flags.json");
+    let path_arg = path.to_str().unwrap();
+    let args = [
+        "--edition",
+        "german",
+        "--players",
+        "2",
+        "--seed",
+        "7",
+        "--no-auctions",
+        "--save",
+        path_arg,
+    ];
+    let output = stdout(&run(&args, ""));
+    assert!(output.contains("Player 0 (top hat): $1500, on Turmstraße\n"));
+    assert!(!output.contains("Player 2"), "{}", output);
+    let saved = GameState::load_from(&path).unwrap();
+    assert_eq!(saved.config().edition, BoardEdition::German);
+    assert_eq!(saved.config().players, 2);
+    assert_eq!(saved.config().seed, 7);
+    assert!(!saved.config().rules.auctions_enabled);
+}
+
+#[test]
+fn simulate_plays_the_strategies_named() {
+    let path = scratch("report.txt");
+    let args = [
+        "simulate",
+        "--games",
+        "10",
+        "--strategies",
+        "greedy,nothing,random",
+        "--double-salary-on-go",
+        "--heatmap",
+        "--output",
+        path.to_str().unwrap(),
+    ];
+    assert_eq!(stdout(&run(&args, "")), "");
+    let report = fs::read_to_string(&path).unwrap();
+    assert!(report.starts_with("10 games, "));
+    assert!(report.contains("\nMost landed on:\n 1. "));
+    for seat in ["0     greedy", "1     nothing", "2     random"] {
+        assert!(report.contains(seat), "{}", report);
+    }
+
+    let output = run(&["simulate", "--strategies", "chess"], "");
+    assert!(!output.status.success());
+    let output = run(&["simulate", "--edition", "deluxe"], "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no edition called deluxe"));
+}
+
+#[test]
+fn replay_steps_through_a_log() {
+    let log = write_log("replay.json", &common::played_game(1, 30).log());
+    let output = stdout(&run(
+        &["replay", log.to_str().unwrap(), "--every", "10"],
+        "",
+    ));
+    assert!(output.starts_with("1. Player 0 rolled "));
+    assert!(output.contains("\n30. "));
+}
+
+#[test]
+fn validate_finds_the_first_event_that_does_not_stand() {
+    let mut log = common::played_game(1, 40).log();
+    let path = write_log("valid.json", &log);
+    let output = stdout(&run(&["validate", path.to_str().unwrap()], ""));
+    assert_eq!(output, "OK: 40 events\n");
+
+    log.records[25].effects = vec![Effect::WentBankrupt(PlayerId(0))];
+    let path = write_log("invalid.json", &log);
+    let output = run(&["validate", path.to_str().unwrap()], "");
+    assert!(!output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains(": event 25 was logged as [WentBankrupt(PlayerId(0))] but now does "));
+
+    let config = GameConfig {
+        players: 9,
+        ..GameConfig::default()
+    };
+    let path = write_log(
+        "unplayable.json",
+        &GameLog {
+            config,
+            records: vec![],
+        },
+    );
+    let output = run(&["validate", path.to_str().unwrap()], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("cannot start the game"));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use monopoly::{Clock, GameConfig, GameState, SharedClock};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Reads out the instants it was given, in order.
+struct ScriptedClock(Mutex<VecDeque<u64>>);
+
+impl Clock for ScriptedClock {
+    fn now(&self) -> u64 {
+        self.0.lock().unwrap().pop_front().expect("out of instants")
+    }
+}
+
+fn scripted(instants: &[u64]) -> SharedClock {
+    SharedClock::new(ScriptedClock(Mutex::new(
+        instants.iter().copied().collect(),
+    )))
+}
+
+#[test]
+fn events_carry_the_time_from_the_clock() {
+    let (config, actions) = common::scripted_game();
+    let config = GameConfig {
+        clock: scripted(&[1_000, 2_500, 2_600]),
+        ..config
+    };
+    let state = GameState::play(config, &actions[..3]).unwrap();
+    let times: Vec<_> = state.events().iter().map(|record| record.time).collect();
+    assert_eq!(times, [Some(1_000), Some(2_500), Some(2_600)]);
+}
+
+#[test]
+fn games_have_no_clock_by_default() {
+    let (config, actions) = common::scripted_game();
+    let state = GameState::play(config, &actions).unwrap();
+    assert!(state.events().iter().all(|record| record.time.is_none()));
+}
+
+#[test]
+fn the_clock_does_not_change_the_game() {
+    let (config, actions) = common::scripted_game();
+    let plain = GameState::play(config.clone(), &actions).unwrap();
+    let mut timed = GameState::play(config, &actions[..10]).unwrap();
+    timed.set_clock(scripted(&[7; 10]));
+    for action in &actions[10..] {
+        timed.apply(action.clone()).unwrap();
+    }
+    assert!(timed == plain, "{}", timed.diff(&plain));
+    assert_eq!(timed.events()[9].time, None);
+    assert_eq!(timed.events()[10].time, Some(7));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn times_survive_a_save() {
+    let (config, actions) = common::scripted_game();
+    let config = GameConfig {
+        clock: scripted(&[5, 6]),
+        ..config
+    };
+    let state = GameState::play(config, &actions[..2]).unwrap();
+    let json = serde_json::to_string(&state).unwrap();
+    assert!(json.contains("\"time\":5"), "{}", json);
+    let loaded: GameState = serde_json::from_str(&json).unwrap();
+    common::assert_same(&loaded, &state);
+    assert_eq!(loaded.events()[1].time, Some(6));
+}
@@ -0,0 +1,137 @@
+use monopoly::{Command, GameConfig, Money, PlayerId, PropertyId, RollResult};
+
+// A short two-player game, legal under the default seed, that draws from both
+// decks, holds an auction and ends with a player in jail.
+#[allow(dead_code)]
+pub fn scripted_game() -> (GameConfig, Vec<Command>) {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    let actions = vec![
+        Command::RollDice(p0, RollResult(3, 4)), // Chance: St. Charles Place
+        Command::BuyProperty(p0, PropertyId(11)),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(1, 2)),
+        Command::DeclineToBuy(p1),
+        Command::PlaceBid(p1, Money(45)),
+        Command::PassAuction(p0),
+        Command::EndTurn(p1),
+        Command::RollDice(p0, RollResult(5, 5)),
+        Command::BuyProperty(p0, PropertyId(21)),
+        Command::RollDice(p0, RollResult(2, 4)),
+        Command::BuyProperty(p0, PropertyId(27)),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(6, 5)),
+        Command::BuyProperty(p1, PropertyId(14)),
+        Command::EndTurn(p1),
+        Command::RollDice(p0, RollResult(1, 2)), // Go To Jail
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(1, 2)), // Community Chest
+        Command::EndTurn(p1),
+    ];
+    (config, actions)
+}
+
+// Plays a whole game with a simple policy: buy what's affordable, bid a
+// little in auctions, pay debts when possible and go bankrupt otherwise.
+// Stops at a winner or after `limit` events.
+#[allow(dead_code)]
+pub fn played_game(seed: u64, limit: usize) -> monopoly::GameState {
+    let mut dice = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    let mut roll = move |player| {
+        let mut die = || {
+            dice = dice
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((dice >> 33) % 6 + 1) as i8
+        };
+        Command::RollDice(player, RollResult(die(), die()))
+    };
+    let mut state = monopoly::GameState::new(GameConfig {
+        seed,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    while state.winner().is_none() && state.events().len() < limit {
+        play_move(&mut state, &mut roll);
+    }
+    state
+}
+
+// Carries on for `turns` more turns, or to a winner, rolling the engine's
+// own dice.
+#[allow(dead_code)]
+pub fn play_turns(state: &mut monopoly::GameState, turns: u32) {
+    let end = state.turn_number() + turns;
+    while state.winner().is_none() && state.turn_number() < end {
+        play_move(state, &mut Command::Roll);
+    }
+}
+
+// One move of played_game's policy, rolling with the command from `roll`.
+fn play_move(state: &mut monopoly::GameState, roll: &mut impl FnMut(PlayerId) -> Command) {
+    use monopoly::{PendingDecision, TaxChoice};
+
+    let applied = match state.pending().cloned() {
+        Some(PendingDecision::BuyOrDecline { player, property }) => state
+            .apply(Command::BuyProperty(player, property))
+            .or_else(|_| state.apply(Command::DeclineToBuy(player))),
+        Some(PendingDecision::Auction {
+            high_bid,
+            bidders,
+            next,
+            ..
+        }) => {
+            let bidder = bidders[next];
+            let bid = high_bid.map_or(10, |high| high.1 .0 + 10);
+            if bid < 200 {
+                state
+                    .apply(Command::PlaceBid(bidder, Money(bid)))
+                    .or_else(|_| state.apply(Command::PassAuction(bidder)))
+            } else {
+                state.apply(Command::PassAuction(bidder))
+            }
+        }
+        Some(PendingDecision::PayDebt { debtor, .. })
+        | Some(PendingDecision::PayBid { debtor, .. }) => state
+            .apply(Command::PayDebt(debtor))
+            .or_else(|_| state.apply(Command::DeclareBankruptcy(debtor)))
+            .or_else(|_| state.apply(raise_cash(state, debtor))),
+        Some(PendingDecision::IncomeTax { player, .. }) => state
+            .apply(Command::PayIncomeTax(player, TaxChoice::Flat))
+            .or_else(|_| state.apply(Command::PayIncomeTax(player, TaxChoice::Percent))),
+        Some(PendingDecision::UtilityRoll { player, .. }) => state.apply(roll(player)),
+        None => {
+            let player = state.current_player();
+            state
+                .apply(roll(player))
+                .or_else(|_| state.apply(Command::EndTurn(player)))
+        }
+    };
+    applied.expect("the policy always has a legal move");
+}
+
+// Selling up to pay a debt: the first building or lot that would raise
+// anything.
+fn raise_cash(state: &monopoly::GameState, debtor: PlayerId) -> Command {
+    state
+        .legal_actions()
+        .into_iter()
+        .find(|command| match command {
+            Command::SellHotel(id, _)
+            | Command::SellHouse(id, _)
+            | Command::MortgageProperty(id, _) => *id == debtor,
+            _ => false,
+        })
+        .expect("a debtor who can't go bankrupt has something to sell")
+}
+
+// assert_eq! for game states, which are too big to read in a panic message:
+// the message lists what differs instead.
+#[allow(dead_code)]
+pub fn assert_same(left: &monopoly::GameState, right: &monopoly::GameState) {
+    assert!(left == right, "states differ:\n{}", left.diff(right));
+    assert_eq!(left.events(), right.events(), "histories differ");
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use monopoly::{GameConfig, GameError, GameState, ReplayError};
+
+// A long game and the same game partway through, with its whole history.
+fn long_game() -> (GameState, GameState) {
+    let full = common::played_game(3, 400);
+    assert!(full.event_count() > 300);
+    let partway = full.state_at(300).unwrap();
+    (full, partway)
+}
+
+fn play_on(state: &mut GameState, full: &GameState) {
+    for record in &full.events()[state.event_count()..] {
+        state.apply(record.command.clone()).unwrap();
+    }
+}
+
+#[test]
+fn compaction_changes_nothing_about_the_game() {
+    let (full, mut state) = long_game();
+    let before = state.clone();
+    state.compact(20);
+    assert_eq!(state, before);
+    assert_eq!(state.event_count(), 300);
+    assert_eq!(state.events(), &before.events()[280..]);
+
+    play_on(&mut state, &full);
+    assert_eq!(state, full);
+    assert_eq!(state.events(), &full.events()[280..]);
+}
+
+#[test]
+fn compacting_as_you_go_bounds_the_log() {
+    let full = common::played_game(3, 400);
+    let mut state = GameState::new(GameConfig {
+        seed: 3,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    for record in full.events() {
+        state.apply(record.command.clone()).unwrap();
+        if state.events().len() == 50 {
+            state.compact(10);
+        }
+        assert!(state.events().len() <= 50);
+    }
+    common::assert_same(&state.state_at(state.event_count()).unwrap(), &state);
+    assert_eq!(state, full);
+}
+
+#[test]
+fn compacted_events_cannot_be_revisited() {
+    let (full, mut state) = long_game();
+    state.compact(20);
+    assert_eq!(
+        state.state_at(279).unwrap_err(),
+        ReplayError::Compacted {
+            index: 279,
+            first: 280
+        }
+    );
+    assert_eq!(state.state_at(290).unwrap(), full.state_at(290).unwrap());
+    assert_eq!(state.state_at(280).unwrap(), full.state_at(280).unwrap());
+
+    for _ in 0..20 {
+        state.undo().unwrap();
+    }
+    assert_eq!(state, full.state_at(280).unwrap());
+    assert_eq!(
+        state.undo(),
+        Err(GameError::IllegalAction(
+            "the events before this were compacted away".to_string()
+        ))
+    );
+}
+
+#[test]
+fn snapshots_give_the_same_answers() {
+    let full = common::played_game(3, 400);
+    let mut state = GameState::new(GameConfig {
+        seed: 3,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    state.set_snapshot_interval(Some(50));
+    play_on(&mut state, &full);
+    for index in [0, 49, 50, 51, 199, 300, full.event_count() - 1] {
+        assert_eq!(
+            state.state_at(index).unwrap(),
+            full.state_at(index).unwrap()
+        );
+    }
+
+    let mut undone = full.clone();
+    for _ in 0..60 {
+        assert_eq!(state.undo(), undone.undo());
+    }
+    common::assert_same(&state, &undone);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn a_compacted_game_saves_and_plays_on() {
+    let (full, mut state) = long_game();
+    state.compact(5);
+    let mut loaded: GameState =
+        serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+    common::assert_same(&loaded, &state);
+    play_on(&mut loaded, &full);
+    assert_eq!(loaded, full);
+    assert!(matches!(
+        loaded.state_at(290),
+        Err(ReplayError::Compacted { first: 295, .. })
+    ));
+}
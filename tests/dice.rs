@@ -0,0 +1,233 @@
+mod common;
+
+use monopoly::{
+    Command, Dice, DiceRoller, GameConfig, GameError, GameState, HouseRules, PlayerId, RollResult,
+    SeededDice,
+};
+
+fn engine_config(seed: u64) -> GameConfig {
+    GameConfig {
+        players: 3,
+        seed,
+        engine_dice: true,
+        ..GameConfig::default()
+    }
+}
+
+fn engine_game(seed: u64) -> GameState {
+    GameState::new(engine_config(seed)).unwrap()
+}
+
+fn rolls(state: &GameState) -> Vec<Command> {
+    state
+        .commands()
+        .into_iter()
+        .filter(|command| matches!(command, Command::RollDice(..)))
+        .collect()
+}
+
+#[test]
+fn the_same_seed_rolls_the_same_game() {
+    let (mut a, mut b) = (engine_game(11), engine_game(11));
+    common::play_turns(&mut a, 30);
+    common::play_turns(&mut b, 30);
+    assert!(rolls(&a).len() >= 30);
+    assert_eq!(rolls(&a), rolls(&b));
+    common::assert_same(&a, &b);
+
+    let mut c = engine_game(12);
+    common::play_turns(&mut c, 30);
+    assert_ne!(rolls(&a), rolls(&c));
+}
+
+#[test]
+fn a_roll_is_logged_with_its_dice() {
+    let mut state = engine_game(0);
+    let expected = state.next_roll();
+    state.roll(PlayerId(0)).unwrap();
+    assert_eq!(
+        state.events()[0].command,
+        Command::RollDice(PlayerId(0), expected)
+    );
+}
+
+#[test]
+fn players_cannot_choose_their_dice() {
+    let mut state = engine_game(0);
+    let error = state
+        .apply(Command::RollDice(PlayerId(0), monopoly::RollResult(6, 6)))
+        .unwrap_err();
+    assert_eq!(
+        error,
+        GameError::IllegalAction("in this game the engine rolls the dice".to_string())
+    );
+    assert!(state.events().is_empty());
+    assert!(state.legal_actions().contains(&Command::Roll(PlayerId(0))));
+}
+
+#[test]
+fn an_engine_rolled_log_replays() {
+    let mut state = engine_game(4);
+    common::play_turns(&mut state, 10);
+    let replayed = GameState::replay(engine_config(4), &state.event_stream()).unwrap();
+    common::assert_same(&replayed, &state);
+    let mut undone = state.clone();
+    undone.undo().unwrap();
+    assert_eq!(undone.events(), &state.events()[..state.events().len() - 1]);
+}
+
+// The engine's dice go through the same rules as dice the player names:
+// given the same throws, a game that takes them from its players plays out
+// event for event the same, doubles, jail and all.
+#[test]
+fn engine_rolls_play_as_if_they_were_given() {
+    let mut state = engine_game(7);
+    common::play_turns(&mut state, 40);
+    let doubles = rolls(&state)
+        .into_iter()
+        .filter(|roll| matches!(roll, Command::RollDice(_, RollResult(one, two)) if one == two))
+        .count();
+    assert!(doubles > 0);
+    let given = GameConfig {
+        engine_dice: false,
+        ..engine_config(7)
+    };
+    let supplied = GameState::play(given, &state.commands()).unwrap();
+    assert_eq!(supplied.events(), state.events());
+}
+
+#[test]
+fn seeded_dice_repeat_themselves() {
+    let mut dice = SeededDice::new(9);
+    let mut again = SeededDice::new(9);
+    let first: Vec<_> = (0..50).map(|_| dice.roll()).collect();
+    let second: Vec<_> = (0..50).map(|_| again.roll()).collect();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn scripts_can_leave_the_dice_to_the_engine() {
+    let state = engine_game(0);
+    let commands = Command::parse_script("P0 roll\n", state.board()).unwrap();
+    assert_eq!(commands, [Command::Roll(PlayerId(0))]);
+    assert_eq!(Command::write_script(&commands, state.board()), "P0 roll\n");
+    let thrown = Command::parse_script("P0 throw 3 1 6\n", state.board()).unwrap();
+    assert_eq!(thrown, [Command::RollMany(PlayerId(0), vec![3, 1, 6])]);
+    assert_eq!(
+        Command::write_script(&thrown, state.board()),
+        "P0 throw 3 1 6\n"
+    );
+}
+
+fn thrown_game(dice: Dice, engine_dice: bool) -> GameState {
+    GameState::new(GameConfig {
+        players: 3,
+        seed: 6,
+        engine_dice,
+        rules: HouseRules::official().with_dice(dice),
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+// Every roll in the log, as the dice landed.
+fn throws(state: &GameState) -> Vec<Vec<i8>> {
+    let mut throws = Vec::new();
+    for command in state.commands() {
+        match command {
+            Command::RollDice(_, RollResult(one, two)) => throws.push(vec![one, two]),
+            Command::RollMany(_, dice) => throws.push(dice),
+            _ => {}
+        }
+    }
+    throws
+}
+
+#[test]
+fn games_play_on_one_die_or_three() {
+    for dice in [Dice { count: 1, faces: 6 }, Dice { count: 3, faces: 6 }] {
+        let mut state = thrown_game(dice, true);
+        common::play_turns(&mut state, 12);
+        let throws = throws(&state);
+        assert!(throws.len() >= 12, "{}", dice);
+        assert!(throws.iter().all(|thrown| dice.allows(thrown)), "{}", dice);
+        // Neither is ever logged as a pair.
+        assert!(state
+            .events()
+            .iter()
+            .all(|record| !matches!(record.command, Command::RollDice(..))));
+        let replayed = GameState::replay(state.config().clone(), &state.event_stream()).unwrap();
+        common::assert_same(&replayed, &state);
+    }
+}
+
+#[test]
+fn a_throw_moves_by_its_total() {
+    let mut state = thrown_game(Dice { count: 3, faces: 6 }, false);
+    state
+        .apply(Command::RollMany(PlayerId(0), vec![3, 3, 4]))
+        .unwrap();
+    assert_eq!(state.player(PlayerId(0)).unwrap().position(), 10);
+    // Two alike aren't doubles among three, so there is no second roll.
+    assert!(state.apply(Command::EndTurn(PlayerId(0))).is_ok());
+    assert_eq!(
+        state.events()[0].command.display_with(&state).to_string(),
+        "Player 0 rolled 3+3+4 (10)"
+    );
+}
+
+#[test]
+fn rolls_must_match_the_dice() {
+    let refused = |state: &mut GameState, command| {
+        assert!(
+            matches!(
+                state.apply(command),
+                Err(GameError::IllegalAction(_) | GameError::InvalidRoll { .. })
+            ),
+            "{:?}",
+            state.events()
+        );
+    };
+    let mut three = thrown_game(Dice { count: 3, faces: 6 }, false);
+    refused(&mut three, Command::RollDice(PlayerId(0), RollResult(3, 4)));
+    refused(&mut three, Command::RollMany(PlayerId(0), vec![3, 4, 7]));
+    refused(&mut three, Command::RollMany(PlayerId(0), vec![3, 4, 5, 6]));
+    assert_eq!(
+        three.legal_actions()[0],
+        Command::RollMany(PlayerId(0), vec![1, 1, 1])
+    );
+
+    let mut classic = thrown_game(Dice::CLASSIC, false);
+    refused(&mut classic, Command::RollMany(PlayerId(0), vec![3, 4]));
+    refused(
+        &mut classic,
+        Command::RollDice(PlayerId(0), RollResult(3, 7)),
+    );
+    let mut d4 = thrown_game(Dice { count: 2, faces: 4 }, false);
+    refused(&mut d4, Command::RollDice(PlayerId(0), RollResult(5, 1)));
+    assert!(d4
+        .apply(Command::RollDice(PlayerId(0), RollResult(4, 4)))
+        .is_ok());
+    assert!(three.events().is_empty() && classic.events().is_empty());
+}
+
+#[test]
+fn dice_that_cannot_be_rolled_are_refused() {
+    for dice in [
+        Dice { count: 0, faces: 6 },
+        Dice { count: 2, faces: 1 },
+        Dice {
+            count: 4,
+            faces: 10,
+        },
+    ] {
+        let config = GameConfig {
+            rules: HouseRules::official().with_dice(dice),
+            ..GameConfig::default()
+        };
+        assert!(matches!(
+            GameState::new(config),
+            Err(GameError::InvalidConfig(_))
+        ));
+    }
+}
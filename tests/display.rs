@@ -0,0 +1,60 @@
+use monopoly::{
+    Bid, Command, GameConfig, GameState, Money, PlayerId, PropertyId, RollResult, Trade,
+};
+
+fn render(action: Command) -> String {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    action.display_with(&state).to_string()
+}
+
+#[test]
+fn actions_render_with_property_names() {
+    assert_eq!(
+        render(Command::RollDice(PlayerId(0), RollResult(1, 2))),
+        "Player 0 rolled 1+2 (3)"
+    );
+    assert_eq!(
+        render(Command::BuyProperty(PlayerId(2), PropertyId(39))),
+        "Player 2 bought Boardwalk for $400"
+    );
+    assert_eq!(
+        render(Command::MortgageProperty(PlayerId(1), PropertyId(16))),
+        "Player 1 mortgaged St. James Place"
+    );
+    assert_eq!(
+        render(Command::BuyHouse(PlayerId(0), PropertyId(1))),
+        "Player 0 built a house on Mediterranean Ave"
+    );
+}
+
+#[test]
+fn auctions_and_trades_summarize_their_contents() {
+    assert_eq!(
+        render(Command::AuctionProperty(
+            PropertyId(5),
+            vec![Bid(PlayerId(0), Money(90)), Bid(PlayerId(1), Money(120))]
+        )),
+        "Reading Railroad was auctioned to Player 1 for $120"
+    );
+    assert_eq!(
+        render(Command::ProposeTrade(Trade {
+            proposer: PlayerId(0),
+            recipient: PlayerId(1),
+            offered: vec![PropertyId(3)],
+            requested: vec![PropertyId(8)],
+            offered_cash: Money(50),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        })),
+        "Player 0 offered Player 1 Baltic Ave, $50 for Vermont Ave"
+    );
+}
+
+#[test]
+fn unknown_properties_render_without_panicking() {
+    assert_eq!(
+        render(Command::BuyProperty(PlayerId(0), PropertyId(-1))),
+        "Player 0 bought an unknown square for $0"
+    );
+}
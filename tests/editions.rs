@@ -0,0 +1,83 @@
+mod common;
+
+use monopoly::{BoardEdition, Command, GameConfig, GameState, PlayerId, PropertyId};
+
+fn game(edition: BoardEdition) -> GameState {
+    GameState::new(GameConfig {
+        edition,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn each_edition_names_its_own_squares() {
+    for (edition, boardwalk, reading, income_tax) in [
+        (
+            BoardEdition::Standard,
+            "Boardwalk",
+            "Reading Railroad",
+            "Income Tax",
+        ),
+        (
+            BoardEdition::French,
+            "Rue de la Paix",
+            "Gare Montparnasse",
+            "Impôts sur le revenu",
+        ),
+        (
+            BoardEdition::German,
+            "Schlossallee",
+            "Südbahnhof",
+            "Einkommensteuer",
+        ),
+    ] {
+        let board = game(edition).board().clone();
+        assert_eq!(board.name(39), Some(boardwalk));
+        assert_eq!(board.name(5), Some(reading));
+        assert_eq!(board.name(4), Some(income_tax));
+    }
+}
+
+#[test]
+fn editions_differ_only_in_names() {
+    let (mut standard, mut french) = (game(BoardEdition::Standard), game(BoardEdition::French));
+    let (_, actions) = common::scripted_game();
+    for action in actions.iter().take(12) {
+        assert_eq!(
+            standard.apply(action.clone()),
+            french.apply(action.clone()),
+            "{:?}",
+            action
+        );
+    }
+    assert_eq!(
+        standard.rent_due(PropertyId(39), None),
+        french.rent_due(PropertyId(39), None)
+    );
+}
+
+#[test]
+fn scripts_name_streets_as_the_edition_does() {
+    let mut german = game(BoardEdition::German);
+    let script = "P0 roll 1 2\nP0 buy turmstraße\nP0 endturn\nP1 roll 2 3\nP1 buy süd\n";
+    let actions = Command::parse_script(script, german.board()).unwrap();
+    for action in &actions {
+        german.apply(action.clone()).unwrap();
+    }
+    assert_eq!(german.owner_of(PropertyId(3)), Some(PlayerId(0)));
+    assert_eq!(german.owner_of(PropertyId(5)), Some(PlayerId(1)));
+    // The standard board has no Turmstraße.
+    assert!(Command::parse_script(script, game(BoardEdition::Standard).board()).is_err());
+
+    // A French game writes its log in French.
+    let french = game(BoardEdition::French);
+    let written = Command::write_script(&actions, french.board());
+    assert!(written.contains("P0 buy rue-lecourbe\n"), "{}", written);
+    assert!(
+        written.contains("P1 buy gare-montparnasse\n"),
+        "{}",
+        written
+    );
+    assert_eq!(Command::parse_script(&written, french.board()), Ok(actions));
+}
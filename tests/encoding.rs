@@ -0,0 +1,41 @@
+#![cfg(feature = "binary")]
+
+mod common;
+
+use monopoly::{read_events, write_events};
+
+#[test]
+fn a_long_log_round_trips_in_a_fraction_of_the_json() {
+    let state = common::played_game(3, 5000);
+    let records = state.events();
+    assert_eq!(records.len(), 5000);
+    let mut bytes = Vec::new();
+    write_events(&mut bytes, records).unwrap();
+    assert_eq!(read_events(&mut bytes.as_slice()).unwrap(), records);
+    let json = serde_json::to_vec(records).unwrap();
+    assert!(
+        bytes.len() * 4 < json.len(),
+        "{} vs {}",
+        bytes.len(),
+        json.len()
+    );
+}
+
+#[test]
+fn logs_can_be_appended_to() {
+    let records = common::played_game(1, 300).events().to_vec();
+    let (first, rest) = records.split_at(120);
+    let mut bytes = Vec::new();
+    write_events(&mut bytes, first).unwrap();
+    write_events(&mut bytes, rest).unwrap();
+    assert_eq!(read_events(&mut bytes.as_slice()).unwrap(), records);
+}
+
+#[test]
+fn a_truncated_log_is_an_error() {
+    let records = common::played_game(1, 10).events().to_vec();
+    let mut bytes = Vec::new();
+    write_events(&mut bytes, &records).unwrap();
+    bytes.pop();
+    assert!(read_events(&mut bytes.as_slice()).is_err());
+}
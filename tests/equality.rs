@@ -0,0 +1,73 @@
+use monopoly::{Command, GameConfig, GameState, Money, PlayerId, RollResult, Trade};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn two_player_game() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+fn hash_of(state: &GameState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn cash_gift(from: PlayerId, to: PlayerId, amount: Money) -> Trade {
+    Trade {
+        proposer: from,
+        recipient: to,
+        offered: vec![],
+        requested: vec![],
+        offered_cash: amount,
+        requested_cash: Money(0),
+        offered_jail_cards: 0,
+        requested_jail_cards: 0,
+    }
+}
+
+#[test]
+fn same_actions_give_equal_states() {
+    let (mut a, mut b) = (two_player_game(), two_player_game());
+    for state in [&mut a, &mut b] {
+        state
+            .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+            .unwrap();
+    }
+    assert_eq!(a, b);
+    assert!(a.eq_including_history(&b));
+    assert_eq!(hash_of(&a), hash_of(&b));
+
+    a.apply(Command::DeclineToBuy(PlayerId(0))).unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn positions_compare_equal_regardless_of_how_they_were_reached() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let quiet = two_player_game();
+    let mut busy = two_player_game();
+    busy.apply(Command::ProposeTrade(cash_gift(p0, p1, Money(50))))
+        .unwrap();
+    busy.apply(Command::AcceptTrade(p1)).unwrap();
+    busy.apply(Command::ProposeTrade(cash_gift(p1, p0, Money(50))))
+        .unwrap();
+    busy.apply(Command::AcceptTrade(p0)).unwrap();
+    assert_eq!(busy, quiet);
+    assert_eq!(hash_of(&busy), hash_of(&quiet));
+    assert!(!busy.eq_including_history(&quiet));
+}
+
+#[test]
+fn deck_order_is_part_of_the_position() {
+    let other_seed = GameState::new(GameConfig {
+        players: 2,
+        seed: 1,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    assert_ne!(two_player_game(), other_seed);
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use monopoly::GameState;
+
+fn rows(state: &GameState) -> Vec<Vec<String>> {
+    let mut out = Vec::new();
+    state.export_csv(&mut out).unwrap();
+    String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| line.split(',').map(str::to_string).collect())
+        .collect()
+}
+
+#[test]
+fn one_row_per_player_per_turn() {
+    let (config, actions) = common::scripted_game();
+    let state = GameState::play(config, &actions).unwrap();
+    let rows = rows(&state);
+    assert_eq!(
+        rows[0].join(","),
+        "turn,player,cash,net_worth,position,properties_owned,houses,hotels,in_jail"
+    );
+    // Six finished turns and the one just begun, for two players.
+    assert_eq!(rows.len(), 1 + 7 * 2);
+    assert!(rows[1..].iter().all(|row| row.len() == 9));
+
+    // Player 0 ended the first turn having bought St. Charles Place.
+    assert_eq!(rows[1].join(","), "1,0,1360,1500,11,1,0,0,false");
+    assert_eq!(rows[2].join(","), "1,1,1500,1500,0,0,0,0,false");
+    // And went to jail in the fifth.
+    assert_eq!(rows[9][..2], ["5", "0"]);
+    assert_eq!(rows[9][4..], ["10", "3", "0", "0", "true"]);
+}
+
+#[test]
+fn a_new_game_has_just_the_first_turn() {
+    let (config, _) = common::scripted_game();
+    let rows = rows(&GameState::new(config).unwrap());
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1][..3], ["1", "0", "1500"]);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "ffi")]
+
+use monopoly::{
+    monopoly_game_apply, monopoly_game_free, monopoly_game_legal_actions, monopoly_game_new,
+    monopoly_game_state, monopoly_last_error, MonopolyGame,
+};
+use serde_json::{json, Value};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+// Reads out a string the way a C caller would: asks for its length, then
+// hands over a buffer with room for it and the NUL.
+unsafe fn read(fill: impl Fn(*mut c_char, usize) -> isize) -> String {
+    let length = fill(ptr::null_mut(), 0);
+    assert!(length >= 0);
+    let mut buffer = vec![1 as c_char; length as usize + 1];
+    assert_eq!(fill(buffer.as_mut_ptr(), buffer.len()), length);
+    CStr::from_ptr(buffer.as_ptr())
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+unsafe fn json_of(fill: impl Fn(*mut c_char, usize) -> isize) -> Value {
+    serde_json::from_str(&read(fill)).unwrap()
+}
+
+unsafe fn last_error() -> String {
+    read(|buffer, length| monopoly_last_error(buffer, length))
+}
+
+fn c(text: &str) -> CString {
+    CString::new(text).unwrap()
+}
+
+unsafe fn apply(game: *mut MonopolyGame, command: &str) -> i32 {
+    monopoly_game_apply(game, c(command).as_ptr())
+}
+
+#[test]
+fn a_game_from_start_to_free() {
+    unsafe {
+        // Under seed 2 the first roll is 2+3, onto Reading Railroad.
+        let game = monopoly_game_new(c(r#"{"players": 2, "seed": 2}"#).as_ptr());
+        assert!(!game.is_null());
+        let legal = json_of(|buffer, length| monopoly_game_legal_actions(game, buffer, length));
+        assert!(legal
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "RollDice": [0, [1, 2]] })));
+
+        assert_eq!(apply(game, r#"{"Roll": 0}"#), 0);
+        assert_eq!(apply(game, r#"{"BuyProperty": [0, 5]}"#), 0);
+        let state = json_of(|buffer, length| monopoly_game_state(game, buffer, length));
+        assert_eq!(state["players"][0]["cash"], 1300);
+        assert_eq!(state["deeds"][5]["owner"], 0);
+
+        monopoly_game_free(game);
+        monopoly_game_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn failures_leave_a_reason() {
+    unsafe {
+        assert_eq!(last_error(), "");
+        assert!(monopoly_game_new(c(r#"{"players": 9}"#).as_ptr()).is_null());
+        assert!(last_error().contains("players"));
+
+        let game = monopoly_game_new(ptr::null());
+        assert!(!game.is_null());
+        assert_eq!(apply(game, r#"{"EndTurn": 1}"#), -1);
+        assert_eq!(last_error(), "it is not player 1's turn");
+        assert_eq!(apply(game, r#"{"Fly": 0}"#), -1);
+        assert!(last_error().contains("unknown variant"));
+        assert_eq!(monopoly_game_apply(game, ptr::null()), -1);
+        assert_eq!(last_error(), "a string is NULL");
+
+        // The reason stays until the next failure.
+        assert_eq!(apply(game, r#"{"Roll": 0}"#), 0);
+        assert_eq!(last_error(), "a string is NULL");
+
+        assert_eq!(monopoly_game_state(ptr::null(), ptr::null_mut(), 0), -1);
+        assert_eq!(last_error(), "the game is NULL");
+        monopoly_game_free(game);
+    }
+}
+
+#[test]
+fn small_buffers_are_left_alone() {
+    unsafe {
+        let game = monopoly_game_new(ptr::null());
+        let length = monopoly_game_state(game, ptr::null_mut(), 0);
+        let mut buffer = vec![1 as c_char; length as usize];
+        assert_eq!(
+            monopoly_game_state(game, buffer.as_mut_ptr(), buffer.len()),
+            length
+        );
+        assert!(buffer.iter().all(|&byte| byte == 1));
+        monopoly_game_free(game);
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "fuzzing")]
+
+// The fuzz targets only run under cargo fuzz; these run their entry points
+// for a bounded while on every test run so they keep building and the corpus
+// keeps meaning something.
+
+use monopoly::{fuzz_commands, fuzz_log, read_events, GameConfig, GameState};
+use std::fs;
+use std::path::PathBuf;
+
+fn corpus() -> Vec<(String, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus/log");
+    let mut files: Vec<(String, Vec<u8>)> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read(path).unwrap())
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+// The game a corpus log records, played back in full.
+fn replayed(data: &[u8]) -> GameState {
+    let (header, mut log) = data.split_at(9);
+    let mut seed = [0; 8];
+    seed.copy_from_slice(&header[1..]);
+    let config = GameConfig {
+        players: header[0] as usize,
+        seed: u64::from_le_bytes(seed),
+        ..GameConfig::default()
+    };
+    let records = read_events(&mut log).unwrap();
+    let events: Vec<_> = records.iter().flat_map(|record| record.events()).collect();
+    GameState::replay(config, &events).unwrap()
+}
+
+// A little LCG, so the inputs are the same every run.
+fn noise(seed: u64) -> impl FnMut() -> u8 {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 56) as u8
+    }
+}
+
+#[test]
+fn the_corpus_replays() {
+    let corpus = corpus();
+    let names: Vec<&str> = corpus.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["bankruptcy", "full-game", "scripted"]);
+    for (name, data) in &corpus {
+        let state = replayed(data);
+        match name.as_str() {
+            "scripted" => assert!(state.winner().is_none()),
+            _ => assert!(state.winner().is_some(), "{} has no winner", name),
+        }
+        fuzz_log(data);
+    }
+}
+
+#[test]
+fn damaged_logs_are_survived() {
+    let mut byte = noise(1);
+    for (_, data) in corpus() {
+        for _ in 0..100 {
+            let mut damaged = data.clone();
+            for _ in 0..1 + byte() % 4 {
+                let at = (byte() as usize * 257 + byte() as usize) % damaged.len();
+                damaged[at] = byte();
+            }
+            let keep = damaged.len() - byte() as usize % 64;
+            damaged.truncate(keep);
+            fuzz_log(&damaged);
+        }
+    }
+}
+
+#[test]
+fn random_commands_are_survived() {
+    let mut byte = noise(2);
+    for _ in 0..500 {
+        let len = byte() as usize * 16;
+        let data: Vec<u8> = (0..len).map(|_| byte()).collect();
+        fuzz_commands(&data);
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{generate_state, GameState};
+
+#[test]
+fn generated_states_hold_up_and_round_trip() {
+    for seed in 0..500 {
+        let state = generate_state(seed, (seed * 7 % 200) as u32);
+        if let Err(error) = state.check_invariants() {
+            panic!("seed {}: {}", seed, error);
+        }
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: GameState = serde_json::from_str(&json).unwrap();
+        common::assert_same(&loaded, &state);
+    }
+}
+
+#[test]
+fn a_seed_always_generates_the_same_state() {
+    let state = generate_state(11, 60);
+    common::assert_same(&state, &generate_state(11, 60));
+    assert_eq!(state.players().count(), 3);
+    assert!(state.events().len() > 60);
+    assert_ne!(state, generate_state(12, 60));
+}
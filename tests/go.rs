@@ -0,0 +1,137 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{
+    Command, Effect, GameConfig, GameLog, GameState, GameStateBuilder, LandingStats, Money,
+    PlayerId, RollResult,
+};
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+
+const P0: PlayerId = PlayerId(0);
+
+fn salary() -> Effect {
+    Effect::CollectedSalary {
+        player: P0,
+        amount: Money(200),
+    }
+}
+
+#[test]
+fn a_roll_that_ends_on_go_lands_on_it() {
+    for double_salary_on_go in [false, true] {
+        let mut config = GameConfig {
+            players: 2,
+            ..GameConfig::default()
+        };
+        config.rules.double_salary_on_go = double_salary_on_go;
+        let mut state = GameStateBuilder::new(config)
+            .player(Money(1500), 37)
+            .player(Money(1500), 0)
+            .build()
+            .unwrap();
+        let effects = state
+            .apply(Command::RollDice(P0, RollResult(1, 2)))
+            .unwrap();
+        let mut expected = vec![
+            Effect::LandedOnGo(P0),
+            salary(),
+            Effect::Moved { player: P0, to: 0 },
+        ];
+        if double_salary_on_go {
+            expected.push(salary());
+        }
+        assert_eq!(effects, expected);
+    }
+}
+
+#[test]
+fn a_roll_that_goes_round_passes_go() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 37)
+        .player(Money(1500), 0)
+        .build()
+        .unwrap();
+    let effects = state
+        .apply(Command::RollDice(P0, RollResult(2, 3)))
+        .unwrap();
+    assert_eq!(
+        effects[..3],
+        [
+            Effect::PassedGo(P0),
+            salary(),
+            Effect::Moved { player: P0, to: 2 }
+        ]
+    );
+    let stats = LandingStats::for_game(&state);
+    assert_eq!((stats.passed_go(), stats.landed_on_go()), (1, 0));
+}
+
+#[test]
+fn landing_statistics_tell_passing_from_landing() {
+    let state = common::played_game(3, 400);
+    let (mut passed, mut landed) = (0, 0);
+    for record in state.events() {
+        for effect in &record.effects {
+            match effect {
+                Effect::PassedGo(_) => passed += 1,
+                Effect::LandedOnGo(_) => landed += 1,
+                _ => {}
+            }
+        }
+    }
+    let stats = LandingStats::for_game(&state);
+    assert!(passed > landed && landed > 0);
+    assert_eq!((stats.passed_go(), stats.landed_on_go()), (passed, landed));
+}
+
+// A version 3 log, from before either was logged, comes back with both, and
+// with its digests worked out again.
+#[test]
+fn version_3_logs_gain_the_go_effects() {
+    let played = common::played_game(3, 400);
+    for hash_chain in [false, true] {
+        let config = GameConfig {
+            hash_chain,
+            ..played.config().clone()
+        };
+        let log = GameState::play(config, &played.commands()).unwrap().log();
+        let mut old = log.clone();
+        let mut previous = None;
+        for record in &mut old.records {
+            record
+                .effects
+                .retain(|effect| !matches!(effect, Effect::PassedGo(_) | Effect::LandedOnGo(_)));
+            if hash_chain {
+                record.digest = Some(record.chained(previous));
+                previous = record.digest;
+            }
+        }
+        assert_ne!(old, log);
+        let json = old
+            .to_json()
+            .unwrap()
+            .replace("\"version\":6", "\"version\":3");
+        let migrated = GameLog::from_json(&json).unwrap();
+        assert_eq!(migrated, log);
+        migrated.replay().unwrap();
+    }
+}
+
+#[test]
+fn version_3_saves_gain_them_too() {
+    let state = common::played_game(3, 400);
+    let path = env::temp_dir().join(format!("monopoly-go-{}.json", std::process::id()));
+    state.save_to(&path).unwrap();
+    let mut saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    saved["version"] = json!(3);
+    for record in saved["state"]["events"].as_array_mut().unwrap() {
+        record["effects"].as_array_mut().unwrap().retain(|effect| {
+            effect.get("PassedGo").is_none() && effect.get("LandedOnGo").is_none()
+        });
+    }
+    fs::write(&path, saved.to_string()).unwrap();
+    common::assert_same(&GameState::load_from(&path).unwrap(), &state);
+}
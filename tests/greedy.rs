@@ -0,0 +1,102 @@
+use monopoly::{
+    simulate, Command, GameConfig, GameState, GameStateBuilder, GreedyBuyer, Money, PlayerId,
+    PropertyId, RandomStrategy, RollResult, Strategy, Trade,
+};
+
+const P0: PlayerId = PlayerId(0);
+const P1: PlayerId = PlayerId(1);
+
+// Greedy against random, taking turns at going first.
+#[test]
+fn greedy_beats_random_most_of_the_time() {
+    let config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    let mut wins = 0;
+    for seed in 0..200 {
+        let greedy = (seed % 2) as usize;
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(RandomStrategy::new(seed))];
+        strategies.insert(greedy, Box::new(GreedyBuyer::default()));
+        let result = simulate(config.clone(), &mut strategies, seed).unwrap();
+        assert_eq!(result.fallbacks, 0, "seed {}", seed);
+        if result.winner == Some(PlayerId(greedy as i8)) {
+            wins += 1;
+        }
+    }
+    assert!(wins > 140, "greedy won {} of 200", wins);
+}
+
+#[test]
+fn it_builds_only_above_its_reserve() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(400), 0)
+        .player(Money(1500), 0)
+        .owns(P0, PropertyId(1), 0)
+        .owns(P0, PropertyId(3), 0)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(P0, RollResult(4, 6)))
+        .unwrap();
+    let mut greedy = GreedyBuyer::new(Money(0), Money(300));
+    assert_eq!(state.ask(&mut greedy), Command::BuyHouse(P0, PropertyId(1)));
+    state.apply(Command::BuyHouse(P0, PropertyId(1))).unwrap();
+    assert_eq!(state.ask(&mut greedy), Command::BuyHouse(P0, PropertyId(3)));
+    state.apply(Command::BuyHouse(P0, PropertyId(3))).unwrap();
+    assert_eq!(state.ask(&mut greedy), Command::EndTurn(P0));
+}
+
+#[test]
+fn it_pays_out_of_jail_only_above_its_floor() {
+    let jailed = |cash| {
+        GameStateBuilder::default()
+            .player(Money(cash), 0)
+            .player(Money(1500), 0)
+            .in_jail(P0, 0)
+            .build()
+            .unwrap()
+    };
+    let mut greedy = GreedyBuyer::new(Money(500), Money(0));
+    assert_eq!(jailed(501).ask(&mut greedy), Command::PayJailFine(P0));
+    assert_eq!(jailed(500).ask(&mut greedy), Command::Roll(P0));
+}
+
+// Player 0 holds Baltic and player 1 Mediterranean, so whoever ends up with
+// both has the browns; likewise Oriental for the light blues.
+#[test]
+fn it_takes_only_trades_that_win_it_a_monopoly() {
+    let offer = |offered: i8, requested: Vec<PropertyId>| -> GameState {
+        let mut state = GameStateBuilder::default()
+            .player(Money(1500), 0)
+            .player(Money(1500), 0)
+            .owns(P0, PropertyId(3), 0)
+            .owns(P1, PropertyId(1), 0)
+            .owns(P0, PropertyId(5), 0)
+            .owns(P0, PropertyId(8), 0)
+            .owns(P0, PropertyId(9), 0)
+            .owns(P1, PropertyId(6), 0)
+            .build()
+            .unwrap();
+        state
+            .apply(Command::ProposeTrade(Trade {
+                proposer: P0,
+                recipient: P1,
+                offered: vec![PropertyId(offered)],
+                requested,
+                offered_cash: Money(0),
+                requested_cash: Money(0),
+                offered_jail_cards: 0,
+                requested_jail_cards: 0,
+            }))
+            .unwrap();
+        state
+    };
+    let mut greedy = GreedyBuyer::default();
+    let gift = offer(3, vec![]);
+    assert_eq!(gift.ask(&mut greedy), Command::AcceptTrade(P1));
+    let railroad = offer(5, vec![]);
+    assert_eq!(railroad.ask(&mut greedy), Command::RejectTrade(P1));
+    let both_win = offer(3, vec![PropertyId(6)]);
+    assert_eq!(both_win.ask(&mut greedy), Command::RejectTrade(P1));
+}
@@ -0,0 +1,111 @@
+use monopoly::{
+    ColorGroup, Dice, GameConfig, GameError, GameState, GameStateBuilder, HouseRules, Money,
+    PlayerId, PropertyId,
+};
+
+const MEDITERRANEAN: PropertyId = PropertyId(1);
+const BALTIC: PropertyId = PropertyId(3);
+const READING_RAILROAD: PropertyId = PropertyId(5);
+const ELECTRIC_COMPANY: PropertyId = PropertyId(12);
+const ORANGES: [PropertyId; 3] = [PropertyId(16), PropertyId(18), PropertyId(19)];
+const GREENS: [PropertyId; 3] = [PropertyId(31), PropertyId(32), PropertyId(34)];
+
+// Every square as likely as any other.
+const UNIFORM: [f64; 40] = [1.0 / 40.0; 40];
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() < 1e-9
+}
+
+fn two_players() -> GameStateBuilder {
+    GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+}
+
+fn owning(holdings: &[(PropertyId, u8)]) -> GameState {
+    holdings
+        .iter()
+        .fold(two_players(), |builder, (property, houses)| {
+            builder.owns(PlayerId(0), *property, *houses)
+        })
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn income_is_rent_times_the_odds_of_landing() {
+    let state = owning(&[(BALTIC, 0), (ELECTRIC_COMPANY, 0)]);
+    assert!(close(
+        state.expected_income(BALTIC, &UNIFORM).unwrap(),
+        4.0 / 40.0
+    ));
+    // Four times the dice, which average seven.
+    let utility = state.expected_income(ELECTRIC_COMPANY, &UNIFORM).unwrap();
+    assert!(close(utility, 28.0 / 40.0));
+    assert_eq!(state.expected_income(MEDITERRANEAN, &UNIFORM), Ok(0.0));
+}
+
+#[test]
+fn utility_income_follows_the_rules_dice() {
+    let state = GameStateBuilder::new(GameConfig {
+        players: 2,
+        rules: HouseRules::default().with_dice(Dice { count: 3, faces: 6 }),
+        ..GameConfig::default()
+    })
+    .player(Money(1500), 0)
+    .player(Money(1500), 0)
+    .owns(PlayerId(0), ELECTRIC_COMPANY, 0)
+    .build()
+    .unwrap();
+    // Three dice average ten and a half.
+    let utility = state.expected_income(ELECTRIC_COMPANY, &UNIFORM).unwrap();
+    assert!(close(utility, 42.0 / 40.0));
+}
+
+#[test]
+fn a_monopoly_doubles_the_group_income() {
+    let state = owning(&[(MEDITERRANEAN, 0), (BALTIC, 0)]);
+    let brown = state.group_income(ColorGroup::Brown, &UNIFORM).unwrap();
+    assert!(close(brown, (4.0 + 8.0) / 40.0));
+}
+
+#[test]
+fn payback_weighs_a_house_against_the_rent_it_adds() {
+    let state = owning(&[(MEDITERRANEAN, 0), (BALTIC, 0)]);
+    // $50 for rent going from $8 to $20.
+    let baltic = state.payback_turns(BALTIC, &UNIFORM).unwrap().unwrap();
+    assert!(close(baltic, 50.0 / (12.0 / 40.0)));
+    assert_eq!(state.payback_turns(READING_RAILROAD, &UNIFORM), Ok(None));
+    let hotel = owning(&[(MEDITERRANEAN, 5), (BALTIC, 5)]);
+    assert_eq!(hotel.payback_turns(BALTIC, &UNIFORM), Ok(None));
+}
+
+#[test]
+fn the_third_orange_house_beats_the_first_green_one() {
+    let mut holdings: Vec<(PropertyId, u8)> = ORANGES.iter().map(|lot| (*lot, 2)).collect();
+    holdings.extend(GREENS.iter().map(|lot| (*lot, 0)));
+    let state = owning(&holdings);
+    // $100 for $200 to $550 on St. James, against $200 for $52 to $130 on
+    // Pacific.
+    let orange = state.payback_turns(ORANGES[0], &UNIFORM).unwrap().unwrap();
+    let green = state.payback_turns(GREENS[0], &UNIFORM).unwrap().unwrap();
+    assert!(close(orange, 100.0 / (350.0 / 40.0)));
+    assert!(close(green, 200.0 / (78.0 / 40.0)));
+    assert!(orange < green);
+}
+
+#[test]
+fn landing_odds_must_cover_the_board() {
+    let state = owning(&[(BALTIC, 0)]);
+    assert_eq!(
+        state.expected_income(BALTIC, &[0.5, 0.5]),
+        Err(GameError::InvalidConfig(
+            "2 landing odds for 40 squares".to_string()
+        ))
+    );
+    assert_eq!(
+        state.expected_income(PropertyId(4), &UNIFORM),
+        Err(GameError::InvalidProperty(PropertyId(4)))
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use monopoly::{Effect, GameConfig, GameState, Money, ReplayError};
+
+fn chained_game() -> (GameConfig, GameState) {
+    let (config, actions) = common::scripted_game();
+    let config = GameConfig {
+        hash_chain: true,
+        ..config
+    };
+    let state = GameState::play(config.clone(), &actions).unwrap();
+    (config, state)
+}
+
+#[test]
+fn a_played_log_verifies() {
+    let (config, state) = chained_game();
+    assert!(state.events().iter().all(|record| record.digest.is_some()));
+    assert_eq!(state.verify_log(), Ok(()));
+    let replayed = GameState::replay_verified(config, state.events()).unwrap();
+    common::assert_same(&replayed, &state);
+}
+
+#[test]
+fn an_edited_event_is_pinpointed() {
+    let (config, state) = chained_game();
+    let mut records = state.events().to_vec();
+    let edited = records
+        .iter_mut()
+        .find(|record| record.seq >= 5 && !record.effects.is_empty())
+        .unwrap();
+    let index = edited.seq;
+    edited.effects[0] = Effect::CollectedSalary {
+        player: edited.actor.unwrap(),
+        amount: Money(200),
+    };
+    assert_eq!(
+        GameState::replay_verified(config.clone(), &records),
+        Err(ReplayError::Tampered { index })
+    );
+    assert_eq!(
+        ReplayError::Tampered { index }.to_string(),
+        format!("event {} does not match its digest", index)
+    );
+
+    // Dropping an event breaks the chain at the one that followed it.
+    let mut records = state.events().to_vec();
+    records.remove(3);
+    assert_eq!(
+        GameState::replay_verified(config, &records),
+        Err(ReplayError::Tampered { index: 4 })
+    );
+}
+
+#[test]
+fn an_unchained_log_has_nothing_to_verify_against() {
+    let (config, actions) = common::scripted_game();
+    let state = GameState::play(config, &actions).unwrap();
+    assert!(state.events().iter().all(|record| record.digest.is_none()));
+    assert_eq!(state.verify_log(), Err(ReplayError::Tampered { index: 0 }));
+}
+
+#[test]
+fn the_chain_survives_compaction_and_undo() {
+    let (_, mut state) = chained_game();
+    let digests: Vec<_> = state.events().iter().map(|record| record.digest).collect();
+    state.compact(8);
+    assert_eq!(state.verify_log(), Ok(()));
+    state.undo().unwrap();
+    assert_eq!(state.verify_log(), Ok(()));
+    let (_, actions) = common::scripted_game();
+    state.apply(actions.last().unwrap().clone()).unwrap();
+    let last = state.last_event().unwrap().digest;
+    assert_eq!(last, *digests.last().unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn a_tampered_save_is_refused() {
+    let (_, state) = chained_game();
+    let mut json: serde_json::Value = serde_json::to_value(&state).unwrap();
+    json["events"][6]["turn"] = 9.into();
+    let error = serde_json::from_value::<GameState>(json).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("event 6 does not match its digest"),
+        "{}",
+        error
+    );
+}
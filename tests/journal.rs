@@ -0,0 +1,82 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{EventSink, FileSink, GameState, JournalError, Journaled};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("monopoly-journal-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+// Journals the scripted game to a file and returns the finished game.
+fn journal(path: &PathBuf) -> GameState {
+    let (config, actions) = common::scripted_game();
+    let sink = FileSink::create(path).unwrap();
+    let mut game = Journaled::new(GameState::new(config).unwrap(), sink).unwrap();
+    for action in actions {
+        game.apply(action).unwrap();
+    }
+    game.state().clone()
+}
+
+#[test]
+fn a_journal_replays_to_the_game_it_recorded() {
+    let path = temp_file("whole.jsonl");
+    let live = journal(&path);
+    // Everything was flushed as it happened, with the sink still open.
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 21);
+    common::assert_same(&GameState::load_journal(&path).unwrap(), &live);
+}
+
+#[test]
+fn a_torn_last_line_is_ignored() {
+    let path = temp_file("torn.jsonl");
+    let live = journal(&path);
+    let text = fs::read_to_string(&path).unwrap();
+    // Die halfway through writing the last event.
+    fs::write(&path, &text[..text.len() - 10]).unwrap();
+    let recovered = GameState::load_journal(&path).unwrap();
+    assert_eq!(recovered.event_count(), 19);
+    common::assert_same(&recovered, &live.state_at(19).unwrap());
+
+    // Or just after writing one.
+    let end = text[..text.len() - 1].rfind('\n').unwrap();
+    fs::write(&path, &text[..=end]).unwrap();
+    assert_eq!(GameState::load_journal(&path).unwrap().event_count(), 19);
+}
+
+#[test]
+fn a_game_already_underway_is_journaled_from_the_start() {
+    let (config, actions) = common::scripted_game();
+    let path = temp_file("underway.jsonl");
+    let partway = GameState::play(config, &actions[..7]).unwrap();
+    let sink = FileSink::create(&path).unwrap().flush_every(0);
+    let mut game = Journaled::new(partway, sink).unwrap();
+    game.apply(actions[7].clone()).unwrap();
+    game.sink().flush().unwrap();
+    let (live, _) = game.into_inner().unwrap();
+    common::assert_same(&GameState::load_journal(&path).unwrap(), &live);
+}
+
+#[test]
+fn damage_before_the_end_is_an_error() {
+    let path = temp_file("damaged.jsonl");
+    journal(&path);
+    let text = fs::read_to_string(&path).unwrap();
+    fs::write(&path, text.replacen("\"seq\":3", "\"seq\":", 1)).unwrap();
+    assert!(matches!(
+        GameState::load_journal(&path),
+        Err(JournalError::Json { line: 5, .. })
+    ));
+
+    fs::write(&path, "").unwrap();
+    assert!(matches!(
+        GameState::load_journal(&path),
+        Err(JournalError::Io(_))
+    ));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use monopoly::{
+    run_many, BoardEdition, Command, DoNothing, GameConfig, GameState, LandingStats, PlayerId,
+    RollResult, StrategyFactory,
+};
+
+const JAIL: usize = 10;
+const ORANGES: [usize; 3] = [16, 18, 19];
+
+#[test]
+fn a_scripted_game_lands_where_it_says() {
+    let (config, actions) = common::scripted_game();
+    let state = GameState::play(config, &actions).unwrap();
+    let stats = LandingStats::for_game(&state);
+    // Chance at 7 sends player 0 on to St. Charles Place, and the last roll
+    // of theirs is to Go To Jail, which counts only as Jail.
+    for square in [7, 11, 3, 21, 27, JAIL] {
+        assert_eq!(stats.counts()[square], 1, "square {}", square);
+    }
+    assert_eq!(stats.counts()[30], 0);
+    let frequencies = stats.frequencies();
+    assert_eq!(frequencies.len(), 40);
+    assert!((frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn stats_from_two_games_merge() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(4, 6)))
+        .unwrap();
+    let one = LandingStats::for_game(&state);
+    let mut both = one.clone();
+    both.merge(&one);
+    assert_eq!(both.counts()[JAIL], 2);
+    assert_eq!(both.total(), 2);
+    assert_eq!(both.frequencies(), one.frequencies());
+}
+
+// The classic result: jail is the most visited square, and the oranges,
+// a short roll out of it, are visited more than the average square.
+#[test]
+fn jail_and_the_oranges_are_landed_on_most() {
+    const IDLE: StrategyFactory = |_| Box::new(DoNothing);
+    let config = GameConfig {
+        turn_limit: 500,
+        ..GameConfig::default()
+    };
+    let report = run_many(config, &[("idle", IDLE); 4], 40, 0).unwrap();
+    let landings = &report.landings;
+    assert_eq!(landings.ranked()[0], JAIL);
+    let average = 1.0 / 40.0;
+    for square in ORANGES {
+        assert!(
+            landings.frequencies()[square] > average,
+            "square {} at {}",
+            square,
+            landings.frequencies()[square]
+        );
+    }
+}
+
+// Every square landed on as many times as its number, but Jail 200 times and
+// Go To Jail never.
+fn synthetic() -> LandingStats {
+    let mut counts: Vec<u64> = (0..40).collect();
+    counts[JAIL] = 200;
+    counts[30] = 0;
+    LandingStats::from_counts(BoardEdition::Standard, counts).unwrap()
+}
+
+#[test]
+fn a_distribution_renders_as_the_golden_heatmap() {
+    let heatmap = synthetic().render_heatmap();
+    assert_eq!(
+        heatmap,
+        include_str!("fixtures/heatmap.txt"),
+        "\n{}",
+        heatmap
+    );
+}
+
+#[test]
+fn counts_must_cover_the_board() {
+    assert_eq!(
+        LandingStats::from_counts(BoardEdition::Standard, vec![1; 39]),
+        None
+    );
+    let stats = synthetic();
+    assert_eq!(stats.total(), 200 + (0..40).sum::<u64>() - 10 - 30);
+    assert_eq!(&stats.ranked()[..3], [JAIL, 39, 38]);
+}
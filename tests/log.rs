@@ -0,0 +1,187 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{
+    Command, Effect, EventRecord, GameConfig, GameLog, GameState, HouseRules, Money, PlayerId,
+    PropertyId, RollResult, SaveError, Trade, LOG_VERSION,
+};
+
+#[test]
+fn a_version_1_log_gains_turn_metadata() {
+    let log = GameLog::from_json(include_str!("fixtures/log-v1.json")).unwrap();
+    let (config, actions) = common::scripted_game();
+    let live = GameState::play(config.clone(), &actions).unwrap();
+    assert_eq!(log.config, config);
+    assert_eq!(log.records, live.events());
+    assert_eq!(log.records[8].turn, 3);
+    assert_eq!(log.records[8].actor, Some(PlayerId(0)));
+    common::assert_same(&log.replay().unwrap(), &live);
+}
+
+#[test]
+fn the_current_version_round_trips() {
+    let (config, actions) = common::scripted_game();
+    let log = GameState::play(config, &actions).unwrap().log();
+    let json = log.to_json().unwrap();
+    assert!(json.contains(&format!("\"version\":{}", LOG_VERSION)));
+    assert_eq!(GameLog::from_json(&json).unwrap(), log);
+}
+
+#[test]
+fn newer_versions_are_refused_by_name() {
+    let json = include_str!("fixtures/log-v1.json").replace("\"version\": 1", "\"version\": 7");
+    let error = GameLog::from_json(&json).unwrap_err();
+    assert!(matches!(
+        error,
+        SaveError::UnsupportedVersion { found: Some(7), .. }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "version 7 is not supported; this build reads up to version 6"
+    );
+}
+
+#[test]
+fn a_version_1_log_that_no_longer_replays_is_a_migration_error() {
+    let json = include_str!("fixtures/log-v1.json").replacen("\"EndTurn\": 0", "\"EndTurn\": 1", 1);
+    assert!(matches!(
+        GameLog::from_json(&json),
+        Err(SaveError::Migration { from: 1, .. })
+    ));
+}
+
+// Player 0 builds on the light blues, gives away every dollar and is sent to
+// jail, where failing a third time to roll out forces the fine.
+fn jailed_with_houses(hash_chain: bool, jail_fine: Money) -> GameState {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let (oriental, vermont, connecticut) = (PropertyId(6), PropertyId(8), PropertyId(9));
+    let trade = |offered_cash, requested| Trade {
+        proposer: p0,
+        recipient: p1,
+        offered: vec![],
+        requested,
+        offered_cash: Money(offered_cash),
+        requested_cash: Money(0),
+        offered_jail_cards: 0,
+        requested_jail_cards: 0,
+    };
+    let mut commands = vec![
+        Command::RollDice(p0, RollResult(2, 4)),
+        Command::BuyProperty(p0, oriental),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(3, 5)),
+        Command::BuyProperty(p1, vermont),
+        Command::EndTurn(p1),
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::BuyProperty(p0, connecticut),
+        Command::ProposeTrade(trade(0, vec![vermont])),
+        Command::AcceptTrade(p1),
+    ];
+    for lot in [oriental, vermont, connecticut] {
+        commands.push(Command::BuyHouse(p0, lot));
+    }
+    commands.extend([
+        Command::ProposeTrade(trade(1130, vec![])),
+        Command::AcceptTrade(p1),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(2, 3)),
+        Command::BuyProperty(p1, PropertyId(13)),
+        Command::EndTurn(p1),
+        // Kentucky Ave, then Go To Jail.
+        Command::RollDice(p0, RollResult(6, 6)),
+        Command::DeclineToBuy(p0),
+        Command::RollDice(p0, RollResult(4, 5)),
+    ]);
+    for _ in 0..3 {
+        commands.extend([
+            Command::EndTurn(p0),
+            Command::RollDice(p1, RollResult(1, 2)),
+            Command::DeclineToBuy(p1),
+            Command::EndTurn(p1),
+            Command::RollDice(p0, RollResult(1, 2)),
+        ]);
+    }
+    let config = GameConfig {
+        players: 2,
+        hash_chain,
+        rules: HouseRules::default()
+            .with_auctions_enabled(false)
+            .with_jail_fine(jail_fine),
+        ..GameConfig::default()
+    };
+    GameState::play(config, &commands).unwrap()
+}
+
+// A $1000 fine, which the $235 the houses and lots would raise can't pay.
+fn bankrupt_with_houses(hash_chain: bool) -> GameLog {
+    let mut state = jailed_with_houses(hash_chain, Money(1000));
+    state
+        .apply(Command::DeclareBankruptcy(PlayerId(0)))
+        .unwrap();
+    state.log()
+}
+
+// As an older version would have logged it, with or without the sales.
+fn declared(log: &GameLog, sales: bool, version: u64) -> String {
+    let mut old = log.clone();
+    let mut previous = None;
+    for record in &mut old.records {
+        record
+            .effects
+            .retain(|effect| sales || !matches!(effect, Effect::SoldBuildings { .. }));
+        if log.config.hash_chain {
+            record.digest = Some(record.chained(previous));
+            previous = record.digest;
+        }
+    }
+    old.to_json().unwrap().replace(
+        &format!("\"version\":{}", LOG_VERSION),
+        &format!("\"version\":{}", version),
+    )
+}
+
+// Version 4 logged the money for the houses but not their sale.
+#[test]
+fn version_4_logs_gain_the_building_sales() {
+    for hash_chain in [false, true] {
+        let log = bankrupt_with_houses(hash_chain);
+        let sales = log.records.last().unwrap().effects.iter();
+        let sold = sales
+            .filter(|effect| matches!(effect, Effect::SoldBuildings { houses: 1, .. }))
+            .count();
+        assert_eq!(sold, 3);
+
+        let migrated = GameLog::from_json(&declared(&log, false, 4)).unwrap();
+        assert_eq!(migrated, log);
+        migrated.replay().unwrap();
+    }
+}
+
+// Version 5 let a player go bankrupt who could have sold up and paid. Such
+// a game can't be played under the rules now, so the log is refused.
+#[test]
+fn version_5_logs_with_a_bankruptcy_that_could_have_paid_are_refused() {
+    // The $235 would cover a $100 fine.
+    let state = jailed_with_houses(false, Money(100));
+    let mut log = state.log();
+    log.records.push(EventRecord {
+        seq: log.records.len(),
+        turn: state.turn_number(),
+        actor: Some(PlayerId(0)),
+        command: Command::DeclareBankruptcy(PlayerId(0)),
+        effects: vec![],
+        time: None,
+        digest: None,
+    });
+    let json = log.to_json().unwrap();
+    assert!(GameLog::from_json(&json).unwrap().replay().is_err());
+    let old = json.replace(&format!("\"version\":{}", LOG_VERSION), "\"version\":5");
+    assert!(matches!(
+        GameLog::from_json(&old),
+        Err(SaveError::Migration { from: 5, .. })
+    ));
+    // One that couldn't have paid comes through as it was.
+    let log = bankrupt_with_houses(false);
+    assert_eq!(GameLog::from_json(&declared(&log, true, 5)).unwrap(), log);
+}
@@ -0,0 +1,131 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use monopoly::{
+    simulate, Command, GameConfig, GameState, GreedyBuyer, Money, PlayerId, RollResult, Strategy,
+};
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+
+// Keeps every message as (level, target, message).
+struct Capture(Mutex<Vec<(Level, String, String)>>);
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// The logger is the whole process's, so tests take turns with it. Each starts
+// with nothing captured and everything enabled.
+fn listen() -> MutexGuard<'static, ()> {
+    static TURN: Mutex<()> = Mutex::new(());
+    static INSTALL: Once = Once::new();
+    let turn = TURN.lock().unwrap_or_else(PoisonError::into_inner);
+    INSTALL.call_once(|| log::set_logger(&CAPTURE).unwrap());
+    log::set_max_level(LevelFilter::Trace);
+    CAPTURE.0.lock().unwrap().clear();
+    turn
+}
+
+fn captured() -> Vec<(Level, String, String)> {
+    CAPTURE.0.lock().unwrap().clone()
+}
+
+fn two_players() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn a_rejected_command_logs_why_at_debug() {
+    let _turn = listen();
+    let mut state = two_players();
+    let error = state.apply(Command::EndTurn(PlayerId(1))).unwrap_err();
+    assert_eq!(
+        captured(),
+        [(
+            Level::Debug,
+            "monopoly::apply".to_string(),
+            format!("rejected EndTurn(PlayerId(1)): {}", error),
+        )]
+    );
+}
+
+#[test]
+fn rent_and_auctions_log_under_their_own_targets() {
+    let _turn = listen();
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_players();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::DeclineToBuy(p0)).unwrap();
+    state.apply(Command::PlaceBid(p0, Money(10))).unwrap();
+    state.apply(Command::PassAuction(p1)).unwrap();
+    state.apply(Command::EndTurn(p0)).unwrap();
+    state
+        .apply(Command::RollDice(p1, RollResult(1, 2)))
+        .unwrap();
+    let messages = |target: &str| -> Vec<String> {
+        captured()
+            .into_iter()
+            .filter(|(level, logged, _)| *level == Level::Debug && logged == target)
+            .map(|(.., message)| message)
+            .collect()
+    };
+    assert_eq!(
+        messages("monopoly::auction"),
+        [
+            "player 0 declines Baltic Ave, which goes to auction",
+            "player 0 bids $10",
+            "player 1 drops out",
+            "player 0 wins Baltic Ave for $10",
+        ]
+    );
+    assert_eq!(
+        messages("monopoly::rent"),
+        ["player 1 owes player 0 $4 on Baltic Ave"]
+    );
+}
+
+#[test]
+fn games_play_the_same_whether_or_not_anyone_is_listening() {
+    let play = || {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(GreedyBuyer::default()),
+            Box::new(GreedyBuyer::default()),
+        ];
+        simulate(
+            GameConfig {
+                players: 2,
+                ..GameConfig::default()
+            },
+            &mut strategies,
+            3,
+        )
+        .unwrap()
+    };
+    let _turn = listen();
+    log::set_max_level(LevelFilter::Off);
+    let quiet = play();
+    assert!(captured().is_empty());
+    log::set_max_level(LevelFilter::Trace);
+    let heard = play();
+    assert_eq!(quiet, heard);
+    assert!(captured()
+        .iter()
+        .any(|(_, target, _)| target == "monopoly::rent"));
+}
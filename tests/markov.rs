@@ -0,0 +1,91 @@
+use monopoly::{
+    simulate, steady_state, Board, BoardEdition, Command, Dice, DoNothing, Effect, GameConfig,
+    HouseRules, Strategy,
+};
+
+const JAIL: usize = 10;
+const GO_TO_JAIL: usize = 30;
+
+fn exact() -> Vec<f64> {
+    steady_state(
+        &Board::for_edition(BoardEdition::Standard),
+        &HouseRules::default(),
+    )
+}
+
+#[test]
+fn the_distribution_covers_every_roll() {
+    let odds = exact();
+    assert_eq!(odds.len(), 40);
+    assert!((odds.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    // Nobody is ever left standing on Go To Jail.
+    assert_eq!(odds[GO_TO_JAIL], 0.0);
+    let most = (0..40).max_by(|a, b| odds[*a].total_cmp(&odds[*b]));
+    assert_eq!(most, Some(JAIL));
+    // Illinois Avenue, with a card of its own, beats its neighbours.
+    assert!(odds[24] > odds[23] && odds[24] > odds[25]);
+}
+
+// Two players who never buy anything, so the game runs to the turn limit,
+// and never pay to leave jail, as the chain assumes.
+fn lands_about_where_the_chain_says(rules: HouseRules) {
+    let exact = steady_state(&Board::for_edition(BoardEdition::Standard), &rules);
+    let config = GameConfig {
+        players: 2,
+        turn_limit: 60_000,
+        rules,
+        ..GameConfig::default()
+    };
+    let mut players: Vec<Box<dyn Strategy>> = vec![Box::new(DoNothing), Box::new(DoNothing)];
+    let result = simulate(config, &mut players, 7).unwrap();
+    assert_eq!(result.winner, None);
+
+    let mut counts = [0u32; 40];
+    for record in &result.log {
+        if !matches!(
+            record.command,
+            Command::RollDice(..) | Command::RollMany(..)
+        ) {
+            continue;
+        }
+        let end = record
+            .effects
+            .iter()
+            .rev()
+            .find_map(|effect| match effect {
+                Effect::Moved { to, .. } => Some(*to),
+                Effect::WentToJail(_) => Some(JAIL),
+                _ => None,
+            })
+            .unwrap_or(JAIL); // a failed roll for doubles in jail
+        counts[end] += 1;
+    }
+    let rolls: u32 = counts.iter().sum();
+    for (square, odds) in exact.iter().enumerate() {
+        let seen = counts[square] as f64 / rolls as f64;
+        assert!(
+            (seen - odds).abs() < 0.003,
+            "square {}: {:.4} seen, {:.4} expected",
+            square,
+            seen,
+            odds
+        );
+    }
+}
+
+#[test]
+fn a_long_game_lands_about_where_the_chain_says() {
+    lands_about_where_the_chain_says(HouseRules::default());
+}
+
+#[test]
+fn the_chain_follows_the_rules_dice() {
+    let dice = Dice { count: 3, faces: 6 };
+    let odds = steady_state(
+        &Board::for_edition(BoardEdition::Standard),
+        &HouseRules::default().with_dice(dice),
+    );
+    assert!((odds.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert_ne!(odds, exact());
+    lands_about_where_the_chain_says(HouseRules::default().with_dice(dice));
+}
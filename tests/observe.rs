@@ -0,0 +1,153 @@
+use monopoly::{
+    Command, EventRecord, GameConfig, GameState, GameStateBuilder, Money, Observed, Observer,
+    PendingDecision, PlayerId, PropertyId, RollResult,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Writes down every callback it gets, as a line, in a log it shares.
+struct Recorder {
+    name: &'static str,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl Recorder {
+    fn note(&self, line: String) {
+        self.log
+            .borrow_mut()
+            .push(format!("{}: {}", self.name, line));
+    }
+}
+
+impl Observer for Recorder {
+    fn on_event(&mut self, record: &EventRecord, state: &GameState) {
+        self.note(format!(
+            "event {} {}",
+            record.seq,
+            record.command.display_with(state)
+        ));
+    }
+
+    fn on_turn_start(&mut self, turn: u32, player: PlayerId, _state: &GameState) {
+        self.note(format!("turn {} for player {}", turn, player.0));
+    }
+
+    fn on_decision_required(&mut self, decision: &PendingDecision, _state: &GameState) {
+        self.note(format!("decision for player {}", decision.player().0));
+    }
+
+    fn on_game_over(&mut self, winner: PlayerId, _state: &GameState) {
+        self.note(format!("player {} won", winner.0));
+    }
+}
+
+struct Panicker;
+
+impl Observer for Panicker {
+    fn on_event(&mut self, _record: &EventRecord, _state: &GameState) {
+        panic!("observer failed");
+    }
+}
+
+fn two_players() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+// The game with two recorders watching it.
+fn observed(state: GameState) -> (Observed, Rc<RefCell<Vec<String>>>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut game = Observed::new(state);
+    for name in ["first", "second"] {
+        game.add_observer(Box::new(Recorder {
+            name,
+            log: log.clone(),
+        }));
+    }
+    (game, log)
+}
+
+#[test]
+fn a_turn_is_told_in_order_to_each_observer() {
+    let (mut game, log) = observed(two_players());
+    let p0 = PlayerId(0);
+    game.apply(Command::RollDice(p0, RollResult(1, 2))).unwrap();
+    game.apply(Command::BuyProperty(p0, PropertyId(3))).unwrap();
+    game.apply(Command::EndTurn(p0)).unwrap();
+    assert_eq!(
+        *log.borrow(),
+        [
+            "first: event 0 Player 0 rolled 1+2 (3)",
+            "first: decision for player 0",
+            "second: event 0 Player 0 rolled 1+2 (3)",
+            "second: decision for player 0",
+            "first: event 1 Player 0 bought Baltic Ave for $60",
+            "second: event 1 Player 0 bought Baltic Ave for $60",
+            "first: event 2 Player 0 ended their turn",
+            "first: turn 2 for player 1",
+            "second: event 2 Player 0 ended their turn",
+            "second: turn 2 for player 1",
+        ]
+    );
+}
+
+#[test]
+fn refused_commands_are_not_told() {
+    let (mut game, log) = observed(two_players());
+    assert!(game.apply(Command::EndTurn(PlayerId(1))).is_err());
+    assert!(log.borrow().is_empty());
+}
+
+#[test]
+fn the_end_of_the_game_is_told() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    // Player 0 cannot pay the rent on Baltic Ave with a hotel.
+    let state = GameStateBuilder::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .player(Money(10), 0)
+    .player(Money(1500), 0)
+    .owns(p1, PropertyId(1), 5)
+    .owns(p1, PropertyId(3), 5)
+    .build()
+    .unwrap();
+    let (mut game, log) = observed(state);
+    game.apply(Command::RollDice(p0, RollResult(1, 2))).unwrap();
+    game.apply(Command::DeclareBankruptcy(p0)).unwrap();
+    assert_eq!(
+        log.borrow()[4..],
+        [
+            "first: event 1 Player 0 declared bankruptcy",
+            "first: player 1 won",
+            "second: event 1 Player 0 declared bankruptcy",
+            "second: player 1 won",
+        ]
+    );
+}
+
+#[test]
+fn a_panicking_observer_is_dropped_and_the_rest_are_told() {
+    let (mut game, log) = observed(two_players());
+    game.add_observer(Box::new(Panicker));
+    game.add_observer(Box::new(Recorder {
+        name: "third",
+        log: log.clone(),
+    }));
+    let p0 = PlayerId(0);
+    game.apply(Command::RollDice(p0, RollResult(1, 2))).unwrap();
+    assert_eq!(game.dropped(), 1);
+    assert_eq!(game.state().position(p0), Ok(3));
+    game.apply(Command::BuyProperty(p0, PropertyId(3))).unwrap();
+    assert_eq!(game.state().owner_of(PropertyId(3)), Some(p0));
+    assert_eq!(
+        log.borrow()
+            .iter()
+            .filter(|line| line.starts_with("third: event"))
+            .count(),
+        2
+    );
+}
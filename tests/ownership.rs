@@ -0,0 +1,603 @@
+use monopoly::{
+    Bid, Command, Effect, GameConfig, GameError, GameState, GameStateBuilder, HouseRules, Money,
+    PendingDecision, PlayerId, PlayerStatus, PropertyId, RollResult, Trade,
+};
+
+const BALTIC: PropertyId = PropertyId(3);
+const VERMONT: PropertyId = PropertyId(8);
+const ST_CHARLES: PropertyId = PropertyId(11);
+const READING: PropertyId = PropertyId(5);
+
+fn two_player_game() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+fn roll_and_buy(state: &mut GameState, player: PlayerId, roll: RollResult, property: PropertyId) {
+    state.apply(Command::RollDice(player, roll)).unwrap();
+    state.apply(Command::BuyProperty(player, property)).unwrap();
+    state.apply(Command::EndTurn(player)).unwrap();
+}
+
+#[test]
+fn ownership_follows_buy_trade_and_bankruptcy() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    assert_eq!(state.owner_of(VERMONT), None);
+
+    roll_and_buy(&mut state, p0, RollResult(3, 5), VERMONT);
+    assert_eq!(state.owner_of(VERMONT), Some(p0));
+    assert_eq!(state.cash(p0), Ok(Money(1400)));
+    roll_and_buy(&mut state, p1, RollResult(1, 2), BALTIC);
+    roll_and_buy(&mut state, p0, RollResult(1, 2), ST_CHARLES);
+    assert_eq!(
+        state.properties_owned_by(p0),
+        [(VERMONT, false), (ST_CHARLES, false)]
+    );
+
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: p0,
+            recipient: p1,
+            offered: vec![ST_CHARLES],
+            requested: vec![],
+            offered_cash: Money(0),
+            requested_cash: Money(1435),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    assert_eq!(state.owner_of(ST_CHARLES), Some(p0));
+    state.apply(Command::AcceptTrade(p1)).unwrap();
+    assert_eq!(state.owner_of(ST_CHARLES), Some(p1));
+    assert_eq!(
+        state.properties_owned_by(p1),
+        [(BALTIC, false), (ST_CHARLES, false)]
+    );
+    assert_eq!(state.cash(p1), Ok(Money(5)));
+
+    // Player 1 mortgages both and hands over the $105 they come to, which
+    // leaves nothing to pay Vermont's $6 rent with.
+    state.apply(Command::MortgageProperty(p1, BALTIC)).unwrap();
+    state
+        .apply(Command::MortgageProperty(p1, ST_CHARLES))
+        .unwrap();
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: p1,
+            recipient: p0,
+            offered: vec![],
+            requested: vec![],
+            offered_cash: Money(105),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    state.apply(Command::AcceptTrade(p0)).unwrap();
+    state
+        .apply(Command::RollDice(p1, RollResult(2, 3)))
+        .unwrap();
+    state.apply(Command::DeclareBankruptcy(p1)).unwrap();
+    assert_eq!(state.owner_of(BALTIC), Some(p0));
+    assert_eq!(state.owner_of(ST_CHARLES), Some(p0));
+    assert_eq!(state.properties_owned_by(p1), []);
+    assert_eq!(
+        state.properties_owned_by(p0),
+        [(BALTIC, true), (VERMONT, false), (ST_CHARLES, true)]
+    );
+    assert_eq!(state.cash(p0), Ok(Money(2800)));
+    assert_eq!(state.winner(), Some(p0));
+}
+
+#[test]
+fn properties_owned_by_flags_mortgaged_titles() {
+    let p0 = PlayerId(0);
+    let mut state = two_player_game();
+    roll_and_buy(&mut state, p0, RollResult(1, 2), BALTIC);
+    state.apply(Command::MortgageProperty(p0, BALTIC)).unwrap();
+    assert_eq!(state.properties_owned_by(p0), [(BALTIC, true)]);
+    assert_eq!(state.owner_of(BALTIC), Some(p0));
+    assert_eq!(state.cash(p0), Ok(Money(1470)));
+    state
+        .apply(Command::UnmortgageProperty(p0, BALTIC))
+        .unwrap();
+    assert_eq!(state.properties_owned_by(p0), [(BALTIC, false)]);
+    assert_eq!(state.cash(p0), Ok(Money(1437)));
+}
+
+// Brown hotels, houses on the light blues, a railroad, a utility and a
+// mortgaged Boardwalk, with what each would raise worked out by hand.
+#[test]
+fn mortgage_capacity_counts_buildings_at_half_and_clear_titles() {
+    let p0 = PlayerId(0);
+    let light_blues = [PropertyId(6), VERMONT, PropertyId(9)];
+    let state = GameStateBuilder::default()
+        .player(Money(100), 0)
+        .player(Money(1500), 0)
+        .owns(p0, PropertyId(1), 5)
+        .owns(p0, BALTIC, 5)
+        .owns(p0, light_blues[0], 2)
+        .owns(p0, light_blues[1], 2)
+        .owns(p0, light_blues[2], 1)
+        .owns(p0, READING, 0)
+        .owns(p0, PropertyId(12), 0)
+        .owns(p0, PropertyId(39), 0)
+        .mortgaged(PropertyId(39))
+        .build()
+        .unwrap();
+    assert_eq!(state.mortgaged_properties(p0), [PropertyId(39)]);
+    assert_eq!(
+        state.unmortgaged_properties(p0),
+        [
+            PropertyId(1),
+            BALTIC,
+            READING,
+            light_blues[0],
+            light_blues[1],
+            light_blues[2],
+            PropertyId(12)
+        ]
+    );
+    // Hotels: 2 x $250 / 2. Houses: 5 x $50 / 2.
+    let buildings = 250 + 125;
+    // Browns $30 each, light blues $50, $50 and $60, Reading $100 and the
+    // Electric Company $75; Boardwalk is already mortgaged.
+    let mortgages = 30 + 30 + 50 + 50 + 60 + 100 + 75;
+    assert_eq!(state.mortgage_capacity(p0), Money(buildings + mortgages));
+    assert_eq!(state.mortgage_capacity(PlayerId(1)), Money(0));
+}
+
+// Bankruptcy is only open once cash and capacity together fall short.
+#[test]
+fn bankruptcy_waits_until_selling_up_would_not_pay() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    // Boardwalk with a house is $200, and Reading would raise $100.
+    let owing = |cash| {
+        let mut state = GameStateBuilder::default()
+            .player(Money(cash), 35)
+            .player(Money(1500), 0)
+            .owns(p0, READING, 0)
+            .owns(p1, PropertyId(37), 1)
+            .owns(p1, PropertyId(39), 1)
+            .build()
+            .unwrap();
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 3)))
+            .unwrap();
+        state
+    };
+    let mut state = owing(100);
+    assert_eq!(state.mortgage_capacity(p0), Money(100));
+    assert!(matches!(
+        state.apply(Command::DeclareBankruptcy(p0)),
+        Err(GameError::IllegalAction(_))
+    ));
+    assert!(!state
+        .legal_actions()
+        .contains(&Command::DeclareBankruptcy(p0)));
+    // Nor can they concede their way out of it.
+    assert!(matches!(
+        state.apply(Command::Resign(p0)),
+        Err(GameError::IllegalAction(_))
+    ));
+    state.apply(Command::MortgageProperty(p0, READING)).unwrap();
+    state.apply(Command::PayDebt(p0)).unwrap();
+    assert_eq!(state.cash(p0), Ok(Money(0)));
+    assert_eq!(state.mortgaged_properties(p0), [READING]);
+
+    let mut state = owing(99);
+    state.apply(Command::DeclareBankruptcy(p0)).unwrap();
+    assert_eq!(state.owner_of(READING), Some(p1));
+}
+
+#[test]
+fn auction_winner_becomes_owner() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::DeclineToBuy(p0)).unwrap();
+    state.apply(Command::PassAuction(p0)).unwrap();
+    state.apply(Command::PlaceBid(p1, Money(10))).unwrap();
+    assert_eq!(state.owner_of(BALTIC), Some(p1));
+    assert_eq!(state.cash(p1), Ok(Money(1490)));
+    assert_eq!(state.pending(), None);
+}
+
+// A bid may count on what mortgaging would raise, but the title waits until
+// it's paid for.
+#[test]
+fn a_bid_beyond_cash_is_settled_by_selling_up() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let boardwalk = PropertyId(39);
+    // Boardwalk and Reading would raise $200 and $100.
+    let mut state = GameStateBuilder::default()
+        .player(Money(50), 0)
+        .player(Money(1500), 0)
+        .owns(p0, boardwalk, 0)
+        .owns(p0, READING, 0)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::DeclineToBuy(p0)).unwrap();
+    assert_eq!(
+        state.apply(Command::PlaceBid(p0, Money(351))),
+        Err(GameError::InsufficientFunds {
+            needed: Money(351),
+            available: Money(350),
+        })
+    );
+    state.apply(Command::PlaceBid(p0, Money(200))).unwrap();
+    state.apply(Command::PassAuction(p1)).unwrap();
+    assert_eq!(
+        state.pending(),
+        Some(&PendingDecision::PayBid {
+            debtor: p0,
+            property: BALTIC,
+            amount: Money(200),
+        })
+    );
+    assert_eq!(state.owner_of(BALTIC), None);
+    assert!(matches!(
+        state.apply(Command::PayDebt(p0)),
+        Err(GameError::InsufficientFunds { .. })
+    ));
+    state
+        .apply(Command::MortgageProperty(p0, boardwalk))
+        .unwrap();
+    state.apply(Command::PayDebt(p0)).unwrap();
+    assert_eq!(state.owner_of(BALTIC), Some(p0));
+    assert_eq!(state.cash(p0), Ok(Money(50)));
+    assert_eq!(state.pending(), None);
+}
+
+// Player 0 turns down Baltic Ave, with or without auctions.
+fn declined(auctions_enabled: bool) -> GameState {
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        rules: HouseRules {
+            auctions_enabled,
+            ..HouseRules::default()
+        },
+        ..GameConfig::default()
+    })
+    .unwrap();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::DeclineToBuy(PlayerId(0))).unwrap();
+    state
+}
+
+#[test]
+fn without_auctions_a_declined_property_stays_with_the_bank() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    assert!(matches!(
+        declined(true).pending(),
+        Some(PendingDecision::Auction { .. })
+    ));
+
+    let mut state = declined(false);
+    assert_eq!(state.pending(), None);
+    assert_eq!(state.owner_of(BALTIC), None);
+    state.apply(Command::EndTurn(p0)).unwrap();
+    assert_eq!(state.current_player(), p1);
+}
+
+#[test]
+fn without_auctions_bidding_is_refused() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let bidding = [
+        Command::PlaceBid(p1, Money(10)),
+        Command::PassAuction(p0),
+        Command::AuctionProperty(BALTIC, vec![Bid(p1, Money(10))]),
+    ];
+    let disabled = GameError::IllegalAction("auctions disabled".to_string());
+    for command in bidding {
+        assert_eq!(declined(false).apply(command), Err(disabled.clone()));
+    }
+    // With them, the same commands are the auction's to take or refuse.
+    let mut state = declined(true);
+    assert_ne!(
+        state.apply(Command::PlaceBid(p1, Money(10))),
+        Err(disabled.clone())
+    );
+    state
+        .apply(Command::AuctionProperty(BALTIC, vec![Bid(p1, Money(10))]))
+        .unwrap();
+    assert_eq!(state.owner_of(BALTIC), Some(p1));
+}
+
+fn trading(trades_any_time: bool) -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        rules: HouseRules {
+            trades_any_time,
+            ..HouseRules::default()
+        },
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+fn vermont_for_cash(proposer: PlayerId, recipient: PlayerId) -> Trade {
+    Trade {
+        proposer,
+        recipient,
+        offered: vec![VERMONT],
+        requested: vec![],
+        offered_cash: Money(0),
+        requested_cash: Money(150),
+        offered_jail_cards: 0,
+        requested_jail_cards: 0,
+    }
+}
+
+#[test]
+fn strict_trades_keep_to_the_proposers_turn() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let off_turn =
+        GameError::IllegalAction("trades may only be made during the proposer's own turn".into());
+    for trades_any_time in [true, false] {
+        let mut state = trading(trades_any_time);
+        roll_and_buy(&mut state, p0, RollResult(3, 5), VERMONT);
+        // Player 1's turn now, so player 0 is out of turn.
+        let proposed = state.apply(Command::ProposeTrade(vermont_for_cash(p0, p1)));
+        if trades_any_time {
+            proposed.unwrap();
+            state.apply(Command::AcceptTrade(p1)).unwrap();
+            assert_eq!(state.owner_of(VERMONT), Some(p1));
+        } else {
+            assert_eq!(proposed, Err(off_turn.clone()));
+            assert_eq!(state.owner_of(VERMONT), Some(p0));
+        }
+    }
+}
+
+#[test]
+fn strict_trades_lapse_when_the_turn_passes() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    for trades_any_time in [true, false] {
+        let mut state = trading(trades_any_time);
+        state
+            .apply(Command::RollDice(p0, RollResult(3, 5)))
+            .unwrap();
+        state.apply(Command::BuyProperty(p0, VERMONT)).unwrap();
+        state
+            .apply(Command::ProposeTrade(vermont_for_cash(p0, p1)))
+            .unwrap();
+        state.apply(Command::EndTurn(p0)).unwrap();
+        let accepted = state.apply(Command::AcceptTrade(p1));
+        assert_eq!(accepted.is_ok(), trades_any_time);
+        if !trades_any_time {
+            assert!(!state.legal_actions().contains(&Command::AcceptTrade(p1)));
+            state.apply(Command::RejectTrade(p1)).unwrap();
+            assert_eq!(state.owner_of(VERMONT), Some(p0));
+        }
+    }
+}
+
+#[test]
+fn strict_trades_wait_out_an_auction() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    for trades_any_time in [true, false] {
+        let mut state = trading(trades_any_time);
+        roll_and_buy(&mut state, p0, RollResult(3, 5), VERMONT);
+        state
+            .apply(Command::RollDice(p1, RollResult(3, 5)))
+            .unwrap();
+        state.apply(Command::EndTurn(p1)).unwrap();
+        // Player 0 turns down Baltic Ave, which goes up for auction.
+        state
+            .apply(Command::RollDice(p0, RollResult(1, 2)))
+            .unwrap();
+        state.apply(Command::DeclineToBuy(p0)).unwrap();
+        let proposed = state.apply(Command::ProposeTrade(vermont_for_cash(p0, p1)));
+        if trades_any_time {
+            proposed.unwrap();
+        } else {
+            assert_eq!(
+                proposed,
+                Err(GameError::IllegalAction(
+                    "trades may not be made while an auction is running".into()
+                ))
+            );
+            state.apply(Command::PassAuction(p0)).unwrap();
+            state.apply(Command::PassAuction(p1)).unwrap();
+            state
+                .apply(Command::ProposeTrade(vermont_for_cash(p0, p1)))
+                .unwrap();
+        }
+    }
+}
+
+// Player 0 holds the browns with two houses each, Reading Railroad under a
+// mortgage and $500, and resigns on their own turn.
+fn resigning_landlord() -> GameState {
+    let p0 = PlayerId(0);
+    GameStateBuilder::new(GameConfig {
+        players: 3,
+        ..GameConfig::default()
+    })
+    .player(Money(500), 0)
+    .player(Money(1500), 0)
+    .player(Money(1500), 0)
+    .owns(p0, PropertyId(1), 2)
+    .owns(p0, BALTIC, 2)
+    .owns(p0, READING, 0)
+    .mortgaged(READING)
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn a_resignation_puts_everything_up_for_auction() {
+    let (p0, p1, p2) = (PlayerId(0), PlayerId(1), PlayerId(2));
+    let mut state = resigning_landlord();
+    let houses = state.houses_remaining().unwrap();
+    let effects = state.apply(Command::Resign(p0)).unwrap();
+    assert!(effects.contains(&Effect::WentBankrupt(p0)));
+    assert_eq!(
+        effects
+            .iter()
+            .filter(|effect| matches!(effect, Effect::SoldBuildings { .. }))
+            .count(),
+        2
+    );
+    assert_eq!(state.houses_remaining(), Some(houses + 4));
+    assert_eq!(state.cash(p0), Ok(Money(0)));
+    assert_eq!(
+        state.player(p0).unwrap().status(),
+        PlayerStatus::Eliminated { on_turn: 1 }
+    );
+    assert_eq!(state.properties_owned_by(p0), []);
+    assert_eq!(state.current_player(), p1);
+    assert_eq!(state.winner(), None);
+
+    // The bank sells each title in board order, free of its mortgage, to
+    // those still playing.
+    let up_for_auction = |state: &GameState| match state.pending() {
+        Some(PendingDecision::Auction {
+            property, bidders, ..
+        }) => {
+            assert_eq!(bidders, &[p1, p2], "before anyone has bid");
+            Some(*property)
+        }
+        _ => None,
+    };
+    assert_eq!(up_for_auction(&state), Some(PropertyId(1)));
+    state.apply(Command::PlaceBid(p1, Money(10))).unwrap();
+    state.apply(Command::PassAuction(p2)).unwrap();
+    assert_eq!(up_for_auction(&state), Some(BALTIC));
+    state.apply(Command::PassAuction(p1)).unwrap();
+    state.apply(Command::PlaceBid(p2, Money(30))).unwrap();
+    assert_eq!(up_for_auction(&state), Some(READING));
+    state.apply(Command::PlaceBid(p1, Money(120))).unwrap();
+    state.apply(Command::PassAuction(p2)).unwrap();
+    assert_eq!(state.pending(), None);
+    assert_eq!(
+        state.properties_owned_by(p1),
+        [(PropertyId(1), false), (READING, false)]
+    );
+    assert_eq!(state.properties_owned_by(p2), [(BALTIC, false)]);
+    assert_eq!(state.cash(p1), Ok(Money(1370)));
+}
+
+// A bidder who goes bankrupt over their bid leaves the auctions still to come
+// to everyone else.
+#[test]
+fn a_bankrupt_bidder_drops_out_of_the_auctions_to_come() {
+    let (p0, p1, p2, p3) = (PlayerId(0), PlayerId(1), PlayerId(2), PlayerId(3));
+    let boardwalk = PropertyId(39);
+    let mut state = GameStateBuilder::new(GameConfig {
+        players: 4,
+        rules: HouseRules {
+            trades_any_time: true,
+            ..HouseRules::default()
+        },
+        ..GameConfig::default()
+    })
+    .player(Money(500), 0)
+    .player(Money(50), 0)
+    .player(Money(1500), 0)
+    .player(Money(1500), 0)
+    .owns(p0, PropertyId(1), 0)
+    .owns(p0, BALTIC, 0)
+    .owns(p0, READING, 0)
+    .owns(p1, boardwalk, 0)
+    .build()
+    .unwrap();
+    state.apply(Command::Resign(p0)).unwrap();
+
+    // Player 1 bids on what Boardwalk would raise, then gives it away.
+    state.apply(Command::PlaceBid(p1, Money(200))).unwrap();
+    state.apply(Command::PassAuction(p2)).unwrap();
+    state.apply(Command::PassAuction(p3)).unwrap();
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: p1,
+            recipient: p2,
+            offered: vec![boardwalk],
+            requested: vec![],
+            offered_cash: Money(0),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    state.apply(Command::AcceptTrade(p2)).unwrap();
+    state.apply(Command::DeclareBankruptcy(p1)).unwrap();
+    assert_eq!(state.owner_of(PropertyId(1)), None);
+    assert_eq!(
+        state.pending(),
+        Some(&PendingDecision::Auction {
+            property: BALTIC,
+            high_bid: None,
+            bidders: vec![p2, p3],
+            next: 0,
+        })
+    );
+    state.apply(Command::PlaceBid(p2, Money(10))).unwrap();
+    state.apply(Command::PassAuction(p3)).unwrap();
+    assert_eq!(state.owner_of(BALTIC), Some(p2));
+    assert!(matches!(
+        state.pending(),
+        Some(PendingDecision::Auction { property: READING, bidders, .. }) if bidders == &[p2, p3]
+    ));
+}
+
+#[test]
+fn resigning_is_for_your_own_turn_or_an_unpayable_debt() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = resigning_landlord();
+    assert_eq!(
+        state.apply(Command::Resign(p1)),
+        Err(GameError::NotYourTurn(p1))
+    );
+    state
+        .apply(Command::RollDice(p0, RollResult(2, 4)))
+        .unwrap();
+    assert!(matches!(
+        state.apply(Command::Resign(p0)),
+        Err(GameError::DecisionPending(
+            PendingDecision::BuyOrDecline { .. }
+        ))
+    ));
+
+    // Facing Baltic's rent with $1, resigning is declaring bankruptcy: the
+    // creditor gets what's left.
+    let mut state = two_player_game();
+    roll_and_buy(&mut state, p0, RollResult(1, 2), BALTIC);
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: p1,
+            recipient: p0,
+            offered: vec![],
+            requested: vec![],
+            offered_cash: Money(1499),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    state.apply(Command::AcceptTrade(p0)).unwrap();
+    state
+        .apply(Command::RollDice(p1, RollResult(1, 2)))
+        .unwrap();
+    assert!(matches!(
+        state.pending(),
+        Some(PendingDecision::PayDebt { .. })
+    ));
+    let mut declared = state.clone();
+    declared.apply(Command::DeclareBankruptcy(p1)).unwrap();
+    state.apply(Command::Resign(p1)).unwrap();
+    assert_eq!(state, declared);
+    assert_eq!(state.winner(), Some(p0));
+}
@@ -0,0 +1,337 @@
+use monopoly::{
+    Command, Effect, GameConfig, GameError, GameState, GameStateBuilder, Money, PlayerId,
+    PlayerStatus, PropertyId, RollResult, Token, MAX_NAME_LENGTH, MAX_PLAYERS,
+};
+
+#[test]
+fn accessors_report_starting_cash_and_position() {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    let ids: Vec<PlayerId> = state.players().map(|player| player.id()).collect();
+    assert_eq!(ids, [PlayerId(0), PlayerId(1), PlayerId(2), PlayerId(3)]);
+    assert_eq!(state.cash(PlayerId(2)), Ok(Money(1500)));
+    assert_eq!(state.position(PlayerId(2)), Ok(0));
+    assert_eq!(state.player(PlayerId(2)).unwrap().id(), PlayerId(2));
+}
+
+#[test]
+fn accessors_reject_unknown_players() {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    assert_eq!(
+        state.player(PlayerId(4)),
+        Err(GameError::InvalidPlayer(PlayerId(4)))
+    );
+    assert_eq!(
+        state.cash(PlayerId(-3)),
+        Err(GameError::InvalidPlayer(PlayerId(-3)))
+    );
+}
+
+#[test]
+fn added_player_gets_starting_cash_on_go() {
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        starting_cash: Money(900),
+        ..GameConfig::default()
+    })
+    .unwrap();
+    let id = state.add_player().unwrap();
+    assert_eq!(id, PlayerId(2));
+    assert_eq!(state.cash(id), Ok(Money(900)));
+    assert_eq!(state.position(id), Ok(0));
+}
+
+#[test]
+fn tokens_are_handed_out_in_order() {
+    let mut state = GameState::new(GameConfig {
+        players: MAX_PLAYERS,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    let tokens: Vec<Token> = state.players().map(|player| player.token()).collect();
+    assert_eq!(tokens, Token::ALL);
+    assert_eq!(
+        state.add_player_as(Token::Dog),
+        Err(GameError::TooManyPlayers)
+    );
+}
+
+#[test]
+fn a_chosen_token_is_skipped_by_those_who_do_not_choose() {
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    assert_eq!(state.add_player_as(Token::RaceCar), Ok(PlayerId(2)));
+    assert_eq!(state.add_player_as(Token::Boot), Ok(PlayerId(3)));
+    assert_eq!(state.add_player(), Ok(PlayerId(4)));
+    let tokens: Vec<Token> = state.players().map(|player| player.token()).collect();
+    assert_eq!(
+        tokens,
+        [
+            Token::TopHat,
+            Token::Thimble,
+            Token::RaceCar,
+            Token::Boot,
+            Token::Dog
+        ]
+    );
+    assert!(state
+        .to_string()
+        .contains("Player 2 (race car): $1500, on GO"));
+}
+
+#[test]
+fn a_token_cannot_be_taken_twice() {
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    let error = state.add_player_as(Token::Thimble).unwrap_err();
+    assert_eq!(error, GameError::TokenTaken(Token::Thimble));
+    assert_eq!(error.to_string(), "the thimble is already taken");
+    assert_eq!(state.players().count(), 2);
+    state.check_invariants().unwrap();
+}
+
+fn two_players() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn names_stand_in_for_ids_where_given() {
+    let mut state = two_players();
+    state.rename(PlayerId(0), "  Rich Uncle  ").unwrap();
+    assert_eq!(
+        state.player(PlayerId(0)).unwrap().name(),
+        Some("Rich Uncle")
+    );
+    assert_eq!(state.player(PlayerId(1)).unwrap().name(), None);
+    let roll = Command::RollDice(PlayerId(0), RollResult(1, 2));
+    assert_eq!(
+        roll.display_with(&state).to_string(),
+        "Rich Uncle rolled 1+2 (3)"
+    );
+    let summary = state.to_string();
+    assert!(summary.contains("Turn 1: Rich Uncle to move"));
+    assert!(summary.contains("Rich Uncle (top hat): $1500, on GO"));
+    assert!(summary.contains("Player 1 (thimble): $1500, on GO"));
+
+    assert_eq!(state.display_name(PlayerId(7)), "Player 7");
+    state.apply(roll).unwrap();
+    state.undo().unwrap();
+    assert_eq!(state.display_name(PlayerId(0)), "Rich Uncle");
+}
+
+#[test]
+fn names_are_checked_and_settled_before_the_game() {
+    let mut state = two_players();
+    let blank = state.rename(PlayerId(0), "   ").unwrap_err();
+    assert_eq!(blank.to_string(), "invalid name: a name cannot be blank");
+    let long = "x".repeat(MAX_NAME_LENGTH + 1);
+    assert!(matches!(
+        state.rename(PlayerId(0), &long),
+        Err(GameError::InvalidName(_))
+    ));
+    assert_eq!(
+        state.rename(PlayerId(2), "Ghost"),
+        Err(GameError::InvalidPlayer(PlayerId(2)))
+    );
+    state
+        .rename(PlayerId(0), &"x".repeat(MAX_NAME_LENGTH))
+        .unwrap();
+
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+        .unwrap();
+    assert!(matches!(
+        state.rename(PlayerId(1), "Late"),
+        Err(GameError::IllegalAction(_))
+    ));
+    assert_eq!(state.display_name(PlayerId(1)), "Player 1");
+}
+
+// No two players go by the same name, whether chosen or given by seat, so
+// one added later can't take a name already in use either.
+#[test]
+fn names_are_never_shared() {
+    let mut state = two_players();
+    state.rename(PlayerId(0), "Rich Uncle").unwrap();
+    let taken = state.rename(PlayerId(1), " rich uncle ").unwrap_err();
+    assert_eq!(taken, GameError::NameTaken("rich uncle".to_string()));
+    assert_eq!(taken.to_string(), "rich uncle is already someone's name");
+    for seat in ["Player 0", "player 2", "Player 7"] {
+        assert_eq!(
+            state.rename(PlayerId(1), seat),
+            Err(GameError::NameTaken(seat.to_string()))
+        );
+    }
+    // Their own seat's name, and their own name again, are theirs.
+    state.rename(PlayerId(1), "Player 1").unwrap();
+    state.rename(PlayerId(0), "RICH UNCLE").unwrap();
+    assert_eq!(state.add_player(), Ok(PlayerId(2)));
+    let names: Vec<String> = state.players().map(|player| player.to_string()).collect();
+    assert_eq!(names, ["RICH UNCLE", "Player 1", "Player 2"]);
+    state.check_invariants().unwrap();
+}
+
+// Seats are handed out in order and never twice, and not at all once the
+// first roll is in.
+#[test]
+fn seats_stay_dense_and_close_with_the_first_roll() {
+    let mut state = two_players();
+    let mut seated = vec![];
+    while let Ok(id) = state.add_player() {
+        seated.push(id);
+    }
+    assert_eq!(
+        seated,
+        (2..MAX_PLAYERS as i8).map(PlayerId).collect::<Vec<_>>()
+    );
+    assert_eq!(state.add_player(), Err(GameError::TooManyPlayers));
+    let ids: Vec<PlayerId> = state.players().map(|player| player.id()).collect();
+    assert_eq!(
+        ids,
+        (0..MAX_PLAYERS as i8).map(PlayerId).collect::<Vec<_>>()
+    );
+
+    let mut state = two_players();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(3, 3)))
+        .unwrap();
+    assert_eq!(state.add_player(), Err(GameError::RosterClosed));
+    assert_eq!(
+        state.add_player_as(Token::Dog),
+        Err(GameError::RosterClosed)
+    );
+    assert_eq!(state.players().count(), 2);
+    assert_eq!(state.current_player(), PlayerId(0));
+}
+
+// Player 1 lands on player 0's hotel on Baltic Ave with $5 to their name,
+// and goes bankrupt on turn 1, leaving two to play on.
+fn one_out_of_three() -> GameState {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = GameStateBuilder::new(GameConfig {
+        players: 3,
+        ..GameConfig::default()
+    })
+    .player(Money(1500), 0)
+    .player(Money(5), 0)
+    .player(Money(1500), 0)
+    .owns(p0, PropertyId(1), 5)
+    .owns(p0, PropertyId(3), 5)
+    .current_player(p1)
+    .build()
+    .unwrap();
+    state
+        .apply(Command::RollDice(p1, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::DeclareBankruptcy(p1)).unwrap();
+    state
+}
+
+#[test]
+fn the_bankrupt_keep_their_seat_and_their_history() {
+    let p1 = PlayerId(1);
+    let mut state = one_out_of_three();
+    let active: Vec<PlayerId> = state.active_players().map(|player| player.id()).collect();
+    assert_eq!(active, [PlayerId(0), PlayerId(2)]);
+    assert_eq!(state.players().count(), 3);
+
+    let player = state.player(p1).unwrap();
+    assert_eq!(player.status(), PlayerStatus::Eliminated { on_turn: 1 });
+    assert!(player.is_bankrupt());
+    assert_eq!(player.position(), 3);
+    assert_eq!(player.token(), Token::Thimble);
+    assert_eq!(state.net_worth(p1), Ok(Money(0)));
+    assert_eq!(
+        state.player(PlayerId(2)).unwrap().status(),
+        PlayerStatus::Active
+    );
+    assert!(state
+        .to_string()
+        .contains("Player 1 (thimble): bankrupt on turn 1"));
+
+    // Play carries on, and the log still says who did what.
+    state
+        .apply(Command::RollDice(PlayerId(2), RollResult(2, 3)))
+        .unwrap();
+    let theirs: Vec<String> = state
+        .events_for(p1)
+        .map(|record| record.command.display_with(&state).to_string())
+        .collect();
+    assert_eq!(
+        theirs,
+        ["Player 1 rolled 1+2 (3)", "Player 1 declared bankruptcy"]
+    );
+    assert!(state.events()[1]
+        .effects
+        .contains(&Effect::WentBankrupt(p1)));
+    assert_eq!(state.events()[1].turn, 1);
+}
+
+#[test]
+fn only_active_players_are_offered_moves() {
+    let (p0, p2) = (PlayerId(0), PlayerId(2));
+    let mut state = one_out_of_three();
+    let offered_to = |state: &GameState| -> Vec<PlayerId> {
+        let mut actors: Vec<PlayerId> = state
+            .legal_actions()
+            .iter()
+            .filter_map(Command::actor)
+            .collect();
+        actors.dedup();
+        actors
+    };
+    assert_eq!(state.current_player(), p2);
+    assert!(!offered_to(&state).contains(&PlayerId(1)));
+    state
+        .apply(Command::RollDice(p2, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::EndTurn(p2)).unwrap();
+    // Round past the empty seat.
+    assert_eq!(state.current_player(), p0);
+    assert!(!offered_to(&state).contains(&PlayerId(1)));
+    assert_eq!(state.winner(), None);
+}
+
+#[test]
+fn players_join_up_to_the_first_roll_and_not_after() {
+    let mut state = two_players();
+    assert!(!state.has_started());
+    assert_eq!(state.add_player(), Ok(PlayerId(2)));
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+        .unwrap();
+    assert!(state.has_started());
+    let error = state.add_player().unwrap_err();
+    assert_eq!(error, GameError::RosterClosed);
+    assert_eq!(
+        error.to_string(),
+        "players can only join before the first turn"
+    );
+    assert_eq!(
+        state.add_player_as(Token::Cat),
+        Err(GameError::RosterClosed)
+    );
+    assert_eq!(state.players().count(), 3);
+
+    // Taking the roll back undoes the start.
+    state.undo().unwrap();
+    assert!(!state.has_started());
+    assert_eq!(state.add_player_as(Token::Cat), Ok(PlayerId(3)));
+}
+
+#[test]
+fn a_refused_first_command_does_not_start_the_game() {
+    let mut state = two_players();
+    assert!(state.apply(Command::EndTurn(PlayerId(1))).is_err());
+    assert!(!state.has_started());
+    assert_eq!(state.add_player(), Ok(PlayerId(2)));
+}
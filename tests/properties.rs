@@ -0,0 +1,85 @@
+#![cfg(feature = "proptest")]
+
+use monopoly::{game_states, ColorGroup, Command, GameConfig, GameState, BANK_HOTELS, BANK_HOUSES};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+// Every house and hotel is on the board or in the bank, however the game got
+// here.
+fn buildings_are_all_accounted_for(state: &GameState) -> Result<(), TestCaseError> {
+    let (mut houses, mut hotels) = (
+        state.houses_remaining().unwrap() as u32,
+        state.hotels_remaining().unwrap() as u32,
+    );
+    for color in ColorGroup::ALL.iter() {
+        for (_, built, hotel) in state.buildings_on(*color) {
+            houses += built as u32;
+            hotels += hotel as u32;
+        }
+    }
+    prop_assert_eq!(houses, BANK_HOUSES as u32);
+    prop_assert_eq!(hotels, BANK_HOTELS as u32);
+    Ok(())
+}
+
+// Applies the command, checking that dry_run saw it coming, that a refusal
+// changes nothing, and that the game is sound either way.
+fn apply_soundly(state: &mut GameState, command: Command) -> Result<bool, TestCaseError> {
+    let before = state.clone();
+    let preview = state.dry_run(command.clone());
+    prop_assert!(before.eq_including_history(state));
+    let applied = state.apply(command);
+    prop_assert_eq!(&preview, &applied);
+    if applied.is_err() {
+        prop_assert!(before.eq_including_history(state));
+    }
+    prop_assert_eq!(state.check_invariants(), Ok(()));
+    prop_assert!(state.players().all(|player| player.cash().0 >= 0));
+    buildings_are_all_accounted_for(state)?;
+    Ok(applied.is_ok())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn every_legal_action_applies(state in game_states()) {
+        for action in state.legal_actions() {
+            prop_assert!(apply_soundly(&mut state.clone(), action)?);
+        }
+    }
+
+    #[test]
+    fn legal_actions_do_not_depend_on_the_log(state in game_states()) {
+        let mut compacted = state.clone();
+        compacted.compact(0);
+        prop_assert_eq!(compacted.legal_actions(), state.legal_actions());
+    }
+
+    #[test]
+    fn any_command_is_applied_or_refused_cleanly(
+        state in game_states(),
+        commands in vec(any::<Command>(), 1..20),
+    ) {
+        let mut state = state;
+        for command in commands {
+            apply_soundly(&mut state, command)?;
+        }
+    }
+
+    // With the players naming the dice, so any roll at all can come in.
+    #[test]
+    fn any_command_is_safe_from_the_start(
+        players in 2..=8usize,
+        commands in vec(any::<Command>(), 1..60),
+    ) {
+        let mut state = GameState::new(GameConfig {
+            players,
+            ..GameConfig::default()
+        })
+        .unwrap();
+        for command in commands {
+            apply_soundly(&mut state, command)?;
+        }
+    }
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "serde")]
+
+use monopoly::{
+    ClientMessage, Command, GameConfig, GameError, GameState, PendingDecision, PlayerId,
+    PropertyId, Recipient, ServerMessage, Table,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+const TAKEOVER: Duration = Duration::from_secs(30);
+
+// Under seed 2 the first roll is 2+3, onto Reading Railroad.
+fn table() -> Table {
+    let state = GameState::new(GameConfig {
+        players: 2,
+        seed: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    Table::new(state, TAKEOVER)
+}
+
+fn join(table: &mut Table, seat: Option<i8>) -> Option<PlayerId> {
+    let mut joined = None;
+    let message = ClientMessage::Join {
+        seat: seat.map(PlayerId),
+    };
+    table.handle(&mut joined, message);
+    joined
+}
+
+fn act(table: &mut Table, seat: PlayerId, command: Command) -> Vec<(Recipient, ServerMessage)> {
+    table.handle(&mut Some(seat), ClientMessage::Action { command })
+}
+
+// The commands announced to everyone.
+fn events(out: &[(Recipient, ServerMessage)]) -> Vec<Command> {
+    out.iter()
+        .filter_map(|(recipient, message)| match (recipient, message) {
+            (Recipient::Everyone, ServerMessage::Event { record }) => Some(record.command.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn requested(out: &[(Recipient, ServerMessage)]) -> Option<PlayerId> {
+    out.iter().find_map(|(recipient, message)| match message {
+        ServerMessage::ActionRequest { seat, .. } => {
+            assert_eq!(*recipient, Recipient::Seat(*seat));
+            Some(*seat)
+        }
+        _ => None,
+    })
+}
+
+#[test]
+fn messages_are_tagged_by_type() {
+    let join: ClientMessage = serde_json::from_value(json!({ "type": "join", "seat": 1 })).unwrap();
+    assert_eq!(
+        join,
+        ClientMessage::Join {
+            seat: Some(PlayerId(1))
+        }
+    );
+    let action = ClientMessage::Action {
+        command: Command::Roll(PlayerId(0)),
+    };
+    assert_eq!(
+        serde_json::to_value(&action).unwrap(),
+        json!({ "type": "action", "command": { "Roll": 0 } })
+    );
+    let error = ServerMessage::Error {
+        message: "it is not player 1's turn".to_string(),
+        error: Some(GameError::NotYourTurn(PlayerId(1))),
+    };
+    let sent = serde_json::to_value(&error).unwrap();
+    assert_eq!(sent["type"], "error");
+    assert_eq!(sent["error"], json!({ "NotYourTurn": 1 }));
+    assert_eq!(
+        serde_json::from_value::<ServerMessage>(sent).unwrap(),
+        error
+    );
+}
+
+#[test]
+fn a_snapshot_carries_the_whole_game() {
+    let mut table = table();
+    let mut seat = None;
+    let out = table.handle(&mut seat, ClientMessage::Join { seat: None });
+    assert_eq!(seat, Some(PlayerId(0)));
+    assert_eq!(
+        out[0],
+        (
+            Recipient::Sender,
+            ServerMessage::SeatAssignment { seat: PlayerId(0) }
+        )
+    );
+    let text = serde_json::to_string(&out[1].1).unwrap();
+    match serde_json::from_str(&text).unwrap() {
+        ServerMessage::StateSnapshot { state } => assert_eq!(*state, *table.state()),
+        other => panic!("expected a snapshot, got {:?}", other),
+    }
+    // The game is waiting on the seat just taken.
+    assert_eq!(requested(&out), Some(PlayerId(0)));
+}
+
+#[test]
+fn seats_are_claimed_once() {
+    let mut table = table();
+    assert_eq!(join(&mut table, Some(1)), Some(PlayerId(1)));
+    assert_eq!(join(&mut table, Some(1)), None);
+    assert_eq!(join(&mut table, Some(5)), None);
+    assert_eq!(join(&mut table, None), Some(PlayerId(0)));
+    assert_eq!(join(&mut table, None), None);
+
+    let mut seat = Some(PlayerId(0));
+    let out = table.handle(&mut seat, ClientMessage::Join { seat: None });
+    assert!(matches!(
+        &out[..],
+        [(Recipient::Sender, ServerMessage::Error { .. })]
+    ));
+}
+
+#[test]
+fn a_loopback_session_plays_a_turn() {
+    let mut table = table();
+    let (p0, p1) = (
+        join(&mut table, None).unwrap(),
+        join(&mut table, None).unwrap(),
+    );
+
+    // Out of turn, and on someone else's behalf.
+    let out = act(&mut table, p1, Command::Roll(p1));
+    assert_eq!(
+        out,
+        [(
+            Recipient::Sender,
+            ServerMessage::Error {
+                message: "it is not player 1's turn".to_string(),
+                error: Some(GameError::NotYourTurn(p1)),
+            }
+        )]
+    );
+    let out = act(&mut table, p1, Command::Roll(p0));
+    assert!(matches!(
+        &out[..],
+        [(Recipient::Sender, ServerMessage::Error { error: None, .. })]
+    ));
+    let unseated = table.handle(
+        &mut None,
+        ClientMessage::Action {
+            command: Command::Roll(p0),
+        },
+    );
+    assert!(matches!(
+        &unseated[..],
+        [(Recipient::Sender, ServerMessage::Error { .. })]
+    ));
+
+    let out = act(&mut table, p0, Command::Roll(p0));
+    assert!(matches!(
+        out[0],
+        (Recipient::Sender, ServerMessage::ActionResult { .. })
+    ));
+    assert_eq!(events(&out), [table.state().events()[0].command.clone()]);
+    match &out[2] {
+        (
+            Recipient::Seat(seat),
+            ServerMessage::ActionRequest {
+                decision,
+                legal_actions,
+                ..
+            },
+        ) => {
+            assert_eq!(*seat, p0);
+            assert_eq!(
+                *decision,
+                Some(PendingDecision::BuyOrDecline {
+                    player: p0,
+                    property: PropertyId(5),
+                })
+            );
+            assert!(legal_actions.contains(&Command::BuyProperty(p0, PropertyId(5))));
+        }
+        other => panic!("expected a request, got {:?}", other),
+    }
+
+    act(&mut table, p0, Command::BuyProperty(p0, PropertyId(5)));
+    let out = act(&mut table, p0, Command::EndTurn(p0));
+    assert_eq!(events(&out), [Command::EndTurn(p0)]);
+    assert_eq!(requested(&out), Some(p1));
+}
+
+#[test]
+fn a_bot_takes_over_a_seat_left_too_long() {
+    let mut table = table();
+    let p0 = join(&mut table, None).unwrap();
+    let p1 = join(&mut table, None).unwrap();
+    let start = Instant::now();
+    table.leave(p1, start);
+    assert!(table.tick(start + TAKEOVER / 2).is_empty());
+
+    act(&mut table, p0, Command::Roll(p0));
+    act(&mut table, p0, Command::BuyProperty(p0, PropertyId(5)));
+    let out = act(&mut table, p0, Command::EndTurn(p0));
+    // Nobody is asked while the seat is held.
+    assert_eq!(requested(&out), None);
+    assert_eq!(table.state().to_act(), p1);
+
+    let out = table.tick(start + TAKEOVER);
+    let played = events(&out);
+    assert!(played.contains(&Command::EndTurn(p1)));
+    assert!(played.iter().all(|command| command.actor() == Some(p1)));
+    assert_eq!(requested(&out), Some(p0));
+    // The bot keeps the seat.
+    assert_eq!(join(&mut table, Some(1)), None);
+}
+
+#[test]
+fn a_seat_can_be_taken_back_before_the_bot_comes() {
+    let mut table = table();
+    let p0 = join(&mut table, None).unwrap();
+    let start = Instant::now();
+    table.leave(p0, start);
+    assert_eq!(join(&mut table, None), Some(PlayerId(1)));
+    assert_eq!(join(&mut table, Some(0)), Some(p0));
+    assert!(table.tick(start + TAKEOVER).is_empty());
+}
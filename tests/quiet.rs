@@ -0,0 +1,56 @@
+use monopoly::{Command, GameConfig, GameState, PlayerId, PropertyId, RollResult, TaxChoice};
+use std::env;
+use std::process;
+
+const CHILD: &str = "MONOPOLY_QUIET_CHILD";
+const START: &str = "-- start of game --";
+const END: &str = "-- end of game --";
+
+fn scripted_game() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    for action in [
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::BuyProperty(p0, PropertyId(3)),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(1, 3)),
+        Command::PayIncomeTax(p1, TaxChoice::Flat),
+        Command::EndTurn(p1),
+        Command::RollDice(p0, RollResult(2, 3)),
+        Command::BuyProperty(p0, PropertyId(8)),
+        Command::EndTurn(p0),
+    ] {
+        state.apply(action).unwrap();
+    }
+}
+
+// The test harness swallows output from tests, so the game runs in a child
+// copy of this binary that prints straight to the real stdout.
+#[test]
+fn the_engine_writes_nothing_to_stdout() {
+    if env::var_os(CHILD).is_some() {
+        println!("{}", START);
+        scripted_game();
+        println!("{}", END);
+        return;
+    }
+    let output = process::Command::new(env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "the_engine_writes_nothing_to_stdout",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let start = stdout.find(START).expect("the child ran the game") + START.len();
+    let end = stdout.find(END).unwrap();
+    assert_eq!(stdout[start..end].trim(), "");
+}
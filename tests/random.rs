@@ -0,0 +1,28 @@
+use monopoly::{simulate, GameConfig, RandomStrategy, Strategy};
+
+fn random_game(players: usize, seed: u64) -> monopoly::GameResult {
+    let config = GameConfig {
+        players,
+        ..GameConfig::default()
+    };
+    let mut strategies: Vec<Box<dyn Strategy>> = (0..players)
+        .map(|seat| Box::new(RandomStrategy::new(seed * 8 + seat as u64)) as Box<dyn Strategy>)
+        .collect();
+    simulate(config, &mut strategies, seed).unwrap()
+}
+
+// Everything the bots try is something the engine accepts, across every
+// corner of the rules a thousand random games reach.
+#[test]
+fn a_thousand_random_games_play_cleanly() {
+    for seed in 0..1000 {
+        let result = random_game(2 + seed as usize % 3, seed);
+        assert_eq!(result.fallbacks, 0, "seed {}", seed);
+    }
+}
+
+#[test]
+fn the_seed_decides_the_game() {
+    assert_eq!(random_game(4, 21), random_game(4, 21));
+    assert_ne!(random_game(4, 21).log, random_game(4, 22).log);
+}
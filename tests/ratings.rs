@@ -0,0 +1,77 @@
+use monopoly::{
+    tournament, DoNothing, GameConfig, GreedyBuyer, Ratings, StrategyFactory, INITIAL_RATING,
+};
+
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+const NOTHING: StrategyFactory = |_| Box::new(DoNothing);
+
+#[test]
+fn an_even_game_moves_half_the_k_factor() {
+    let mut ratings = Ratings::new(2, 32.0);
+    assert_eq!(ratings.expected(0, 1), 0.5);
+    ratings.update(0, &[1]);
+    assert_eq!(
+        ratings.ratings(),
+        [INITIAL_RATING + 16.0, INITIAL_RATING - 16.0]
+    );
+    // The favorite now gains less for the same win.
+    ratings.update(0, &[1]);
+    assert!(ratings.rating(0) - INITIAL_RATING < 32.0);
+    assert!(ratings.expected(0, 1) > 0.5);
+}
+
+#[test]
+fn a_table_of_losers_is_scored_from_the_ratings_before_the_game() {
+    let mut ratings = Ratings::new(4, 16.0);
+    ratings.update(2, &[0, 1, 3]);
+    assert_eq!(ratings.rating(2), INITIAL_RATING + 24.0);
+    for loser in [0, 1, 3] {
+        assert_eq!(ratings.rating(loser), INITIAL_RATING - 8.0);
+    }
+    let total: f64 = ratings.ratings().iter().sum();
+    assert!((total - 4.0 * INITIAL_RATING).abs() < 1e-9);
+}
+
+#[test]
+fn a_strategy_that_always_wins_rates_highest() {
+    let games = [(0, vec![1, 2]), (0, vec![2]), (0, vec![1]), (0, vec![2, 1])];
+    let mut ratings = Ratings::new(3, 24.0);
+    for (winner, losers) in &games {
+        ratings.update(*winner, losers);
+    }
+    assert!(ratings.rating(0) > ratings.rating(1));
+    assert!(ratings.rating(0) > ratings.rating(2));
+}
+
+#[test]
+fn ratings_do_not_depend_on_labels() {
+    let games = [(0, vec![1, 2]), (2, vec![0]), (1, vec![2, 0]), (2, vec![1])];
+    let relabel = [2, 0, 1];
+    let mut ratings = Ratings::new(3, 32.0);
+    let mut relabeled = Ratings::new(3, 32.0);
+    for (winner, losers) in &games {
+        ratings.update(*winner, losers);
+        let losers: Vec<usize> = losers.iter().map(|loser| relabel[*loser]).collect();
+        relabeled.update(relabel[*winner], &losers);
+    }
+    for (player, label) in relabel.iter().enumerate() {
+        assert!((ratings.rating(player) - relabeled.rating(*label)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn tournaments_rate_the_same_for_the_same_seed() {
+    let config = GameConfig {
+        players: 2,
+        turn_limit: 200,
+        ..GameConfig::default()
+    };
+    let entrants = [("greedy", GREEDY), ("nothing", NOTHING)];
+    let report = tournament(config.clone(), &entrants, 5, 3).unwrap();
+    let again = tournament(config, &entrants, 5, 3).unwrap();
+    assert_eq!(report.ratings(16.0), again.ratings(16.0));
+    let ratings = report.ratings(16.0);
+    assert!(ratings.rating(0) > INITIAL_RATING);
+    assert!(ratings.rating(0) > ratings.rating(1));
+    assert!(report.to_string().contains("rating"));
+}
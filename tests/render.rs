@@ -0,0 +1,59 @@
+use monopoly::{GameConfig, GameStateBuilder, Money, PlayerId, PropertyId};
+
+#[test]
+fn a_game_under_way_renders_as_the_golden_board() {
+    let state = GameStateBuilder::new(GameConfig {
+        players: 3,
+        ..GameConfig::default()
+    })
+    .player(Money(1500), 0)
+    .player(Money(900), 24)
+    .player(Money(300), 10)
+    .owns(PlayerId(0), PropertyId(1), 5)
+    .owns(PlayerId(0), PropertyId(3), 4)
+    .owns(PlayerId(1), PropertyId(5), 0)
+    .mortgaged(PropertyId(5))
+    .owns(PlayerId(2), PropertyId(39), 0)
+    .in_jail(PlayerId(2), 1)
+    .build()
+    .unwrap();
+    assert_eq!(
+        state.render_board(),
+        include_str!("fixtures/board.txt"),
+        "\n{}",
+        state.render_board()
+    );
+}
+
+#[test]
+fn visitors_are_told_apart_from_prisoners() {
+    let state = GameStateBuilder::new(GameConfig {
+        players: 3,
+        ..GameConfig::default()
+    })
+    .player(Money(1500), 10)
+    .player(Money(1500), 10)
+    .player(Money(1500), 10)
+    .in_jail(PlayerId(1), 0)
+    .build()
+    .unwrap();
+    let board = state.render_board();
+    let jail: Vec<&str> = board
+        .lines()
+        .rev()
+        .skip(1)
+        .take(3)
+        .map(|line| &line[1..9])
+        .collect();
+    assert_eq!(jail, ["HB      ", "in T    ", "Jail    "]);
+}
+
+#[test]
+fn every_line_is_the_same_width() {
+    let board = GameStateBuilder::new(GameConfig::default())
+        .build()
+        .unwrap()
+        .render_board();
+    assert_eq!(board.lines().count(), 45);
+    assert!(board.lines().all(|line| line.len() == 100));
+}
@@ -0,0 +1,107 @@
+use monopoly::{
+    Command, GameConfig, GameError, GameState, Money, PlayerId, PropertyId, RollResult,
+};
+
+const BALTIC: PropertyId = PropertyId(3);
+const READING_RAILROAD: PropertyId = PropertyId(5);
+const ELECTRIC_COMPANY: PropertyId = PropertyId(12);
+
+fn two_player_game() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+// Lands the player with the roll and returns how much cash they lost.
+fn charge_for_landing(state: &mut GameState, player: PlayerId, roll: RollResult) -> Money {
+    let before = state.cash(player).unwrap();
+    state.apply(Command::RollDice(player, roll)).unwrap();
+    Money(before.0 - state.cash(player).unwrap().0)
+}
+
+#[test]
+fn unowned_and_mortgaged_properties_cost_nothing() {
+    let p0 = PlayerId(0);
+    let mut state = two_player_game();
+    assert_eq!(state.rent_due(BALTIC, None), Ok(Money(0)));
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::BuyProperty(p0, BALTIC)).unwrap();
+    state.apply(Command::MortgageProperty(p0, BALTIC)).unwrap();
+    assert_eq!(state.rent_due(BALTIC, None), Ok(Money(0)));
+}
+
+#[test]
+fn rent_due_rejects_squares_that_are_not_properties() {
+    let state = two_player_game();
+    assert_eq!(
+        state.rent_due(PropertyId(4), None),
+        Err(GameError::InvalidProperty(PropertyId(4)))
+    );
+    assert_eq!(
+        state.rent_due(PropertyId(40), None),
+        Err(GameError::InvalidProperty(PropertyId(40)))
+    );
+}
+
+#[test]
+fn street_preview_matches_the_charge_on_landing() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state.apply(Command::BuyProperty(p0, BALTIC)).unwrap();
+    state.apply(Command::EndTurn(p0)).unwrap();
+    let preview = state.rent_due(BALTIC, None).unwrap();
+    assert_eq!(preview, Money(4));
+    assert_eq!(
+        charge_for_landing(&mut state, p1, RollResult(1, 2)),
+        preview
+    );
+    assert_eq!(state.cash(p0), Ok(Money(1444)));
+}
+
+#[test]
+fn railroad_preview_matches_the_charge_on_landing() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(2, 3)))
+        .unwrap();
+    state
+        .apply(Command::BuyProperty(p0, READING_RAILROAD))
+        .unwrap();
+    state.apply(Command::EndTurn(p0)).unwrap();
+    let preview = state.rent_due(READING_RAILROAD, None).unwrap();
+    assert_eq!(preview, Money(25));
+    assert_eq!(
+        charge_for_landing(&mut state, p1, RollResult(1, 4)),
+        preview
+    );
+}
+
+#[test]
+fn utility_preview_needs_the_roll_and_matches_the_charge() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(6, 6)))
+        .unwrap();
+    state
+        .apply(Command::BuyProperty(p0, ELECTRIC_COMPANY))
+        .unwrap();
+    // The doubles re-roll lands on Free Parking.
+    state
+        .apply(Command::RollDice(p0, RollResult(3, 5)))
+        .unwrap();
+    state.apply(Command::EndTurn(p0)).unwrap();
+    assert!(state.rent_due(ELECTRIC_COMPANY, None).is_err());
+    let roll = RollResult(6, 6);
+    let preview = state.rent_due(ELECTRIC_COMPANY, Some(&roll)).unwrap();
+    assert_eq!(preview, Money(48));
+    assert_eq!(charge_for_landing(&mut state, p1, roll), preview);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "serde")]
+
+use monopoly::{GameState, PlayerId, PropertyId};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command as Process, Stdio};
+
+// Runs `monopoly play` with the lines as its input and returns what it
+// printed.
+fn session(args: &[&str], input: &str) -> String {
+    let mut child = Process::new(env!("CARGO_BIN_EXE_monopoly"))
+        .arg("play")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn a_short_session_is_played_and_saved() {
+    let dir = env::temp_dir().join(format!("monopoly-repl-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("session.json");
+    // Under seed 2 the first roll is 2+3, onto Reading Railroad.
+    let input = format!(
+        "roll\nbuy\nfly\nbuild boardwalk\nend\nroll\nquit\n{}\n",
+        path.display()
+    );
+    let output = session(&["--seed", "2", "--players", "2"], &input);
+    assert!(output.contains("Player 0 can: roll, resign\nP0> "));
+    assert!(output.contains("Player 0 can: decline, buy reading-railroad\n"));
+    assert!(output.contains("Player 0 bought Reading Railroad for $200\n"));
+    assert!(output.contains("P0> unknown action 'fly'\n"));
+    assert!(output.contains("P0> Can't do that: "));
+    assert!(output.contains("P1> Player 1 rolled "));
+    assert!(output.ends_with(&format!(
+        "Save the game? Give a file name, or nothing to quit: Saved to {}\n",
+        path.display()
+    )));
+
+    let saved = GameState::load_from(&path).unwrap();
+    assert_eq!(saved.owner_of(PropertyId(5)), Some(PlayerId(0)));
+    assert_eq!(saved.current_player(), PlayerId(1));
+
+    // Picking up where it left off.
+    let output = session(&["--load", path.to_str().unwrap()], "");
+    assert!(output.contains("Player 0 (top hat): $1300, on Reading Railroad"));
+    assert!(output.ends_with("nothing to quit: "));
+}
+
+#[test]
+fn bots_play_their_turns_around_the_human() {
+    // Under seed 5 player 0's first roll is onto Chance and their second
+    // onto St. Charles Place.
+    let output = session(
+        &["--seed", "5", "--humans", "1", "--bots", "greedy"],
+        "roll\nend\nroll\nbuy\nend\n",
+    );
+    let at = |text: &str, from: usize| from + output[from..].find(text).unwrap();
+    let first_end = at("P0> Player 0 ended their turn\n", 0);
+    let bot_turn = at("Player 1 rolled ", first_end);
+    let bot_end = at("Player 1 ended their turn\n", bot_turn);
+    let human_turn = at("P0> Player 0 rolled ", bot_end);
+    let bought = at(
+        "P0> Player 0 bought St. Charles Place for $140\n",
+        human_turn,
+    );
+    let second_end = at("P0> Player 0 ended their turn\n", bought);
+    let next_bot_turn = at("Player 1 rolled ", second_end);
+    at("Player 0 can: roll", next_bot_turn);
+    // The bot is never prompted.
+    assert!(!output.contains("P1>"));
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use monopoly::{
+    Command, Effect, Event, GameConfig, GameError, GameState, Money, PlayerId, PropertyId,
+    ReplayError, ReplayFailure, RollResult,
+};
+
+fn play(config: GameConfig, actions: &[Command]) -> GameState {
+    let mut state = GameState::new(config).unwrap();
+    for action in actions {
+        state.apply(action.clone()).unwrap();
+    }
+    state
+}
+
+#[test]
+fn replaying_the_log_reproduces_the_game() {
+    let (config, actions) = common::scripted_game();
+    let live = play(config.clone(), &actions);
+    let replayed = GameState::replay(config, &live.event_stream()).unwrap();
+    common::assert_same(&replayed, &live);
+}
+
+#[test]
+fn replay_checks_the_logged_effects() {
+    let (config, actions) = common::scripted_game();
+    let mut events = GameState::play(config.clone(), &actions)
+        .unwrap()
+        .event_stream();
+    // St. Charles Place cost $140; say it cost $1.
+    let paid = events
+        .iter()
+        .position(|event| matches!(event, Event::Effect(Effect::Paid { .. })))
+        .unwrap();
+    let logged = events[paid].clone();
+    events[paid] = Event::Effect(Effect::Paid {
+        from: PlayerId(0),
+        to: None,
+        amount: Money(1),
+    });
+    match GameState::replay(config.clone(), &events).unwrap_err() {
+        ReplayError::Diverged {
+            index,
+            logged,
+            produced,
+        } => {
+            assert_eq!(index, 1);
+            assert_eq!(
+                logged[0],
+                Effect::Paid {
+                    from: PlayerId(0),
+                    to: None,
+                    amount: Money(1)
+                }
+            );
+            assert!(matches!(
+                produced[0],
+                Effect::Paid {
+                    amount: Money(140),
+                    ..
+                }
+            ));
+        }
+        error => panic!("unexpected {:?}", error),
+    }
+
+    // A log of bare commands has nothing to check.
+    events[paid] = logged;
+    let commands: Vec<Event> = actions.into_iter().map(Event::from).collect();
+    assert_eq!(
+        GameState::replay(config.clone(), &commands).unwrap(),
+        GameState::replay(config, &events).unwrap()
+    );
+}
+
+#[test]
+fn replay_reports_the_first_illegal_event() {
+    let (config, mut actions) = common::scripted_game();
+    actions.insert(3, Command::EndTurn(PlayerId(0)));
+    match GameState::play(config, &actions).unwrap_err() {
+        ReplayError::Event(failure) => {
+            assert_eq!((failure.index, failure.turn), (3, 2));
+            assert_eq!(failure.underlying, GameError::NotYourTurn(PlayerId(0)));
+        }
+        error => panic!("unexpected {:?}", error),
+    }
+}
+
+#[test]
+fn replay_lists_what_could_have_happened_instead() {
+    let (config, mut actions) = common::scripted_game();
+    let p0 = PlayerId(0);
+    // Kentucky Avenue was bought here, not Illinois Avenue.
+    actions[9] = Command::BuyProperty(p0, PropertyId(24));
+    let error = GameState::play(config, &actions).unwrap_err();
+    assert_eq!(
+        error,
+        ReplayError::Event(Box::new(ReplayFailure {
+            index: 9,
+            turn: 3,
+            action: Command::BuyProperty(p0, PropertyId(24)),
+            underlying: GameError::IllegalAction("that property is not for sale".to_string()),
+            legal_actions_at_failure: vec![
+                Command::DeclineToBuy(p0),
+                Command::MortgageProperty(p0, PropertyId(11)),
+                Command::BuyProperty(p0, PropertyId(21)),
+                Command::MortgageProperty(PlayerId(1), PropertyId(3)),
+            ],
+        }))
+    );
+    assert_eq!(
+        error.to_string(),
+        "event 9 (turn 3, BuyProperty(PlayerId(0), PropertyId(24))): that property is not for sale"
+    );
+}
+
+#[test]
+fn only_rolling_or_resigning_is_legal_at_the_start() {
+    let (config, _) = common::scripted_game();
+    assert_eq!(
+        GameState::new(config).unwrap().legal_actions(),
+        vec![
+            Command::RollDice(PlayerId(0), RollResult(1, 2)),
+            Command::Resign(PlayerId(0))
+        ]
+    );
+}
+
+#[test]
+fn replay_rejects_a_bad_config() {
+    let (mut config, actions) = common::scripted_game();
+    config.players = 1;
+    assert!(matches!(
+        GameState::play(config, &actions),
+        Err(ReplayError::Config(GameError::InvalidConfig(_)))
+    ));
+}
+
+#[test]
+fn state_at_reproduces_earlier_positions() {
+    let (config, actions) = common::scripted_game();
+    let p1 = PlayerId(1);
+    let mut live = GameState::new(config).unwrap();
+    let mut seen = Vec::new();
+    for (index, action) in actions.iter().enumerate() {
+        if [4, 8, 19].contains(&index) {
+            seen.push((index, live.cash(p1).unwrap()));
+        }
+        if index == 12 {
+            live.checkpoint();
+        }
+        live.apply(action.clone()).unwrap();
+    }
+    for (index, cash) in seen {
+        assert_eq!(live.state_at(index).unwrap().cash(p1), Ok(cash));
+    }
+    assert!(live
+        .state_at(live.event_count())
+        .unwrap()
+        .eq_including_history(&live));
+    assert_eq!(
+        live.state_at(100).unwrap_err(),
+        ReplayError::NoSuchEvent {
+            index: 100,
+            events: actions.len(),
+        }
+    );
+}
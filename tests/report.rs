@@ -0,0 +1,150 @@
+use monopoly::{
+    run_many, run_many_parallel, simulate, GameConfig, GreedyBuyer, RandomStrategy, Strategy,
+    StrategyFactory,
+};
+
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+const RANDOM: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+
+fn three_players() -> GameConfig {
+    GameConfig {
+        players: 3,
+        turn_limit: 300,
+        ..GameConfig::default()
+    }
+}
+
+#[test]
+fn the_totals_add_up() {
+    let lineup = [("greedy", GREEDY), ("random", RANDOM), ("random", RANDOM)];
+    let report = run_many(three_players(), &lineup, 40, 100).unwrap();
+    assert_eq!(report.games, 40);
+    assert_eq!(report.lengths.len(), 40);
+    assert_eq!(report.wins.iter().sum::<usize>() + report.unfinished, 40);
+    let by_seat: f64 = report.win_rate_by_seat().iter().sum();
+    let by_strategy: f64 = report
+        .win_rate_by_strategy()
+        .iter()
+        .map(|(_, rate)| rate)
+        .sum();
+    let unfinished = report.unfinished as f64 / 40.0;
+    assert!((by_seat + unfinished - 1.0).abs() < 1e-9);
+    assert!((by_strategy - by_seat).abs() < 1e-9);
+    assert_eq!(
+        report
+            .win_rate_by_strategy()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        ["greedy", "random"]
+    );
+    // A finished game of three has two bankruptcies, an unfinished one fewer.
+    let histogram: usize = report.bankruptcy_histogram(25).iter().sum();
+    assert_eq!(histogram, report.bankruptcies.len());
+    assert!(report.bankruptcies.len() >= 2 * (40 - report.unfinished));
+    assert!(report.bankruptcies.len() <= 2 * 40);
+    let mut sorted = report.lengths.clone();
+    sorted.sort_unstable();
+    assert!(report.median_length() >= sorted[19] as f64);
+    assert!(report.median_length() <= sorted[20] as f64);
+}
+
+#[test]
+fn each_game_is_the_one_simulate_would_play() {
+    let lineup = [("random", RANDOM), ("random", RANDOM), ("greedy", GREEDY)];
+    let report = run_many(three_players(), &lineup, 3, 7).unwrap();
+    assert_eq!(report, run_many(three_players(), &lineup, 3, 7).unwrap());
+    // GreedyBuyer ignores its seed, so game i is simulate's game 7 + i.
+    let report = run_many(three_players(), &[("greedy", GREEDY); 3], 3, 7).unwrap();
+    for game in 0..3 {
+        let mut strategies: Vec<Box<dyn Strategy>> = (0..3).map(|_| GREEDY(0)).collect();
+        let alone = simulate(three_players(), &mut strategies, 7 + game).unwrap();
+        assert_eq!(report.lengths[game as usize], alone.turns);
+    }
+}
+
+#[test]
+fn the_summary_lists_every_seat() {
+    let lineup = [("greedy", GREEDY), ("random", RANDOM)];
+    let config = GameConfig {
+        players: 2,
+        ..three_players()
+    };
+    let summary = run_many(config, &lineup, 5, 0).unwrap().to_string();
+    assert!(summary.starts_with("5 games, "));
+    assert!(summary.contains("\n0     greedy "));
+    assert!(summary.contains("\n1     random "));
+}
+
+#[test]
+fn threads_do_not_change_the_report() {
+    let lineup = [("greedy", GREEDY), ("random", RANDOM), ("random", RANDOM)];
+    let serial = run_many(three_players(), &lineup, 30, 11).unwrap();
+    for threads in [1, 4, 8, 64] {
+        let parallel = run_many_parallel(three_players(), &lineup, 30, 11, threads).unwrap();
+        assert_eq!(parallel, serial, "{} threads", threads);
+    }
+    let none = run_many_parallel(three_players(), &lineup, 0, 11, 8).unwrap();
+    assert_eq!(none.games, 0);
+}
+
+#[test]
+fn length_buckets_hold_every_game() {
+    let lineup = [("greedy", GREEDY), ("random", RANDOM), ("random", RANDOM)];
+    let config = GameConfig {
+        turn_limit: 120,
+        ..three_players()
+    };
+    let report = run_many(config, &lineup, 30, 5).unwrap();
+    assert_eq!(report.rolls.len(), 30);
+    assert!(report
+        .rolls
+        .iter()
+        .zip(&report.lengths)
+        .all(|(rolls, turns)| rolls >= turns));
+    for histogram in [report.length_histogram(25), report.roll_histogram(40)] {
+        let games: usize = histogram.iter().map(|bucket| bucket.games).sum();
+        assert_eq!(games, 30);
+        for bucket in &histogram {
+            let wins: usize = bucket.wins.iter().sum();
+            assert_eq!(wins + bucket.unfinished, bucket.games);
+        }
+    }
+    // Capped games all run to the limit, so they share the last bucket.
+    assert!(report.unfinished > 0);
+    let turns = report.length_histogram(25);
+    assert_eq!((turns[4].from, turns[4].to), (101, 125));
+    assert_eq!(turns.len(), 5);
+    assert_eq!(turns[4].unfinished, report.unfinished);
+    assert!((report.unfinished_rate() - report.unfinished as f64 / 30.0).abs() < 1e-9);
+    for (game, winner) in report.winners.iter().enumerate() {
+        if winner.is_none() {
+            assert_eq!(report.lengths[game], 120);
+        }
+    }
+}
+
+#[test]
+fn game_lengths_export_a_row_per_game() {
+    let lineup = [("greedy", GREEDY), ("random", RANDOM), ("random", RANDOM)];
+    let config = GameConfig {
+        turn_limit: 120,
+        ..three_players()
+    };
+    let report = run_many(config, &lineup, 10, 5).unwrap();
+    let mut csv = Vec::new();
+    report.export_lengths_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "game,turns,rolls,winner,strategy,unfinished");
+    assert_eq!(rows.len(), 11);
+    for (game, row) in rows[1..].iter().enumerate() {
+        let fields: Vec<&str> = row.split(',').collect();
+        assert_eq!(fields[0], game.to_string());
+        assert_eq!(fields[1], report.lengths[game].to_string());
+        match report.winners[game] {
+            Some(seat) => assert_eq!(fields[3..], [&seat.to_string(), lineup[seat].0, "false"]),
+            None => assert_eq!(fields[3..], ["", "", "true"]),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use monopoly::{
+    Command, Dice, Effect, Event, GameConfig, GameError, GameState, GameStateBuilder, Money,
+    PendingDecision, PlayerId, PropertyId, RollResult,
+};
+
+#[test]
+fn roll_dice_with_invalid_player_raises() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    let result = state.apply(Command::RollDice(PlayerId(4), RollResult(1, 2)));
+    assert_eq!(result, Err(GameError::InvalidPlayer(PlayerId(4))));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "player 4 is not a valid player"
+    );
+}
+
+#[test]
+fn roll_dice_with_valid_player_logs_roll() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    let id = PlayerId(0);
+    let result = state.apply(Command::RollDice(id, RollResult(1, 2)));
+    assert_eq!(
+        result,
+        Ok(vec![
+            Effect::Moved { player: id, to: 3 },
+            Effect::DecisionPending(PendingDecision::BuyOrDecline {
+                player: id,
+                property: PropertyId(3),
+            }),
+        ])
+    );
+    assert_eq!(state.commands(), [Command::RollDice(id, RollResult(1, 2))]);
+    assert_eq!(
+        state.event_stream(),
+        [
+            Event::Command(Command::RollDice(id, RollResult(1, 2))),
+            Event::Effect(Effect::Moved { player: id, to: 3 }),
+            Event::Effect(Effect::DecisionPending(PendingDecision::BuyOrDecline {
+                player: id,
+                property: PropertyId(3),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn one_roll_logs_everything_it_caused() {
+    let id = PlayerId(0);
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 38)
+        .player(Money(1500), 0)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(id, RollResult(1, 2)))
+        .unwrap();
+    assert_eq!(
+        state.event_stream(),
+        [
+            Event::Command(Command::RollDice(id, RollResult(1, 2))),
+            Event::Effect(Effect::PassedGo(id)),
+            Event::Effect(Effect::CollectedSalary {
+                player: id,
+                amount: Money(200),
+            }),
+            Event::Effect(Effect::Moved { player: id, to: 1 }),
+            Event::Effect(Effect::DecisionPending(PendingDecision::BuyOrDecline {
+                player: id,
+                property: PropertyId(1),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn roll_dice_with_negative_player_raises() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    let result = state.apply(Command::RollDice(PlayerId(-1), RollResult(1, 2)));
+    assert_eq!(result, Err(GameError::InvalidPlayer(PlayerId(-1))));
+}
+
+#[test]
+fn dice_off_their_faces_are_refused() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    for (one, two) in [(0, 3), (7, 1), (-2, 5), (3, 19)] {
+        assert_eq!(
+            state.apply(Command::RollDice(PlayerId(0), RollResult(one, two))),
+            Err(GameError::InvalidRoll {
+                dice: vec![one, two],
+                allowed: Dice::CLASSIC,
+            })
+        );
+    }
+    assert!(state.events().is_empty());
+    assert_eq!(
+        state
+            .apply(Command::RollDice(PlayerId(0), RollResult(0, 7)))
+            .unwrap_err()
+            .to_string(),
+        "0+7 is not a roll of 2d6: 2 dice of 1 to 6"
+    );
+}
+
+#[test]
+fn double_sixes_are_doubles() {
+    let mut state = GameState::new(GameConfig::default()).unwrap();
+    let id = PlayerId(0);
+    state
+        .apply(Command::RollDice(id, RollResult(6, 6)))
+        .unwrap();
+    assert_eq!(state.player(id).unwrap().position(), 12);
+    state.apply(Command::DeclineToBuy(id)).unwrap();
+    while state.pending().is_some() {
+        let passer = state.legal_actions()[0].actor().unwrap();
+        state.apply(Command::PassAuction(passer)).unwrap();
+    }
+    // Doubles owe another roll before the turn can end.
+    assert!(state.apply(Command::EndTurn(id)).is_err());
+    assert!(state.apply(Command::RollDice(id, RollResult(1, 2))).is_ok());
+}
@@ -0,0 +1,357 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{
+    BoardEdition, Command, EventRecord, GameConfig, GameLog, GameRules, GameState, HouseRules,
+    Money, PendingDecision, PlayerId, PlayerStatus, PropertyId, ReplayError, RollResult, SaveError,
+    TaxChoice, Token, Trade,
+};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("monopoly-save-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[test]
+fn a_game_resumes_mid_auction() {
+    let (config, actions) = common::scripted_game();
+    let (before, after) = actions.split_at(5);
+    let live = GameState::play(config.clone(), before).unwrap();
+    assert!(matches!(
+        live.pending(),
+        Some(PendingDecision::Auction { .. })
+    ));
+    let path = temp_file("auction.json");
+    live.save_to(&path).unwrap();
+    let mut resumed = GameState::load_from(&path).unwrap();
+    common::assert_same(&resumed, &live);
+    for action in after {
+        resumed.apply(action.clone()).unwrap();
+    }
+    let finished = GameState::play(config, &actions).unwrap();
+    common::assert_same(&resumed, &finished);
+}
+
+#[test]
+fn a_saved_game_keeps_its_edition() {
+    let config = GameConfig {
+        edition: BoardEdition::German,
+        ..GameConfig::default()
+    };
+    let (_, actions) = common::scripted_game();
+    let live = GameState::play(config, &actions[..3]).unwrap();
+    let path = temp_file("edition.json");
+    live.save_to(&path).unwrap();
+    let resumed = GameState::load_from(&path).unwrap();
+    assert_eq!(resumed.config().edition, BoardEdition::German);
+    assert_eq!(resumed.board().name(39), Some("Schlossallee"));
+    common::assert_same(&resumed, &live);
+}
+
+#[test]
+fn a_resumed_game_rolls_the_same_dice() {
+    let mut live = common::played_game(3, 40);
+    let path = temp_file("dice.json");
+    live.save_to(&path).unwrap();
+    let mut resumed = GameState::load_from(&path).unwrap();
+    assert_eq!(resumed.next_roll(), live.next_roll());
+    common::play_turns(&mut live, 50);
+    common::play_turns(&mut resumed, 50);
+    assert!(live.turn_number() > 50 || live.winner().is_some());
+    assert_eq!(resumed.events(), live.events());
+    common::assert_same(&resumed, &live);
+}
+
+#[test]
+fn other_versions_are_refused() {
+    let (config, _) = common::scripted_game();
+    let path = temp_file("version.json");
+    GameState::new(config).unwrap().save_to(&path).unwrap();
+    let saved = fs::read_to_string(&path).unwrap();
+    fs::write(&path, saved.replace("\"version\":9", "\"version\":10")).unwrap();
+    let error = GameState::load_from(&path).unwrap_err();
+    assert!(matches!(
+        error,
+        SaveError::UnsupportedVersion {
+            found: Some(10),
+            supported: 9
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "version 10 is not supported; this build reads up to version 9"
+    );
+    assert!(matches!(
+        GameState::load_from(temp_file("missing.json")),
+        Err(SaveError::Io(_))
+    ));
+}
+
+#[test]
+fn a_version_1_save_is_migrated() {
+    let (config, actions) = common::scripted_game();
+    let live = GameState::play(config, &actions).unwrap();
+    let path = temp_file("v1.json");
+    live.save_to(&path).unwrap();
+    // Version 1 records held the command as "action" and no effects, and
+    // the dice weren't saved.
+    let mut saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    saved["version"] = 1.into();
+    saved["state"].as_object_mut().unwrap().remove("dice");
+    for record in saved["state"]["events"].as_array_mut().unwrap() {
+        let record = record.as_object_mut().unwrap();
+        let command = record.remove("command").unwrap();
+        record.remove("effects");
+        record.insert("action".to_string(), command);
+    }
+    fs::write(&path, saved.to_string()).unwrap();
+    let migrated = GameState::load_from(&path).unwrap();
+    common::assert_same(&migrated, &live);
+    assert_eq!(migrated.event_stream(), live.event_stream());
+    assert_eq!(migrated.next_roll(), live.next_roll());
+}
+
+#[test]
+fn a_version_5_save_deals_the_tokens_by_seat() {
+    let (config, actions) = common::scripted_game();
+    let mut live = GameState::new(config).unwrap();
+    live.add_player_as(Token::Cat).unwrap();
+    for action in &actions[..3] {
+        live.apply(action.clone()).unwrap();
+    }
+    let path = temp_file("v5.json");
+    live.save_to(&path).unwrap();
+    common::assert_same(&GameState::load_from(&path).unwrap(), &live);
+
+    let mut saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    // Nor did the config know of the seats, or count the one who joined.
+    saved["version"] = 5.into();
+    saved["state"]["config"]["players"] = 2.into();
+    saved["state"]["config"]
+        .as_object_mut()
+        .unwrap()
+        .remove("seats");
+    for player in saved["state"]["players"].as_array_mut().unwrap() {
+        player.as_object_mut().unwrap().remove("token");
+    }
+    fs::write(&path, saved.to_string()).unwrap();
+    let migrated = GameState::load_from(&path).unwrap();
+    let tokens: Vec<Token> = migrated.players().map(|player| player.token()).collect();
+    assert_eq!(tokens, Token::ALL[..3]);
+    assert_eq!(migrated.config().players, 3);
+}
+
+#[test]
+fn a_version_6_save_dates_each_bankruptcy_from_the_log() {
+    let p0 = PlayerId(0);
+    // The log is played again from the config on the way in, so the game
+    // has to have started from it: player 0 can't pay the $200 income tax.
+    let config = GameConfig {
+        players: 3,
+        starting_cash: Money(5),
+        ..GameConfig::default()
+    };
+    let live = GameState::play(
+        config,
+        &[
+            Command::RollDice(p0, RollResult(1, 3)),
+            Command::PayIncomeTax(p0, TaxChoice::Flat),
+            Command::DeclareBankruptcy(p0),
+        ],
+    )
+    .unwrap();
+    assert_eq!(live.turn_number(), 2);
+    let path = temp_file("v6.json");
+    live.save_to(&path).unwrap();
+
+    // Version 6 only said who was bankrupt.
+    let mut saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    saved["version"] = 6.into();
+    for player in saved["state"]["players"].as_array_mut().unwrap() {
+        let player = player.as_object_mut().unwrap();
+        let bankrupt = !player.remove("eliminated").unwrap().is_null();
+        player.insert("bankrupt".to_string(), bankrupt.into());
+    }
+    fs::write(&path, saved.to_string()).unwrap();
+    let migrated = GameState::load_from(&path).unwrap();
+    common::assert_same(&migrated, &live);
+    assert_eq!(
+        migrated.player(p0).unwrap().status(),
+        PlayerStatus::Eliminated { on_turn: 1 }
+    );
+}
+
+// Player 0 hands over every dollar and then owes $25 at Reading Railroad,
+// which mortgaging Baltic would pay. Version 7 let them go bankrupt over it,
+// which the rules no longer allow, so the save is refused.
+#[test]
+fn a_version_7_save_with_a_bankruptcy_that_could_have_paid_is_refused() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    let commands = vec![
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::BuyProperty(p0, PropertyId(3)),
+        Command::ProposeTrade(Trade {
+            proposer: p0,
+            recipient: p1,
+            offered: vec![],
+            requested: vec![],
+            offered_cash: Money(1440),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }),
+        Command::AcceptTrade(p1),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(2, 3)),
+        Command::BuyProperty(p1, PropertyId(5)),
+        Command::EndTurn(p1),
+        Command::RollDice(p0, RollResult(1, 1)),
+    ];
+    let live = GameState::play(config, &commands).unwrap();
+    for refused in [Command::DeclareBankruptcy(p0), Command::Resign(p0)] {
+        assert!(live.applied(refused).is_err());
+    }
+    let path = temp_file("v7.json");
+    live.save_to(&path).unwrap();
+
+    let mut saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    saved["version"] = 7.into();
+    let declared = EventRecord {
+        seq: commands.len(),
+        turn: live.turn_number(),
+        actor: Some(p0),
+        command: Command::DeclareBankruptcy(p0),
+        effects: vec![],
+        time: None,
+        digest: None,
+    };
+    let events = saved["state"]["events"].as_array_mut().unwrap();
+    events.push(serde_json::to_value(&declared).unwrap());
+    fs::write(&path, saved.to_string()).unwrap();
+    assert!(matches!(
+        GameState::load_from(&path),
+        Err(SaveError::Migration { from: 7, .. })
+    ));
+}
+
+#[test]
+fn a_game_keeps_its_house_rules() {
+    let p0 = PlayerId(0);
+    let rules = GameRules::official()
+        .with_auctions_enabled(false)
+        .with_free_parking_pot(true)
+        .with_jail_fine(Money(100));
+    let config = GameConfig {
+        players: 2,
+        rules: rules.clone(),
+        ..GameConfig::default()
+    };
+    let commands = [
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::DeclineToBuy(p0),
+        Command::EndTurn(p0),
+    ];
+    let live = GameState::play(config, &commands).unwrap();
+    assert_eq!(live.owner_of(PropertyId(3)), None);
+    let path = temp_file("rules.json");
+    live.save_to(&path).unwrap();
+    let loaded = GameState::load_from(&path).unwrap();
+    assert_eq!(loaded.config().rules, rules);
+    let log = GameLog::from_json(&loaded.log().to_json().unwrap()).unwrap();
+    common::assert_same(&log.replay().unwrap(), &live);
+
+    // Under the official rules the decline puts Baltic Ave up for auction,
+    // and the turn can't end.
+    let official = GameLog {
+        config: GameConfig {
+            rules: HouseRules::official(),
+            ..log.config.clone()
+        },
+        ..log
+    };
+    assert!(matches!(
+        official.replay(),
+        Err(ReplayError::Event(failure)) if failure.index == 2
+    ));
+}
+
+// Player 2 joins a two-player game, and is the last left to bid on Baltic.
+fn joined_and_bidding() -> GameState {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    state.add_player_as(Token::Cat).unwrap();
+    let commands = [
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::DeclineToBuy(p0),
+        Command::PlaceBid(p0, Money(10)),
+        Command::PassAuction(p1),
+    ];
+    for command in commands.iter().cloned() {
+        state.apply(command).unwrap();
+    }
+    state
+}
+
+#[test]
+fn a_joined_seat_survives_a_reload_and_an_undo() {
+    let live = joined_and_bidding();
+    assert_eq!(live.config().players, 3);
+    let path = temp_file("joined.json");
+    live.save_to(&path).unwrap();
+    let mut loaded = GameState::load_from(&path).unwrap();
+    loaded.undo().unwrap();
+    assert_eq!(loaded.players().count(), 3);
+    assert_eq!(loaded.player(PlayerId(2)).unwrap().token(), Token::Cat);
+    assert!(matches!(
+        loaded.pending(),
+        Some(PendingDecision::Auction { next: 1, .. })
+    ));
+    // And on from there as the live game went.
+    loaded.apply(Command::PassAuction(PlayerId(1))).unwrap();
+    assert!(matches!(
+        loaded.pending(),
+        Some(PendingDecision::Auction { .. })
+    ));
+    assert_eq!(loaded.pending(), live.pending());
+}
+
+#[test]
+fn a_log_replays_with_its_joined_seat() {
+    let live = joined_and_bidding();
+    let log = GameLog::from_json(&live.log().to_json().unwrap()).unwrap();
+    common::assert_same(&log.replay().unwrap(), &live);
+}
+
+#[test]
+fn a_log_keeps_the_names_and_tokens_chosen() {
+    let (config, actions) = common::scripted_game();
+    let mut live = GameState::new(config).unwrap();
+    live.rename(PlayerId(0), "Ada").unwrap();
+    live.add_player_as(Token::Cat).unwrap();
+    live.rename(PlayerId(2), "Grace").unwrap();
+    for action in &actions[..3] {
+        live.apply(action.clone()).unwrap();
+    }
+    let log = GameLog::from_json(&live.log().to_json().unwrap()).unwrap();
+    let replayed = log.replay().unwrap();
+    common::assert_same(&replayed, &live);
+    assert_eq!(replayed.display_name(PlayerId(0)), "Ada");
+    assert_eq!(replayed.player(PlayerId(2)).unwrap().token(), Token::Cat);
+}
@@ -0,0 +1,112 @@
+#![cfg(all(feature = "schema", feature = "testing"))]
+
+use monopoly::{
+    generate_state, schema, ClientMessage, Command, Dice, GameConfig, GameState, HouseRules, Money,
+    PropertyId, Table, SCHEMA_TYPES,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+fn validator(name: &str) -> jsonschema::Validator {
+    jsonschema::validator_for(&schema(name).unwrap()).unwrap()
+}
+
+fn assert_valid(validator: &jsonschema::Validator, value: &impl Serialize) {
+    let instance = serde_json::to_value(value).unwrap();
+    if let Err(error) = validator.validate(&instance) {
+        panic!("{} does not validate: {}", instance, error);
+    }
+}
+
+// Games at all stages, from seeds that give several player counts.
+fn games() -> Vec<GameState> {
+    (0..10)
+        .flat_map(|seed| [0, 20, 200].map(|turns| generate_state(seed, turns)))
+        .collect()
+}
+
+#[test]
+fn every_type_has_a_schema() {
+    for name in SCHEMA_TYPES {
+        let schema = schema(name).unwrap();
+        assert!(jsonschema::meta::is_valid(&schema), "{}", name);
+    }
+    assert_eq!(schema("chess"), None);
+}
+
+#[test]
+fn games_validate_against_theirs() {
+    let (actions, config, state) = (validator("action"), validator("config"), validator("state"));
+    let (effect, event) = (validator("effect"), validator("event"));
+    for game in games() {
+        assert_valid(&state, &game);
+        assert_valid(&config, game.config());
+        for command in game.legal_actions() {
+            assert_valid(&actions, &command);
+        }
+        for record in game.events() {
+            assert_valid(&event, record);
+            assert_valid(&actions, &record.command);
+            for applied in &record.effects {
+                assert_valid(&effect, applied);
+            }
+        }
+    }
+    assert_valid(
+        &config,
+        &GameConfig {
+            rules: HouseRules {
+                free_parking_pot: true,
+                double_salary_on_go: true,
+                auctions_enabled: false,
+                snake_eyes_bonus: Some(Money(500)),
+                unlimited_buildings: true,
+                trades_any_time: false,
+                jail_fine: Money(100),
+                dice: Dice { count: 3, faces: 4 },
+            },
+            ..GameConfig::default()
+        },
+    );
+}
+
+#[test]
+fn protocol_messages_validate_against_theirs() {
+    let (client, server) = (validator("client-message"), validator("server-message"));
+    let mut table = Table::new(generate_state(3, 20), Duration::from_secs(30));
+    let mut seat = None;
+    let join = ClientMessage::Join { seat: None };
+    assert_valid(&client, &join);
+    let mut out = table.handle(&mut seat, join);
+    let command = table.state().legal_actions().remove(0);
+    let action = ClientMessage::Action { command };
+    assert_valid(&client, &action);
+    out.extend(table.handle(&mut seat, action));
+    // Refusals, by the table and by the engine.
+    out.extend(table.handle(&mut seat, ClientMessage::Join { seat: None }));
+    let go = Command::BuyProperty(seat.unwrap(), PropertyId(0));
+    out.extend(table.handle(&mut seat, ClientMessage::Action { command: go }));
+    assert!(out.len() > 3);
+    for (_, message) in &out {
+        assert_valid(&server, message);
+    }
+}
+
+#[test]
+fn what_serde_would_refuse_does_not_validate() {
+    let actions = validator("action");
+    assert!(actions.is_valid(&json!({ "Roll": 0 })));
+    for refused in [
+        json!({ "Fly": 0 }),
+        json!({ "Roll": "zero" }),
+        json!({ "BuyProperty": [0] }),
+        json!("EndTurn"),
+    ] {
+        assert!(!actions.is_valid(&refused), "{}", refused);
+        assert!(serde_json::from_value::<Command>(refused).is_err());
+    }
+    let client = validator("client-message");
+    assert!(client.is_valid(&json!({ "type": "join", "seat": null })));
+    assert!(!client.is_valid(&json!({ "type": "leave" })));
+}
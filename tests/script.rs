@@ -0,0 +1,115 @@
+mod common;
+
+use monopoly::{
+    Bid, Card, ChanceCard, Command, GameConfig, GameState, Money, PlayerId, PropertyId, Trade,
+};
+
+fn board() -> GameState {
+    GameState::new(GameConfig::default()).unwrap()
+}
+
+#[test]
+fn scripted_game_round_trips() {
+    let (config, actions) = common::scripted_game();
+    let state = GameState::new(config).unwrap();
+    let script = Command::write_script(&actions, state.board());
+    assert!(script.starts_with("P0 roll 3 4\nP0 buy st-charles-place\nP0 endturn\n"));
+    assert_eq!(Command::parse_script(&script, state.board()), Ok(actions));
+}
+
+#[test]
+fn everything_else_round_trips() {
+    let state = board();
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let actions = vec![
+        Command::AuctionProperty(PropertyId(39), vec![Bid(p0, Money(10)), Bid(p1, Money(20))]),
+        Command::DrawCard(p0, Card::Chance(ChanceCard::AdvanceToGo)),
+        Command::MortgageProperty(p1, PropertyId(16)),
+        Command::BuyHotel(p0, PropertyId(1)),
+        Command::PayTaxes(p0, Money(75)),
+        Command::ProposeTrade(Trade {
+            proposer: p0,
+            recipient: p1,
+            offered: vec![PropertyId(1), PropertyId(5)],
+            requested: vec![],
+            offered_cash: Money(50),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 1,
+        }),
+    ];
+    let script = Command::write_script(&actions, state.board());
+    assert!(script.contains("P0 offer P1 mediterranean-ave reading-railroad $50 for jail-card\n"));
+    assert_eq!(Command::parse_script(&script, state.board()), Ok(actions));
+}
+
+#[test]
+fn names_are_forgiving() {
+    let state = board();
+    let script = "
+        # comments and blank lines are skipped
+        P1 buy Boardwalk
+        P2 mortgage st-james   # a prefix will do
+        P0 unmortgage ST. JAMES place
+        P3 bid $20
+        P0 end-turn
+        P1 build st-james   # as the prompt says it
+        P1 end
+    ";
+    assert_eq!(
+        Command::parse_script(script, state.board()),
+        Ok(vec![
+            Command::BuyProperty(PlayerId(1), PropertyId(39)),
+            Command::MortgageProperty(PlayerId(2), PropertyId(16)),
+            Command::UnmortgageProperty(PlayerId(0), PropertyId(16)),
+            Command::PlaceBid(PlayerId(3), Money(20)),
+            Command::EndTurn(PlayerId(0)),
+            Command::BuyHouse(PlayerId(1), PropertyId(16)),
+            Command::EndTurn(PlayerId(1)),
+        ])
+    );
+}
+
+fn error(script: &str) -> (usize, usize, String) {
+    let error = Command::parse_script(script, board().board()).unwrap_err();
+    (error.line, error.column, error.message)
+}
+
+#[test]
+fn errors_point_at_the_problem() {
+    assert_eq!(
+        error("P0 roll 1 2\n  P0 jump"),
+        (2, 6, "unknown action 'jump'".to_string())
+    );
+    assert_eq!(
+        error("P0 buy park lane"),
+        (1, 8, "no property is called 'park lane'".to_string())
+    );
+    assert_eq!(
+        error("P0 buy pennsylvania"),
+        (
+            1,
+            8,
+            "'pennsylvania' could be Pennsylvania Railroad or Pennsylvania Ave".to_string()
+        )
+    );
+    assert_eq!(
+        error("P0 roll 1 x"),
+        (1, 11, "expected a die, got 'x'".to_string())
+    );
+    assert_eq!(error("P0 roll 1"), (1, 10, "expected a die".to_string()));
+    assert_eq!(
+        error("Q0 endturn"),
+        (1, 1, "expected a player like P0, got 'Q0'".to_string())
+    );
+    assert_eq!(
+        error("P0 endturn now"),
+        (1, 12, "unexpected 'now'".to_string())
+    );
+}
+
+#[test]
+fn errors_display_their_position() {
+    let error = Command::parse_script("P0 fly", board().board()).unwrap_err();
+    assert_eq!(error.to_string(), "line 1, column 4: unknown action 'fly'");
+}
@@ -0,0 +1,77 @@
+use monopoly::{
+    play_seeded, Command, DoNothing, GameConfig, GreedyBuyer, RandomStrategy, RollResult,
+    StrategyFactory,
+};
+use std::process::Command as Process;
+
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+const RANDOM: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+const NOTHING: StrategyFactory = |_| Box::new(DoNothing);
+
+fn players(players: usize) -> GameConfig {
+    GameConfig {
+        players,
+        turn_limit: 300,
+        ..GameConfig::default()
+    }
+}
+
+fn rolls(players: usize, seed: u64) -> Vec<RollResult> {
+    let result = play_seeded(&self::players(players), &vec![NOTHING; players], seed).unwrap();
+    result
+        .log
+        .into_iter()
+        .filter_map(|record| match record.command {
+            Command::RollDice(_, roll) => Some(roll),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn a_seed_plays_the_same_game_every_time() {
+    let lineup = [GREEDY, RANDOM, RANDOM, RANDOM];
+    let game = |seed| play_seeded(&players(4), &lineup, seed).unwrap();
+    assert_eq!(game(12345).log, game(12345).log);
+    assert_ne!(game(12345).log, game(12346).log);
+}
+
+#[test]
+fn another_player_does_not_change_the_dice() {
+    let (two, four) = (rolls(2, 9), rolls(4, 9));
+    assert!(two.len() > 100 && four.len() > 100);
+    assert_eq!(two[..100], four[..100]);
+}
+
+#[test]
+fn the_binary_replays_a_seed() {
+    let run = |seed: &str| {
+        let output = Process::new(env!("CARGO_BIN_EXE_monopoly"))
+            .args(["--seed", seed, "--players", "4"])
+            .args(["--strategy", "greedy,random,random,random"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let game = run("12345");
+    assert!(game.starts_with("seed 12345\n"));
+    assert_eq!(game, run("12345"));
+    assert_ne!(game, run("54321"));
+}
+
+#[test]
+fn the_binary_prints_the_seed_it_picked() {
+    let output = Process::new(env!("CARGO_BIN_EXE_monopoly"))
+        .args(["--strategy", "nothing", "--players", "2"])
+        .output()
+        .unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    let seed = output
+        .lines()
+        .next()
+        .unwrap()
+        .strip_prefix("seed ")
+        .unwrap();
+    assert!(seed.parse::<u64>().is_ok());
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use monopoly::{
+    Command, GameConfig, GameState, GameStateBuilder, Money, PlayerId, PropertyId, RollResult,
+    Trade,
+};
+
+fn round_trip(state: &GameState) -> GameState {
+    let json = serde_json::to_string(state).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn a_fresh_game_round_trips() {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    common::assert_same(&round_trip(&state), &state);
+}
+
+#[test]
+fn a_mid_game_state_round_trips() {
+    let (config, actions) = common::scripted_game();
+    let mut state = GameState::play(config, &actions).unwrap();
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: PlayerId(0),
+            recipient: PlayerId(1),
+            offered: vec![PropertyId(21)],
+            requested: vec![PropertyId(3)],
+            offered_cash: Money(0),
+            requested_cash: Money(20),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    state
+        .apply(Command::MortgageProperty(PlayerId(0), PropertyId(27)))
+        .unwrap();
+    let loaded = round_trip(&state);
+    common::assert_same(&loaded, &state);
+    assert_eq!(loaded.events(), state.events());
+    // The loaded game carries on as the original would.
+    let mut original = state;
+    let mut loaded = loaded;
+    let next = Command::AcceptTrade(PlayerId(1));
+    assert_eq!(original.apply(next.clone()), loaded.apply(next));
+    assert_eq!(original.undo(), loaded.undo());
+    common::assert_same(&loaded, &original);
+}
+
+#[test]
+fn buildings_and_pending_decisions_round_trip() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(40), 1)
+        .player(Money(1500), 0)
+        .owns(PlayerId(1), PropertyId(37), 5)
+        .owns(PlayerId(1), PropertyId(39), 4)
+        .in_jail(PlayerId(1), 1)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 1)))
+        .unwrap();
+    assert!(state.pending().is_some());
+    common::assert_same(&round_trip(&state), &state);
+}
+
+fn corrupted(edit: impl FnOnce(&mut serde_json::Value)) -> serde_json::Result<GameState> {
+    let state = GameState::new(GameConfig::default()).unwrap();
+    let mut value = serde_json::to_value(&state).unwrap();
+    edit(&mut value);
+    serde_json::from_value(value)
+}
+
+#[test]
+fn corrupted_payloads_are_rejected() {
+    let error = corrupted(|value| value["players"][0]["position"] = 99.into()).unwrap_err();
+    assert!(error.to_string().contains("player 0 is on square 99"));
+    assert!(corrupted(|value| value["deeds"][0]["owner"] = 0.into()).is_err());
+    assert!(corrupted(|value| value["deeds"][1]["owner"] = 9.into()).is_err());
+    assert!(corrupted(|value| value["houses"] = 40.into()).is_err());
+    assert!(corrupted(|value| {
+        value["chance"].as_array_mut().unwrap().pop();
+    })
+    .is_err());
+    assert!(corrupted(|value| value["turn"]["player"] = 7.into()).is_err());
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "server")]
+
+use monopoly::Server;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+// Serves on a free port for as long as the test runs.
+fn start() -> SocketAddr {
+    let server = Arc::new(Server::bind("127.0.0.1:0").unwrap());
+    let addr = server.local_addr();
+    thread::spawn(move || server.run(2));
+    addr
+}
+
+// One request on its own connection; the status and the JSON that came back.
+fn request(addr: SocketAddr, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+    let body = body.map_or(String::new(), |body| body.to_string());
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn a_few_moves_are_played_over_http() {
+    let addr = start();
+    // Under seed 2 the first roll is 2+3, onto Reading Railroad.
+    let config = json!({ "players": 2, "seed": 2, "engine_dice": true });
+    let (status, created) = request(addr, "POST", "/games", Some(config));
+    assert_eq!(status, 201);
+    let game = format!("/games/{}", created["id"]);
+
+    let (status, state) = request(addr, "GET", &game, None);
+    assert_eq!(status, 200);
+    assert_eq!(state["to_act"], 0);
+    assert_eq!(state["winner"], Value::Null);
+    assert!(state["legal_actions"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "Roll": 0 })));
+
+    let actions = format!("{}/actions", game);
+    let (status, rolled) = request(addr, "POST", &actions, Some(json!({ "Roll": 0 })));
+    assert_eq!(status, 200);
+    assert!(rolled["effects"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "Moved": { "player": 0, "to": 5 } })));
+    let buy = json!({ "BuyProperty": [0, 5] });
+    assert_eq!(request(addr, "POST", &actions, Some(buy)).0, 200);
+    assert_eq!(
+        request(addr, "POST", &actions, Some(json!({ "EndTurn": 0 }))).0,
+        200
+    );
+
+    // Out of turn.
+    let (status, refused) = request(addr, "POST", &actions, Some(json!({ "EndTurn": 0 })));
+    assert_eq!(status, 400);
+    assert_eq!(refused["error"], json!({ "NotYourTurn": 0 }));
+    assert_eq!(refused["message"], "it is not player 0's turn");
+
+    let (status, events) = request(addr, "GET", &format!("{}/events", game), None);
+    assert_eq!(status, 200);
+    let commands: Vec<&Value> = events
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| &record["command"])
+        .collect();
+    assert_eq!(
+        commands,
+        [
+            &json!({ "RollDice": [0, [2, 3]] }),
+            &json!({ "BuyProperty": [0, 5] }),
+            &json!({ "EndTurn": 0 }),
+        ]
+    );
+    let (_, state) = request(addr, "GET", &game, None);
+    assert_eq!(state["to_act"], 1);
+}
+
+#[test]
+fn games_are_kept_apart() {
+    let addr = start();
+    let config = json!({ "players": 2, "engine_dice": true });
+    let (_, first) = request(addr, "POST", "/games", Some(config.clone()));
+    let (_, second) = request(addr, "POST", "/games", Some(config));
+    assert_ne!(first["id"], second["id"]);
+    let path = format!("/games/{}/actions", first["id"]);
+    request(addr, "POST", &path, Some(json!({ "Roll": 0 })));
+    let (_, events) = request(
+        addr,
+        "GET",
+        &format!("/games/{}/events", second["id"]),
+        None,
+    );
+    assert_eq!(events, json!([]));
+}
+
+#[test]
+fn bad_requests_are_explained() {
+    let addr = start();
+    let (status, body) = request(addr, "GET", "/games/7", None);
+    assert_eq!((status, body["message"].as_str()), (404, Some("no game 7")));
+    let (status, body) = request(addr, "POST", "/games", Some(json!({ "players": 9 })));
+    assert_eq!(status, 400);
+    assert_eq!(
+        body["error"],
+        json!({ "InvalidConfig": "expected 2 to 8 players, got 9" })
+    );
+    let (status, created) = request(addr, "POST", "/games", Some(json!({})));
+    assert_eq!(status, 201);
+    let path = format!("/games/{}/actions", created["id"]);
+    let (status, body) = request(addr, "POST", &path, Some(json!({ "Fly": 0 })));
+    assert_eq!(status, 400);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown variant"));
+    assert_eq!(request(addr, "DELETE", "/games", None).0, 405);
+    assert_eq!(request(addr, "GET", "/tables", None).0, 404);
+}
+
+#[test]
+fn stopping_lets_run_return() {
+    let server = Arc::new(Server::bind("127.0.0.1:0").unwrap());
+    let running = {
+        let server = server.clone();
+        thread::spawn(move || server.run(3))
+    };
+    let addr = server.local_addr();
+    assert_eq!(request(addr, "POST", "/games", Some(json!({}))).0, 201);
+    server.stop();
+    running.join().unwrap();
+}
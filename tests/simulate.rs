@@ -0,0 +1,204 @@
+use monopoly::{
+    simulate, Bid, Command, DoNothing, GameConfig, GameError, GameResult, GameState,
+    GameStateBuilder, Money, Outcome, PlayerId, PropertyId, Strategy, Trade,
+};
+
+// Buys whatever it lands on and otherwise does nothing.
+struct Buyer;
+
+impl Strategy for Buyer {
+    fn buy_or_decline(&mut self, _: &GameState, player: PlayerId, property: PropertyId) -> Command {
+        Command::BuyProperty(player, property)
+    }
+
+    fn bid(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+        high_bid: Option<&Bid>,
+    ) -> Command {
+        DoNothing.bid(state, player, property, high_bid)
+    }
+
+    fn income_tax(&mut self, state: &GameState, player: PlayerId, worth: Money) -> Command {
+        DoNothing.income_tax(state, player, worth)
+    }
+
+    fn leave_jail(&mut self, state: &GameState, player: PlayerId) -> Command {
+        DoNothing.leave_jail(state, player)
+    }
+
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        creditor: Option<PlayerId>,
+        amount: Money,
+    ) -> Command {
+        DoNothing.pay_debt(state, player, creditor, amount)
+    }
+
+    fn answer_trade(&mut self, state: &GameState, player: PlayerId, trade: &Trade) -> Command {
+        DoNothing.answer_trade(state, player, trade)
+    }
+
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command {
+        DoNothing.end_turn(state, player)
+    }
+}
+
+// Answers everything with something that never applies, and offers a trade
+// at the end of every turn that it then takes back.
+struct Nonsense;
+
+impl Nonsense {
+    fn answer(&self) -> Command {
+        Command::EndTurn(PlayerId(7))
+    }
+}
+
+impl Strategy for Nonsense {
+    fn buy_or_decline(&mut self, _: &GameState, _: PlayerId, _: PropertyId) -> Command {
+        self.answer()
+    }
+
+    fn bid(&mut self, _: &GameState, _: PlayerId, _: PropertyId, _: Option<&Bid>) -> Command {
+        self.answer()
+    }
+
+    fn income_tax(&mut self, _: &GameState, _: PlayerId, _: Money) -> Command {
+        self.answer()
+    }
+
+    fn leave_jail(&mut self, _: &GameState, _: PlayerId) -> Command {
+        self.answer()
+    }
+
+    fn pay_debt(&mut self, _: &GameState, _: PlayerId, _: Option<PlayerId>, _: Money) -> Command {
+        self.answer()
+    }
+
+    fn answer_trade(&mut self, _: &GameState, _: PlayerId, _: &Trade) -> Command {
+        self.answer()
+    }
+
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command {
+        match state.trade_offer() {
+            Some(_) => Command::RejectTrade(player),
+            None => Command::ProposeTrade(Trade {
+                proposer: player,
+                recipient: PlayerId(1 - player.0),
+                offered: vec![],
+                requested: vec![],
+                offered_cash: Money(1),
+                requested_cash: Money(0),
+                offered_jail_cards: 0,
+                requested_jail_cards: 0,
+            }),
+        }
+    }
+}
+
+fn buyers(count: usize) -> Vec<Box<dyn Strategy>> {
+    (0..count)
+        .map(|_| Box::new(Buyer) as Box<dyn Strategy>)
+        .collect()
+}
+
+fn four_players() -> GameConfig {
+    GameConfig {
+        players: 4,
+        ..GameConfig::default()
+    }
+}
+
+#[test]
+fn a_seeded_game_plays_out_the_same_every_time() {
+    let first = simulate(four_players(), &mut buyers(4), 3).unwrap();
+    let second = simulate(four_players(), &mut buyers(4), 3).unwrap();
+    assert_eq!(first, second);
+    assert!(first.winner.is_some() || first.turns == four_players().turn_limit);
+    assert_eq!(first.net_worths.len(), 4);
+    assert_eq!(first.turns, first.log.last().unwrap().turn);
+
+    let other = simulate(four_players(), &mut buyers(4), 4).unwrap();
+    assert_ne!(first.log, other.log);
+}
+
+#[test]
+fn the_log_replays_to_the_final_worths() {
+    let result = simulate(four_players(), &mut buyers(4), 8).unwrap();
+    let commands: Vec<Command> = result.log.iter().map(|r| r.command.clone()).collect();
+    let config = GameConfig {
+        seed: 8,
+        ..four_players()
+    };
+    let state = GameState::play(config, &commands).unwrap();
+    let worths: Vec<Money> = state
+        .players()
+        .map(|p| state.net_worth(p.id()).unwrap())
+        .collect();
+    assert_eq!(worths, result.net_worths);
+    assert_eq!(state.winner(), result.winner);
+    assert_eq!(state.outcome(), Some(result.outcome));
+}
+
+#[test]
+fn strategies_that_never_apply_still_finish() {
+    let config = GameConfig {
+        players: 2,
+        turn_limit: 50,
+        ..GameConfig::default()
+    };
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(Nonsense), Box::new(Nonsense)];
+    let GameResult { winner, turns, .. } = simulate(config, &mut strategies, 1).unwrap();
+    assert_eq!((winner, turns), (None, 50));
+}
+
+#[test]
+fn every_seat_needs_a_strategy() {
+    let result = simulate(four_players(), &mut buyers(3), 0);
+    assert_eq!(
+        result.unwrap_err(),
+        GameError::InvalidConfig("4 players but 3 strategies".to_string())
+    );
+}
+
+// Each player's cash and titles, in a game whose only turn is already past
+// its limit.
+fn at_the_limit(players: &[(i32, &[PropertyId])]) -> GameState {
+    let mut builder = GameStateBuilder::new(GameConfig {
+        players: players.len(),
+        turn_limit: 0,
+        ..GameConfig::default()
+    });
+    for (seat, (cash, titles)) in players.iter().enumerate() {
+        builder = builder.player(Money(*cash), 0);
+        for title in *titles {
+            builder = builder.owns(PlayerId(seat as i8), *title, 0);
+        }
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn at_the_limit_ties_in_worth_go_to_cash_then_titles() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let (oriental, vermont, reading) = (PropertyId(6), PropertyId(8), PropertyId(5));
+    let playing = GameState::new(GameConfig::default()).unwrap();
+    assert_eq!(playing.outcome(), None);
+
+    // Reading Railroad is worth twice Oriental Ave.
+    let state = at_the_limit(&[(1000, &[oriental]), (1000, &[reading])]);
+    assert_eq!(state.outcome(), Some(Outcome::Won(p1)));
+    // $1100 each, all of it cash for player 0.
+    let state = at_the_limit(&[(1100, &[]), (1000, &[oriental])]);
+    assert_eq!(state.outcome(), Some(Outcome::Won(p0)));
+    // $1200 each and $1000 of it cash, but two titles beat one.
+    let state = at_the_limit(&[(1000, &[oriental, vermont]), (1000, &[reading])]);
+    assert_eq!(state.outcome(), Some(Outcome::Won(p0)));
+    // Level on all three, with player 2 behind.
+    let state = at_the_limit(&[(1000, &[oriental]), (1000, &[vermont]), (900, &[])]);
+    assert_eq!(state.outcome(), Some(Outcome::Draw(vec![p0, p1])));
+}
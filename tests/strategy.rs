@@ -0,0 +1,170 @@
+use monopoly::{
+    Bid, Command, DoNothing, GameConfig, GameState, GameStateBuilder, Money, PlayerId, PropertyId,
+    RollResult, Strategy, TaxChoice, Trade,
+};
+
+const P0: PlayerId = PlayerId(0);
+const P1: PlayerId = PlayerId(1);
+
+// Does what DoNothing does, noting down each question it was asked.
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl Strategy for Recorder {
+    fn buy_or_decline(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+    ) -> Command {
+        self.0
+            .push(format!("buy_or_decline {} {}", player.0, property.0));
+        DoNothing.buy_or_decline(state, player, property)
+    }
+
+    fn bid(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        property: PropertyId,
+        high_bid: Option<&Bid>,
+    ) -> Command {
+        self.0
+            .push(format!("bid {} {} {:?}", player.0, property.0, high_bid));
+        DoNothing.bid(state, player, property, high_bid)
+    }
+
+    fn income_tax(&mut self, state: &GameState, player: PlayerId, worth: Money) -> Command {
+        self.0.push(format!("income_tax {} {}", player.0, worth.0));
+        DoNothing.income_tax(state, player, worth)
+    }
+
+    fn leave_jail(&mut self, state: &GameState, player: PlayerId) -> Command {
+        self.0.push(format!("leave_jail {}", player.0));
+        DoNothing.leave_jail(state, player)
+    }
+
+    fn pay_debt(
+        &mut self,
+        state: &GameState,
+        player: PlayerId,
+        creditor: Option<PlayerId>,
+        amount: Money,
+    ) -> Command {
+        self.0
+            .push(format!("pay_debt {} {:?} {}", player.0, creditor, amount.0));
+        DoNothing.pay_debt(state, player, creditor, amount)
+    }
+
+    fn answer_trade(&mut self, state: &GameState, player: PlayerId, trade: &Trade) -> Command {
+        self.0.push(format!(
+            "answer_trade {} from {}",
+            player.0, trade.proposer.0
+        ));
+        DoNothing.answer_trade(state, player, trade)
+    }
+
+    fn end_turn(&mut self, state: &GameState, player: PlayerId) -> Command {
+        self.0.push(format!("end_turn {}", player.0));
+        DoNothing.end_turn(state, player)
+    }
+}
+
+fn two_players() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+// Asks a fresh Recorder what to do, checks it was asked what was expected,
+// and applies its answer.
+fn ask(state: &mut GameState, asked: &[&str]) -> Command {
+    let mut recorder = Recorder::default();
+    let command = state.ask(&mut recorder);
+    assert_eq!(recorder.0, asked);
+    state.apply(command.clone()).unwrap();
+    command
+}
+
+#[test]
+fn rolls_are_not_asked_about() {
+    let mut state = two_players();
+    assert_eq!(ask(&mut state, &[]), Command::Roll(P0));
+}
+
+#[test]
+fn landing_on_a_lot_asks_to_buy_then_to_bid() {
+    let mut state = two_players();
+    state
+        .apply(Command::RollDice(P0, RollResult(1, 2)))
+        .unwrap();
+    let decline = ask(&mut state, &["buy_or_decline 0 3"]);
+    assert_eq!(decline, Command::DeclineToBuy(P0));
+    assert_eq!(ask(&mut state, &["bid 0 3 None"]), Command::PassAuction(P0));
+    assert_eq!(state.to_act(), P1);
+    assert_eq!(ask(&mut state, &["bid 1 3 None"]), Command::PassAuction(P1));
+    assert_eq!(ask(&mut state, &["end_turn 0"]), Command::EndTurn(P0));
+}
+
+#[test]
+fn income_tax_asks_with_the_worth() {
+    let mut state = two_players();
+    state
+        .apply(Command::RollDice(P0, RollResult(1, 3)))
+        .unwrap();
+    let paid = ask(&mut state, &["income_tax 0 1500"]);
+    assert_eq!(paid, Command::PayIncomeTax(P0, TaxChoice::Flat));
+}
+
+#[test]
+fn a_jailed_player_is_asked_before_rolling() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(1500), 0)
+        .player(Money(1500), 0)
+        .in_jail(P0, 0)
+        .build()
+        .unwrap();
+    assert_eq!(ask(&mut state, &["leave_jail 0"]), Command::Roll(P0));
+}
+
+#[test]
+fn a_debt_asks_the_debtor() {
+    let mut state = GameStateBuilder::default()
+        .player(Money(10), 0)
+        .player(Money(1500), 0)
+        .owns(P1, PropertyId(1), 5)
+        .owns(P1, PropertyId(3), 5)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(P0, RollResult(1, 2)))
+        .unwrap();
+    let bankrupt = ask(&mut state, &["pay_debt 0 Some(PlayerId(1)) 450"]);
+    assert_eq!(bankrupt, Command::DeclareBankruptcy(P0));
+    assert_eq!(state.winner(), Some(P1));
+}
+
+#[test]
+fn a_trade_offer_asks_the_recipient() {
+    let mut state = two_players();
+    state
+        .apply(Command::ProposeTrade(Trade {
+            proposer: P0,
+            recipient: P1,
+            offered: vec![],
+            requested: vec![],
+            offered_cash: Money(10),
+            requested_cash: Money(0),
+            offered_jail_cards: 0,
+            requested_jail_cards: 0,
+        }))
+        .unwrap();
+    assert_eq!(state.to_act(), P1);
+    assert_eq!(
+        ask(&mut state, &["answer_trade 1 from 0"]),
+        Command::RejectTrade(P1)
+    );
+    assert_eq!(state.trade_offer(), None);
+}
@@ -0,0 +1,64 @@
+use monopoly::{
+    play_seeded, Board, Command, EventRecord, GameConfig, GameError, GameResult, GameState,
+    GreedyBuyer, ParseError, RandomStrategy, ReplayError, SimulationReport, Strategy,
+    StrategyFactory,
+};
+use std::sync::Arc;
+use std::thread;
+
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+const RANDOM: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn games_and_what_comes_with_them_can_cross_threads() {
+    assert_send::<GameState>();
+    assert_sync::<GameState>();
+    assert_send::<Board>();
+    assert_sync::<Board>();
+    assert_send::<GameConfig>();
+    assert_send::<Command>();
+    assert_send::<EventRecord>();
+    assert_send::<GameResult>();
+    assert_send::<SimulationReport>();
+    assert_send::<Box<dyn Strategy>>();
+    assert_send::<GameError>();
+    assert_sync::<GameError>();
+    assert_send::<ReplayError>();
+    assert_send::<ParseError>();
+    // So ? can carry one out of a thread.
+    let _: Box<dyn std::error::Error + Send + Sync> = Box::new(GameError::TooManyPlayers);
+}
+
+#[test]
+fn a_hundred_games_on_four_threads_play_as_they_do_on_one() {
+    let config = GameConfig {
+        players: 3,
+        engine_dice: true,
+        turn_limit: 300,
+        ..GameConfig::default()
+    };
+    let seats = [GREEDY, RANDOM, GREEDY];
+    let config = Arc::new(config);
+    let threads: Vec<_> = (0..4u64)
+        .map(|thread| {
+            let config = Arc::clone(&config);
+            thread::spawn(move || {
+                (thread * 25..thread * 25 + 25)
+                    .map(|seed| play_seeded(&config, &seats, seed).unwrap())
+                    .collect::<Vec<GameResult>>()
+            })
+        })
+        .collect();
+    let results: Vec<GameResult> = threads
+        .into_iter()
+        .flat_map(|thread| thread.join().unwrap())
+        .collect();
+    assert_eq!(results.len(), 100);
+    for (seed, result) in results.iter().enumerate() {
+        let alone = play_seeded(&config, &seats, seed as u64).unwrap();
+        assert_eq!(result.log, alone.log, "seed {}", seed);
+    }
+}
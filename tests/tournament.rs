@@ -0,0 +1,90 @@
+use monopoly::{
+    tournament, DoNothing, GameConfig, GameError, GreedyBuyer, RandomStrategy, StrategyFactory,
+};
+
+const GREEDY: StrategyFactory = |_| Box::new(GreedyBuyer::default());
+const RANDOM: StrategyFactory = |seed| Box::new(RandomStrategy::new(seed));
+const NOTHING: StrategyFactory = |_| Box::new(DoNothing);
+
+fn table(players: usize) -> GameConfig {
+    GameConfig {
+        players,
+        turn_limit: 200,
+        ..GameConfig::default()
+    }
+}
+
+#[test]
+fn every_pairing_plays_from_both_seats() {
+    let entrants = [("greedy", GREEDY), ("random", RANDOM), ("nothing", NOTHING)];
+    let report = tournament(table(2), &entrants, 2, 0).unwrap();
+    // Three pairs, each seated both ways, twice.
+    assert_eq!(report.games.len(), 12);
+    for strategy in 0..3 {
+        assert_eq!(report.played(strategy), 8);
+        for other in (0..3).filter(|other| *other != strategy) {
+            assert_eq!(report.head_to_head(strategy, other).1, 4);
+            let first = report
+                .games
+                .iter()
+                .filter(|game| game.seats == [strategy, other])
+                .count();
+            assert_eq!(first, 2);
+        }
+    }
+    let wins: usize = (0..3).map(|strategy| report.wins(strategy)).sum();
+    let unfinished = report
+        .games
+        .iter()
+        .filter(|game| game.winner.is_none())
+        .count();
+    assert_eq!(wins + unfinished, 12);
+}
+
+#[test]
+fn bigger_tables_rotate_every_lineup() {
+    let entrants = [("a", GREEDY), ("b", RANDOM), ("c", NOTHING), ("d", RANDOM)];
+    let report = tournament(table(3), &entrants, 1, 5).unwrap();
+    // Four lineups of three, each in three rotations.
+    assert_eq!(report.games.len(), 12);
+    for strategy in 0..4 {
+        assert_eq!(report.played(strategy), 9);
+        let seats: Vec<usize> = (0..3)
+            .map(|seat| {
+                report
+                    .games
+                    .iter()
+                    .filter(|game| game.seats[seat] == strategy)
+                    .count()
+            })
+            .collect();
+        assert_eq!(seats, [3, 3, 3]);
+    }
+    assert_eq!(report.head_to_head(0, 1).1, 6);
+}
+
+#[test]
+fn intervals_hold_the_win_rate() {
+    let entrants = [("greedy", GREEDY), ("nothing", NOTHING)];
+    let report = tournament(table(2), &entrants, 10, 0).unwrap();
+    for strategy in 0..2 {
+        let (low, high) = report.confidence_interval(strategy);
+        let rate = report.win_rate(strategy);
+        assert!(0.0 <= low && low <= rate && rate <= high && high <= 1.0);
+    }
+    let summary = report.to_string();
+    let row = summary
+        .lines()
+        .rfind(|line| line.starts_with("greedy"))
+        .unwrap();
+    assert_eq!(row.split_whitespace().nth(1), Some("-"));
+}
+
+#[test]
+fn a_table_needs_enough_strategies() {
+    let error = tournament(table(3), &[("greedy", GREEDY), ("random", RANDOM)], 1, 0);
+    assert_eq!(
+        error.unwrap_err(),
+        GameError::InvalidConfig("2 strategies cannot fill 3 seats".to_string())
+    );
+}
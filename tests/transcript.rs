@@ -0,0 +1,73 @@
+mod common;
+
+use monopoly::{Command, GameConfig, GameState};
+
+// The scripted game played on to a trade, a mortgage and a resignation.
+fn fixture_game() -> GameState {
+    let config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    let board = GameState::new(config.clone()).unwrap();
+    let script = include_str!("fixtures/transcript-game.txt");
+    let actions = Command::parse_script(script, board.board()).unwrap();
+    GameState::play(config, &actions).unwrap()
+}
+
+// assert_eq! for long texts: says which lines differ rather than printing
+// both in full.
+fn assert_same_text(actual: &str, expected: &str) {
+    if actual == expected {
+        return;
+    }
+    let (actual, expected): (Vec<&str>, Vec<&str>) =
+        (actual.lines().collect(), expected.lines().collect());
+    let mut diff = String::new();
+    for line in 0..actual.len().max(expected.len()) {
+        let (got, want) = (actual.get(line), expected.get(line));
+        if got != want {
+            if let Some(want) = want {
+                diff.push_str(&format!("{:>4} - {}\n", line + 1, want));
+            }
+            if let Some(got) = got {
+                diff.push_str(&format!("{:>4} + {}\n", line + 1, got));
+            }
+        }
+    }
+    if diff.is_empty() {
+        diff.push_str("only the line endings differ\n");
+    }
+    panic!(
+        "the transcript has changed (- expected, + actual):\n{}",
+        diff
+    );
+}
+
+#[test]
+fn the_fixture_game_reads_as_the_golden_transcript() {
+    assert_same_text(
+        &fixture_game().transcript(),
+        include_str!("fixtures/transcript.txt"),
+    );
+}
+
+#[test]
+fn the_same_game_always_gives_the_same_transcript() {
+    let state = fixture_game();
+    assert_eq!(fixture_game().transcript(), state.transcript());
+    let replayed = GameState::replay(state.config().clone(), &state.event_stream()).unwrap();
+    assert_eq!(replayed.transcript(), state.transcript());
+}
+
+#[test]
+fn an_unfinished_game_says_so() {
+    let (config, actions) = common::scripted_game();
+    let transcript = GameState::play(config, &actions).unwrap().transcript();
+    assert!(
+        transcript.ends_with(
+            "\nUnfinished on turn 7\nPlayer 0: $880, worth $1500\nPlayer 1: $1245, worth $1465\n"
+        ),
+        "{}",
+        transcript
+    );
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "tui")]
+
+use monopoly::{
+    draw_game, BoardWidget, Command, GameConfig, GameState, PlayerId, PlayersWidget, PromptWidget,
+};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use ratatui::Terminal;
+
+// The buffer's rows as text, trailing blanks trimmed.
+fn rows(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.y..area.y + area.height)
+        .map(|y| {
+            let row: String = (area.x..area.x + area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            row.trim_end().to_string()
+        })
+        .collect()
+}
+
+fn started() -> GameState {
+    let mut state = GameState::new(GameConfig {
+        players: 2,
+        seed: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    state.apply(Command::Roll(PlayerId(0))).unwrap();
+    state
+}
+
+#[test]
+fn the_board_widget_draws_the_text_board() {
+    let state = started();
+    let text = state.render_board();
+    let width = text.lines().map(str::len).max().unwrap() as u16;
+    let height = text.lines().count() as u16;
+    let mut buffer = Buffer::empty(Rect::new(0, 0, width, height));
+    BoardWidget(&state).render(buffer.area, &mut buffer);
+    let expected: Vec<&str> = text.lines().map(str::trim_end).collect();
+    assert_eq!(rows(&buffer), expected);
+}
+
+#[test]
+fn the_players_widget_lists_everyone() {
+    let state = started();
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 60, 8));
+    PlayersWidget(&state).render(buffer.area, &mut buffer);
+    let rows = rows(&buffer);
+    assert!(rows[0].contains(" Players "));
+    assert!(rows
+        .iter()
+        .any(|row| row.contains("Player 0 (top hat): $1500")));
+    assert!(rows
+        .iter()
+        .any(|row| row.contains("Player 1 (thimble): $1500")));
+}
+
+#[test]
+fn the_screen_has_the_board_the_log_and_the_prompt() {
+    let state = started();
+    let mut terminal = Terminal::new(TestBackend::new(180, 50)).unwrap();
+    terminal
+        .draw(|frame| {
+            let prompt = PromptWidget {
+                prompt: "P0> ",
+                input: "buy",
+                message: "Player 0 can: decline, buy reading-railroad",
+            };
+            draw_game(frame, &state, prompt)
+        })
+        .unwrap();
+    let rows = rows(terminal.backend().buffer());
+    let board_row = state.render_board().lines().next().unwrap().to_string();
+    assert!(rows[0].starts_with(&board_row));
+    assert!(rows.iter().any(|row| row.contains(" Log ")));
+    assert!(rows.iter().any(|row| row.contains("Player 0 rolled 2+3")));
+    assert_eq!(rows[48], "Player 0 can: decline, buy reading-railroad");
+    assert_eq!(rows[49], "P0> buy");
+    terminal.backend_mut().assert_cursor_position((7, 49));
+}
@@ -0,0 +1,434 @@
+use monopoly::{
+    Command, Effect, GameConfig, GameError, GameState, GameStateBuilder, Money, PendingDecision,
+    PlayerId, PropertyId, RollResult, TaxChoice, Trade,
+};
+
+fn two_player_game() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn landing_on_an_unowned_property_offers_it() {
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+        .unwrap();
+    assert_eq!(state.position(PlayerId(0)), Ok(3));
+    assert_eq!(
+        state.pending(),
+        Some(&PendingDecision::BuyOrDecline {
+            player: PlayerId(0),
+            property: PropertyId(3),
+        })
+    );
+    assert!(matches!(
+        state.apply(Command::EndTurn(PlayerId(0))),
+        Err(GameError::DecisionPending(_))
+    ));
+}
+
+#[test]
+fn turns_alternate_and_players_cannot_act_out_of_turn() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    assert_eq!(
+        state.apply(Command::RollDice(p1, RollResult(2, 3))),
+        Err(GameError::NotYourTurn(p1))
+    );
+    assert!(state.apply(Command::EndTurn(p0)).is_err());
+    state
+        .apply(Command::RollDice(p0, RollResult(4, 6)))
+        .unwrap();
+    assert!(state
+        .apply(Command::RollDice(p0, RollResult(4, 6)))
+        .is_err());
+    state.apply(Command::EndTurn(p0)).unwrap();
+    assert_eq!(state.current_player(), p1);
+    assert_eq!(state.turn_number(), 2);
+}
+
+#[test]
+fn doubles_grant_another_roll_and_the_third_sends_to_jail() {
+    let p0 = PlayerId(0);
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(2, 2)))
+        .unwrap();
+    state
+        .apply(Command::PayIncomeTax(p0, TaxChoice::Flat))
+        .unwrap();
+    assert_eq!(state.cash(p0), Ok(Money(1300)));
+    state
+        .apply(Command::RollDice(p0, RollResult(3, 3)))
+        .unwrap();
+    assert_eq!(state.position(p0), Ok(10));
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 1)))
+        .unwrap();
+    assert_eq!(state.position(p0), Ok(10));
+    assert!(state.player(p0).unwrap().in_jail());
+    state.apply(Command::EndTurn(p0)).unwrap();
+}
+
+#[test]
+fn income_tax_is_a_tenth_of_the_worth_on_landing() {
+    let (p0, baltic) = (PlayerId(0), PropertyId(3));
+    let mut state = GameStateBuilder::default()
+        .player(Money(1000), 0)
+        .player(Money(1500), 0)
+        .owns(p0, PropertyId(1), 2)
+        .owns(p0, baltic, 2)
+        .build()
+        .unwrap();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 3)))
+        .unwrap();
+    // $1000 in cash, $120 of titles and $200 of houses.
+    let due = PendingDecision::IncomeTax {
+        player: p0,
+        worth: Money(1320),
+    };
+    assert_eq!(state.pending(), Some(&due));
+    state.apply(Command::SellHouse(p0, baltic)).unwrap();
+    assert_eq!(state.pending(), Some(&due));
+    state
+        .apply(Command::PayIncomeTax(p0, TaxChoice::Percent))
+        .unwrap();
+    // $132 of the worth on landing, where the $1295 after the sale would
+    // have made it $129.
+    assert_eq!(state.cash(p0), Ok(Money(1000 + 25 - 132)));
+}
+
+// Player 0 in jail after `attempts` failed rolls, with a $100 fine that goes
+// to Free Parking.
+fn jailed_with_a_higher_fine(attempts: u8) -> GameState {
+    let mut config = GameConfig::default();
+    config.rules.jail_fine = Money(100);
+    config.rules.free_parking_pot = true;
+    GameStateBuilder::new(config)
+        .player(Money(1500), 10)
+        .player(Money(1500), 0)
+        .in_jail(PlayerId(0), attempts)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn paying_to_leave_jail_costs_the_configured_fine() {
+    let p0 = PlayerId(0);
+    let mut state = jailed_with_a_higher_fine(0);
+    state.apply(Command::PayJailFine(p0)).unwrap();
+    assert!(!state.player(p0).unwrap().in_jail());
+    assert_eq!(state.cash(p0), Ok(Money(1400)));
+    assert_eq!(state.free_parking_pot(), Money(100));
+}
+
+#[test]
+fn the_third_failed_roll_forces_the_configured_fine() {
+    let p0 = PlayerId(0);
+    let mut state = jailed_with_a_higher_fine(2);
+    let effects = state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    assert!(effects.contains(&Effect::Paid {
+        from: p0,
+        to: None,
+        amount: Money(100),
+    }));
+    assert_eq!(state.position(p0), Ok(13));
+    assert_eq!(state.cash(p0), Ok(Money(1400)));
+    assert_eq!(state.free_parking_pot(), Money(100));
+}
+
+// Player 0 on Jail, in it or just visiting, rolls doubles onto Electric
+// Company and turns it down.
+fn doubles_onto_electric_company(jailed: bool) -> GameState {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut builder = GameStateBuilder::default()
+        .player(Money(1500), 10)
+        .player(Money(1500), 0);
+    if jailed {
+        builder = builder.in_jail(p0, 0);
+    }
+    let mut state = builder.build().unwrap();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 1)))
+        .unwrap();
+    assert_eq!(state.position(p0), Ok(12));
+    state.apply(Command::DeclineToBuy(p0)).unwrap();
+    state.apply(Command::PassAuction(p0)).unwrap();
+    state.apply(Command::PassAuction(p1)).unwrap();
+    state
+}
+
+#[test]
+fn doubles_out_of_jail_move_and_then_the_turn_passes() {
+    let p0 = PlayerId(0);
+    let mut state = doubles_onto_electric_company(true);
+    assert!(!state.player(p0).unwrap().in_jail());
+    assert!(!state
+        .legal_actions()
+        .iter()
+        .any(|command| matches!(command, Command::RollDice(..) | Command::Roll(_))));
+    assert!(state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .is_err());
+    state.apply(Command::EndTurn(p0)).unwrap();
+    assert_eq!(state.current_player(), PlayerId(1));
+}
+
+#[test]
+fn doubles_while_free_must_be_rolled_again_before_the_turn_ends() {
+    let p0 = PlayerId(0);
+    let mut state = doubles_onto_electric_company(false);
+    assert_eq!(
+        state.apply(Command::EndTurn(p0)),
+        Err(GameError::IllegalAction(
+            "the player must roll before ending the turn".to_string()
+        ))
+    );
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    assert_eq!(state.position(p0), Ok(15));
+}
+
+fn snake_eyes_game(bonus: Option<Money>) -> GameState {
+    let mut config = GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    };
+    config.rules.snake_eyes_bonus = bonus;
+    GameStateBuilder::new(config)
+        .player(Money(1500), 10)
+        .player(Money(1500), 0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn snake_eyes_pay_the_bonus_before_the_move() {
+    let p0 = PlayerId(0);
+    let mut state = snake_eyes_game(Some(Money(500)));
+    let effects = state
+        .apply(Command::RollDice(p0, RollResult(1, 1)))
+        .unwrap();
+    assert_eq!(
+        effects[..3],
+        [
+            Effect::SnakeEyes(p0),
+            Effect::Received {
+                player: p0,
+                amount: Money(500)
+            },
+            Effect::Moved { player: p0, to: 12 },
+        ]
+    );
+    assert_eq!(state.cash(p0), Ok(Money(2000)));
+    state.apply(Command::DeclineToBuy(p0)).unwrap();
+    state.apply(Command::PassAuction(p0)).unwrap();
+    state.apply(Command::PassAuction(PlayerId(1))).unwrap();
+    // Still doubles, so still another roll.
+    assert!(state.apply(Command::EndTurn(p0)).is_err());
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    assert_eq!(state.cash(p0), Ok(Money(2000)));
+}
+
+#[test]
+fn snake_eyes_are_plain_doubles_without_the_rule() {
+    let p0 = PlayerId(0);
+    let mut state = snake_eyes_game(None);
+    let effects = state
+        .apply(Command::RollDice(p0, RollResult(1, 1)))
+        .unwrap();
+    assert_eq!(effects[0], Effect::Moved { player: p0, to: 12 });
+    assert_eq!(state.cash(p0), Ok(Money(1500)));
+}
+
+#[test]
+fn snake_eyes_the_third_time_pay_and_then_go_to_jail() {
+    let p0 = PlayerId(0);
+    let mut state = snake_eyes_game(Some(Money(100)));
+    for (roll, to) in [(RollResult(2, 2), 14), (RollResult(3, 3), 20)] {
+        state.apply(Command::RollDice(p0, roll)).unwrap();
+        assert_eq!(state.position(p0), Ok(to));
+        if state.pending().is_some() {
+            state.apply(Command::DeclineToBuy(p0)).unwrap();
+            state.apply(Command::PassAuction(p0)).unwrap();
+            state.apply(Command::PassAuction(PlayerId(1))).unwrap();
+        }
+    }
+    let effects = state
+        .apply(Command::RollDice(p0, RollResult(1, 1)))
+        .unwrap();
+    assert_eq!(effects[0], Effect::SnakeEyes(p0));
+    assert_eq!(effects.last(), Some(&Effect::WentToJail(p0)));
+    assert_eq!(state.cash(p0), Ok(Money(1600)));
+    state.apply(Command::EndTurn(p0)).unwrap();
+}
+
+#[test]
+fn records_group_events_by_turn() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    for action in [
+        Command::RollDice(p0, RollResult(2, 2)),
+        Command::PayIncomeTax(p0, TaxChoice::Flat),
+        Command::RollDice(p0, RollResult(1, 2)), // Chance: St. Charles Place
+        Command::BuyProperty(p0, PropertyId(11)),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(1, 2)),
+        Command::BuyProperty(p1, PropertyId(3)),
+        Command::EndTurn(p1),
+    ] {
+        state.apply(action).unwrap();
+    }
+    let first: Vec<usize> = state.events_in_turn(1).map(|record| record.seq).collect();
+    assert_eq!(first, [0, 1, 2, 3, 4]);
+    assert!(state
+        .events_in_turn(1)
+        .all(|record| record.actor == Some(p0)));
+    let second: Vec<&Command> = state
+        .events_in_turn(2)
+        .map(|record| &record.command)
+        .collect();
+    assert_eq!(
+        second,
+        [
+            &Command::RollDice(p1, RollResult(1, 2)),
+            &Command::BuyProperty(p1, PropertyId(3)),
+            &Command::EndTurn(p1),
+        ]
+    );
+    assert_eq!(state.events_for(p1).count(), 3);
+    assert_eq!(state.events()[7].turn, 2);
+    assert_eq!(
+        state.last_event().map(|record| &record.command),
+        Some(&Command::EndTurn(p1))
+    );
+    assert_eq!(state.events_in_turn(3).count(), 0);
+}
+
+// Player 2 takes $50 from player 0 in the first turn, then all three buy a
+// lot in turn.
+#[test]
+fn the_log_filters_by_player_and_by_turn() {
+    let (p0, p1, p2) = (PlayerId(0), PlayerId(1), PlayerId(2));
+    let mut state = GameState::new(GameConfig {
+        players: 3,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    let gift = Trade {
+        proposer: p0,
+        recipient: p2,
+        offered: vec![],
+        requested: vec![],
+        offered_cash: Money(50),
+        requested_cash: Money(0),
+        offered_jail_cards: 0,
+        requested_jail_cards: 0,
+    };
+    for action in [
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::BuyProperty(p0, PropertyId(3)),
+        Command::ProposeTrade(gift),
+        Command::AcceptTrade(p2),
+        Command::EndTurn(p0),
+        Command::RollDice(p1, RollResult(2, 4)),
+        Command::BuyProperty(p1, PropertyId(6)),
+        Command::EndTurn(p1),
+        Command::RollDice(p2, RollResult(2, 3)),
+        Command::BuyProperty(p2, PropertyId(5)),
+        Command::EndTurn(p2),
+    ] {
+        state.apply(action).unwrap();
+    }
+    let seqs: Vec<usize> = state.events().iter().map(|record| record.seq).collect();
+    assert_eq!(seqs, (0..11).collect::<Vec<_>>());
+    let commands: Vec<Command> = state
+        .events()
+        .iter()
+        .map(|record| record.command.clone())
+        .collect();
+    assert_eq!(commands, state.commands());
+
+    let turns = |player| {
+        state
+            .events_for(player)
+            .map(|record| record.turn)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(turns(p0), [1, 1, 1, 1]);
+    assert_eq!(turns(p1), [2, 2, 2]);
+    // Accepting the trade was player 2's, in player 0's turn.
+    assert_eq!(turns(p2), [1, 3, 3, 3]);
+    assert_eq!(
+        state.events_for(p2).next().map(|record| &record.command),
+        Some(&Command::AcceptTrade(p2))
+    );
+
+    let actors = |turn| {
+        state
+            .events_in_turn(turn)
+            .map(|record| record.actor)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        actors(1),
+        [Some(p0), Some(p0), Some(p0), Some(p2), Some(p0)]
+    );
+    assert_eq!(actors(2), [Some(p1); 3]);
+    assert_eq!(actors(3), [Some(p2); 3]);
+    assert_eq!(actors(4), []);
+
+    let last = state.last_event().unwrap();
+    assert_eq!((last.seq, last.turn), (10, 3));
+    assert_eq!(last.command, Command::EndTurn(p2));
+    assert!(two_player_game().last_event().is_none());
+}
+
+#[test]
+fn nothing_applies_once_the_game_is_won() {
+    let (p0, p1) = (PlayerId(0), PlayerId(1));
+    let mut state = two_player_game();
+    state
+        .apply(Command::RollDice(p0, RollResult(1, 2)))
+        .unwrap();
+    state
+        .apply(Command::BuyProperty(p0, PropertyId(3)))
+        .unwrap();
+    state.apply(Command::Resign(p0)).unwrap();
+    assert_eq!(state.winner(), Some(p1));
+
+    let error = GameError::GameOver { winner: p1 };
+    let over = Err(error.clone());
+    assert_eq!(state.apply(Command::RollDice(p1, RollResult(1, 2))), over);
+    assert_eq!(state.apply(Command::BuyProperty(p1, PropertyId(3))), over);
+    assert_eq!(state.apply(Command::Roll(p1)), over);
+    let trade = Trade {
+        proposer: p1,
+        recipient: p0,
+        offered: vec![],
+        requested: vec![],
+        offered_cash: Money(10),
+        requested_cash: Money(0),
+        offered_jail_cards: 0,
+        requested_jail_cards: 0,
+    };
+    assert_eq!(state.apply(Command::ProposeTrade(trade)), over);
+    assert_eq!(error.to_string(), "the game is over; player 1 has won");
+    assert_eq!(state.legal_actions(), []);
+
+    // Asking about the game still works.
+    assert_eq!(state.cash(p1), Ok(Money(1500)));
+    assert_eq!(state.owner_of(PropertyId(3)), None);
+    assert_eq!(state.events().len(), 3);
+    assert!(state.to_string().starts_with("Player 1"));
+}
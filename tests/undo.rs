@@ -0,0 +1,59 @@
+mod common;
+
+use monopoly::{Command, GameConfig, GameState, Money, PlayerId, PropertyId, RollResult};
+
+fn two_player_game() -> GameState {
+    GameState::new(GameConfig {
+        players: 2,
+        ..GameConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn undoing_twice_leaves_only_the_first_action() {
+    let p0 = PlayerId(0);
+    let actions = [
+        Command::RollDice(p0, RollResult(1, 2)),
+        Command::BuyProperty(p0, PropertyId(3)),
+        Command::EndTurn(p0),
+    ];
+    let mut state = two_player_game();
+    for action in &actions {
+        state.apply(action.clone()).unwrap();
+    }
+    assert_eq!(state.undo(), Ok(actions[2].clone()));
+    assert_eq!(state.undo(), Ok(actions[1].clone()));
+    let mut expected = two_player_game();
+    expected.apply(actions[0].clone()).unwrap();
+    common::assert_same(&state, &expected);
+}
+
+#[test]
+fn undo_restores_drawn_cards_across_turns() {
+    let (config, actions) = common::scripted_game();
+    let mut state = GameState::play(config.clone(), &actions).unwrap();
+    // Back past the Community Chest draw, the jail trip and a turn change.
+    for _ in 0..4 {
+        state.undo().unwrap();
+    }
+    let expected = GameState::play(config, &actions[..actions.len() - 4]).unwrap();
+    common::assert_same(&state, &expected);
+    // Doing it again draws the same card.
+    for action in &actions[actions.len() - 4..] {
+        state.apply(action.clone()).unwrap();
+    }
+    assert_eq!(state.cash(PlayerId(1)), Ok(Money(1245)));
+}
+
+#[test]
+fn undo_starts_from_players_added_before_play() {
+    let mut state = two_player_game();
+    let p2 = state.add_player().unwrap();
+    state
+        .apply(Command::RollDice(PlayerId(0), RollResult(1, 2)))
+        .unwrap();
+    state.undo().unwrap();
+    assert!(state.player(p2).is_ok());
+    assert!(state.undo().is_err());
+}
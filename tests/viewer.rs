@@ -0,0 +1,43 @@
+#![cfg(feature = "serde")]
+
+use monopoly::{Command, GameLog, GameState};
+
+#[test]
+fn replay_output_matches_the_golden_file() {
+    let log = GameLog::from_json(include_str!("fixtures/replay-log.json")).unwrap();
+    let actions: Vec<Command> = log
+        .records
+        .iter()
+        .map(|record| record.command.clone())
+        .collect();
+    let mut out = Vec::new();
+    let state = GameState::write_replay(log.config.clone(), &actions, Some(8), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        include_str!("fixtures/replay.txt")
+    );
+    assert_eq!(state, log.replay().unwrap());
+}
+
+#[test]
+fn an_illegal_event_stops_the_replay() {
+    let log = GameLog::from_json(include_str!("fixtures/replay-log.json")).unwrap();
+    let mut actions: Vec<Command> = log
+        .records
+        .iter()
+        .map(|record| record.command.clone())
+        .collect();
+    actions.swap(1, 2);
+    let mut out = Vec::new();
+    let error = GameState::write_replay(log.config, &actions, None, &mut out).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("event 1 (turn 1, EndTurn(PlayerId(0)))"),
+        "{}",
+        error
+    );
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .ends_with("2. Player 0 ended their turn\n"));
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "wasm")]
+
+use monopoly::WasmGame;
+use serde_json::{json, Value};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn parse(text: String) -> Value {
+    serde_json::from_str(&text).unwrap()
+}
+
+// Under seed 2 the first roll is 2+3, onto Reading Railroad.
+fn game() -> WasmGame {
+    WasmGame::new(r#"{"players": 2, "seed": 2}"#).unwrap()
+}
+
+#[test]
+fn a_roll_and_a_buy() {
+    let mut game = game();
+    assert_eq!(game.to_act(), 0);
+    assert!(parse(game.legal_actions())
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "RollDice": [0, [1, 2]] })));
+
+    let rolled = parse(game.apply(r#"{"Roll": 0}"#));
+    assert!(rolled["effects"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "Moved": { "player": 0, "to": 5 } })));
+    let bought = parse(game.apply(r#"{"BuyProperty": [0, 5]}"#));
+    assert!(bought["effects"].is_array());
+
+    let state = parse(game.state());
+    assert_eq!(state["players"][0]["cash"], 1300);
+    assert_eq!(state["deeds"][5]["owner"], 0);
+}
+
+#[test]
+fn refusals_are_answered_not_thrown() {
+    let mut game = game();
+    let refused = parse(game.apply(r#"{"EndTurn": 1}"#));
+    assert_eq!(refused["error"], json!({ "NotYourTurn": 1 }));
+    assert_eq!(refused["message"], "it is not player 1's turn");
+    let garbled = parse(game.apply(r#"{"Fly": 0}"#));
+    assert!(garbled["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown variant"));
+    assert!(WasmGame::new(r#"{"players": 9}"#).is_err());
+}
+
+#[test]
+fn bots_play_out_a_turn() {
+    let mut game = game();
+    let played: Vec<Value> = (0..3).map(|_| parse(game.play_bot("greedy"))).collect();
+    assert_eq!(
+        played
+            .iter()
+            .map(|reply| reply["command"].clone())
+            .collect::<Vec<_>>(),
+        [
+            json!({ "Roll": 0 }),
+            json!({ "BuyProperty": [0, 5] }),
+            json!({ "EndTurn": 0 }),
+        ]
+    );
+    assert_eq!(game.to_act(), 1);
+    assert!(parse(game.play_bot("chess"))["message"]
+        .as_str()
+        .unwrap()
+        .contains("no strategy called chess"));
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "multiplayer")]
+
+use futures_util::{SinkExt, StreamExt};
+use monopoly::{
+    serve_table, ClientMessage, Command, GameConfig, GameState, PlayerId, ServerMessage, Table,
+};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn send(client: &mut Client, message: ClientMessage) {
+    let text = serde_json::to_string(&message).unwrap();
+    client.send(Message::text(text)).await.unwrap();
+}
+
+// Reads messages until one matches.
+async fn until(client: &mut Client, wanted: impl Fn(&ServerMessage) -> bool) -> ServerMessage {
+    loop {
+        let frame = client.next().await.unwrap().unwrap();
+        let message = serde_json::from_str(frame.to_text().unwrap()).unwrap();
+        if wanted(&message) {
+            return message;
+        }
+    }
+}
+
+#[tokio::test]
+async fn two_clients_play_over_websockets() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let state = GameState::new(GameConfig {
+        players: 2,
+        seed: 2,
+        ..GameConfig::default()
+    })
+    .unwrap();
+    // Quick to hand an empty seat over, so the test sees it happen.
+    tokio::spawn(serve_table(
+        listener,
+        Table::new(state, Duration::from_millis(200)),
+    ));
+
+    let url = format!("ws://{}", addr);
+    let (mut first, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    let (mut second, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    send(&mut first, ClientMessage::Join { seat: None }).await;
+    let seated = until(&mut first, |_| true).await;
+    assert_eq!(seated, ServerMessage::SeatAssignment { seat: PlayerId(0) });
+    send(&mut second, ClientMessage::Join { seat: None }).await;
+    until(&mut second, |message| {
+        *message == ServerMessage::SeatAssignment { seat: PlayerId(1) }
+    })
+    .await;
+
+    // Not the second client's turn.
+    send(
+        &mut second,
+        ClientMessage::Action {
+            command: Command::Roll(PlayerId(1)),
+        },
+    )
+    .await;
+    until(&mut second, |message| {
+        matches!(message, ServerMessage::Error { .. })
+    })
+    .await;
+
+    send(
+        &mut first,
+        ClientMessage::Action {
+            command: Command::Roll(PlayerId(0)),
+        },
+    )
+    .await;
+    until(&mut first, |message| {
+        matches!(message, ServerMessage::ActionResult { .. })
+    })
+    .await;
+    // Everyone hears about it.
+    let heard = until(&mut second, |message| {
+        matches!(message, ServerMessage::Event { .. })
+    })
+    .await;
+    match heard {
+        ServerMessage::Event { record } => assert_eq!(record.actor, Some(PlayerId(0))),
+        _ => unreachable!(),
+    }
+    send(
+        &mut first,
+        ClientMessage::Action {
+            command: Command::DeclineToBuy(PlayerId(0)),
+        },
+    )
+    .await;
+
+    // The second client leaves in the middle of the auction, and a bot
+    // bids for it once the seat has been empty long enough.
+    drop(second);
+    send(
+        &mut first,
+        ClientMessage::Action {
+            command: Command::PassAuction(PlayerId(0)),
+        },
+    )
+    .await;
+    until(&mut first, |message| match message {
+        ServerMessage::Event { record } => record.actor == Some(PlayerId(1)),
+        _ => false,
+    })
+    .await;
+}